@@ -1,5 +1,5 @@
 use loaa_core::{
-    init_database_with_config, DatabaseConfig, DatabaseMode, Kid, KidRepository,
+    init_database_with_config, DatabaseConfig, DatabaseMode, Kid, KidRepository, Repository,
 };
 use std::path::PathBuf;
 
@@ -12,6 +12,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         mode: DatabaseMode::Embedded,
         url: None,
         path: Some(PathBuf::from("./data/test_embedded.db")),
+        namespace: None,
+        database: None,
+        username: None,
+        password: None,
+        token: None,
     };
 
     // Initialize database
@@ -0,0 +1,159 @@
+//! Storage for photo attachments
+//!
+//! Image bytes are kept outside the database in an `AttachmentStore`. The
+//! store is a small key/value trait so other backends (e.g. object storage)
+//! can be plugged in; `LocalFsStore` keeps files in a directory on disk.
+
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use image::ImageFormat;
+use crate::error::{Error, Result};
+
+/// Longest edge of generated thumbnails, in pixels
+pub const THUMBNAIL_SIZE: u32 = 256;
+
+/// Content type of generated thumbnails
+pub const THUMBNAIL_CONTENT_TYPE: &str = "image/jpeg";
+
+/// Backend that stores attachment bytes by key
+pub trait AttachmentStore: Send + Sync {
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<()>;
+
+    /// Returns `Error::NotFound` if nothing is stored under `key`
+    fn get(&self, key: &str) -> Result<Vec<u8>>;
+
+    /// Deleting a missing key is not an error
+    fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// Stores each attachment as a file in a directory
+#[derive(Debug, Clone)]
+pub struct LocalFsStore {
+    root: PathBuf,
+}
+
+impl LocalFsStore {
+    /// Create a store rooted at `root`, creating the directory if needed
+    pub fn new(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        std::fs::create_dir_all(&root)
+            .map_err(|e| Error::Internal(format!("Failed to create attachment directory {}: {}", root.display(), e)))?;
+        Ok(Self { root })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path_for(&self, key: &str) -> Result<PathBuf> {
+        let valid = !key.is_empty()
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(Error::Validation(format!("Invalid attachment key: {}", key)));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+impl AttachmentStore for LocalFsStore {
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<()> {
+        let path = self.path_for(key)?;
+        std::fs::write(&path, bytes)
+            .map_err(|e| Error::Internal(format!("Failed to write {}: {}", path.display(), e)))
+    }
+
+    fn get(&self, key: &str) -> Result<Vec<u8>> {
+        let path = self.path_for(key)?;
+        std::fs::read(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => Error::NotFound(format!("Attachment data for {}", key)),
+            _ => Error::Internal(format!("Failed to read {}: {}", path.display(), e)),
+        })
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        let path = self.path_for(key)?;
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(Error::Internal(format!("Failed to delete {}: {}", path.display(), e))),
+        }
+    }
+}
+
+/// Detect the content type of an uploaded image
+///
+/// Only JPEG and PNG photos are accepted.
+pub fn detect_content_type(bytes: &[u8]) -> Result<&'static str> {
+    match image::guess_format(bytes) {
+        Ok(ImageFormat::Jpeg) => Ok("image/jpeg"),
+        Ok(ImageFormat::Png) => Ok("image/png"),
+        _ => Err(Error::Validation("Photos must be JPEG or PNG images".to_string())),
+    }
+}
+
+/// Scale an image down to fit within `THUMBNAIL_SIZE` and encode it as JPEG
+pub fn make_thumbnail(bytes: &[u8]) -> Result<Vec<u8>> {
+    let image = image::load_from_memory(bytes)
+        .map_err(|e| Error::Validation(format!("Could not decode image: {}", e)))?;
+    let thumbnail = image::DynamicImage::ImageRgb8(
+        image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgb8(),
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    thumbnail
+        .write_to(&mut out, ImageFormat::Jpeg)
+        .map_err(|e| Error::Internal(format!("Failed to encode thumbnail: {}", e)))?;
+    Ok(out.into_inner())
+}
+
+#[cfg(test)]
+pub(crate) fn sample_png(width: u32, height: u32) -> Vec<u8> {
+    let image = image::RgbImage::from_pixel(width, height, image::Rgb([200, 120, 40]));
+    let mut out = Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgb8(image)
+        .write_to(&mut out, ImageFormat::Png)
+        .unwrap();
+    out.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_store_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = LocalFsStore::new(dir.path().join("attachments")).unwrap();
+
+        store.put("abc-thumb", b"hello").unwrap();
+        assert_eq!(store.get("abc-thumb").unwrap(), b"hello");
+
+        store.delete("abc-thumb").unwrap();
+        assert!(matches!(store.get("abc-thumb"), Err(Error::NotFound(_))));
+        // Deleting again is fine
+        store.delete("abc-thumb").unwrap();
+    }
+
+    #[test]
+    fn test_local_store_rejects_path_traversal() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = LocalFsStore::new(dir.path()).unwrap();
+        assert!(store.put("../escape", b"x").is_err());
+        assert!(store.get("a/b").is_err());
+    }
+
+    #[test]
+    fn test_detect_content_type() {
+        assert_eq!(detect_content_type(&sample_png(4, 4)).unwrap(), "image/png");
+        assert!(detect_content_type(b"not an image").is_err());
+    }
+
+    #[test]
+    fn test_thumbnail_fits_within_bounds() {
+        let thumb = make_thumbnail(&sample_png(1024, 512)).unwrap();
+        let decoded = image::load_from_memory(&thumb).unwrap();
+        assert_eq!(decoded.width(), THUMBNAIL_SIZE);
+        assert_eq!(decoded.height(), THUMBNAIL_SIZE / 2);
+        assert_eq!(detect_content_type(&thumb).unwrap(), THUMBNAIL_CONTENT_TYPE);
+    }
+}
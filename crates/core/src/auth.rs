@@ -1,8 +1,11 @@
 use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
-    Argon2,
+    Algorithm, Argon2, Params, Version,
 };
 use crate::error::{Error, Result};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
 
 /// Hash a password using Argon2id
 ///
@@ -36,6 +39,109 @@ pub fn verify_password(password: &str, hash: &str) -> Result<bool> {
     }
 }
 
+/// Shortest and longest PINs accepted for kid accounts
+pub const MIN_PIN_LENGTH: usize = 4;
+pub const MAX_PIN_LENGTH: usize = 8;
+
+/// Argon2id instance used for kid PINs
+///
+/// PINs have far less entropy than passwords, so they get a much higher
+/// memory and time cost (64 MiB, 4 passes) to slow down offline guessing.
+/// Online guessing is limited by the account lockout.
+fn pin_hasher() -> Result<Argon2<'static>> {
+    let params = Params::new(64 * 1024, 4, 1, None)
+        .map_err(|e| Error::Internal(format!("Invalid PIN hash parameters: {}", e)))?;
+    Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+}
+
+/// Check that a PIN is 4-8 digits
+pub fn validate_pin(pin: &str) -> Result<()> {
+    if !(MIN_PIN_LENGTH..=MAX_PIN_LENGTH).contains(&pin.len())
+        || !pin.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(Error::Validation(format!(
+            "PIN must be {} to {} digits",
+            MIN_PIN_LENGTH, MAX_PIN_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+/// Hash a kid's PIN using Argon2id with PIN-specific parameters
+pub fn hash_pin(pin: &str) -> Result<String> {
+    validate_pin(pin)?;
+    let salt = SaltString::generate(&mut OsRng);
+
+    let pin_hash = pin_hasher()?
+        .hash_password(pin.as_bytes(), &salt)
+        .map_err(|e| Error::Internal(format!("Failed to hash PIN: {}", e)))?;
+
+    Ok(pin_hash.to_string())
+}
+
+/// Verify a PIN against a stored hash
+///
+/// The hash records its own parameters, so this verifies with whatever
+/// cost the PIN was hashed with.
+pub fn verify_pin(pin: &str, hash: &str) -> Result<bool> {
+    verify_password(pin, hash)
+}
+
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Check a self-chosen password (e.g. when registering from an invite)
+pub fn validate_password(password: &str) -> Result<()> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(Error::Validation(format!(
+            "Password must be at least {} characters",
+            MIN_PASSWORD_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+/// Bytes of the HMAC kept in invite codes; short enough to fit in a link
+const INVITE_SIGNATURE_BYTES: usize = 16;
+
+fn invite_mac(invite_id: Uuid, secret: &str) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(b"loaa-invite:");
+    mac.update(invite_id.as_bytes());
+    mac
+}
+
+/// Build the code handed out for an invite: its ID plus an HMAC signature
+///
+/// Only someone holding the server secret can produce a valid code, so
+/// invite IDs can't be guessed or forged.
+pub fn sign_invite_code(invite_id: Uuid, secret: &str) -> String {
+    let signature = invite_mac(invite_id, secret).finalize().into_bytes();
+    let hex: String = signature[..INVITE_SIGNATURE_BYTES]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("{}.{}", invite_id.simple(), hex)
+}
+
+/// Check an invite code's signature and return the invite ID it names
+pub fn verify_invite_code(code: &str, secret: &str) -> Option<Uuid> {
+    let (id, hex) = code.trim().split_once('.')?;
+    let invite_id = Uuid::parse_str(id).ok()?;
+    if hex.len() != INVITE_SIGNATURE_BYTES * 2 || !hex.is_ascii() {
+        return None;
+    }
+    let signature = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+
+    invite_mac(invite_id, secret)
+        .verify_truncated_left(&signature)
+        .ok()
+        .map(|_| invite_id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,4 +195,46 @@ mod tests {
         assert!(verify_password(password, &hash1).unwrap());
         assert!(verify_password(password, &hash2).unwrap());
     }
+
+    #[test]
+    fn test_pin_round_trip() {
+        let hash = hash_pin("4821").unwrap();
+        assert!(hash.starts_with("$argon2id$v=19$m=65536,t=4,p=1$"));
+        assert!(verify_pin("4821", &hash).unwrap());
+        assert!(!verify_pin("4822", &hash).unwrap());
+    }
+
+    #[test]
+    fn test_validate_pin() {
+        assert!(validate_pin("1234").is_ok());
+        assert!(validate_pin("12345678").is_ok());
+        assert!(validate_pin("123").is_err());
+        assert!(validate_pin("123456789").is_err());
+        assert!(validate_pin("12a4").is_err());
+    }
+
+    #[test]
+    fn test_validate_password() {
+        assert!(validate_password("longenough").is_ok());
+        assert!(validate_password("short").is_err());
+    }
+
+    #[test]
+    fn test_invite_code_roundtrip() {
+        let id = Uuid::new_v4();
+        let code = sign_invite_code(id, "secret");
+        assert_eq!(verify_invite_code(&code, "secret"), Some(id));
+
+        // Wrong secret, tampered signature or swapped ID are all rejected
+        assert_eq!(verify_invite_code(&code, "other-secret"), None);
+        let mut tampered = code.clone();
+        let last = if tampered.ends_with('0') { '1' } else { '0' };
+        tampered.pop();
+        tampered.push(last);
+        assert_eq!(verify_invite_code(&tampered, "secret"), None);
+        let (_, signature) = code.split_once('.').unwrap();
+        let swapped = format!("{}.{}", Uuid::new_v4().simple(), signature);
+        assert_eq!(verify_invite_code(&swapped, "secret"), None);
+        assert_eq!(verify_invite_code("garbage", "secret"), None);
+    }
 }
@@ -0,0 +1,175 @@
+//! Payout budgets: how much of a household's or a kid's budget this week or
+//! month has been paid out
+//!
+//! Task earnings count toward a budget; adjustments don't. Weeks start on
+//! Monday and months on the first, like earnings reports.
+
+use crate::models::{BudgetEnforcement, BudgetPeriod, EntryType, PayoutBudget};
+use crate::reports::{EarningsTotal, ReportPeriod};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Share of a budget at which it counts as nearly spent
+const NEARLY_SPENT: Decimal = Decimal::from_parts(8, 0, 0, false, 1);
+
+/// A budget level a payout can take the spending past
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetThreshold {
+    /// 80% of the budget is spent
+    NearlySpent,
+    /// All of the budget is spent
+    Spent,
+}
+
+impl BudgetThreshold {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetThreshold::NearlySpent => "nearly_spent",
+            BudgetThreshold::Spent => "spent",
+        }
+    }
+}
+
+/// One budget's spending in the period that's under way
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BudgetStatus {
+    /// The kid the budget is for, or `None` for the household's
+    pub kid_id: Option<Uuid>,
+    /// The kid's or household's name
+    pub name: String,
+    pub budget: PayoutBudget,
+    pub period_start: NaiveDate,
+    pub spent: Decimal,
+    /// What's left before the budget is spent; negative once it's over
+    pub remaining: Decimal,
+}
+
+impl BudgetStatus {
+    /// Sum the task earnings in `totals` (for the budget's kids, over the
+    /// period starting on `period_start`) against `budget`
+    pub fn new(
+        kid_id: Option<Uuid>,
+        name: String,
+        budget: PayoutBudget,
+        period_start: NaiveDate,
+        totals: &[EarningsTotal],
+    ) -> Self {
+        let spent: Decimal = totals
+            .iter()
+            .filter(|t| t.entry_type == EntryType::Earned && kid_id.is_none_or(|id| t.kid_id == id))
+            .map(|t| t.total)
+            .sum();
+        Self { kid_id, name, budget, period_start, spent, remaining: budget.amount - spent }
+    }
+
+    /// Whether paying `amount` more keeps within the budget
+    pub fn allows(&self, amount: Decimal) -> bool {
+        amount <= self.remaining
+    }
+
+    /// Whether a payout would be refused
+    pub fn blocks(&self, amount: Decimal) -> bool {
+        self.budget.enforcement == BudgetEnforcement::Block && !self.allows(amount)
+    }
+
+    /// Why paying `amount` is refused, for a budget that blocks it
+    pub fn refusal(&self, amount: Decimal) -> String {
+        format!(
+            "Paying ${:.2} would go over {}'s {}ly budget",
+            amount,
+            self.name,
+            self.budget.period.as_str()
+        )
+    }
+
+    /// The status once `amount` more has been paid
+    pub fn after(&self, amount: Decimal) -> Self {
+        Self { spent: self.spent + amount, remaining: self.remaining - amount, ..self.clone() }
+    }
+
+    /// The highest threshold the last `paid` of the spending took it past
+    pub fn crossed(&self, paid: Decimal) -> Option<BudgetThreshold> {
+        let before = self.spent - paid;
+        let past = |level: Decimal| before < level && self.spent >= level;
+        if past(self.budget.amount) {
+            Some(BudgetThreshold::Spent)
+        } else if past(self.budget.amount * NEARLY_SPENT) {
+            Some(BudgetThreshold::NearlySpent)
+        } else {
+            None
+        }
+    }
+
+    /// Set once the budget is spent
+    pub fn warning(&self) -> Option<String> {
+        (self.remaining <= Decimal::ZERO).then(|| {
+            format!(
+                "{} has been paid ${:.2} this {}, using up the {}ly budget of ${:.2}",
+                self.name,
+                self.spent,
+                self.budget.period.as_str(),
+                self.budget.period.as_str(),
+                self.budget.amount
+            )
+        })
+    }
+}
+
+/// The report period matching a budget's, for summing its spending
+pub(crate) fn report_period(period: BudgetPeriod) -> ReportPeriod {
+    match period {
+        BudgetPeriod::Week => ReportPeriod::Week,
+        BudgetPeriod::Month => ReportPeriod::Month,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn earned(kid_id: Uuid, total: Decimal) -> EarningsTotal {
+        EarningsTotal {
+            kid_id,
+            period_start: NaiveDate::from_ymd_opt(2026, 10, 12).unwrap(),
+            task_id: None,
+            category: None,
+            entry_type: EntryType::Earned,
+            entries: 1,
+            total,
+        }
+    }
+
+    #[test]
+    fn test_spending_and_thresholds() {
+        let (kai, mia) = (Uuid::new_v4(), Uuid::new_v4());
+        let budget = PayoutBudget::new(dec!(20), BudgetPeriod::Week).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        let mut adjusted = earned(kai, dec!(-5));
+        adjusted.entry_type = EntryType::Adjusted;
+        let totals = vec![earned(kai, dec!(10)), earned(mia, dec!(5)), adjusted];
+
+        let household = BudgetStatus::new(None, "Home".to_string(), budget, monday, &totals);
+        assert_eq!((household.spent, household.remaining), (dec!(15), dec!(5)));
+        let kid = BudgetStatus::new(Some(kai), "Kai".to_string(), budget, monday, &totals);
+        assert_eq!(kid.spent, dec!(10));
+
+        assert_eq!(household.after(dec!(1)).crossed(dec!(1)), Some(BudgetThreshold::NearlySpent));
+        assert_eq!(household.after(dec!(6)).crossed(dec!(6)), Some(BudgetThreshold::Spent));
+        assert_eq!(household.after(dec!(6)).after(dec!(1)).crossed(dec!(1)), None);
+        assert!(household.warning().is_none());
+        assert_eq!(
+            household.after(dec!(5)).warning().as_deref(),
+            Some("Home has been paid $20.00 this week, using up the weekly budget of $20.00")
+        );
+
+        // Only blocking budgets refuse payouts
+        assert!(!household.blocks(dec!(6)));
+        let strict = BudgetStatus::new(None, "Home".to_string(), budget.with_enforcement(BudgetEnforcement::Block), monday, &totals);
+        assert!(strict.blocks(dec!(6)));
+        assert!(!strict.blocks(dec!(5)));
+    }
+}
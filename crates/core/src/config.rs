@@ -5,6 +5,16 @@ use std::path::PathBuf;
 pub struct Config {
     pub database: DatabaseConfig,
     pub server: ServerConfig,
+    #[serde(default)]
+    pub attachments: AttachmentConfig,
+    #[serde(default)]
+    pub archive: ArchiveConfig,
+    #[serde(default)]
+    pub idempotency: IdempotencyConfig,
+    #[serde(default)]
+    pub backup: BackupConfig,
+    #[serde(default)]
+    pub statements: StatementConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,7 +23,7 @@ pub struct DatabaseConfig {
     pub mode: DatabaseMode,
     /// Database URL for remote mode (e.g., "127.0.0.1:8000" or "wss://cloud.surrealdb.com")
     pub url: Option<String>,
-    /// File path for embedded mode (e.g., "./data/loaa.db")
+    /// File path for embedded and SQLite modes (e.g., "./data/loaa.db")
     pub path: Option<PathBuf>,
     /// Namespace for remote mode
     pub namespace: Option<String>,
@@ -36,6 +46,8 @@ pub enum DatabaseMode {
     Embedded,
     /// Remote SurrealDB server
     Remote,
+    /// SQLite database file, for installs without SurrealDB
+    Sqlite,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +62,173 @@ pub struct ServerConfig {
     pub jwt_secret: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentConfig {
+    /// Directory where photo attachments are stored
+    pub dir: PathBuf,
+    /// Days to keep photos after they're linked to a completion
+    pub retention_days: u32,
+    /// Largest accepted upload, in bytes
+    pub max_bytes: u64,
+}
+
+impl Default for AttachmentConfig {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("./data/attachments"),
+            retention_days: 30,
+            max_bytes: 10 * 1024 * 1024,
+        }
+    }
+}
+
+impl AttachmentConfig {
+    /// Load attachment settings from environment variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            dir: std::env::var("LOAA_ATTACHMENT_DIR")
+                .map(PathBuf::from)
+                .unwrap_or(defaults.dir),
+            retention_days: std::env::var("LOAA_ATTACHMENT_RETENTION_DAYS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.retention_days),
+            max_bytes: std::env::var("LOAA_ATTACHMENT_MAX_BYTES")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.max_bytes),
+        }
+    }
+
+    pub fn retention(&self) -> chrono::Duration {
+        chrono::Duration::days(self.retention_days.into())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveConfig {
+    /// Days an archived kid or task can be restored before it is purged
+    pub restore_days: u32,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self { restore_days: 30 }
+    }
+}
+
+impl ArchiveConfig {
+    /// Load archive settings from environment variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            restore_days: std::env::var("LOAA_RESTORE_WINDOW_DAYS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.restore_days),
+        }
+    }
+
+    pub fn restore_window(&self) -> chrono::Duration {
+        chrono::Duration::days(self.restore_days.into())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdempotencyConfig {
+    /// Hours a request's idempotency key and result are kept for replays
+    pub retention_hours: u32,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self { retention_hours: 24 }
+    }
+}
+
+impl IdempotencyConfig {
+    /// Load idempotency settings from environment variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            retention_hours: std::env::var("LOAA_IDEMPOTENCY_RETENTION_HOURS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.retention_hours),
+        }
+    }
+
+    pub fn retention(&self) -> chrono::Duration {
+        chrono::Duration::hours(self.retention_hours.into())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupConfig {
+    /// Directory for scheduled backups; none are taken when unset
+    pub dir: Option<PathBuf>,
+    /// Hours between scheduled backups
+    pub interval_hours: u32,
+    /// Days for which the newest backup of the day is kept
+    pub keep_daily: u32,
+    /// Weeks for which the newest backup of the week is kept
+    pub keep_weekly: u32,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            interval_hours: 24,
+            keep_daily: 7,
+            keep_weekly: 4,
+        }
+    }
+}
+
+impl BackupConfig {
+    /// Load backup settings from environment variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            dir: std::env::var("LOAA_BACKUP_DIR").ok().map(PathBuf::from),
+            interval_hours: std::env::var("LOAA_BACKUP_INTERVAL_HOURS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.interval_hours),
+            keep_daily: std::env::var("LOAA_BACKUP_KEEP_DAILY")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.keep_daily),
+            keep_weekly: std::env::var("LOAA_BACKUP_KEEP_WEEKLY")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.keep_weekly),
+        }
+    }
+
+    pub fn interval(&self) -> std::time::Duration {
+        // At least hourly, so a zero doesn't spin
+        std::time::Duration::from_secs(u64::from(self.interval_hours.max(1)) * 60 * 60)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatementConfig {
+    /// Directory monthly statements are archived to; none are archived when unset
+    pub dir: Option<PathBuf>,
+}
+
+impl StatementConfig {
+    /// Load statement settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            dir: std::env::var("LOAA_STATEMENT_DIR").ok().map(PathBuf::from),
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -69,6 +248,11 @@ impl Default for Config {
                 include_mcp: false,
                 jwt_secret: "insecure-default-change-me".to_string(),
             },
+            attachments: AttachmentConfig::default(),
+            archive: ArchiveConfig::default(),
+            idempotency: IdempotencyConfig::default(),
+            backup: BackupConfig::default(),
+            statements: StatementConfig::default(),
         }
     }
 }
@@ -82,7 +266,12 @@ impl Config {
                 "memory" => Some(DatabaseMode::Memory),
                 "embedded" => Some(DatabaseMode::Embedded),
                 "remote" => Some(DatabaseMode::Remote),
-                _ => None,
+                "sqlite" => Some(DatabaseMode::Sqlite),
+                other => {
+                    // Rather than silently keeping data in memory only
+                    eprintln!("WARNING: Unknown LOAA_DB_MODE '{}'. Using memory mode.", other);
+                    None
+                }
             })
             .unwrap_or(DatabaseMode::Memory);
 
@@ -109,6 +298,12 @@ impl Config {
             "insecure-default-change-me".to_string()
         });
 
+        let attachments = AttachmentConfig::from_env();
+        let archive = ArchiveConfig::from_env();
+        let idempotency = IdempotencyConfig::from_env();
+        let backup = BackupConfig::from_env();
+        let statements = StatementConfig::from_env();
+
         Self {
             database: DatabaseConfig {
                 mode: db_mode,
@@ -126,6 +321,11 @@ impl Config {
                 include_mcp,
                 jwt_secret,
             },
+            attachments,
+            archive,
+            idempotency,
+            backup,
+            statements,
         }
     }
 
@@ -149,6 +349,13 @@ impl Config {
                     );
                 }
             }
+            DatabaseMode::Sqlite => {
+                if self.database.path.is_none() {
+                    return Err(
+                        "SQLite database mode requires LOAA_DB_PATH to be set".to_string()
+                    );
+                }
+            }
         }
 
         Ok(())
@@ -205,6 +412,24 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_validate_sqlite_mode_missing_path() {
+        let config = Config {
+            database: DatabaseConfig {
+                mode: DatabaseMode::Sqlite,
+                url: None,
+                path: None,
+                namespace: None,
+                database: None,
+                username: None,
+                password: None,
+                token: None,
+            },
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_validate_remote_mode_missing_url() {
         let config = Config {
@@ -0,0 +1,102 @@
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use crate::db::repository::{into_entities, Entity, Record};
+use crate::db::Transaction;
+use crate::models::Attachment;
+use crate::error::{Error, Result};
+use uuid::Uuid;
+use std::sync::Arc;
+
+impl Entity for Attachment {
+    const TABLE: &'static str = "attachment";
+    const NAME: &'static str = "Attachment";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_id(&mut self, id: Uuid) {
+        self.id = id;
+    }
+}
+
+pub struct AttachmentRepository {
+    db: Arc<Surreal<Any>>,
+}
+
+impl AttachmentRepository {
+    pub fn new(db: Arc<Surreal<Any>>) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, attachment: Attachment) -> Result<Attachment> {
+        let attachment_id = attachment.id.to_string();
+        let created: Option<Record<Attachment>> = self.db
+            .create(("attachment", &attachment_id))
+            .content(attachment)
+            .await?;
+
+        created
+            .ok_or_else(|| Error::Database("Failed to create attachment".to_string()))?
+            .into_entity()
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<Attachment> {
+        let record: Option<Record<Attachment>> = self.db
+            .select(("attachment", id.to_string()))
+            .await?;
+
+        record
+            .ok_or_else(|| Error::NotFound(format!("Attachment with id {}", id)))?
+            .into_entity()
+    }
+
+    pub async fn list(&self) -> Result<Vec<Attachment>> {
+        let records: Vec<Record<Attachment>> = self.db
+            .select("attachment")
+            .await?;
+
+        into_entities(records)
+    }
+
+    pub async fn list_by_kid(&self, kid_id: Uuid) -> Result<Vec<Attachment>> {
+        let records: Vec<Record<Attachment>> = self.db
+            .query("SELECT * FROM attachment WHERE kid_id = $kid_id ORDER BY created_at ASC")
+            .bind(("kid_id", kid_id.to_string()))
+            .await?
+            .take(0)?;
+
+        into_entities(records)
+    }
+
+    pub async fn update(&self, attachment: Attachment) -> Result<Attachment> {
+        let attachment_id = attachment.id;
+
+        // First check if the attachment exists
+        let _existing: Attachment = self.get(attachment_id).await?;
+
+        let updated: Option<Record<Attachment>> = self.db
+            .update(("attachment", attachment_id.to_string()))
+            .content(attachment)
+            .await?;
+
+        updated
+            .ok_or_else(|| Error::NotFound(format!("Attachment with id {}", attachment_id)))?
+            .into_entity()
+    }
+
+    /// Add saving `attachment` to a transaction
+    pub fn update_in(&self, tx: &mut Transaction, attachment: &Attachment) -> Result<()> {
+        let id = tx.bind(attachment.id.to_string())?;
+        let content = tx.bind(attachment.clone())?;
+        tx.push(format!("UPDATE type::thing('attachment', {}) CONTENT {}", id, content));
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<()> {
+        let _deleted: Option<Record<Attachment>> = self.db
+            .delete(("attachment", id.to_string()))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use crate::db::repository::{into_entities, Entity, Record};
+use crate::db::Transaction;
+use crate::models::{ClaimStatus, TaskClaim};
+use crate::error::{Error, Result};
+use uuid::Uuid;
+use std::sync::Arc;
+
+impl Entity for TaskClaim {
+    const TABLE: &'static str = "task_claim";
+    const NAME: &'static str = "Task claim";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_id(&mut self, id: Uuid) {
+        self.id = id;
+    }
+}
+
+pub struct ClaimRepository {
+    db: Arc<Surreal<Any>>,
+}
+
+impl ClaimRepository {
+    pub fn new(db: Arc<Surreal<Any>>) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, claim: TaskClaim) -> Result<TaskClaim> {
+        let claim_id = claim.id.to_string();
+        let created: Option<Record<TaskClaim>> = self.db
+            .create(("task_claim", &claim_id))
+            .content(claim)
+            .await?;
+
+        created
+            .ok_or_else(|| Error::Database("Failed to create task claim".to_string()))?
+            .into_entity()
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<TaskClaim> {
+        let record: Option<Record<TaskClaim>> = self.db
+            .select(("task_claim", id.to_string()))
+            .await?;
+
+        record
+            .ok_or_else(|| Error::NotFound(format!("Task claim with id {}", id)))?
+            .into_entity()
+    }
+
+    /// Claims waiting for review, oldest first
+    pub async fn list_pending(&self, owner_id: &str) -> Result<Vec<TaskClaim>> {
+        let records: Vec<Record<TaskClaim>> = self.db
+            .query("SELECT * FROM task_claim WHERE owner_id = $owner_id AND status = $status ORDER BY created_at ASC")
+            .bind(("owner_id", owner_id.to_string()))
+            .bind(("status", ClaimStatus::Pending))
+            .await?
+            .take(0)?;
+
+        into_entities(records)
+    }
+
+    /// All of a kid's claims, newest first
+    pub async fn list_by_kid(&self, kid_id: Uuid) -> Result<Vec<TaskClaim>> {
+        let records: Vec<Record<TaskClaim>> = self.db
+            .query("SELECT * FROM task_claim WHERE kid_id = $kid_id ORDER BY created_at DESC")
+            .bind(("kid_id", kid_id.to_string()))
+            .await?
+            .take(0)?;
+
+        into_entities(records)
+    }
+
+    /// Start a transaction on this repository's database
+    pub fn begin(&self) -> Transaction {
+        Transaction::new(self.db.clone())
+    }
+
+    /// Add a parent's decision on a claim to a transaction, which fails if
+    /// someone else decided it first
+    pub fn review_in(&self, tx: &mut Transaction, claim: &TaskClaim) -> Result<()> {
+        let id = tx.bind(claim.id.to_string())?;
+        tx.ensure(
+            format!("(SELECT VALUE status FROM ONLY type::thing('task_claim', {})) = 'Pending'", id),
+            Error::Validation("Claim has already been reviewed".to_string()),
+        );
+        let content = tx.bind(claim.clone())?;
+        tx.push(format!("UPDATE type::thing('task_claim', {}) CONTENT {}", id, content));
+        Ok(())
+    }
+
+    pub async fn update(&self, claim: TaskClaim) -> Result<TaskClaim> {
+        let claim_id = claim.id;
+
+        // First check if the claim exists
+        let _existing: TaskClaim = self.get(claim_id).await?;
+
+        let updated: Option<Record<TaskClaim>> = self.db
+            .update(("task_claim", claim_id.to_string()))
+            .content(claim)
+            .await?;
+
+        updated
+            .ok_or_else(|| Error::NotFound(format!("Task claim with id {}", claim_id)))?
+            .into_entity()
+    }
+}
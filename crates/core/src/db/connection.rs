@@ -2,16 +2,30 @@ use surrealdb::opt::auth::{Root, Namespace};
 use surrealdb::Surreal;
 use surrealdb::engine::any::{self, Any};
 use crate::config::{DatabaseConfig, DatabaseMode};
+use crate::db::mirror::SqliteMirror;
+use crate::db::MigrationRunner;
 use crate::error::{Error, Result};
 use std::sync::Arc;
 
 pub struct Database {
     pub client: Arc<Surreal<Any>>,
+    /// The file the data is kept in, in SQLite mode
+    sqlite: Option<Arc<SqliteMirror>>,
 }
 
 impl Database {
-    /// Initialize database with configuration
+    /// Initialize database with configuration, applying pending migrations
     pub async fn init_with_config(config: &DatabaseConfig) -> Result<Self> {
+        let database = Self::connect(config).await?;
+        MigrationRunner::new(database.client.clone()).run().await?;
+        Ok(database)
+    }
+
+    /// Connect without touching the schema (see `MigrationRunner`)
+    ///
+    /// SQLite mode loads the file into memory, with the schema its recorded
+    /// migrations left it in.
+    pub async fn connect(config: &DatabaseConfig) -> Result<Self> {
         let connection_str: String = match &config.mode {
             DatabaseMode::Memory => "memory".to_string(),
             DatabaseMode::Embedded => {
@@ -27,6 +41,10 @@ impl Database {
                 // Return path as string for RocksDB
                 format!("rocksdb://{}", path.display())
             }
+            DatabaseMode::Sqlite => {
+                let sqlite = SqliteMirror::open(config).await?;
+                return Ok(Self { client: sqlite.client(), sqlite: Some(sqlite) });
+            }
             DatabaseMode::Remote => {
                 let url = config.url.as_ref()
                     .ok_or_else(|| Error::Database("Remote mode requires database URL".to_string()))?;
@@ -85,7 +103,19 @@ impl Database {
         db.use_ns(namespace).use_db(database).await
             .map_err(|e| Error::Database(format!("Failed to set namespace/database: {}", e)))?;
 
-        Ok(Self { client: Arc::new(db) })
+        Ok(Self { client: Arc::new(db), sqlite: None })
+    }
+
+    /// Write every change to the database file now
+    ///
+    /// SQLite mode saves changes in the background a moment after they're
+    /// made, so commands call this before exiting. The other modes have
+    /// nothing to do.
+    pub async fn flush(&self) -> Result<()> {
+        match &self.sqlite {
+            Some(sqlite) => sqlite.flush().await,
+            None => Ok(()),
+        }
     }
 
     /// Legacy init method for backward compatibility (uses remote mode)
@@ -0,0 +1,124 @@
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use surrealdb::sql::Thing;
+use crate::db::repository::{Entity, Record, Repository, SurrealRepository};
+use crate::models::Household;
+use crate::error::{Error, Result};
+use uuid::Uuid;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// Tables whose records carry a household ID in `owner_id`
+const OWNED_TABLES: [&str; 6] = ["kid", "task", "task_claim", "attachment", "withdrawal", "transfer"];
+
+impl Entity for Household {
+    const TABLE: &'static str = "household";
+    const NAME: &'static str = "Household";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_id(&mut self, id: Uuid) {
+        self.id = id;
+    }
+}
+
+pub struct HouseholdRepository {
+    db: Arc<Surreal<Any>>,
+    records: SurrealRepository<Household>,
+}
+
+impl HouseholdRepository {
+    pub fn new(db: Arc<Surreal<Any>>) -> Self {
+        Self { records: SurrealRepository::new(db.clone()), db }
+    }
+
+    /// The household a user belongs to, if any
+    pub async fn find_by_member(&self, user_id: &str) -> Result<Option<Household>> {
+        let records: Vec<Record<Household>> = self.db
+            .query("SELECT * FROM household WHERE members.user_id CONTAINS $user_id ORDER BY created_at ASC LIMIT 1")
+            .bind(("user_id", user_id.to_string()))
+            .await?
+            .take(0)?;
+
+        records.into_iter().next().map(Record::into_entity).transpose()
+    }
+
+    /// The household whose ID an `owner_id` field holds, if any (data from
+    /// before households existed has a user ID there instead)
+    pub async fn find_by_owner_id(&self, owner_id: &str) -> Result<Option<Household>> {
+        let Ok(id) = Uuid::parse_str(owner_id) else {
+            return Ok(None);
+        };
+        match self.records.get(id).await {
+            Ok(household) => Ok(Some(household)),
+            Err(Error::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Distinct `owner_id` values across all owned records
+    pub async fn owner_ids_in_use(&self) -> Result<BTreeSet<String>> {
+        let mut owner_ids = BTreeSet::new();
+        for table in OWNED_TABLES {
+            // Records from before ownership existed may have no owner_id at all
+            let ids: Vec<Option<String>> = self.db
+                .query(format!("RETURN array::distinct(SELECT VALUE owner_id FROM {})", table))
+                .await?
+                .take(0)?;
+            owner_ids.extend(ids.into_iter().flatten());
+        }
+        Ok(owner_ids)
+    }
+
+    /// Whether any kids, tasks or other records are owned by `owner_id`
+    pub async fn owns_records(&self, owner_id: &str) -> Result<bool> {
+        for table in OWNED_TABLES {
+            let ids: Vec<Thing> = self.db
+                .query(format!("SELECT VALUE id FROM {} WHERE owner_id = $owner LIMIT 1", table))
+                .bind(("owner", owner_id.to_string()))
+                .await?
+                .take(0)?;
+            if !ids.is_empty() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Move every record owned by `from` over to `to`
+    pub async fn reassign_owner(&self, from: &str, to: &str) -> Result<()> {
+        for table in OWNED_TABLES {
+            self.db
+                .query(format!("UPDATE {} SET owner_id = $to WHERE owner_id = $from", table))
+                .bind(("from", from.to_string()))
+                .bind(("to", to.to_string()))
+                .await?
+                .check()?;
+        }
+        Ok(())
+    }
+}
+
+impl Repository<Household> for HouseholdRepository {
+    async fn create(&self, household: Household) -> Result<Household> {
+        self.records.create(household).await
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Household> {
+        self.records.get(id).await
+    }
+
+    async fn list(&self) -> Result<Vec<Household>> {
+        self.records.list().await
+    }
+
+    async fn update(&self, household: Household) -> Result<Household> {
+        self.records.update(household).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        self.records.delete(id).await
+    }
+}
@@ -0,0 +1,100 @@
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use crate::db::repository::{Entity, Record, Repository, SurrealRepository};
+use crate::models::IdempotencyKey;
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use std::sync::Arc;
+
+impl Entity for IdempotencyKey {
+    const TABLE: &'static str = "idempotency_key";
+    const NAME: &'static str = "Idempotency key";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_id(&mut self, id: Uuid) {
+        self.id = id;
+    }
+}
+
+pub struct IdempotencyRepository {
+    db: Arc<Surreal<Any>>,
+    records: SurrealRepository<IdempotencyKey>,
+}
+
+impl IdempotencyRepository {
+    pub fn new(db: Arc<Surreal<Any>>) -> Self {
+        Self { records: SurrealRepository::new(db.clone()), db }
+    }
+
+    /// Store a new key, unless one with the same ID is already stored
+    ///
+    /// Returns whether this call stored it, so of two requests racing with
+    /// the same key only one goes ahead.
+    pub async fn reserve(&self, key: IdempotencyKey) -> Result<bool> {
+        let key_id = key.id;
+        match self.records.create(key).await {
+            Ok(_) => Ok(true),
+            // Creating fails when the record exists; anything else is a real error
+            Err(e) => match self.find(key_id).await? {
+                Some(_) => Ok(false),
+                None => Err(e),
+            },
+        }
+    }
+
+    /// The key stored under `id`, if any
+    pub async fn find(&self, id: Uuid) -> Result<Option<IdempotencyKey>> {
+        match self.records.get(id).await {
+            Ok(key) => Ok(Some(key)),
+            Err(Error::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Store the result of the operation a key was reserved for
+    pub async fn set_response(&self, id: Uuid, response: String) -> Result<()> {
+        self.db
+            .query("UPDATE type::thing('idempotency_key', $id) SET response = $response")
+            .bind(("id", id.to_string()))
+            .bind(("response", response))
+            .await?
+            .check()?;
+        Ok(())
+    }
+
+    /// Delete keys created before `cutoff`, returning how many were removed
+    pub async fn delete_before(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let deleted: Vec<Record<IdempotencyKey>> = self.db
+            .query("DELETE idempotency_key WHERE created_at < $cutoff RETURN BEFORE")
+            .bind(("cutoff", cutoff))
+            .await?
+            .take(0)?;
+        Ok(deleted.len())
+    }
+}
+
+impl Repository<IdempotencyKey> for IdempotencyRepository {
+    async fn create(&self, key: IdempotencyKey) -> Result<IdempotencyKey> {
+        self.records.create(key).await
+    }
+
+    async fn get(&self, id: Uuid) -> Result<IdempotencyKey> {
+        self.records.get(id).await
+    }
+
+    async fn list(&self) -> Result<Vec<IdempotencyKey>> {
+        self.records.list().await
+    }
+
+    async fn update(&self, key: IdempotencyKey) -> Result<IdempotencyKey> {
+        self.records.update(key).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        self.records.delete(id).await
+    }
+}
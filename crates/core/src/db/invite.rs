@@ -0,0 +1,96 @@
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use crate::db::repository::{into_entities, Entity, Record};
+use crate::models::Invite;
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use std::sync::Arc;
+
+impl Entity for Invite {
+    const TABLE: &'static str = "invite";
+    const NAME: &'static str = "Invite";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_id(&mut self, id: Uuid) {
+        self.id = id;
+    }
+}
+
+pub struct InviteRepository {
+    db: Arc<Surreal<Any>>,
+}
+
+impl InviteRepository {
+    pub fn new(db: Arc<Surreal<Any>>) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, invite: Invite) -> Result<Invite> {
+        let invite_id = invite.id.to_string();
+        let created: Option<Record<Invite>> = self.db
+            .create(("invite", &invite_id))
+            .content(invite)
+            .await?;
+
+        created
+            .ok_or_else(|| Error::Database("Failed to create invite".to_string()))?
+            .into_entity()
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<Invite> {
+        let record: Option<Record<Invite>> = self.db
+            .select(("invite", id.to_string()))
+            .await?;
+
+        record
+            .ok_or_else(|| Error::NotFound(format!("Invite with id {}", id)))?
+            .into_entity()
+    }
+
+    /// All of a household's invites, newest first
+    pub async fn list_by_household(&self, household_id: Uuid) -> Result<Vec<Invite>> {
+        let records: Vec<Record<Invite>> = self.db
+            .query("SELECT * FROM invite WHERE household_id = $household_id ORDER BY created_at DESC")
+            .bind(("household_id", household_id.to_string()))
+            .await?
+            .take(0)?;
+
+        into_entities(records)
+    }
+
+    /// Mark an invite used, unless it already was (or was revoked)
+    ///
+    /// Done in a single conditional update so two people racing to use the
+    /// same code can't both succeed. Returns whether this call claimed it.
+    pub async fn mark_used(&self, id: Uuid, user_id: &str, now: DateTime<Utc>) -> Result<bool> {
+        let updated: Vec<Record<Invite>> = self.db
+            .query("UPDATE type::thing('invite', $id) SET used_by = $user_id, used_at = $now WHERE used_at = NONE AND revoked_at = NONE")
+            .bind(("id", id.to_string()))
+            .bind(("user_id", user_id.to_string()))
+            .bind(("now", now))
+            .await?
+            .take(0)?;
+
+        Ok(!updated.is_empty())
+    }
+
+    pub async fn update(&self, invite: Invite) -> Result<Invite> {
+        let invite_id = invite.id;
+
+        // First check if the invite exists
+        let _existing: Invite = self.get(invite_id).await?;
+
+        let updated: Option<Record<Invite>> = self.db
+            .update(("invite", invite_id.to_string()))
+            .content(invite)
+            .await?;
+
+        updated
+            .ok_or_else(|| Error::NotFound(format!("Invite with id {}", invite_id)))?
+            .into_entity()
+    }
+}
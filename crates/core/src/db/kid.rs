@@ -1,104 +1,134 @@
 use surrealdb::Surreal;
 use surrealdb::engine::any::Any;
-use surrealdb::sql::Thing;
+use crate::db::Transaction;
+use crate::db::repository::{into_entities, Entity, Record, Repository, SurrealRepository};
 use crate::models::Kid;
 use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use std::sync::Arc;
-use serde::{Deserialize, Serialize};
-
-// Helper struct to handle SurrealDB record with id
-#[derive(Debug, Serialize, Deserialize)]
-struct KidRecord {
-    id: Thing,
-    #[serde(flatten)]
-    kid: Kid,
-}
 
-impl KidRecord {
-    fn into_kid(self) -> Kid {
-        let mut kid = self.kid;
-        // Extract UUID from SurrealDB Thing
-        // SurrealDB wraps the ID in angle brackets: ⟨uuid⟩
-        let id_str = self.id.id.to_string();
-        let clean_id = id_str.trim_start_matches('⟨').trim_end_matches('⟩');
-        kid.id = Uuid::parse_str(clean_id)
-            .unwrap_or_else(|_| Uuid::nil());
-        kid
+impl Entity for Kid {
+    const TABLE: &'static str = "kid";
+    const NAME: &'static str = "Kid";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_id(&mut self, id: Uuid) {
+        self.id = id;
+    }
+
+    fn version(&self) -> Option<u64> {
+        Some(self.version)
+    }
+
+    fn set_version(&mut self, version: u64) {
+        self.version = version;
     }
 }
 
 pub struct KidRepository {
     db: Arc<Surreal<Any>>,
+    records: SurrealRepository<Kid>,
 }
 
 impl KidRepository {
     pub fn new(db: Arc<Surreal<Any>>) -> Self {
-        Self { db }
+        Self { records: SurrealRepository::new(db.clone()), db }
     }
 
-    pub async fn create(&self, kid: Kid) -> Result<Kid> {
-        let kid_id = kid.id.to_string();
-        let created: Option<KidRecord> = self.db
-            .create(("kid", &kid_id))
-            .content(kid)
-            .await?;
+    /// An owner's kids, leaving out archived ones
+    pub async fn list_by_owner(&self, owner_id: &str) -> Result<Vec<Kid>> {
+        let records: Vec<Record<Kid>> = self.db
+            .query("SELECT * FROM kid WHERE owner_id = $owner_id AND archived_at = NONE")
+            .bind(("owner_id", owner_id.to_string()))
+            .await?
+            .take(0)?;
 
-        created
-            .map(|rec| rec.into_kid())
-            .ok_or_else(|| Error::Database("Failed to create kid".to_string()))
+        into_entities(records)
     }
 
-    pub async fn get(&self, id: Uuid) -> Result<Kid> {
-        let record: Option<KidRecord> = self.db
-            .select(("kid", id.to_string()))
-            .await?;
+    pub async fn list_archived_by_owner(&self, owner_id: &str) -> Result<Vec<Kid>> {
+        let records: Vec<Record<Kid>> = self.db
+            .query("SELECT * FROM kid WHERE owner_id = $owner_id AND archived_at != NONE ORDER BY archived_at DESC")
+            .bind(("owner_id", owner_id.to_string()))
+            .await?
+            .take(0)?;
 
-        record
-            .map(|rec| rec.into_kid())
-            .ok_or_else(|| Error::NotFound(format!("Kid with id {}", id)))
+        into_entities(records)
     }
 
-    pub async fn list(&self) -> Result<Vec<Kid>> {
-        let records: Vec<KidRecord> = self.db
-            .select("kid")
-            .await?;
+    /// Kids archived before `cutoff`, across all owners
+    pub async fn list_archived_before(&self, cutoff: DateTime<Utc>) -> Result<Vec<Kid>> {
+        let records: Vec<Record<Kid>> = self.db
+            .query("SELECT * FROM kid WHERE archived_at != NONE AND archived_at < $cutoff")
+            .bind(("cutoff", cutoff))
+            .await?
+            .take(0)?;
 
-        Ok(records.into_iter().map(|rec| rec.into_kid()).collect())
+        into_entities(records)
     }
 
-    pub async fn list_by_owner(&self, owner_id: &str) -> Result<Vec<Kid>> {
-        let records: Vec<KidRecord> = self.db
-            .query("SELECT * FROM kid WHERE owner_id = $owner_id")
-            .bind(("owner_id", owner_id.to_string()))
-            .await?
-            .take(0)?;
+    /// Make a transaction fail unless the kid still exists and isn't archived
+    /// when it commits
+    pub fn ensure_active_in(&self, tx: &mut Transaction, id: Uuid) -> Result<()> {
+        let param = tx.bind(id.to_string())?;
+        tx.ensure(
+            format!("(SELECT VALUE archived_at FROM ONLY type::thing('kid', {param})) = NONE AND record::exists(type::thing('kid', {param}))"),
+            Error::Validation(format!("Kid {} was archived or deleted", id)),
+        );
+        Ok(())
+    }
 
-        Ok(records.into_iter().map(|rec| rec.into_kid()).collect())
+    /// Remove a kid together with everything that belongs to them: ledger
+    /// entries, task claims, photo records, withdrawals, transfers and their
+    /// kid login
+    ///
+    /// The other kid's side of a transfer stays in their ledger, unlinked
+    /// from the deleted transfer.
+    ///
+    /// Runs as one transaction, so either all of it is gone or none of it.
+    /// Photo files are not touched; callers delete those from the store.
+    pub async fn purge(&self, id: Uuid) -> Result<()> {
+        let mut tx = Transaction::new(self.db.clone());
+        let kid_id = tx.bind(id.to_string())?;
+        // Before deleting the kid's entries: SurrealDB's update misses the
+        // sibling's entries once the same transaction has deleted from the table
+        tx.push(format!(
+            "UPDATE ledger_entry SET transfer_id = NONE WHERE transfer_id IN \
+             (SELECT VALUE record::id(id) FROM transfer WHERE from_kid_id = {0} OR to_kid_id = {0})",
+            kid_id
+        ));
+        tx.push(format!("DELETE ledger_entry WHERE kid_id = type::thing('kid', {})", kid_id));
+        for table in ["task_claim", "attachment", "withdrawal", "user"] {
+            tx.push(format!("DELETE {} WHERE kid_id = {}", table, kid_id));
+        }
+        tx.push(format!("DELETE transfer WHERE from_kid_id = {0} OR to_kid_id = {0}", kid_id));
+        tx.push(format!("DELETE type::thing('kid', {})", kid_id));
+        tx.commit().await
     }
+}
 
-    pub async fn update(&self, kid: Kid) -> Result<Kid> {
-        let kid_id = kid.id;
+impl Repository<Kid> for KidRepository {
+    async fn create(&self, kid: Kid) -> Result<Kid> {
+        self.records.create(kid).await
+    }
 
-        // First check if the kid exists
-        let _existing: Kid = self.get(kid_id).await?;
+    async fn get(&self, id: Uuid) -> Result<Kid> {
+        self.records.get(id).await
+    }
 
-        // If it exists, update it
-        let updated: Option<KidRecord> = self.db
-            .update(("kid", kid_id.to_string()))
-            .content(kid)
-            .await?;
+    async fn list(&self) -> Result<Vec<Kid>> {
+        self.records.list().await
+    }
 
-        updated
-            .map(|rec| rec.into_kid())
-            .ok_or_else(|| Error::NotFound(format!("Kid with id {}", kid_id)))
+    async fn update(&self, kid: Kid) -> Result<Kid> {
+        self.records.update(kid).await
     }
 
-    pub async fn delete(&self, id: Uuid) -> Result<()> {
-        let _deleted: Option<KidRecord> = self.db
-            .delete(("kid", id.to_string()))
-            .await?;
-        Ok(())
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        self.records.delete(id).await
     }
 }
-
@@ -1,33 +1,53 @@
 use surrealdb::Surreal;
 use surrealdb::engine::any::Any;
 use surrealdb::sql::Thing;
-use crate::models::{LedgerEntry, Ledger};
+use crate::budget::{report_period, BudgetStatus};
+use crate::db::repository::{into_entities, Entity, Record};
+use crate::db::Transaction;
+use crate::models::{EntryType, LedgerEntry, Ledger, TaskCategory};
+use crate::reports::{EarningsTotal, ReportPeriod};
 use crate::error::{Error, Result};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Deserialize;
 use uuid::Uuid;
 use std::sync::Arc;
-use serde::{Deserialize, Serialize};
-
-// Helper struct to handle SurrealDB record with id
-#[derive(Debug, Serialize, Deserialize)]
-struct LedgerEntryRecord {
-    id: Thing,
-    #[serde(flatten)]
-    entry: LedgerEntry,
-}
 
-impl LedgerEntryRecord {
-    fn into_entry(self) -> LedgerEntry {
-        let mut entry = self.entry;
-        // Extract UUID from SurrealDB Thing
-        // SurrealDB wraps the ID in angle brackets: ⟨uuid⟩
-        let id_str = self.id.id.to_string();
-        let clean_id = id_str.trim_start_matches('⟨').trim_end_matches('⟩');
-        entry.id = Uuid::parse_str(clean_id)
-            .unwrap_or_else(|_| Uuid::nil());
-        entry
+impl Entity for LedgerEntry {
+    const TABLE: &'static str = "ledger_entry";
+    const NAME: &'static str = "Ledger entry";
+    const FIELDS: &'static str = ENTRY_FIELDS;
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_id(&mut self, id: Uuid) {
+        self.id = id;
     }
 }
 
+/// Entries store `kid_id` as a `record<kid>` link (the schema converts the
+/// string `LedgerEntry` writes), so reads turn it back into the plain ID
+const ENTRY_FIELDS: &str = "*, record::id(kid_id) AS kid_id";
+
+/// The `record<kid>` link ledger entries refer to their kid by
+fn kid_link(kid_id: Uuid) -> Thing {
+    Thing::from(("kid", kid_id.to_string().as_str()))
+}
+
+/// A row of `earnings_totals`' query
+#[derive(Deserialize)]
+struct TotalRow {
+    kid: String,
+    bucket: String,
+    task_id: Option<String>,
+    category: Option<TaskCategory>,
+    entry_type: EntryType,
+    entries: u64,
+    total: Decimal,
+}
+
 pub struct LedgerRepository {
     db: Arc<Surreal<Any>>,
 }
@@ -39,34 +59,156 @@ impl LedgerRepository {
 
     pub async fn create_entry(&self, entry: LedgerEntry) -> Result<LedgerEntry> {
         let entry_id = entry.id.to_string();
-        let created: Option<LedgerEntryRecord> = self.db
-            .create(("ledger_entry", &entry_id))
-            .content(entry)
-            .await?;
+        let created: Option<Record<LedgerEntry>> = self.db
+            .query("CREATE type::thing('ledger_entry', $id) CONTENT $entry RETURN NONE")
+            .query(format!("SELECT {} FROM ONLY type::thing('ledger_entry', $id)", ENTRY_FIELDS))
+            .bind(("id", entry_id))
+            .bind(("entry", entry))
+            .await?
+            .take(1)?;
 
         created
-            .map(|rec| rec.into_entry())
-            .ok_or_else(|| Error::Database("Failed to create ledger entry".to_string()))
+            .ok_or_else(|| Error::Database("Failed to create ledger entry".to_string()))?
+            .into_entity()
+    }
+
+    /// Start a transaction on this repository's database
+    pub fn begin(&self) -> Transaction {
+        Transaction::new(self.db.clone())
+    }
+
+    /// Add creating `entry` to a transaction
+    pub fn create_entry_in(&self, tx: &mut Transaction, entry: &LedgerEntry) -> Result<()> {
+        let id = tx.bind(entry.id.to_string())?;
+        let content = tx.bind(entry.clone())?;
+        tx.push(format!("CREATE type::thing('ledger_entry', {}) CONTENT {}", id, content));
+        Ok(())
+    }
+
+    /// Make a transaction fail unless the kid's balance covers `amount` when
+    /// it runs, so two withdrawals can't both spend the same money
+    pub fn ensure_balance_in(&self, tx: &mut Transaction, kid_id: Uuid, amount: Decimal) -> Result<()> {
+        let kid = tx.bind(kid_link(kid_id))?;
+        let amount_param = tx.bind(amount)?;
+        tx.ensure(
+            format!(
+                "math::sum((SELECT VALUE <decimal> amount FROM ledger_entry WHERE kid_id = {})) >= <decimal> {}",
+                kid, amount_param
+            ),
+            Error::Validation(format!("The balance doesn't cover ${:.2}", amount)),
+        );
+        Ok(())
+    }
+
+    /// Make a transaction fail if paying `kid_id` another `amount` of task
+    /// earnings would go over the budget `status` was worked out for, so two
+    /// completions can't both use its last room
+    ///
+    /// A household budget counts every kid of the household `kid_id` is in.
+    pub fn ensure_within_budget_in(
+        &self,
+        tx: &mut Transaction,
+        kid_id: Uuid,
+        status: &BudgetStatus,
+        amount: Decimal,
+    ) -> Result<()> {
+        let kid = tx.bind(kid_link(kid_id))?;
+        let kids = match status.kid_id {
+            Some(_) => format!("kid_id = {}", kid),
+            None => format!("kid_id.owner_id = (SELECT VALUE owner_id FROM ONLY {})", kid),
+        };
+        let end = report_period(status.budget.period).next(status.period_start);
+        let from = tx.bind(status.period_start.and_time(chrono::NaiveTime::MIN).and_utc())?;
+        let to = tx.bind(end.and_time(chrono::NaiveTime::MIN).and_utc())?;
+        let amount_param = tx.bind(amount)?;
+        let budget = tx.bind(status.budget.amount)?;
+        tx.ensure(
+            format!(
+                "math::sum((SELECT VALUE <decimal> amount FROM ledger_entry WHERE {} AND entry_type = 'Earned' \
+                 AND <datetime> created_at >= <datetime> {} AND <datetime> created_at < <datetime> {})) \
+                 + <decimal> {} <= <decimal> {}",
+                kids, from, to, amount_param, budget
+            ),
+            Error::Validation(status.refusal(amount)),
+        );
+        Ok(())
+    }
+
+    pub async fn get_entry(&self, id: Uuid) -> Result<LedgerEntry> {
+        let record: Option<Record<LedgerEntry>> = self.db
+            .query(format!("SELECT {} FROM ONLY type::thing('ledger_entry', $id)", ENTRY_FIELDS))
+            .bind(("id", id.to_string()))
+            .await?
+            .take(0)?;
+
+        record
+            .ok_or_else(|| Error::NotFound(format!("Ledger entry with id {}", id)))?
+            .into_entity()
     }
 
     pub async fn get_ledger(&self, kid_id: Uuid) -> Result<Ledger> {
         let mut response = self.db
-            .query("SELECT * FROM ledger_entry WHERE string::lowercase(kid_id) = string::lowercase($kid_id) ORDER BY created_at ASC")
-            .bind(("kid_id", kid_id.to_string()))
+            .query(format!("SELECT {} FROM ledger_entry WHERE kid_id = $kid ORDER BY created_at ASC", ENTRY_FIELDS))
+            .bind(("kid", kid_link(kid_id)))
             .await?;
 
-        let records: Vec<LedgerEntryRecord> = response.take(0)?;
-        let entries: Vec<LedgerEntry> = records.into_iter().map(|rec| rec.into_entry()).collect();
+        let records: Vec<Record<LedgerEntry>> = response.take(0)?;
+        let entries: Vec<LedgerEntry> = into_entities(records)?;
         Ok(Ledger::new(kid_id, entries))
     }
 
     pub async fn list_entries(&self, kid_id: Uuid) -> Result<Vec<LedgerEntry>> {
         let mut response = self.db
-            .query("SELECT * FROM ledger_entry WHERE string::lowercase(kid_id) = string::lowercase($kid_id) ORDER BY created_at DESC")
-            .bind(("kid_id", kid_id.to_string()))
+            .query(format!("SELECT {} FROM ledger_entry WHERE kid_id = $kid ORDER BY created_at DESC", ENTRY_FIELDS))
+            .bind(("kid", kid_link(kid_id)))
             .await?;
 
-        let records: Vec<LedgerEntryRecord> = response.take(0)?;
-        Ok(records.into_iter().map(|rec| rec.into_entry()).collect())
+        let records: Vec<Record<LedgerEntry>> = response.take(0)?;
+        into_entities(records)
+    }
+
+    /// Entries of `kid_ids` from `from` up to (not including) `to`, summed
+    /// per kid, period, task, category and entry type by the database
+    pub async fn earnings_totals(
+        &self,
+        kid_ids: &[Uuid],
+        period: ReportPeriod,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<EarningsTotal>> {
+        // Amounts and times are stored as strings; the inner query converts
+        // them so the outer one can group and sum
+        let rows: Vec<TotalRow> = self.db
+            .query(
+                "SELECT kid, bucket, task_id, category, entry_type, math::sum(amount) AS total, count() AS entries FROM (
+                    SELECT record::id(kid_id) AS kid, time::format(<datetime> created_at, $format) AS bucket,
+                        task_id, category, entry_type, <decimal> amount AS amount
+                    FROM ledger_entry
+                    WHERE kid_id IN $kids AND <datetime> created_at >= <datetime> $from AND <datetime> created_at < <datetime> $to
+                ) GROUP BY kid, bucket, task_id, category, entry_type",
+            )
+            .bind(("format", period.bucket_format()))
+            .bind(("kids", kid_ids.iter().map(|id| kid_link(*id)).collect::<Vec<_>>()))
+            .bind(("from", from.and_time(chrono::NaiveTime::MIN).and_utc()))
+            .bind(("to", to.and_time(chrono::NaiveTime::MIN).and_utc()))
+            .await?
+            .take(0)?;
+
+        rows.into_iter()
+            .map(|row| {
+                let invalid = |what: &str| Error::Database(format!("Unexpected {} in earnings totals", what));
+                Ok(EarningsTotal {
+                    kid_id: Uuid::parse_str(&row.kid).map_err(|_| invalid("kid ID"))?,
+                    period_start: period.parse_bucket(&row.bucket).ok_or_else(|| invalid("period"))?,
+                    task_id: row.task_id
+                        .map(|id| Uuid::parse_str(&id).map_err(|_| invalid("task ID")))
+                        .transpose()?,
+                    category: row.category,
+                    entry_type: row.entry_type,
+                    entries: row.entries,
+                    total: row.total,
+                })
+            })
+            .collect()
     }
 }
@@ -0,0 +1,182 @@
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use crate::db::Transaction;
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+
+/// A SurrealQL script that changes the schema or data, applied once per
+/// database in version order
+#[derive(Debug)]
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub script: &'static str,
+}
+
+impl Migration {
+    /// SHA-256 of the script, recorded when it is applied so later edits to
+    /// it are noticed
+    pub fn checksum(&self) -> String {
+        Sha256::digest(self.script.as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+/// Every migration, oldest first
+///
+/// Never edit or remove one that has been released; add a new one instead.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial_schema",
+        script: include_str!("../../migrations/0001_initial_schema.surql"),
+    },
+    Migration {
+        version: 2,
+        name: "indexes_and_kid_links",
+        script: include_str!("../../migrations/0002_indexes_and_kid_links.surql"),
+    },
+    Migration {
+        version: 3,
+        name: "withdrawals",
+        script: include_str!("../../migrations/0003_withdrawals.surql"),
+    },
+    Migration {
+        version: 4,
+        name: "transfers",
+        script: include_str!("../../migrations/0004_transfers.surql"),
+    },
+];
+
+/// A migration as recorded in the `schema_migration` table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub name: String,
+    pub checksum: String,
+    pub applied_at: DateTime<Utc>,
+}
+
+/// Brings a database's schema up to date
+pub struct MigrationRunner {
+    db: Arc<Surreal<Any>>,
+    migrations: &'static [Migration],
+}
+
+impl MigrationRunner {
+    pub fn new(db: Arc<Surreal<Any>>) -> Self {
+        Self::with_migrations(db, MIGRATIONS)
+    }
+
+    pub fn with_migrations(db: Arc<Surreal<Any>>, migrations: &'static [Migration]) -> Self {
+        Self { db, migrations }
+    }
+
+    /// Migrations applied to this database, oldest first
+    pub async fn applied(&self) -> Result<Vec<AppliedMigration>> {
+        let applied: Vec<AppliedMigration> = self.db
+            .query("SELECT version, name, checksum, applied_at FROM schema_migration ORDER BY version")
+            .await?
+            .take(0)?;
+        Ok(applied)
+    }
+
+    /// Migrations not yet applied, in the order they will run
+    ///
+    /// Fails if an applied migration's script has changed since, or the
+    /// database was migrated by a newer version of the app.
+    pub async fn pending(&self) -> Result<Vec<&'static Migration>> {
+        let applied = self.applied().await?;
+        for record in &applied {
+            let migration = self.migrations
+                .iter()
+                .find(|m| m.version == record.version)
+                .ok_or_else(|| Error::Database(format!(
+                    "Database has migration {} ({}), which this version doesn't know; upgrade the app",
+                    record.version, record.name
+                )))?;
+            if migration.checksum() != record.checksum {
+                return Err(Error::Database(format!(
+                    "Migration {} ({}) was changed after it was applied",
+                    migration.version, migration.name
+                )));
+            }
+        }
+
+        Ok(self.migrations
+            .iter()
+            .filter(|m| !applied.iter().any(|a| a.version == m.version))
+            .collect())
+    }
+
+    /// Apply pending migrations, returning the ones that ran
+    ///
+    /// Each migration runs in its own transaction together with its record
+    /// in `schema_migration`, so a failed one leaves nothing half done.
+    pub async fn run(&self) -> Result<Vec<&'static Migration>> {
+        let pending = self.pending().await?;
+        let mut ran = Vec::new();
+        for migration in pending {
+            if self.apply(migration).await? {
+                ran.push(migration);
+            }
+        }
+        Ok(ran)
+    }
+
+    /// Returns false if another process applied the migration first
+    async fn apply(&self, migration: &Migration) -> Result<bool> {
+        let mut tx = Transaction::new(self.db.clone());
+        let version = tx.bind(migration.version)?;
+        tx.ensure(
+            format!("!record::exists(type::thing('schema_migration', {version}))"),
+            Error::Database(format!("Migration {} is already applied", migration.version)),
+        );
+        tx.push(migration.script.trim().trim_end_matches(';'));
+
+        let record = tx.bind(AppliedMigration {
+            version: migration.version,
+            name: migration.name.to_string(),
+            checksum: migration.checksum(),
+            applied_at: Utc::now(),
+        })?;
+        tx.push(format!("CREATE type::thing('schema_migration', {version}) CONTENT {record}"));
+
+        match tx.commit().await {
+            Ok(()) => Ok(true),
+            // Servers starting at the same time race to migrate; only one wins
+            Err(e) => match self.applied().await?.iter().find(|a| a.version == migration.version) {
+                Some(applied) if applied.checksum == migration.checksum() => Ok(false),
+                _ => Err(Error::Database(format!(
+                    "Migration {} ({}) failed: {}",
+                    migration.version, migration.name, e
+                ))),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        for (i, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version as usize, i + 1);
+        }
+    }
+
+    #[test]
+    fn test_checksum_changes_with_script() {
+        let original = Migration { version: 1, name: "test", script: "DEFINE TABLE test;" };
+        let edited = Migration { script: "DEFINE TABLE test SCHEMAFULL;", ..original };
+        assert_eq!(original.checksum().len(), 64);
+        assert_ne!(original.checksum(), edited.checksum());
+    }
+}
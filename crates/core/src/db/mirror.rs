@@ -0,0 +1,254 @@
+use surrealdb::{Notification, Surreal};
+use surrealdb::engine::any::{self, Any};
+use surrealdb::sql::Thing;
+use crate::config::DatabaseConfig;
+use crate::db::repository::{Entity, Repository, SurrealRepository};
+use crate::db::sqlite::{Change, Row, SqliteDatabase};
+use crate::db::{MigrationRunner, MIGRATIONS};
+use crate::models::{
+    Attachment, Household, IdempotencyKey, Invite, Kid, LedgerEntry, Task, TaskClaim, Transfer, User, Withdrawal,
+};
+use crate::error::{Error, Result};
+use futures::{FutureExt, StreamExt};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Weak};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Tables SQLite mode keeps, in the order they are loaded
+///
+/// Every table the app writes belongs here, or its records are gone after a
+/// restart.
+const TABLES: &[&str] = &[
+    MIGRATION_TABLE,
+    "household",
+    "user",
+    "kid",
+    "task",
+    "ledger_entry",
+    "task_claim",
+    "attachment",
+    "withdrawal",
+    "transfer",
+    "invite",
+    "idempotency_key",
+];
+
+/// Keyed by the migration's version rather than a UUID
+const MIGRATION_TABLE: &str = "schema_migration";
+
+/// Files open in this process, so the web server and the MCP server it runs
+/// share one copy of the data
+static OPEN: Mutex<Vec<(PathBuf, Weak<SqliteMirror>)>> = Mutex::const_new(Vec::new());
+
+/// What a live query says about a changed record
+#[derive(Deserialize)]
+struct Changed {
+    id: Thing,
+}
+
+/// `DatabaseMode::Sqlite`: queries run on an in-memory SurrealDB loaded from
+/// a SQLite file, and every change is written back to the file
+///
+/// Changes are saved in the background a moment after they commit, by
+/// reading the changed records again, so the file always ends up with their
+/// latest version. `flush` saves everything at once, for commands that exit
+/// straight after making changes.
+pub(crate) struct SqliteMirror {
+    path: PathBuf,
+    client: Arc<Surreal<Any>>,
+    sqlite: SqliteDatabase,
+    /// Held while copying records, so an older copy never overwrites a newer one
+    copying: Mutex<()>,
+}
+
+impl SqliteMirror {
+    /// Load the file at `config.path` and start saving changes to it
+    ///
+    /// Brings back the schema the file was last used with, by running the
+    /// migrations it records on the empty database; newer migrations are
+    /// left for `MigrationRunner`.
+    pub(crate) async fn open(config: &DatabaseConfig) -> Result<Arc<Self>> {
+        let path = config.path.as_ref()
+            .ok_or_else(|| Error::Database("SQLite mode requires database path".to_string()))?;
+        let key = std::path::absolute(path)
+            .map_err(|e| Error::Database(format!("Invalid database path {}: {}", path.display(), e)))?;
+        // Held until the file is loaded, so servers starting together don't both load it
+        let mut open = OPEN.lock().await;
+        open.retain(|(_, mirror)| mirror.strong_count() > 0);
+        if let Some(mirror) = open.iter().find(|(path, _)| *path == key).and_then(|(_, mirror)| mirror.upgrade()) {
+            return Ok(mirror);
+        }
+
+        let sqlite = SqliteDatabase::init_with_config(config)?;
+        sqlite.lock(path)?;
+
+        let client = any::connect("memory")
+            .await
+            .map_err(|e| Error::Database(format!("Failed to connect to database: {}", e)))?;
+        client.use_ns(config.namespace.as_deref().unwrap_or("loaa"))
+            .use_db(config.database.as_deref().unwrap_or("main"))
+            .await
+            .map_err(|e| Error::Database(format!("Failed to set namespace/database: {}", e)))?;
+        let client = Arc::new(client);
+
+        let applied: Vec<u32> = sqlite.rows(MIGRATION_TABLE)?
+            .iter()
+            .map(|row| row.version as u32)
+            .collect();
+        let recorded = MIGRATIONS.iter().take_while(|m| applied.contains(&m.version)).count();
+        MigrationRunner::with_migrations(client.clone(), &MIGRATIONS[..recorded]).run().await?;
+        for &table in TABLES {
+            load(&client, table, sqlite.rows(table)?).await?;
+        }
+
+        let query = TABLES.iter().map(|table| format!("LIVE SELECT * FROM {};", table)).collect::<String>();
+        let mut changes = client.query(query).await?.stream::<Notification<Changed>>(())?;
+
+        let mirror = Arc::new(Self {
+            path: key.clone(),
+            client: client.clone(),
+            sqlite,
+            copying: Mutex::new(()),
+        });
+
+        // Runs as long as the runtime does, since repositories hold on to
+        // the client long after the `Database` it came from is dropped
+        let saver = mirror.clone();
+        tokio::spawn(async move {
+            while let Some(first) = changes.next().await {
+                // Save whatever else has arrived along with it in the same transaction
+                let mut changed = BTreeSet::new();
+                let mut next = Some(first);
+                while let Some(notification) = next {
+                    match notification {
+                        Ok(notification) => {
+                            changed.insert((notification.data.id.tb, notification.data.id.id.to_raw()));
+                        }
+                        Err(e) => eprintln!("WARNING: SQLite mode missed a change: {}", e),
+                    }
+                    next = changes.next().now_or_never().flatten();
+                }
+
+                if let Err(e) = saver.save(changed).await {
+                    eprintln!("WARNING: Failed to save changes to {}: {}", saver.path.display(), e);
+                }
+            }
+        });
+
+        open.push((key, Arc::downgrade(&mirror)));
+        Ok(mirror)
+    }
+
+    pub(crate) fn client(&self) -> Arc<Surreal<Any>> {
+        self.client.clone()
+    }
+
+    /// Copy every record to the file, replacing what it held
+    pub(crate) async fn flush(&self) -> Result<()> {
+        let _copying = self.copying.lock().await;
+        let mut tables = Vec::new();
+        for &table in TABLES {
+            tables.push((table, read(&self.client, table, None).await?));
+        }
+        self.sqlite.replace(&tables)
+    }
+
+    /// Copy the current version of some records, or delete them from the
+    /// file if they're gone
+    async fn save(&self, changed: BTreeSet<(String, String)>) -> Result<()> {
+        let _copying = self.copying.lock().await;
+        let mut changes = Vec::new();
+        for (table, id) in changed {
+            match read(&self.client, &table, Some(&id)).await?.pop() {
+                Some(row) => changes.push(Change::Save { table, row }),
+                None => changes.push(Change::Delete { table, id }),
+            }
+        }
+        self.sqlite.apply(&changes)
+    }
+}
+
+/// A table's records as rows, or just the one with `id`
+async fn read(db: &Arc<Surreal<Any>>, table: &str, id: Option<&str>) -> Result<Vec<Row>> {
+    match table {
+        MIGRATION_TABLE => read_migrations(db, id).await,
+        "household" => read_entities::<Household>(db, id).await,
+        "user" => read_entities::<User>(db, id).await,
+        "kid" => read_entities::<Kid>(db, id).await,
+        "task" => read_entities::<Task>(db, id).await,
+        "ledger_entry" => read_entities::<LedgerEntry>(db, id).await,
+        "task_claim" => read_entities::<TaskClaim>(db, id).await,
+        "attachment" => read_entities::<Attachment>(db, id).await,
+        "withdrawal" => read_entities::<Withdrawal>(db, id).await,
+        "transfer" => read_entities::<Transfer>(db, id).await,
+        "invite" => read_entities::<Invite>(db, id).await,
+        "idempotency_key" => read_entities::<IdempotencyKey>(db, id).await,
+        _ => Err(Error::Internal(format!("Table {} isn't kept in SQLite", table))),
+    }
+}
+
+async fn read_entities<T: Entity>(db: &Arc<Surreal<Any>>, id: Option<&str>) -> Result<Vec<Row>> {
+    let repository = SurrealRepository::<T>::new(db.clone());
+    let entities = match id {
+        None => repository.list().await?,
+        Some(id) => {
+            let id = Uuid::parse_str(id)
+                .map_err(|e| Error::Database(format!("Record {}:{} doesn't have a UUID as its ID: {}", T::TABLE, id, e)))?;
+            match repository.get(id).await {
+                Ok(entity) => vec![entity],
+                Err(Error::NotFound(_)) => Vec::new(),
+                Err(e) => return Err(e),
+            }
+        }
+    };
+    entities
+        .iter()
+        .map(|entity| Ok(Row {
+            id: entity.id().to_string(),
+            version: entity.version().unwrap_or(0),
+            data: serde_json::to_string(entity)?,
+        }))
+        .collect()
+}
+
+async fn read_migrations(db: &Arc<Surreal<Any>>, id: Option<&str>) -> Result<Vec<Row>> {
+    MigrationRunner::new(db.clone())
+        .applied()
+        .await?
+        .into_iter()
+        .filter(|migration| id.is_none_or(|id| id == migration.version.to_string()))
+        .map(|migration| Ok(Row {
+            id: migration.version.to_string(),
+            version: migration.version.into(),
+            data: serde_json::to_string(&migration)?,
+        }))
+        .collect()
+}
+
+/// Replace a table's records with the file's rows
+async fn load(db: &Surreal<Any>, table: &str, rows: Vec<Row>) -> Result<()> {
+    let records = rows
+        .into_iter()
+        .map(|row| {
+            let mut data: Value = serde_json::from_str(&row.data)?;
+            let id = match table {
+                MIGRATION_TABLE => Value::from(row.version),
+                _ => Value::from(row.id),
+            };
+            data.as_object_mut()
+                .ok_or_else(|| Error::Database(format!("Row {} of {} isn't a JSON object", id, table)))?
+                .insert("id".to_string(), id);
+            Ok(data)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    db.query(format!("DELETE {table}; INSERT INTO {table} $records"))
+        .bind(("records", records))
+        .await?
+        .check()?;
+    Ok(())
+}
@@ -3,10 +3,34 @@ mod kid;
 mod task;
 mod ledger;
 mod user;
+mod attachment;
+mod claim;
+mod household;
+mod invite;
+mod transaction;
+mod idempotency;
+mod version;
+mod migrations;
+mod repository;
+mod sqlite;
+mod mirror;
+mod withdrawal;
+mod transfer;
 
 pub use connection::{Database, init_database, init_database_with_config};
 pub use kid::KidRepository;
-pub use task::TaskRepository;
+pub use task::{patch_task, TaskRepository};
 pub use ledger::LedgerRepository;
 pub use user::UserRepository;
+pub use attachment::AttachmentRepository;
+pub use claim::ClaimRepository;
+pub use household::HouseholdRepository;
+pub use invite::InviteRepository;
+pub use transaction::Transaction;
+pub use idempotency::IdempotencyRepository;
+pub use withdrawal::WithdrawalRepository;
+pub use transfer::TransferRepository;
+pub use migrations::{Migration, MigrationRunner, AppliedMigration, MIGRATIONS};
+pub use repository::{Entity, Repository, SurrealRepository, InMemoryRepository};
+pub use sqlite::{SqliteDatabase, SqliteRepository};
 
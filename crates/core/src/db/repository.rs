@@ -0,0 +1,260 @@
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use surrealdb::sql::Thing;
+use crate::db::version::{conflict, update_versioned};
+use crate::error::{Error, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// A model stored in its own table under a UUID
+///
+/// Models skip their `id` when serialized; the table's record ID holds it.
+pub trait Entity: Clone + Serialize + DeserializeOwned + Send + Sync + 'static {
+    const TABLE: &'static str;
+    /// What error messages call it ("Kid with id ...")
+    const NAME: &'static str;
+    /// What SurrealDB selects to read it, for tables that store a field
+    /// differently from how the model holds it
+    const FIELDS: &'static str = "*";
+
+    fn id(&self) -> Uuid;
+    fn set_id(&mut self, id: Uuid);
+
+    /// Models with a version are only updated if it still matches the stored
+    /// one (see `Task::version`)
+    fn version(&self) -> Option<u64> {
+        None
+    }
+
+    fn set_version(&mut self, _version: u64) {}
+}
+
+/// Basic storage for one kind of model
+///
+/// Implemented on SurrealDB by [`SurrealRepository`] and in memory by
+/// [`InMemoryRepository`], so code written against this trait can be tested
+/// without a database.
+pub trait Repository<T: Entity>: Send + Sync {
+    fn create(&self, entity: T) -> impl Future<Output = Result<T>> + Send;
+
+    /// Fails with `Error::NotFound` if there's no such record
+    fn get(&self, id: Uuid) -> impl Future<Output = Result<T>> + Send;
+
+    fn list(&self) -> impl Future<Output = Result<Vec<T>>> + Send;
+
+    /// Save changes to an existing record
+    ///
+    /// Versioned models fail with `Error::Conflict` if someone else saved
+    /// the record since it was read; the returned copy has the next version.
+    fn update(&self, entity: T) -> impl Future<Output = Result<T>> + Send;
+
+    /// Deleting a record that doesn't exist is not an error
+    fn delete(&self, id: Uuid) -> impl Future<Output = Result<()>> + Send;
+}
+
+/// A record as SurrealDB returns it, with its ID alongside the model's fields
+#[derive(Debug, Deserialize)]
+#[serde(bound = "T: Entity")]
+pub(crate) struct Record<T> {
+    id: Thing,
+    #[serde(flatten)]
+    entity: T,
+}
+
+impl<T: Entity> Record<T> {
+    pub(crate) fn into_entity(self) -> Result<T> {
+        let mut entity = self.entity;
+        entity.set_id(record_uuid(&self.id)?);
+        Ok(entity)
+    }
+}
+
+pub(crate) fn into_entities<T: Entity>(records: Vec<Record<T>>) -> Result<Vec<T>> {
+    records.into_iter().map(Record::into_entity).collect()
+}
+
+/// The UUID a record is stored under
+///
+/// Every record is created under a UUID, so anything else means the data was
+/// written by something other than this app.
+pub(crate) fn record_uuid(thing: &Thing) -> Result<Uuid> {
+    Uuid::parse_str(&thing.id.to_raw())
+        .map_err(|e| Error::Database(format!("Record {} doesn't have a UUID as its ID: {}", thing, e)))
+}
+
+/// [`Repository`] on a SurrealDB table
+pub struct SurrealRepository<T> {
+    db: Arc<Surreal<Any>>,
+    entity: PhantomData<fn() -> T>,
+}
+
+impl<T: Entity> SurrealRepository<T> {
+    pub fn new(db: Arc<Surreal<Any>>) -> Self {
+        Self { db, entity: PhantomData }
+    }
+}
+
+impl<T: Entity> Repository<T> for SurrealRepository<T> {
+    async fn create(&self, entity: T) -> Result<T> {
+        let created: Option<Record<T>> = self.db
+            .create((T::TABLE, entity.id().to_string()))
+            .content(entity)
+            .await?;
+
+        created
+            .ok_or_else(|| Error::Database(format!("Failed to create {}", T::NAME.to_lowercase())))?
+            .into_entity()
+    }
+
+    async fn get(&self, id: Uuid) -> Result<T> {
+        let record: Option<Record<T>> = self.db
+            .query(format!("SELECT {} FROM ONLY type::thing($table, $id)", T::FIELDS))
+            .bind(("table", T::TABLE))
+            .bind(("id", id.to_string()))
+            .await?
+            .take(0)?;
+
+        record
+            .ok_or_else(|| Error::NotFound(format!("{} with id {}", T::NAME, id)))?
+            .into_entity()
+    }
+
+    async fn list(&self) -> Result<Vec<T>> {
+        let records: Vec<Record<T>> = self.db
+            .query(format!("SELECT {} FROM type::table($table)", T::FIELDS))
+            .bind(("table", T::TABLE))
+            .await?
+            .take(0)?;
+
+        into_entities(records)
+    }
+
+    async fn update(&self, mut entity: T) -> Result<T> {
+        let id = entity.id();
+        let Some(read_version) = entity.version() else {
+            self.get(id).await?;
+            let updated: Option<Record<T>> = self.db
+                .update((T::TABLE, id.to_string()))
+                .content(entity)
+                .await?;
+            return updated
+                .ok_or_else(|| Error::NotFound(format!("{} with id {}", T::NAME, id)))?
+                .into_entity();
+        };
+
+        entity.set_version(read_version + 1);
+        let updated: Option<Record<T>> =
+            update_versioned(&self.db, T::TABLE, id, entity, read_version).await?;
+        match updated {
+            Some(record) => record.into_entity(),
+            None => {
+                // Not found if it's gone; otherwise it moved on
+                self.get(id).await?;
+                Err(conflict(format!("{} {}", T::NAME, id), read_version))
+            }
+        }
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        let _deleted: Option<Record<T>> = self.db
+            .delete((T::TABLE, id.to_string()))
+            .await?;
+        Ok(())
+    }
+}
+
+/// [`Repository`] on a `HashMap`, for unit tests that shouldn't need a
+/// database
+///
+/// Behaves like [`SurrealRepository`], version checks included. Clones share
+/// the same records.
+pub struct InMemoryRepository<T> {
+    records: Arc<Mutex<HashMap<Uuid, T>>>,
+}
+
+impl<T: Entity> InMemoryRepository<T> {
+    pub fn new() -> Self {
+        Self { records: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    fn records(&self) -> std::sync::MutexGuard<'_, HashMap<Uuid, T>> {
+        // A panicking test can't leave a half-written map behind
+        self.records.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<T: Entity> Default for InMemoryRepository<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for InMemoryRepository<T> {
+    fn clone(&self) -> Self {
+        Self { records: self.records.clone() }
+    }
+}
+
+impl<T: Entity> Repository<T> for InMemoryRepository<T> {
+    async fn create(&self, entity: T) -> Result<T> {
+        let mut records = self.records();
+        if records.contains_key(&entity.id()) {
+            return Err(Error::Database(format!("{} {} already exists", T::NAME, entity.id())));
+        }
+        records.insert(entity.id(), entity.clone());
+        Ok(entity)
+    }
+
+    async fn get(&self, id: Uuid) -> Result<T> {
+        self.records()
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("{} with id {}", T::NAME, id)))
+    }
+
+    async fn list(&self) -> Result<Vec<T>> {
+        Ok(self.records().values().cloned().collect())
+    }
+
+    async fn update(&self, mut entity: T) -> Result<T> {
+        let mut records = self.records();
+        let id = entity.id();
+        let stored = records
+            .get(&id)
+            .ok_or_else(|| Error::NotFound(format!("{} with id {}", T::NAME, id)))?;
+
+        if let Some(read_version) = entity.version() {
+            if stored.version() != Some(read_version) {
+                return Err(conflict(format!("{} {}", T::NAME, id), read_version));
+            }
+            entity.set_version(read_version + 1);
+        }
+        records.insert(id, entity.clone());
+        Ok(entity)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        self.records().remove(&id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_uuid() {
+        let id = Uuid::new_v4();
+        let thing = Thing::from(("kid", id.to_string().as_str()));
+        assert_eq!(record_uuid(&thing).unwrap(), id);
+
+        let thing = Thing::from(("kid", "not-a-uuid"));
+        assert!(matches!(record_uuid(&thing), Err(Error::Database(_))));
+    }
+}
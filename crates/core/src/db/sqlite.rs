@@ -0,0 +1,333 @@
+use rusqlite::{params, Connection, OptionalExtension, ToSql};
+use crate::config::{DatabaseConfig, DatabaseMode};
+use crate::db::repository::{Entity, Repository};
+use crate::db::version::conflict;
+use crate::models::{Kid, Ledger, LedgerEntry, Task, User};
+use crate::error::{Error, Result};
+use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use uuid::Uuid;
+
+/// Tables for the models SQLite can store
+///
+/// Each row holds a model as a JSON document next to its ID and version, so
+/// the models serialize exactly as they do for SurrealDB. Fields that
+/// queries filter on are indexed as JSON expressions. SQLite mode keeps
+/// every table here; `schema_migration` rows are keyed by the migration's
+/// version.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS household (id TEXT PRIMARY KEY, version INTEGER NOT NULL, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS kid (id TEXT PRIMARY KEY, version INTEGER NOT NULL, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS task (id TEXT PRIMARY KEY, version INTEGER NOT NULL, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS user (id TEXT PRIMARY KEY, version INTEGER NOT NULL, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS ledger_entry (id TEXT PRIMARY KEY, version INTEGER NOT NULL, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS task_claim (id TEXT PRIMARY KEY, version INTEGER NOT NULL, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS attachment (id TEXT PRIMARY KEY, version INTEGER NOT NULL, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS withdrawal (id TEXT PRIMARY KEY, version INTEGER NOT NULL, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS transfer (id TEXT PRIMARY KEY, version INTEGER NOT NULL, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS invite (id TEXT PRIMARY KEY, version INTEGER NOT NULL, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS idempotency_key (id TEXT PRIMARY KEY, version INTEGER NOT NULL, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS schema_migration (id TEXT PRIMARY KEY, version INTEGER NOT NULL, data TEXT NOT NULL);
+    CREATE INDEX IF NOT EXISTS kid_owner ON kid (json_extract(data, '$.owner_id'));
+    CREATE INDEX IF NOT EXISTS task_owner ON task (json_extract(data, '$.owner_id'));
+    CREATE UNIQUE INDEX IF NOT EXISTS user_username ON user (json_extract(data, '$.username'));
+    CREATE INDEX IF NOT EXISTS ledger_entry_kid ON ledger_entry (json_extract(data, '$.kid_id'));
+";
+
+/// A SQLite database file, for installs that don't want SurrealDB
+///
+/// `SqliteRepository` reads and writes kids, tasks, users and the ledger in
+/// it directly. In `DatabaseMode::Sqlite` the servers keep all their data in
+/// it (see `SqliteMirror`). Queries are quick enough on one machine that the
+/// connection is shared behind a mutex rather than pooled.
+#[derive(Clone)]
+pub struct SqliteDatabase {
+    conn: Arc<Mutex<Connection>>,
+}
+
+/// A record as a row of one of the tables in `SCHEMA`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Row {
+    pub(crate) id: String,
+    pub(crate) version: u64,
+    pub(crate) data: String,
+}
+
+/// A change for `SqliteDatabase::apply`
+#[derive(Debug)]
+pub(crate) enum Change {
+    Save { table: String, row: Row },
+    Delete { table: String, id: String },
+}
+
+impl SqliteDatabase {
+    /// Open the database at `config.path`, which requires `DatabaseMode::Sqlite`
+    pub fn init_with_config(config: &DatabaseConfig) -> Result<Self> {
+        if config.mode != DatabaseMode::Sqlite {
+            return Err(Error::Database("SqliteDatabase requires sqlite database mode".to_string()));
+        }
+        let path = config.path.as_ref()
+            .ok_or_else(|| Error::Database("SQLite mode requires database path".to_string()))?;
+        Self::open(path)
+    }
+
+    /// Open or create a database file, creating any missing tables
+    pub fn open(path: &Path) -> Result<Self> {
+        // Ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| Error::Database(format!("Failed to create database directory: {}", e)))?;
+        }
+        Self::init(Connection::open(path)?)
+    }
+
+    /// A database that lives only as long as this value and its clones
+    pub fn memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn: Arc::new(Mutex::new(conn)) })
+    }
+
+    pub fn repository<T: Entity>(&self) -> SqliteRepository<T> {
+        SqliteRepository { conn: self.conn.clone(), entity: PhantomData }
+    }
+
+    fn conn(&self) -> MutexGuard<'_, Connection> {
+        // SQLite rolls back a statement that didn't finish
+        self.conn.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Keep other processes out of the file until this connection closes
+    ///
+    /// Two processes each keeping their own copy of the data in memory would
+    /// overwrite each other's changes.
+    pub(crate) fn lock(&self, path: &Path) -> Result<()> {
+        let conn = self.conn();
+        conn.pragma_update(None, "locking_mode", "EXCLUSIVE")?;
+        // The lock is taken by the first write and then kept
+        conn.execute_batch("BEGIN EXCLUSIVE; COMMIT;").map_err(|e| match e.sqlite_error_code() {
+            Some(rusqlite::ErrorCode::DatabaseBusy) => Error::Database(format!(
+                "{} is in use by another Loa'a process", path.display()
+            )),
+            _ => e.into(),
+        })
+    }
+
+    /// Every row of a table, by ID
+    pub(crate) fn rows(&self, table: &str) -> Result<Vec<Row>> {
+        let conn = self.conn();
+        let mut statement = conn.prepare(&format!("SELECT id, version, data FROM {} ORDER BY id", table))?;
+        let rows = statement.query_map([], |row| Ok(Row { id: row.get(0)?, version: row.get(1)?, data: row.get(2)? }))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Save and delete rows in one transaction
+    pub(crate) fn apply(&self, changes: &[Change]) -> Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        for change in changes {
+            match change {
+                Change::Save { table, row } => tx.execute(
+                    &format!(
+                        "INSERT INTO {} (id, version, data) VALUES (?1, ?2, ?3)
+                         ON CONFLICT (id) DO UPDATE SET version = excluded.version, data = excluded.data",
+                        table
+                    ),
+                    params![row.id, row.version, row.data],
+                )?,
+                Change::Delete { table, id } => tx.execute(
+                    &format!("DELETE FROM {} WHERE id = ?1", table),
+                    params![id],
+                )?,
+            };
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Replace the whole contents of some tables in one transaction
+    pub(crate) fn replace(&self, tables: &[(&str, Vec<Row>)]) -> Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        for (table, rows) in tables {
+            tx.execute(&format!("DELETE FROM {}", table), [])?;
+            for row in rows {
+                tx.execute(
+                    &format!("INSERT INTO {} (id, version, data) VALUES (?1, ?2, ?3)", table),
+                    params![row.id, row.version, row.data],
+                )?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+/// [`Repository`] on a SQLite table
+pub struct SqliteRepository<T> {
+    conn: Arc<Mutex<Connection>>,
+    entity: PhantomData<fn() -> T>,
+}
+
+impl<T: Entity> SqliteRepository<T> {
+    fn conn(&self) -> MutexGuard<'_, Connection> {
+        // SQLite rolls back a statement that didn't finish
+        self.conn.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Records matching a `WHERE` clause over the `data` column
+    fn select(&self, condition: &str, order: &str, params: &[&dyn ToSql]) -> Result<Vec<T>> {
+        let conn = self.conn();
+        let mut statement = conn.prepare(&format!(
+            "SELECT id, data FROM {} WHERE {} ORDER BY {}, rowid",
+            T::TABLE, condition, order
+        ))?;
+        let rows = statement.query_map(params, |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+        rows.map(|row| {
+            let (id, data) = row?;
+            entity_from_row(&id, &data)
+        })
+        .collect()
+    }
+}
+
+/// Rebuild a model from its row, failing on an ID that isn't a UUID
+fn entity_from_row<T: Entity>(id: &str, data: &str) -> Result<T> {
+    let mut entity: T = serde_json::from_str(data)?;
+    let id = Uuid::parse_str(id)
+        .map_err(|e| Error::Database(format!("Record {}:{} doesn't have a UUID as its ID: {}", T::TABLE, id, e)))?;
+    entity.set_id(id);
+    Ok(entity)
+}
+
+impl<T: Entity> Repository<T> for SqliteRepository<T> {
+    async fn create(&self, entity: T) -> Result<T> {
+        let data = serde_json::to_string(&entity)?;
+        self.conn().execute(
+            &format!("INSERT INTO {} (id, version, data) VALUES (?1, ?2, ?3)", T::TABLE),
+            params![entity.id().to_string(), entity.version().unwrap_or(0), data],
+        )?;
+        Ok(entity)
+    }
+
+    async fn get(&self, id: Uuid) -> Result<T> {
+        let data: Option<String> = self.conn()
+            .query_row(
+                &format!("SELECT data FROM {} WHERE id = ?1", T::TABLE),
+                params![id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let data = data.ok_or_else(|| Error::NotFound(format!("{} with id {}", T::NAME, id)))?;
+        entity_from_row(&id.to_string(), &data)
+    }
+
+    async fn list(&self) -> Result<Vec<T>> {
+        self.select("1", "id", &[])
+    }
+
+    async fn update(&self, mut entity: T) -> Result<T> {
+        let id = entity.id();
+        let read_version = entity.version();
+        if let Some(read_version) = read_version {
+            entity.set_version(read_version + 1);
+        }
+
+        let data = serde_json::to_string(&entity)?;
+        let conn = self.conn();
+        let updated = match read_version {
+            Some(read_version) => conn.execute(
+                &format!("UPDATE {} SET version = ?2, data = ?3 WHERE id = ?1 AND version = ?4", T::TABLE),
+                params![id.to_string(), read_version + 1, data, read_version],
+            )?,
+            None => conn.execute(
+                &format!("UPDATE {} SET data = ?2 WHERE id = ?1", T::TABLE),
+                params![id.to_string(), data],
+            )?,
+        };
+        if updated == 1 {
+            return Ok(entity);
+        }
+
+        // Not found if it's gone; otherwise it moved on
+        let exists: bool = conn.query_row(
+            &format!("SELECT EXISTS (SELECT 1 FROM {} WHERE id = ?1)", T::TABLE),
+            params![id.to_string()],
+            |row| row.get(0),
+        )?;
+        match read_version {
+            Some(read_version) if exists => Err(conflict(format!("{} {}", T::NAME, id), read_version)),
+            _ => Err(Error::NotFound(format!("{} with id {}", T::NAME, id))),
+        }
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        self.conn().execute(
+            &format!("DELETE FROM {} WHERE id = ?1", T::TABLE),
+            params![id.to_string()],
+        )?;
+        Ok(())
+    }
+}
+
+impl SqliteRepository<Kid> {
+    /// An owner's kids, leaving out archived ones
+    pub async fn list_by_owner(&self, owner_id: &str) -> Result<Vec<Kid>> {
+        self.select(
+            "json_extract(data, '$.owner_id') = ?1 AND json_extract(data, '$.archived_at') IS NULL",
+            "julianday(json_extract(data, '$.created_at'))",
+            &[&owner_id],
+        )
+    }
+}
+
+impl SqliteRepository<Task> {
+    /// An owner's tasks, leaving out archived ones
+    pub async fn list_by_owner(&self, owner_id: &str) -> Result<Vec<Task>> {
+        self.select(
+            "json_extract(data, '$.owner_id') = ?1 AND json_extract(data, '$.archived_at') IS NULL",
+            "julianday(json_extract(data, '$.created_at'))",
+            &[&owner_id],
+        )
+    }
+}
+
+impl SqliteRepository<User> {
+    pub async fn get_by_username(&self, username: &str) -> Result<User> {
+        self.select("json_extract(data, '$.username') = ?1", "id", &[&username])?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::NotFound(format!("User with username '{}'", username)))
+    }
+}
+
+impl SqliteRepository<LedgerEntry> {
+    pub async fn create_entry(&self, entry: LedgerEntry) -> Result<LedgerEntry> {
+        self.create(entry).await
+    }
+
+    pub async fn get_entry(&self, id: Uuid) -> Result<LedgerEntry> {
+        self.get(id).await
+    }
+
+    pub async fn get_ledger(&self, kid_id: Uuid) -> Result<Ledger> {
+        let entries = self.select(
+            "json_extract(data, '$.kid_id') = ?1",
+            "julianday(json_extract(data, '$.created_at'))",
+            &[&kid_id.to_string()],
+        )?;
+        Ok(Ledger::new(kid_id, entries))
+    }
+
+    /// A kid's entries, newest first
+    pub async fn list_entries(&self, kid_id: Uuid) -> Result<Vec<LedgerEntry>> {
+        let mut entries = self.get_ledger(kid_id).await?.entries;
+        entries.reverse();
+        Ok(entries)
+    }
+}
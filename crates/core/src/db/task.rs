@@ -1,104 +1,206 @@
 use surrealdb::Surreal;
 use surrealdb::engine::any::Any;
-use surrealdb::sql::Thing;
-use crate::models::Task;
+use crate::db::Transaction;
+use crate::db::repository::{into_entities, Entity, Record, Repository, SurrealRepository};
+use crate::models::{Task, TaskCategory, TaskPatch};
+use crate::db::version::conflict;
 use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use std::sync::Arc;
-use serde::{Deserialize, Serialize};
-
-// Helper struct to handle SurrealDB record with id
-#[derive(Debug, Serialize, Deserialize)]
-struct TaskRecord {
-    id: Thing,
-    #[serde(flatten)]
-    task: Task,
-}
 
-impl TaskRecord {
-    fn into_task(self) -> Task {
-        let mut task = self.task;
-        // Extract UUID from SurrealDB Thing
-        // SurrealDB wraps the ID in angle brackets: ⟨uuid⟩
-        let id_str = self.id.id.to_string();
-        let clean_id = id_str.trim_start_matches('⟨').trim_end_matches('⟩');
-        task.id = Uuid::parse_str(clean_id)
-            .unwrap_or_else(|_| Uuid::nil());
-        task
+impl Entity for Task {
+    const TABLE: &'static str = "task";
+    const NAME: &'static str = "Task";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_id(&mut self, id: Uuid) {
+        self.id = id;
+    }
+
+    fn version(&self) -> Option<u64> {
+        Some(self.version)
+    }
+
+    fn set_version(&mut self, version: u64) {
+        self.version = version;
     }
 }
 
+/// How often `patch_task` re-reads a task that keeps changing
+/// under it before giving up with a conflict
+const PATCH_ATTEMPTS: u32 = 3;
+
 pub struct TaskRepository {
     db: Arc<Surreal<Any>>,
+    records: SurrealRepository<Task>,
 }
 
 impl TaskRepository {
     pub fn new(db: Arc<Surreal<Any>>) -> Self {
-        Self { db }
+        Self { records: SurrealRepository::new(db.clone()), db }
     }
 
-    pub async fn create(&self, task: Task) -> Result<Task> {
-        let task_id = task.id.to_string();
-        let created: Option<TaskRecord> = self.db
-            .create(("task", &task_id))
-            .content(task)
-            .await?;
+    /// An owner's tasks, leaving out archived ones
+    pub async fn list_by_owner(&self, owner_id: &str) -> Result<Vec<Task>> {
+        let records: Vec<Record<Task>> = self.db
+            .query("SELECT * FROM task WHERE owner_id = $owner_id AND archived_at = NONE")
+            .bind(("owner_id", owner_id.to_string()))
+            .await?
+            .take(0)?;
 
-        created
-            .map(|rec| rec.into_task())
-            .ok_or_else(|| Error::Database("Failed to create task".to_string()))
+        into_entities(records)
     }
 
-    pub async fn get(&self, id: Uuid) -> Result<Task> {
-        let record: Option<TaskRecord> = self.db
-            .select(("task", id.to_string()))
-            .await?;
+    pub async fn list_by_category(&self, owner_id: &str, category: TaskCategory) -> Result<Vec<Task>> {
+        let records: Vec<Record<Task>> = self.db
+            .query("SELECT * FROM task WHERE owner_id = $owner_id AND category = $category AND archived_at = NONE")
+            .bind(("owner_id", owner_id.to_string()))
+            .bind(("category", category))
+            .await?
+            .take(0)?;
 
-        record
-            .map(|rec| rec.into_task())
-            .ok_or_else(|| Error::NotFound(format!("Task with id {}", id)))
+        into_entities(records)
     }
 
-    pub async fn list(&self) -> Result<Vec<Task>> {
-        let records: Vec<TaskRecord> = self.db
-            .select("task")
-            .await?;
+    pub async fn list_by_tag(&self, owner_id: &str, tag: &str) -> Result<Vec<Task>> {
+        let records: Vec<Record<Task>> = self.db
+            .query("SELECT * FROM task WHERE owner_id = $owner_id AND tags CONTAINS $tag AND archived_at = NONE")
+            .bind(("owner_id", owner_id.to_string()))
+            .bind(("tag", tag.trim().to_lowercase()))
+            .await?
+            .take(0)?;
 
-        Ok(records.into_iter().map(|rec| rec.into_task()).collect())
+        into_entities(records)
     }
 
-    pub async fn list_by_owner(&self, owner_id: &str) -> Result<Vec<Task>> {
-        let records: Vec<TaskRecord> = self.db
-            .query("SELECT * FROM task WHERE owner_id = $owner_id")
+    /// List an owner's tasks, optionally narrowed to a category and/or tag
+    pub async fn list_filtered(
+        &self,
+        owner_id: &str,
+        category: Option<TaskCategory>,
+        tag: Option<&str>,
+    ) -> Result<Vec<Task>> {
+        let mut tasks = match category {
+            Some(category) => self.list_by_category(owner_id, category).await?,
+            None => self.list_by_owner(owner_id).await?,
+        };
+        if let Some(tag) = tag {
+            tasks.retain(|t| t.has_tag(tag));
+        }
+        Ok(tasks)
+    }
+
+    pub async fn list_archived_by_owner(&self, owner_id: &str) -> Result<Vec<Task>> {
+        let records: Vec<Record<Task>> = self.db
+            .query("SELECT * FROM task WHERE owner_id = $owner_id AND archived_at != NONE ORDER BY archived_at DESC")
             .bind(("owner_id", owner_id.to_string()))
             .await?
             .take(0)?;
 
-        Ok(records.into_iter().map(|rec| rec.into_task()).collect())
+        into_entities(records)
     }
 
-    pub async fn update(&self, task: Task) -> Result<Task> {
-        let task_id = task.id;
+    /// Tasks archived before `cutoff`, across all owners
+    pub async fn list_archived_before(&self, cutoff: DateTime<Utc>) -> Result<Vec<Task>> {
+        let records: Vec<Record<Task>> = self.db
+            .query("SELECT * FROM task WHERE archived_at != NONE AND archived_at < $cutoff")
+            .bind(("cutoff", cutoff))
+            .await?
+            .take(0)?;
 
-        // First check if the task exists
-        let _existing: Task = self.get(task_id).await?;
+        into_entities(records)
+    }
 
-        // If it exists, update it
-        let updated: Option<TaskRecord> = self.db
-            .update(("task", task_id.to_string()))
-            .content(task)
-            .await?;
+    /// Add creating `task` to a transaction
+    pub fn create_in(&self, tx: &mut Transaction, task: &Task) -> Result<()> {
+        let id = tx.bind(task.id.to_string())?;
+        let content = tx.bind(task.clone())?;
+        tx.push(format!("CREATE type::thing('task', {}) CONTENT {}", id, content));
+        Ok(())
+    }
 
-        updated
-            .map(|rec| rec.into_task())
-            .ok_or_else(|| Error::NotFound(format!("Task with id {}", task_id)))
+    /// Make a transaction fail unless the task still exists and isn't
+    /// archived when it commits
+    pub fn ensure_active_in(&self, tx: &mut Transaction, id: Uuid) -> Result<()> {
+        let param = tx.bind(id.to_string())?;
+        tx.ensure(
+            format!("(SELECT VALUE archived_at FROM ONLY type::thing('task', {param})) = NONE AND record::exists(type::thing('task', {param}))"),
+            Error::Validation(format!("Task {} was archived or deleted", id)),
+        );
+        Ok(())
     }
 
-    pub async fn delete(&self, id: Uuid) -> Result<()> {
-        let _deleted: Option<TaskRecord> = self.db
-            .delete(("task", id.to_string()))
-            .await?;
+    /// Add saving `task` to a transaction, which fails with
+    /// `Error::Conflict` if someone else saved the task since it was read
+    pub fn update_in(&self, tx: &mut Transaction, task: &Task) -> Result<()> {
+        let id = tx.bind(task.id.to_string())?;
+        let version = tx.bind(task.version)?;
+        tx.ensure(
+            format!("((SELECT VALUE version FROM ONLY type::thing('task', {id})) ?? 0) = {version}"),
+            conflict(format!("Task {}", task.id), task.version),
+        );
+
+        let mut next = task.clone();
+        next.version += 1;
+        let content = tx.bind(next)?;
+        tx.push(format!("UPDATE type::thing('task', {}) CONTENT {}", id, content));
         Ok(())
     }
 }
 
+impl Repository<Task> for TaskRepository {
+    async fn create(&self, task: Task) -> Result<Task> {
+        self.records.create(task).await
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Task> {
+        self.records.get(id).await
+    }
+
+    async fn list(&self) -> Result<Vec<Task>> {
+        self.records.list().await
+    }
+
+    async fn update(&self, task: Task) -> Result<Task> {
+        self.records.update(task).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        self.records.delete(id).await
+    }
+}
+
+/// Change some of a task's fields
+///
+/// With `expected_version`, fails with `Error::Conflict` unless the task is
+/// still at the version the caller last saw. Without one, a patch that races
+/// with another save is re-applied on top of it, since the fields it doesn't
+/// set are left alone either way.
+pub async fn patch_task(
+    tasks: &impl Repository<Task>,
+    id: Uuid,
+    patch: &TaskPatch,
+    expected_version: Option<u64>,
+) -> Result<Task> {
+    let mut attempts = 1;
+    loop {
+        let mut task = tasks.get(id).await?;
+        if let Some(expected) = expected_version {
+            if task.version != expected {
+                return Err(conflict(format!("Task {}", id), expected));
+            }
+        }
+        task.apply(patch, Utc::now())?;
+
+        match tasks.update(task).await {
+            Err(Error::Conflict(_)) if expected_version.is_none() && attempts < PATCH_ATTEMPTS => {
+                attempts += 1;
+            }
+            result => return result,
+        }
+    }
+}
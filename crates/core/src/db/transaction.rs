@@ -0,0 +1,137 @@
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use surrealdb::sql::{to_value, Object, Value};
+use crate::error::{Error, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+const GUARD_PREFIX: &str = "transaction guard #";
+
+/// Changes to several records that are committed together or not at all
+///
+/// SurrealDB transactions can't span round trips, so repositories add their
+/// statements to a `Transaction` (the `*_in` methods) and [`commit`] sends
+/// them as one `BEGIN TRANSACTION; ...; COMMIT TRANSACTION;` query. If any
+/// statement fails, or a condition added with [`ensure`] no longer holds
+/// when the transaction runs, nothing is written.
+///
+/// [`commit`]: Transaction::commit
+/// [`ensure`]: Transaction::ensure
+pub struct Transaction {
+    db: Arc<Surreal<Any>>,
+    statements: Vec<String>,
+    bindings: BTreeMap<String, Value>,
+    guards: Vec<Error>,
+}
+
+impl Transaction {
+    pub fn new(db: Arc<Surreal<Any>>) -> Self {
+        Self {
+            db,
+            statements: Vec::new(),
+            bindings: BTreeMap::new(),
+            guards: Vec::new(),
+        }
+    }
+
+    /// Bind a value for use in a statement, returning its `$parameter` name
+    pub fn bind(&mut self, value: impl Serialize + 'static) -> Result<String> {
+        let name = format!("p{}", self.bindings.len());
+        let value = to_value(value)
+            .map_err(|e| Error::Database(format!("Failed to bind transaction value: {}", e)))?;
+        self.bindings.insert(name.clone(), value);
+        Ok(format!("${}", name))
+    }
+
+    /// Add a statement; parameters come from [`Transaction::bind`]
+    pub fn push(&mut self, statement: impl Into<String>) {
+        self.statements.push(statement.into());
+    }
+
+    /// Cancel the transaction with `error` unless `condition` holds when it
+    /// runs
+    ///
+    /// Checks made in Rust before committing can be outdated by the time the
+    /// transaction runs; a guard re-checks them inside it.
+    pub fn ensure(&mut self, condition: impl AsRef<str>, error: Error) {
+        let guard = self.guards.len();
+        self.guards.push(error);
+        self.push(format!(
+            "IF !({}) {{ THROW \"{}{}\" }}",
+            condition.as_ref(),
+            GUARD_PREFIX,
+            guard
+        ));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    /// Run every statement in a single database transaction
+    pub async fn commit(mut self) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        let mut query = String::from("BEGIN TRANSACTION;\n");
+        for statement in &self.statements {
+            query.push_str(statement);
+            query.push_str(";\n");
+        }
+        query.push_str("COMMIT TRANSACTION;");
+
+        let bindings = std::mem::take(&mut self.bindings);
+        let mut response = self.db
+            .query(query)
+            .bind(Object::from(bindings))
+            .await?;
+
+        let mut errors: Vec<(usize, String)> = response
+            .take_errors()
+            .into_iter()
+            .map(|(index, e)| (index, e.to_string()))
+            .collect();
+        if errors.is_empty() {
+            return Ok(());
+        }
+        errors.sort();
+
+        // A failed guard explains itself
+        for (_, message) in &errors {
+            if let Some(guard) = guard_index(message) {
+                if guard < self.guards.len() {
+                    return Err(self.guards.swap_remove(guard));
+                }
+            }
+        }
+
+        // Every other statement reports the cancelled transaction, so prefer
+        // the one that caused it
+        let message = errors
+            .iter()
+            .map(|(_, message)| message)
+            .find(|message| !message.contains("failed transaction"))
+            .unwrap_or(&errors[0].1);
+        Err(Error::Database(message.clone()))
+    }
+}
+
+fn guard_index(message: &str) -> Option<usize> {
+    let start = message.find(GUARD_PREFIX)? + GUARD_PREFIX.len();
+    let digits: String = message[start..].chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guard_index() {
+        assert_eq!(guard_index("An error occurred: transaction guard #3"), Some(3));
+        assert_eq!(guard_index("There was a problem with the database: transaction guard #12"), Some(12));
+        assert_eq!(guard_index("The query was not executed due to a failed transaction"), None);
+    }
+}
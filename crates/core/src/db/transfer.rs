@@ -0,0 +1,89 @@
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use crate::db::repository::{into_entities, Entity, Record};
+use crate::db::Transaction;
+use crate::models::{Transfer, TransferStatus};
+use crate::error::{Error, Result};
+use uuid::Uuid;
+use std::sync::Arc;
+
+impl Entity for Transfer {
+    const TABLE: &'static str = "transfer";
+    const NAME: &'static str = "Transfer";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_id(&mut self, id: Uuid) {
+        self.id = id;
+    }
+}
+
+pub struct TransferRepository {
+    db: Arc<Surreal<Any>>,
+}
+
+impl TransferRepository {
+    pub fn new(db: Arc<Surreal<Any>>) -> Self {
+        Self { db }
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<Transfer> {
+        let record: Option<Record<Transfer>> = self.db
+            .select(("transfer", id.to_string()))
+            .await?;
+
+        record
+            .ok_or_else(|| Error::NotFound(format!("Transfer with id {}", id)))?
+            .into_entity()
+    }
+
+    /// Transfers waiting for approval, oldest first
+    pub async fn list_pending(&self, owner_id: &str) -> Result<Vec<Transfer>> {
+        let records: Vec<Record<Transfer>> = self.db
+            .query("SELECT * FROM transfer WHERE owner_id = $owner_id AND status = $status ORDER BY created_at ASC")
+            .bind(("owner_id", owner_id.to_string()))
+            .bind(("status", TransferStatus::Pending))
+            .await?
+            .take(0)?;
+
+        into_entities(records)
+    }
+
+    /// Transfers a kid sent or received, newest first
+    pub async fn list_by_kid(&self, kid_id: Uuid) -> Result<Vec<Transfer>> {
+        let records: Vec<Record<Transfer>> = self.db
+            .query("SELECT * FROM transfer WHERE from_kid_id = $kid_id OR to_kid_id = $kid_id ORDER BY created_at DESC")
+            .bind(("kid_id", kid_id.to_string()))
+            .await?
+            .take(0)?;
+
+        into_entities(records)
+    }
+
+    /// Start a transaction on this repository's database
+    pub fn begin(&self) -> Transaction {
+        Transaction::new(self.db.clone())
+    }
+
+    pub fn create_in(&self, tx: &mut Transaction, transfer: &Transfer) -> Result<()> {
+        let id = tx.bind(transfer.id.to_string())?;
+        let content = tx.bind(transfer.clone())?;
+        tx.push(format!("CREATE type::thing('transfer', {}) CONTENT {}", id, content));
+        Ok(())
+    }
+
+    /// Add a parent's decision on a transfer to a transaction, which fails if
+    /// someone else decided it first
+    pub fn review_in(&self, tx: &mut Transaction, transfer: &Transfer) -> Result<()> {
+        let id = tx.bind(transfer.id.to_string())?;
+        tx.ensure(
+            format!("(SELECT VALUE status FROM ONLY type::thing('transfer', {})) = 'Pending'", id),
+            Error::Validation("Transfer has already been reviewed".to_string()),
+        );
+        let content = tx.bind(transfer.clone())?;
+        tx.push(format!("UPDATE type::thing('transfer', {}) CONTENT {}", id, content));
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use crate::error::{Error, Result};
+use uuid::Uuid;
+
+/// Overwrite a record with `content`, but only if it is still at `version`
+///
+/// `content` should carry the next version. Records saved before versions
+/// existed have none and count as version 0. Returns `None` when nothing was
+/// written: the record is gone or has moved on to another version.
+pub(crate) async fn update_versioned<R, C>(
+    db: &Surreal<Any>,
+    table: &str,
+    id: Uuid,
+    content: C,
+    version: u64,
+) -> Result<Option<R>>
+where
+    R: DeserializeOwned,
+    C: Serialize + 'static,
+{
+    let updated: surrealdb::Result<Vec<R>> = db
+        .query("UPDATE type::thing($table, $id) CONTENT $content WHERE (version ?? 0) = $version")
+        .bind(("table", table.to_string()))
+        .bind(("id", id.to_string()))
+        .bind(("content", content))
+        .bind(("version", version))
+        .await?
+        .take(0);
+    match updated {
+        Ok(mut updated) => Ok(updated.pop()),
+        // SurrealDB only reports this as a message
+        Err(e) if e.to_string().contains("This transaction can be retried") => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The error for saving a record read at `version` after someone else saved it
+pub(crate) fn conflict(what: String, version: u64) -> Error {
+    Error::Conflict(format!(
+        "{} was changed by someone else after version {} was read",
+        what, version
+    ))
+}
@@ -0,0 +1,101 @@
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use crate::db::repository::{into_entities, Entity, Record};
+use crate::db::Transaction;
+use crate::models::{Withdrawal, WithdrawalStatus};
+use crate::error::{Error, Result};
+use uuid::Uuid;
+use std::sync::Arc;
+
+impl Entity for Withdrawal {
+    const TABLE: &'static str = "withdrawal";
+    const NAME: &'static str = "Withdrawal";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_id(&mut self, id: Uuid) {
+        self.id = id;
+    }
+}
+
+pub struct WithdrawalRepository {
+    db: Arc<Surreal<Any>>,
+}
+
+impl WithdrawalRepository {
+    pub fn new(db: Arc<Surreal<Any>>) -> Self {
+        Self { db }
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<Withdrawal> {
+        let record: Option<Record<Withdrawal>> = self.db
+            .select(("withdrawal", id.to_string()))
+            .await?;
+
+        record
+            .ok_or_else(|| Error::NotFound(format!("Withdrawal with id {}", id)))?
+            .into_entity()
+    }
+
+    /// Requested and approved withdrawals, oldest first
+    pub async fn list_open(&self, owner_id: &str) -> Result<Vec<Withdrawal>> {
+        let records: Vec<Record<Withdrawal>> = self.db
+            .query("SELECT * FROM withdrawal WHERE owner_id = $owner_id AND status IN $statuses ORDER BY requested_at ASC")
+            .bind(("owner_id", owner_id.to_string()))
+            .bind(("statuses", vec![WithdrawalStatus::Requested, WithdrawalStatus::Approved]))
+            .await?
+            .take(0)?;
+
+        into_entities(records)
+    }
+
+    /// Every withdrawal in a household, newest first
+    pub async fn list_by_owner(&self, owner_id: &str) -> Result<Vec<Withdrawal>> {
+        let records: Vec<Record<Withdrawal>> = self.db
+            .query("SELECT * FROM withdrawal WHERE owner_id = $owner_id ORDER BY requested_at DESC")
+            .bind(("owner_id", owner_id.to_string()))
+            .await?
+            .take(0)?;
+
+        into_entities(records)
+    }
+
+    /// All of a kid's withdrawals, newest first
+    pub async fn list_by_kid(&self, kid_id: Uuid) -> Result<Vec<Withdrawal>> {
+        let records: Vec<Record<Withdrawal>> = self.db
+            .query("SELECT * FROM withdrawal WHERE kid_id = $kid_id ORDER BY requested_at DESC")
+            .bind(("kid_id", kid_id.to_string()))
+            .await?
+            .take(0)?;
+
+        into_entities(records)
+    }
+
+    /// Start a transaction on this repository's database
+    pub fn begin(&self) -> Transaction {
+        Transaction::new(self.db.clone())
+    }
+
+    pub fn create_in(&self, tx: &mut Transaction, withdrawal: &Withdrawal) -> Result<()> {
+        let id = tx.bind(withdrawal.id.to_string())?;
+        let content = tx.bind(withdrawal.clone())?;
+        tx.push(format!("CREATE type::thing('withdrawal', {}) CONTENT {}", id, content));
+        Ok(())
+    }
+
+    /// Add a withdrawal's next step to a transaction, which fails unless it
+    /// was still `from` then, so a step can't be taken twice
+    pub fn update_in(&self, tx: &mut Transaction, withdrawal: &Withdrawal, from: WithdrawalStatus) -> Result<()> {
+        let id = tx.bind(withdrawal.id.to_string())?;
+        let status = tx.bind(from)?;
+        tx.ensure(
+            format!("(SELECT VALUE status FROM ONLY type::thing('withdrawal', {})) = {}", id, status),
+            Error::Validation("Withdrawal has already been reviewed".to_string()),
+        );
+        let content = tx.bind(withdrawal.clone())?;
+        tx.push(format!("UPDATE type::thing('withdrawal', {}) CONTENT {}", id, content));
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
-/// Real-time event system for broadcasting data changes
-/// This module is used by both the MCP server and web server for SSE updates
+//! Real-time event system for broadcasting data changes
+//! This module is used by both the MCP server and web server for SSE updates
 
 use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
@@ -30,6 +30,36 @@ pub enum DataEvent {
         amount: String,
         description: String,
     },
+    /// Tasks were imported from a CSV file
+    TasksImported { count: usize },
+    /// Past ledger entries were imported from a CSV file
+    LedgerImported { count: usize },
+    /// A payout took spending past 80% or all of a payout budget
+    BudgetThresholdCrossed {
+        /// None for the household's budget
+        kid_id: Option<String>,
+        name: String,
+        /// "nearly_spent" or "spent"
+        threshold: String,
+        remaining: String,
+    },
+    /// A withdrawal was requested, approved, paid or declined
+    WithdrawalUpdated {
+        id: String,
+        kid_id: String,
+        /// "requested", "approved", "paid" or "declined"
+        status: String,
+        amount: String,
+    },
+    /// Money was moved between two kids, or a transfer is waiting for approval
+    TransferUpdated {
+        id: String,
+        from_kid_id: String,
+        to_kid_id: String,
+        /// "pending", "completed" or "declined"
+        status: String,
+        amount: String,
+    },
 }
 
 /// Sender half of the event channel
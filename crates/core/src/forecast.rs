@@ -0,0 +1,246 @@
+//! Projected payouts for the coming week and month
+//!
+//! Each recurring task is projected per kid from how often the kid did it
+//! over the last four weeks and what it paid them. Tasks too new to have that
+//! history use the kid's overall completion rate. One-time tasks nobody has
+//! done yet are counted once, at full value, in the household's total only,
+//! since there's no telling which kid will take them.
+
+use crate::models::{BudgetPeriod, Cadence, EntryType, Kid, PayoutBudget, Task};
+use crate::reports::EarningsTotal;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Days of history a forecast is based on
+pub const LOOKBACK_DAYS: i64 = 28;
+
+/// Days projected as the next week and the next month
+const WEEK_DAYS: i64 = 7;
+const MONTH_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Forecast {
+    /// The day the projection starts after
+    pub today: NaiveDate,
+    pub kids: Vec<KidForecast>,
+    /// Full value of one-time tasks nobody has done yet
+    pub open_one_time: Decimal,
+    /// Projected payouts over the next 7 days, one-time tasks included
+    pub next_week: Decimal,
+    /// Projected payouts over the next 30 days, one-time tasks included
+    pub next_month: Decimal,
+    pub budget: Option<PayoutBudget>,
+    /// Set when the projection for the budget's period goes over it
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KidForecast {
+    pub kid_id: Uuid,
+    pub name: String,
+    /// Share of recurring chores the kid did over the last four weeks
+    pub completion_rate: Decimal,
+    pub next_week: Decimal,
+    pub next_month: Decimal,
+    /// Tasks the kid is expected to be paid for, largest first
+    pub tasks: Vec<TaskForecast>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskForecast {
+    pub task_id: Uuid,
+    pub name: String,
+    pub cadence: Cadence,
+    /// Times the kid did it over the last four weeks
+    pub completions: u64,
+    /// Completions per time the task came up, e.g. 0.5 for every other day
+    pub rate: Decimal,
+    /// What a completion paid on average, or the task's value without history
+    pub average_payout: Decimal,
+    pub next_week: Decimal,
+    pub next_month: Decimal,
+}
+
+impl Forecast {
+    /// Project payouts from `history`, the ledger's totals over the last
+    /// `LOOKBACK_DAYS` up to `today`
+    ///
+    /// `done_one_time` lists one-time tasks someone has already been paid for.
+    pub fn new(
+        kids: &[Kid],
+        tasks: &[Task],
+        history: &[EarningsTotal],
+        done_one_time: &[Uuid],
+        budget: Option<PayoutBudget>,
+        today: NaiveDate,
+    ) -> Self {
+        let recurring: Vec<&Task> = tasks.iter().filter(|t| t.cadence != Cadence::OneTime).collect();
+        let kids: Vec<KidForecast> = kids.iter().map(|kid| kid_forecast(kid, &recurring, history, today)).collect();
+
+        let open_one_time: Decimal = tasks
+            .iter()
+            .filter(|t| t.cadence == Cadence::OneTime && !done_one_time.contains(&t.id))
+            .map(|t| t.value)
+            .sum();
+        let next_week = kids.iter().map(|k| k.next_week).sum::<Decimal>() + open_one_time;
+        let next_month = kids.iter().map(|k| k.next_month).sum::<Decimal>() + open_one_time;
+
+        let warning = budget.and_then(|budget| {
+            let (projected, when) = match budget.period {
+                BudgetPeriod::Week => (next_week, "next week"),
+                BudgetPeriod::Month => (next_month, "over the next month"),
+            };
+            (projected > budget.amount).then(|| {
+                format!(
+                    "Projected payouts of ${:.2} {} are over the {}ly budget of ${:.2}",
+                    projected,
+                    when,
+                    budget.period.as_str(),
+                    budget.amount
+                )
+            })
+        });
+
+        Self { today, kids, open_one_time, next_week, next_month, budget, warning }
+    }
+}
+
+fn kid_forecast(kid: &Kid, recurring: &[&Task], history: &[EarningsTotal], today: NaiveDate) -> KidForecast {
+    // Completions and pay per task over the lookback
+    let done = |task: &Task| -> (u64, Decimal) {
+        history
+            .iter()
+            .filter(|t| t.kid_id == kid.id && t.task_id == Some(task.id) && t.entry_type == EntryType::Earned)
+            .fold((0, Decimal::ZERO), |(count, paid), t| (count + t.entries, paid + t.total))
+    };
+
+    let history_days = |task: &Task| (today - task.created_at.date_naive()).num_days() + 1;
+    let occurrences = |task: &Task, days: i64| -> Decimal {
+        let days = Decimal::from(days.max(1));
+        match task.cadence {
+            Cadence::Daily => days,
+            Cadence::Weekly => days / Decimal::from(WEEK_DAYS),
+            Cadence::OneTime => Decimal::ZERO,
+        }
+    };
+    let past = |task: &Task| occurrences(task, history_days(task).min(LOOKBACK_DAYS)).max(Decimal::ONE);
+
+    let (all_done, all_occurrences) = recurring.iter().fold((Decimal::ZERO, Decimal::ZERO), |(count, total), task| {
+        (count + Decimal::from(done(task).0), total + past(task))
+    });
+    let overall_rate = if all_occurrences.is_zero() { Decimal::ZERO } else { all_done / all_occurrences };
+
+    let mut tasks: Vec<TaskForecast> = recurring
+        .iter()
+        .filter_map(|task| {
+            let (completions, paid) = done(task);
+            let rate = if completions > 0 {
+                Decimal::from(completions) / past(task)
+            } else if history_days(task) < LOOKBACK_DAYS {
+                overall_rate
+            } else {
+                Decimal::ZERO
+            };
+            let average_payout = if completions > 0 { paid / Decimal::from(completions) } else { task.value };
+            let projected = |days: i64| (rate * occurrences(task, days) * average_payout).round_dp(2);
+            let forecast = TaskForecast {
+                task_id: task.id,
+                name: task.name.clone(),
+                cadence: task.cadence,
+                completions,
+                rate: rate.round_dp(2),
+                average_payout: average_payout.round_dp(2),
+                next_week: projected(WEEK_DAYS),
+                next_month: projected(MONTH_DAYS),
+            };
+            (!forecast.next_month.is_zero()).then_some(forecast)
+        })
+        .collect();
+    tasks.sort_by(|a, b| b.next_month.cmp(&a.next_month).then_with(|| a.name.cmp(&b.name)));
+
+    KidForecast {
+        kid_id: kid.id,
+        name: kid.name.clone(),
+        completion_rate: overall_rate.round_dp(2),
+        next_week: tasks.iter().map(|t| t.next_week).sum(),
+        next_month: tasks.iter().map(|t| t.next_month).sum(),
+        tasks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use rust_decimal_macros::dec;
+
+    fn task(name: &str, value: Decimal, cadence: Cadence, age_days: i64) -> Task {
+        let mut task = Task::new(name.to_string(), String::new(), value, cadence, "owner".to_string()).unwrap();
+        task.created_at = Utc::now() - Duration::days(age_days);
+        task
+    }
+
+    fn done(kid: &Kid, task: &Task, entries: u64, total: Decimal) -> EarningsTotal {
+        EarningsTotal {
+            kid_id: kid.id,
+            period_start: Utc::now().date_naive(),
+            task_id: Some(task.id),
+            category: None,
+            entry_type: EntryType::Earned,
+            entries,
+            total,
+        }
+    }
+
+    #[test]
+    fn test_projection_from_history() {
+        let today = Utc::now().date_naive();
+        let kai = Kid::new("Kai".to_string(), "owner".to_string()).unwrap();
+        let dishes = task("Dishes", dec!(1.00), Cadence::Daily, 100);
+        let lawn = task("Lawn", dec!(5.00), Cadence::Weekly, 100);
+        let ignored = task("Windows", dec!(3.00), Cadence::Weekly, 100);
+        let new_task = task("Feed cat", dec!(0.50), Cadence::Daily, 2);
+        let garage = task("Garage", dec!(20.00), Cadence::OneTime, 100);
+        let attic = task("Attic", dec!(15.00), Cadence::OneTime, 100);
+        let tasks = vec![dishes.clone(), lawn.clone(), ignored, new_task, garage, attic.clone()];
+
+        // Dishes half the days (sometimes at partial pay), the lawn every week
+        let history = vec![done(&kai, &dishes, 14, dec!(12.60)), done(&kai, &lawn, 4, dec!(20.00))];
+        let forecast = Forecast::new(std::slice::from_ref(&kai), &tasks, &history, &[attic.id], None, today);
+
+        let kid = &forecast.kids[0];
+        let names: Vec<&str> = kid.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Lawn", "Dishes", "Feed cat"]);
+        assert_eq!((kid.tasks[1].rate, kid.tasks[1].average_payout), (dec!(0.5), dec!(0.90)));
+        assert_eq!(kid.tasks[1].next_week, dec!(3.15));
+        assert_eq!(kid.tasks[0].next_week, dec!(5.00));
+        // 18 done of 28 + 4 + 4 + 3 chances; the new task gets that rate
+        assert_eq!(kid.completion_rate, dec!(0.46));
+        assert_eq!(kid.tasks[2].next_week, dec!(1.62));
+
+        assert_eq!(forecast.open_one_time, dec!(20.00));
+        assert_eq!(forecast.next_week, dec!(29.77));
+        assert!(forecast.warning.is_none());
+    }
+
+    #[test]
+    fn test_budget_warning() {
+        let today = Utc::now().date_naive();
+        let kai = Kid::new("Kai".to_string(), "owner".to_string()).unwrap();
+        let garage = task("Garage", dec!(20.00), Cadence::OneTime, 1);
+
+        let weekly = PayoutBudget::new(dec!(15), BudgetPeriod::Week).unwrap();
+        let forecast = Forecast::new(std::slice::from_ref(&kai), std::slice::from_ref(&garage), &[], &[], Some(weekly), today);
+        assert_eq!(
+            forecast.warning.as_deref(),
+            Some("Projected payouts of $20.00 next week are over the weekly budget of $15.00")
+        );
+
+        let monthly = PayoutBudget::new(dec!(25), BudgetPeriod::Month).unwrap();
+        let forecast = Forecast::new(std::slice::from_ref(&kai), std::slice::from_ref(&garage), &[], &[], Some(monthly), today);
+        assert!(forecast.warning.is_none());
+        assert!(PayoutBudget::new(dec!(0), BudgetPeriod::Week).is_err());
+    }
+}
@@ -5,14 +5,28 @@ pub mod workflows;
 pub mod config;
 pub mod auth;
 pub mod events;
+pub mod templates;
+pub mod attachments;
+pub mod policy;
+pub mod statements;
+pub mod reports;
+pub mod forecast;
+pub mod budget;
 
 pub use models::*;
 pub use error::{Error, Result};
-pub use db::{Database, init_database, init_database_with_config, KidRepository, TaskRepository, LedgerRepository, UserRepository};
-pub use workflows::TaskCompletionWorkflow;
-pub use config::{Config, DatabaseConfig, DatabaseMode, ServerConfig};
-pub use auth::{hash_password, verify_password};
+pub use db::{Database, init_database, init_database_with_config, KidRepository, TaskRepository, LedgerRepository, UserRepository, AttachmentRepository, ClaimRepository, HouseholdRepository, InviteRepository, Transaction, IdempotencyRepository, WithdrawalRepository, TransferRepository, MigrationRunner, Repository};
+pub use workflows::{TaskCompletionWorkflow, CompletionStore, PackImportWorkflow, PackImportResult, AttachmentWorkflow, ClaimWorkflow, KidAccountWorkflow, KidLogin, HouseholdWorkflow, InviteWorkflow, ArchiveWorkflow, IdempotencyWorkflow, BackupWorkflow, RestoreMode, CsvWorkflow, StatementWorkflow, ReportWorkflow, ForecastWorkflow, BudgetWorkflow, WithdrawalWorkflow, TransferWorkflow};
+pub use config::{Config, DatabaseConfig, DatabaseMode, ServerConfig, AttachmentConfig, ArchiveConfig, IdempotencyConfig, BackupConfig, StatementConfig};
+pub use auth::{hash_password, verify_password, hash_pin, verify_pin, validate_pin, validate_password, sign_invite_code, verify_invite_code};
 pub use events::{DataEvent, EventSender, EventReceiver, create_event_channel, broadcast_event};
+pub use templates::{TemplateLibrary, TaskPack, TaskTemplate, AgeBand};
+pub use attachments::{AttachmentStore, LocalFsStore};
+pub use policy::{Actor, Policy};
+pub use statements::Statement;
+pub use reports::{EarningsReport, ReportPeriod, ReportQuery};
+pub use forecast::Forecast;
+pub use budget::{BudgetStatus, BudgetThreshold};
 
 // Re-export uuid for convenience
 pub use uuid::Uuid;
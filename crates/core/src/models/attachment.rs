@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Duration, Utc};
+use crate::error::{Error, Result};
+use crate::models::ledger::{
+    serialize_uuid_as_string, deserialize_uuid_from_string,
+    serialize_optional_uuid_as_string, deserialize_optional_uuid_from_string,
+};
+
+/// A photo submitted as proof that a task was done
+///
+/// The image bytes live in an `AttachmentStore`; this record holds the
+/// metadata and the link to the completion (ledger entry) it proves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    #[serde(skip)]
+    pub id: Uuid,
+    /// Household of the kid this photo belongs to
+    pub owner_id: String,
+    #[serde(serialize_with = "serialize_uuid_as_string", deserialize_with = "deserialize_uuid_from_string")]
+    pub kid_id: Uuid,
+    /// Task the photo was taken for, if known at upload time
+    #[serde(
+        default,
+        serialize_with = "serialize_optional_uuid_as_string",
+        deserialize_with = "deserialize_optional_uuid_from_string"
+    )]
+    pub task_id: Option<Uuid>,
+    /// Completion (ledger entry) this photo is attached to
+    #[serde(
+        default,
+        serialize_with = "serialize_optional_uuid_as_string",
+        deserialize_with = "deserialize_optional_uuid_from_string"
+    )]
+    pub ledger_entry_id: Option<Uuid>,
+    pub content_type: String,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+    /// When the photo was linked to a completion
+    #[serde(default)]
+    pub linked_at: Option<DateTime<Utc>>,
+}
+
+impl Attachment {
+    pub fn new(
+        owner_id: String,
+        kid_id: Uuid,
+        task_id: Option<Uuid>,
+        content_type: String,
+        size_bytes: u64,
+    ) -> Result<Self> {
+        let attachment = Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            kid_id,
+            task_id,
+            ledger_entry_id: None,
+            content_type,
+            size_bytes,
+            created_at: Utc::now(),
+            linked_at: None,
+        };
+        attachment.validate()?;
+        Ok(attachment)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.size_bytes == 0 {
+            return Err(Error::Validation("Attachment cannot be empty".to_string()));
+        }
+        if !self.content_type.starts_with("image/") {
+            return Err(Error::Validation("Attachments must be images".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Key of the original image in the attachment store
+    pub fn storage_key(&self) -> String {
+        self.id.to_string()
+    }
+
+    /// Key of the thumbnail in the attachment store
+    pub fn thumbnail_key(&self) -> String {
+        format!("{}-thumb", self.id)
+    }
+
+    pub fn link_to_entry(&mut self, ledger_entry_id: Uuid) {
+        self.ledger_entry_id = Some(ledger_entry_id);
+        self.linked_at = Some(Utc::now());
+    }
+
+    /// Whether the retention period has passed at `now`
+    ///
+    /// Photos on completions expire `retention` after they were linked.
+    /// Photos that were never linked expire `retention` after upload.
+    pub fn is_expired(&self, retention: Duration, now: DateTime<Utc>) -> bool {
+        let since = self.linked_at.unwrap_or(self.created_at);
+        now - since >= retention
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn photo() -> Attachment {
+        Attachment::new("owner".to_string(), Uuid::new_v4(), None, "image/jpeg".to_string(), 1024).unwrap()
+    }
+
+    #[test]
+    fn test_rejects_non_images() {
+        let result = Attachment::new("owner".to_string(), Uuid::new_v4(), None, "text/plain".to_string(), 10);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_rejects_empty() {
+        let result = Attachment::new("owner".to_string(), Uuid::new_v4(), None, "image/png".to_string(), 0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_expiry_counts_from_link_time() {
+        let mut attachment = photo();
+        attachment.created_at = Utc::now() - Duration::days(40);
+        assert!(attachment.is_expired(Duration::days(30), Utc::now()));
+
+        attachment.link_to_entry(Uuid::new_v4());
+        assert!(!attachment.is_expired(Duration::days(30), Utc::now()));
+        assert!(attachment.is_expired(Duration::days(30), Utc::now() + Duration::days(31)));
+    }
+}
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::error::{Error, Result};
+use crate::models::ledger::{
+    serialize_uuid_as_string, deserialize_uuid_from_string,
+    serialize_optional_uuid_as_string, deserialize_optional_uuid_from_string,
+};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ClaimStatus {
+    /// Waiting for a parent to review
+    Pending,
+    /// Approved and paid out
+    Approved,
+    /// Turned down by a parent
+    Rejected,
+}
+
+/// A kid's claim that they finished a task, waiting for a parent's approval
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskClaim {
+    #[serde(skip)]
+    pub id: Uuid,
+    /// Household of the kid and task
+    pub owner_id: String,
+    #[serde(serialize_with = "serialize_uuid_as_string", deserialize_with = "deserialize_uuid_from_string")]
+    pub kid_id: Uuid,
+    #[serde(serialize_with = "serialize_uuid_as_string", deserialize_with = "deserialize_uuid_from_string")]
+    pub task_id: Uuid,
+    pub status: ClaimStatus,
+    /// Optional note from the kid ("cleaned under the bed too")
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Photo proof uploaded with the claim
+    #[serde(
+        default,
+        serialize_with = "serialize_optional_uuid_as_string",
+        deserialize_with = "deserialize_optional_uuid_from_string"
+    )]
+    pub attachment_id: Option<Uuid>,
+    /// Ledger entry created when the claim was approved
+    #[serde(
+        default,
+        serialize_with = "serialize_optional_uuid_as_string",
+        deserialize_with = "deserialize_optional_uuid_from_string"
+    )]
+    pub ledger_entry_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl TaskClaim {
+    pub fn new(
+        owner_id: String,
+        kid_id: Uuid,
+        task_id: Uuid,
+        note: Option<String>,
+        attachment_id: Option<Uuid>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            kid_id,
+            task_id,
+            status: ClaimStatus::Pending,
+            note: note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+            attachment_id,
+            ledger_entry_id: None,
+            created_at: Utc::now(),
+            resolved_at: None,
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.status == ClaimStatus::Pending
+    }
+
+    /// Mark the claim approved, recording the ledger entry that paid it
+    pub fn approve(&mut self, ledger_entry_id: Uuid) -> Result<()> {
+        self.ensure_pending()?;
+        self.status = ClaimStatus::Approved;
+        self.ledger_entry_id = Some(ledger_entry_id);
+        self.resolved_at = Some(Utc::now());
+        Ok(())
+    }
+
+    pub fn reject(&mut self) -> Result<()> {
+        self.ensure_pending()?;
+        self.status = ClaimStatus::Rejected;
+        self.resolved_at = Some(Utc::now());
+        Ok(())
+    }
+
+    fn ensure_pending(&self) -> Result<()> {
+        if !self.is_pending() {
+            return Err(Error::Validation("Claim has already been reviewed".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_claim_is_pending() {
+        let claim = TaskClaim::new("owner".to_string(), Uuid::new_v4(), Uuid::new_v4(), Some("  ".to_string()), None);
+        assert!(claim.is_pending());
+        assert!(claim.note.is_none());
+    }
+
+    #[test]
+    fn test_claim_can_only_be_reviewed_once() {
+        let mut claim = TaskClaim::new("owner".to_string(), Uuid::new_v4(), Uuid::new_v4(), None, None);
+        let entry_id = Uuid::new_v4();
+        claim.approve(entry_id).unwrap();
+        assert_eq!(claim.status, ClaimStatus::Approved);
+        assert_eq!(claim.ledger_entry_id, Some(entry_id));
+        assert!(claim.resolved_at.is_some());
+
+        assert!(claim.reject().is_err());
+        assert!(claim.approve(Uuid::new_v4()).is_err());
+    }
+}
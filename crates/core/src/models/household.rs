@@ -0,0 +1,320 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use crate::error::{Error, Result};
+
+/// What a household member is allowed to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HouseholdRole {
+    /// Full control, including managing members
+    Owner,
+    /// Manages kids, tasks and payouts
+    Parent,
+    /// Read-only access (e.g. grandparents)
+    Viewer,
+}
+
+impl HouseholdRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HouseholdRole::Owner => "owner",
+            HouseholdRole::Parent => "parent",
+            HouseholdRole::Viewer => "viewer",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "owner" => Some(HouseholdRole::Owner),
+            "parent" => Some(HouseholdRole::Parent),
+            "viewer" => Some(HouseholdRole::Viewer),
+            _ => None,
+        }
+    }
+
+    /// Whether this role can change kids, tasks and ledgers
+    pub fn can_edit(&self) -> bool {
+        matches!(self, HouseholdRole::Owner | HouseholdRole::Parent)
+    }
+
+    /// Whether this role can add, remove or change members
+    pub fn can_manage_members(&self) -> bool {
+        matches!(self, HouseholdRole::Owner)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HouseholdMember {
+    /// User ID (or "admin" for the built-in admin login)
+    pub user_id: String,
+    pub role: HouseholdRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+/// How often a payout budget starts over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetPeriod {
+    Week,
+    Month,
+}
+
+impl BudgetPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetPeriod::Week => "week",
+            BudgetPeriod::Month => "month",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "week" | "weekly" => Some(BudgetPeriod::Week),
+            "month" | "monthly" => Some(BudgetPeriod::Month),
+            _ => None,
+        }
+    }
+}
+
+/// What happens to a payout that would go over a budget
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetEnforcement {
+    /// Pay it, but report that the budget is spent
+    #[default]
+    Warn,
+    /// Refuse to pay it
+    Block,
+}
+
+impl BudgetEnforcement {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetEnforcement::Warn => "warn",
+            BudgetEnforcement::Block => "block",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "warn" | "soft" => Some(BudgetEnforcement::Warn),
+            "block" | "hard" => Some(BudgetEnforcement::Block),
+            _ => None,
+        }
+    }
+}
+
+/// The most a household (or one kid) is meant to be paid in chores each
+/// week or month
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayoutBudget {
+    pub amount: Decimal,
+    pub period: BudgetPeriod,
+    #[serde(default)]
+    pub enforcement: BudgetEnforcement,
+}
+
+impl PayoutBudget {
+    pub fn new(amount: Decimal, period: BudgetPeriod) -> Result<Self> {
+        if amount <= Decimal::ZERO {
+            return Err(Error::Validation("A payout budget must be positive".to_string()));
+        }
+        Ok(Self { amount, period, enforcement: BudgetEnforcement::Warn })
+    }
+
+    pub fn with_enforcement(mut self, enforcement: BudgetEnforcement) -> Self {
+        self.enforcement = enforcement;
+        self
+    }
+}
+
+/// A budget for one kid's payouts, on top of the household's
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KidBudget {
+    pub kid_id: Uuid,
+    pub budget: PayoutBudget,
+}
+
+/// A family sharing kids, tasks and ledgers between several parents
+///
+/// Kids, tasks, claims and attachments store the household ID in their
+/// `owner_id` field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Household {
+    #[serde(skip)]
+    pub id: Uuid,
+    pub name: String,
+    pub members: Vec<HouseholdMember>,
+    /// Payouts parents plan for across all kids (see `BudgetWorkflow`)
+    #[serde(default)]
+    pub budget: Option<PayoutBudget>,
+    /// Budgets for single kids' payouts
+    #[serde(default)]
+    pub kid_budgets: Vec<KidBudget>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Namespace for deriving personal household IDs from user IDs
+const PERSONAL_HOUSEHOLD_NAMESPACE: Uuid = Uuid::from_u128(0x6c6f_6161_686f_7573_6568_6f6c_6400_0001);
+
+impl Household {
+    /// Create a household with a single owner
+    pub fn new(name: String, owner_user_id: String) -> Result<Self> {
+        let household = Self {
+            id: Uuid::new_v4(),
+            name: name.trim().to_string(),
+            members: vec![HouseholdMember {
+                user_id: owner_user_id,
+                role: HouseholdRole::Owner,
+                joined_at: Utc::now(),
+            }],
+            budget: None,
+            kid_budgets: Vec::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        household.validate()?;
+        Ok(household)
+    }
+
+    /// The household every user starts out in
+    ///
+    /// Its ID is derived from the user ID, so creating it twice (e.g. from
+    /// the web and MCP servers at once) yields the same record.
+    pub fn personal(owner_user_id: String) -> Self {
+        let mut household = Self::new("My Family".to_string(), owner_user_id.clone())
+            .expect("default household name is valid");
+        household.id = Self::personal_id(&owner_user_id);
+        household
+    }
+
+    pub fn personal_id(user_id: &str) -> Uuid {
+        Uuid::new_v5(&PERSONAL_HOUSEHOLD_NAMESPACE, user_id.as_bytes())
+    }
+
+    /// The household ID as stored in `owner_id` fields
+    pub fn owner_id(&self) -> String {
+        self.id.to_string()
+    }
+
+    pub fn member(&self, user_id: &str) -> Option<&HouseholdMember> {
+        self.members.iter().find(|m| m.user_id == user_id)
+    }
+
+    pub fn role_of(&self, user_id: &str) -> Option<HouseholdRole> {
+        self.member(user_id).map(|m| m.role)
+    }
+
+    pub fn kid_budget(&self, kid_id: Uuid) -> Option<PayoutBudget> {
+        self.kid_budgets.iter().find(|b| b.kid_id == kid_id).map(|b| b.budget)
+    }
+
+    /// Set or clear one kid's budget
+    pub fn set_kid_budget(&mut self, kid_id: Uuid, budget: Option<PayoutBudget>) {
+        self.kid_budgets.retain(|b| b.kid_id != kid_id);
+        if let Some(budget) = budget {
+            self.kid_budgets.push(KidBudget { kid_id, budget });
+        }
+        self.updated_at = Utc::now();
+    }
+
+    pub fn add_member(&mut self, user_id: String, role: HouseholdRole) -> Result<()> {
+        if self.member(&user_id).is_some() {
+            return Err(Error::Validation("User is already a member of this household".to_string()));
+        }
+        self.members.push(HouseholdMember {
+            user_id,
+            role,
+            joined_at: Utc::now(),
+        });
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn set_role(&mut self, user_id: &str, role: HouseholdRole) -> Result<()> {
+        let mut updated = self.clone();
+        let member = updated.members.iter_mut().find(|m| m.user_id == user_id)
+            .ok_or_else(|| Error::NotFound(format!("Household member {}", user_id)))?;
+        member.role = role;
+        updated.validate()?;
+
+        *self = updated;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn remove_member(&mut self, user_id: &str) -> Result<()> {
+        if self.member(user_id).is_none() {
+            return Err(Error::NotFound(format!("Household member {}", user_id)));
+        }
+        let mut updated = self.clone();
+        updated.members.retain(|m| m.user_id != user_id);
+        updated.validate()?;
+
+        *self = updated;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(Error::Validation("Household name cannot be empty".to_string()));
+        }
+        if !self.members.iter().any(|m| m.role == HouseholdRole::Owner) {
+            return Err(Error::Validation("A household needs at least one owner".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_household_has_owner() {
+        let household = Household::new("The Kealohas".to_string(), "user-1".to_string()).unwrap();
+        assert_eq!(household.role_of("user-1"), Some(HouseholdRole::Owner));
+        assert_eq!(household.role_of("user-2"), None);
+        assert!(Household::new("  ".to_string(), "user-1".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_personal_household_id_is_stable() {
+        let a = Household::personal("user-1".to_string());
+        let b = Household::personal("user-1".to_string());
+        assert_eq!(a.id, b.id);
+        assert_ne!(a.id, Household::personal_id("user-2"));
+    }
+
+    #[test]
+    fn test_members_and_roles() {
+        let mut household = Household::new("Family".to_string(), "mom".to_string()).unwrap();
+        household.add_member("dad".to_string(), HouseholdRole::Parent).unwrap();
+        household.add_member("grandma".to_string(), HouseholdRole::Viewer).unwrap();
+        assert!(household.add_member("dad".to_string(), HouseholdRole::Viewer).is_err());
+
+        assert!(household.role_of("dad").unwrap().can_edit());
+        assert!(!household.role_of("dad").unwrap().can_manage_members());
+        assert!(!household.role_of("grandma").unwrap().can_edit());
+
+        // The last owner can't be demoted or removed
+        assert!(household.set_role("mom", HouseholdRole::Parent).is_err());
+        assert!(household.remove_member("mom").is_err());
+        assert_eq!(household.role_of("mom"), Some(HouseholdRole::Owner));
+
+        household.set_role("dad", HouseholdRole::Owner).unwrap();
+        household.remove_member("mom").unwrap();
+        assert_eq!(household.members.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_role() {
+        assert_eq!(HouseholdRole::parse("Parent"), Some(HouseholdRole::Parent));
+        assert_eq!(HouseholdRole::parse("boss"), None);
+        assert_eq!(HouseholdRole::Viewer.as_str(), "viewer");
+    }
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use chrono::{DateTime, Duration, Utc};
+use crate::error::{Error, Result};
+
+/// Longest idempotency key a client may send
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// A key a client sent with a request that changes data, stored with the
+/// result so a retry of the same request gets that result instead of
+/// repeating the change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdempotencyKey {
+    #[serde(skip)]
+    pub id: Uuid,
+    /// Who sent the key (a user ID); keys only need to be unique per scope
+    pub scope: String,
+    pub key: String,
+    /// The operation the key was first used for, e.g. `complete_task`
+    pub operation: String,
+    /// SHA-256 of the request's arguments, so a key sent again with
+    /// different arguments is refused rather than answered with the first
+    /// request's result
+    #[serde(default)]
+    pub request_hash: String,
+    /// JSON result of the operation; `None` while it is still running
+    #[serde(default)]
+    pub response: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl IdempotencyKey {
+    pub fn new(
+        scope: &str,
+        key: &str,
+        operation: &str,
+        arguments: &impl Serialize,
+        now: DateTime<Utc>,
+    ) -> Result<Self> {
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(Error::Validation("Idempotency key cannot be empty".to_string()));
+        }
+        if key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+            return Err(Error::Validation(format!(
+                "Idempotency key cannot be longer than {} characters",
+                MAX_IDEMPOTENCY_KEY_LEN
+            )));
+        }
+
+        Ok(Self {
+            id: Self::record_id(scope, key),
+            scope: scope.to_string(),
+            key: key.to_string(),
+            operation: operation.to_string(),
+            request_hash: Self::hash_arguments(arguments)?,
+            response: None,
+            created_at: now,
+        })
+    }
+
+    /// The record ID for a key, the same every time it is sent so a retry
+    /// finds the first request's record
+    pub fn record_id(scope: &str, key: &str) -> Uuid {
+        Uuid::new_v5(&Uuid::NAMESPACE_OID, format!("{}\n{}", scope, key.trim()).as_bytes())
+    }
+
+    /// Hash a request's arguments as JSON
+    ///
+    /// The hash is unsalted and stored with the key, so callers leave PINs,
+    /// passwords and other secrets out of `arguments`.
+    pub fn hash_arguments(arguments: &impl Serialize) -> Result<String> {
+        let json = serde_json::to_vec(arguments)
+            .map_err(|e| Error::Validation(format!("Failed to read request arguments: {}", e)))?;
+        Ok(Sha256::digest(json).iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    pub fn is_expired(&self, retention: Duration, now: DateTime<Utc>) -> bool {
+        now >= self.created_at + retention
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_is_scoped() {
+        let now = Utc::now();
+        let mine = IdempotencyKey::new("mom", " retry-1 ", "complete_task", &("kai", "dishes"), now).unwrap();
+        assert_eq!(mine.key, "retry-1");
+        assert_eq!(mine.id, IdempotencyKey::record_id("mom", "retry-1"));
+        assert_ne!(mine.id, IdempotencyKey::record_id("dad", "retry-1"));
+
+        assert!(!mine.is_expired(Duration::hours(24), now + Duration::hours(23)));
+        assert!(mine.is_expired(Duration::hours(24), now + Duration::hours(24)));
+    }
+
+    #[test]
+    fn test_arguments_are_hashed() {
+        let now = Utc::now();
+        let first = IdempotencyKey::new("mom", "k", "complete_task", &("kai", "dishes"), now).unwrap();
+        let retry = IdempotencyKey::new("mom", "k", "complete_task", &("kai", "dishes"), now).unwrap();
+        let other = IdempotencyKey::new("mom", "k", "complete_task", &("noa", "dishes"), now).unwrap();
+        assert_eq!(first.request_hash, retry.request_hash);
+        assert_ne!(first.request_hash, other.request_hash);
+    }
+
+    #[test]
+    fn test_key_validation() {
+        let now = Utc::now();
+        assert!(IdempotencyKey::new("mom", "  ", "complete_task", &(), now).is_err());
+        let long = "k".repeat(MAX_IDEMPOTENCY_KEY_LEN + 1);
+        assert!(IdempotencyKey::new("mom", &long, "complete_task", &(), now).is_err());
+    }
+}
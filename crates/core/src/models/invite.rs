@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Duration, Utc};
+use crate::models::HouseholdRole;
+use crate::models::ledger::{serialize_uuid_as_string, deserialize_uuid_from_string};
+
+/// How long invites stay valid unless a different lifetime is chosen
+pub fn default_invite_ttl() -> Duration {
+    Duration::days(7)
+}
+
+/// An invitation for someone to register and join a household
+///
+/// The code handed out is signed (see `auth::sign_invite_code`); this record
+/// tracks whether it has been used, revoked or has expired.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Invite {
+    #[serde(skip)]
+    pub id: Uuid,
+    #[serde(serialize_with = "serialize_uuid_as_string", deserialize_with = "deserialize_uuid_from_string")]
+    pub household_id: Uuid,
+    /// Role the new member gets
+    pub role: HouseholdRole,
+    /// User who created the invite
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// User who registered with this invite
+    #[serde(default)]
+    pub used_by: Option<String>,
+    #[serde(default)]
+    pub used_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl Invite {
+    pub fn new(household_id: Uuid, role: HouseholdRole, created_by: String, ttl: Duration) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            household_id,
+            role,
+            created_by,
+            created_at: now,
+            expires_at: now + ttl,
+            used_by: None,
+            used_at: None,
+            revoked_at: None,
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Can still be used to join
+    pub fn is_pending(&self, now: DateTime<Utc>) -> bool {
+        self.used_at.is_none() && self.revoked_at.is_none() && !self.is_expired(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invite_lifecycle() {
+        let mut invite = Invite::new(Uuid::new_v4(), HouseholdRole::Parent, "mom".to_string(), Duration::hours(1));
+        let now = Utc::now();
+        assert!(invite.is_pending(now));
+        assert!(!invite.is_pending(now + Duration::hours(2)));
+
+        invite.revoked_at = Some(now);
+        assert!(!invite.is_pending(now));
+    }
+
+    #[test]
+    fn test_used_invite_is_not_pending() {
+        let mut invite = Invite::new(Uuid::new_v4(), HouseholdRole::Viewer, "mom".to_string(), default_invite_ttl());
+        invite.used_by = Some("grandma".to_string());
+        invite.used_at = Some(Utc::now());
+        assert!(!invite.is_pending(Utc::now()));
+    }
+}
@@ -1,18 +1,24 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
-use crate::error::Result;
+use chrono::{DateTime, Duration, Utc};
+use crate::error::{Error, Result};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Kid {
     #[serde(skip)]
     pub id: Uuid,
     pub name: String,
-    /// Owner of this kid record (user_id as string, or "admin" for admin-created)
+    /// Household that owns this kid (see `Household::owner_id`)
     #[serde(default)]
     pub owner_id: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set when the kid is archived; archived kids are hidden but keep their history
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Bumped on every save (see `Task::version`)
+    #[serde(default)]
+    pub version: u64,
 }
 
 impl Kid {
@@ -23,6 +29,8 @@ impl Kid {
             owner_id,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            archived_at: None,
+            version: 0,
         };
         kid.validate()?;
         Ok(kid)
@@ -34,6 +42,39 @@ impl Kid {
         }
         Ok(())
     }
+
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
+    pub fn archive(&mut self, now: DateTime<Utc>) {
+        self.archived_at.get_or_insert(now);
+        self.updated_at = now;
+    }
+
+    /// Bring an archived kid back, if still within the restore window
+    pub fn restore(&mut self, window: Duration, now: DateTime<Utc>) -> Result<()> {
+        check_restorable(self.archived_at, window, now)?;
+        self.archived_at = None;
+        self.updated_at = now;
+        Ok(())
+    }
+}
+
+/// Archived records can be restored until `window` after they were archived,
+/// after which they are purged
+pub(crate) fn check_restorable(
+    archived_at: Option<DateTime<Utc>>,
+    window: Duration,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    match archived_at {
+        None => Err(Error::Validation("Not archived".to_string())),
+        Some(archived_at) if now >= archived_at + window => Err(Error::Validation(
+            "The restore window has passed".to_string(),
+        )),
+        Some(_) => Ok(()),
+    }
 }
 
 #[cfg(test)]
@@ -48,6 +89,20 @@ mod tests {
         assert!(!kid.id.is_nil());
     }
 
+    #[test]
+    fn test_archive_and_restore_within_window() {
+        let mut kid = Kid::new("Kainoa".to_string(), "test-owner".to_string()).unwrap();
+        assert!(kid.restore(Duration::days(30), Utc::now()).is_err());
+
+        let archived_at = Utc::now();
+        kid.archive(archived_at);
+        assert!(kid.is_archived());
+        assert!(kid.clone().restore(Duration::days(30), archived_at + Duration::days(31)).is_err());
+
+        kid.restore(Duration::days(30), archived_at + Duration::days(29)).unwrap();
+        assert!(!kid.is_archived());
+    }
+
     #[test]
     fn test_kid_validation_empty_name() {
         let result = Kid::new("   ".to_string(), "test-owner".to_string());
@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use crate::error::{Error, Result};
+use crate::models::{Task, TaskCategory};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LedgerEntry {
@@ -12,18 +14,48 @@ pub struct LedgerEntry {
     pub amount: Decimal,
     pub entry_type: EntryType,
     pub description: String,
+    /// Task this entry pays out for, if it came from a task completion
+    #[serde(
+        default,
+        serialize_with = "serialize_optional_uuid_as_string",
+        deserialize_with = "deserialize_optional_uuid_from_string"
+    )]
+    pub task_id: Option<Uuid>,
+    /// Category of the task at completion time, used for earnings breakdowns
+    #[serde(default)]
+    pub category: Option<TaskCategory>,
+    /// Full value of the task at completion time, before any partial credit
+    #[serde(default)]
+    pub original_value: Option<Decimal>,
+    /// Parent's review of the completion (quality rating, percentage paid, comment)
+    #[serde(default)]
+    pub review: Option<CompletionReview>,
+    /// Withdrawal this entry records a step of, for payouts
+    #[serde(
+        default,
+        serialize_with = "serialize_optional_uuid_as_string",
+        deserialize_with = "deserialize_optional_uuid_from_string"
+    )]
+    pub withdrawal_id: Option<Uuid>,
+    /// Transfer between kids this entry is one side of
+    #[serde(
+        default,
+        serialize_with = "serialize_optional_uuid_as_string",
+        deserialize_with = "deserialize_optional_uuid_from_string"
+    )]
+    pub transfer_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
 
 // Custom serialization for UUID to ensure it's stored as a string
-fn serialize_uuid_as_string<S>(uuid: &Uuid, serializer: S) -> Result<S::Ok, S::Error>
+pub(crate) fn serialize_uuid_as_string<S>(uuid: &Uuid, serializer: S) -> std::result::Result<S::Ok, S::Error>
 where
     S: serde::Serializer,
 {
     serializer.serialize_str(&uuid.to_string())
 }
 
-fn deserialize_uuid_from_string<'de, D>(deserializer: D) -> Result<Uuid, D::Error>
+pub(crate) fn deserialize_uuid_from_string<'de, D>(deserializer: D) -> std::result::Result<Uuid, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
@@ -31,10 +63,111 @@ where
     Uuid::parse_str(&s).map_err(serde::de::Error::custom)
 }
 
+pub(crate) fn serialize_optional_uuid_as_string<S>(uuid: &Option<Uuid>, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match uuid {
+        Some(uuid) => serializer.serialize_some(&uuid.to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub(crate) fn deserialize_optional_uuid_from_string<'de, D>(deserializer: D) -> std::result::Result<Option<Uuid>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = Option::<String>::deserialize(deserializer)?;
+    s.map(|s| Uuid::parse_str(&s).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum EntryType {
     Earned,
     Adjusted,
+    /// Money taken out of the balance by a withdrawal
+    Payout,
+    /// Money sent to or received from another kid
+    Transfer,
+}
+
+impl EntryType {
+    /// Every type, in the order statements and reports list them
+    pub const ALL: [EntryType; 4] = [EntryType::Earned, EntryType::Adjusted, EntryType::Payout, EntryType::Transfer];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntryType::Earned => "earned",
+            EntryType::Adjusted => "adjusted",
+            EntryType::Payout => "payout",
+            EntryType::Transfer => "transfer",
+        }
+    }
+}
+
+/// A parent's assessment of a completed task, applied when approving it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CompletionReview {
+    /// Quality rating from 1 (poor) to 5 (excellent)
+    pub quality: Option<u8>,
+    /// Percentage of the task value paid out (0-100)
+    pub percent_paid: u8,
+    pub comment: Option<String>,
+}
+
+impl CompletionReview {
+    pub const MIN_QUALITY: u8 = 1;
+    pub const MAX_QUALITY: u8 = 5;
+
+    pub fn new(quality: Option<u8>, percent_paid: u8, comment: Option<String>) -> Result<Self> {
+        let comment = comment
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty());
+        let review = Self {
+            quality,
+            percent_paid,
+            comment,
+        };
+        review.validate()?;
+        Ok(review)
+    }
+
+    /// Full credit with no rating or comment
+    pub fn full_credit() -> Self {
+        Self {
+            quality: None,
+            percent_paid: 100,
+            comment: None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if let Some(quality) = self.quality {
+            if !(Self::MIN_QUALITY..=Self::MAX_QUALITY).contains(&quality) {
+                return Err(Error::Validation(format!(
+                    "Quality rating must be between {} and {}",
+                    Self::MIN_QUALITY,
+                    Self::MAX_QUALITY
+                )));
+            }
+        }
+        if self.percent_paid > 100 {
+            return Err(Error::Validation(
+                "Percentage paid must be between 0 and 100".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Amount to pay for a task worth `value`, rounded to cents
+    pub fn amount_for(&self, value: Decimal) -> Decimal {
+        (value * Decimal::from(self.percent_paid) / Decimal::ONE_HUNDRED).round_dp(2)
+    }
+
+    pub fn is_partial(&self) -> bool {
+        self.percent_paid < 100
+    }
 }
 
 impl LedgerEntry {
@@ -45,6 +178,12 @@ impl LedgerEntry {
             amount,
             entry_type,
             description: description.trim().to_string(),
+            task_id: None,
+            category: None,
+            original_value: None,
+            review: None,
+            withdrawal_id: None,
+            transfer_id: None,
             created_at: Utc::now(),
         }
     }
@@ -56,6 +195,70 @@ impl LedgerEntry {
     pub fn adjusted(kid_id: Uuid, amount: Decimal, description: String) -> Self {
         Self::new(kid_id, amount, EntryType::Adjusted, description)
     }
+
+    /// A step of a withdrawal: the amount taken out (negative) when it's
+    /// requested, returned if it's declined, or zero for steps that only
+    /// record what happened
+    pub fn payout(kid_id: Uuid, amount: Decimal, description: String, withdrawal_id: Uuid) -> Self {
+        let mut entry = Self::new(kid_id, amount, EntryType::Payout, description);
+        entry.withdrawal_id = Some(withdrawal_id);
+        entry
+    }
+
+    /// One side of a transfer: negative for the kid sending, positive for the
+    /// kid receiving
+    pub fn transfer(kid_id: Uuid, amount: Decimal, description: String, transfer_id: Uuid) -> Self {
+        let mut entry = Self::new(kid_id, amount, EntryType::Transfer, description);
+        entry.transfer_id = Some(transfer_id);
+        entry
+    }
+
+    /// Earnings for a completed task, paid according to the review
+    pub fn task_completion(kid_id: Uuid, task: &Task, review: Option<CompletionReview>) -> Self {
+        let (amount, description) = match &review {
+            Some(review) if review.is_partial() => (
+                review.amount_for(task.value),
+                format!("Completed: {} ({}% credit)", task.name, review.percent_paid),
+            ),
+            Some(review) => (review.amount_for(task.value), format!("Completed: {}", task.name)),
+            None => (task.value, format!("Completed: {}", task.name)),
+        };
+        let mut entry = Self::earned(kid_id, amount, description);
+        entry.task_id = Some(task.id);
+        entry.category = task.category;
+        entry.original_value = Some(task.value);
+        entry.review = review;
+        entry
+    }
+
+    /// Quality rating given when this completion was approved, if any
+    pub fn quality(&self) -> Option<u8> {
+        self.review.as_ref().and_then(|r| r.quality)
+    }
+}
+
+/// Average quality rating across a set of reviewed completions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QualityStat {
+    /// Mean rating, rounded to two decimal places
+    pub average: Decimal,
+    /// Number of completions that received a rating
+    pub rated_count: usize,
+}
+
+impl QualityStat {
+    /// Returns `None` when none of the entries carry a rating
+    pub fn from_entries<'a>(entries: impl IntoIterator<Item = &'a LedgerEntry>) -> Option<Self> {
+        let ratings: Vec<u8> = entries.into_iter().filter_map(|e| e.quality()).collect();
+        if ratings.is_empty() {
+            return None;
+        }
+        let total: Decimal = ratings.iter().map(|&q| Decimal::from(q)).sum();
+        Some(Self {
+            average: (total / Decimal::from(ratings.len())).round_dp(2),
+            rated_count: ratings.len(),
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +281,49 @@ impl Ledger {
     pub fn calculate_balance(entries: &[LedgerEntry]) -> Decimal {
         entries.iter().map(|e| e.amount).sum()
     }
+
+    /// Average quality across all rated completions in this ledger
+    pub fn average_quality(&self) -> Option<QualityStat> {
+        QualityStat::from_entries(&self.entries)
+    }
+
+    /// Task earnings totalled per category, largest first
+    ///
+    /// Entries recorded before categories existed fall back to the current
+    /// category of their task in `tasks`. Manual adjustments are excluded;
+    /// earnings with no known category are grouped under `None`.
+    pub fn earnings_by_category(&self, tasks: &[Task]) -> Vec<(Option<TaskCategory>, Decimal)> {
+        let mut totals: Vec<(Option<TaskCategory>, Decimal)> = Vec::new();
+        for entry in self.entries.iter().filter(|e| e.entry_type == EntryType::Earned) {
+            let category = entry.category.or_else(|| {
+                entry
+                    .task_id
+                    .and_then(|id| tasks.iter().find(|t| t.id == id))
+                    .and_then(|t| t.category)
+            });
+            match totals.iter_mut().find(|(c, _)| *c == category) {
+                Some((_, total)) => *total += entry.amount,
+                None => totals.push((category, entry.amount)),
+            }
+        }
+        totals.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        totals
+    }
+
+    /// Average quality per task, for tasks with at least one rated completion
+    pub fn average_quality_by_task(&self) -> Vec<(Uuid, QualityStat)> {
+        let mut task_ids: Vec<Uuid> = self.entries.iter().filter_map(|e| e.task_id).collect();
+        task_ids.sort();
+        task_ids.dedup();
+
+        task_ids
+            .into_iter()
+            .filter_map(|task_id| {
+                let entries = self.entries.iter().filter(|e| e.task_id == Some(task_id));
+                QualityStat::from_entries(entries).map(|stat| (task_id, stat))
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -108,5 +354,110 @@ mod tests {
         let ledger = Ledger::new(kid_id, entries);
         assert_eq!(ledger.balance, dec!(7.50));
     }
+
+    fn test_task(value: Decimal) -> Task {
+        Task::new(
+            "Clean room".to_string(),
+            "".to_string(),
+            value,
+            crate::models::Cadence::Daily,
+            "test-owner".to_string(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_review_validation() {
+        assert!(CompletionReview::new(Some(0), 100, None).is_err());
+        assert!(CompletionReview::new(Some(6), 100, None).is_err());
+        assert!(CompletionReview::new(Some(3), 101, None).is_err());
+        let review = CompletionReview::new(Some(5), 0, Some("  ".to_string())).unwrap();
+        assert_eq!(review.comment, None);
+    }
+
+    #[test]
+    fn test_partial_credit_entry() {
+        let kid_id = Uuid::new_v4();
+        let task = test_task(dec!(3.00));
+        let review = CompletionReview::new(Some(2), 50, Some("Half done".to_string())).unwrap();
+
+        let entry = LedgerEntry::task_completion(kid_id, &task, Some(review));
+        assert_eq!(entry.amount, dec!(1.50));
+        assert_eq!(entry.original_value, Some(dec!(3.00)));
+        assert_eq!(entry.task_id, Some(task.id));
+        assert_eq!(entry.quality(), Some(2));
+        assert_eq!(entry.review.unwrap().comment.as_deref(), Some("Half done"));
+        assert!(entry.description.contains("50%"));
+    }
+
+    #[test]
+    fn test_partial_credit_rounds_to_cents() {
+        let review = CompletionReview::new(None, 33, None).unwrap();
+        assert_eq!(review.amount_for(dec!(1.00)), dec!(0.33));
+    }
+
+    #[test]
+    fn test_average_quality() {
+        let kid_id = Uuid::new_v4();
+        let task_a = test_task(dec!(1.00));
+        let task_b = test_task(dec!(2.00));
+        let rated = |task: &Task, quality| {
+            LedgerEntry::task_completion(
+                kid_id,
+                task,
+                Some(CompletionReview::new(Some(quality), 100, None).unwrap()),
+            )
+        };
+        let entries = vec![
+            rated(&task_a, 5),
+            rated(&task_a, 4),
+            rated(&task_b, 2),
+            LedgerEntry::task_completion(kid_id, &task_b, None),
+            LedgerEntry::adjusted(kid_id, dec!(1.00), "Bonus".to_string()),
+        ];
+        let ledger = Ledger::new(kid_id, entries);
+
+        let overall = ledger.average_quality().unwrap();
+        assert_eq!(overall.average, dec!(3.67));
+        assert_eq!(overall.rated_count, 3);
+
+        let by_task = ledger.average_quality_by_task();
+        assert_eq!(by_task.len(), 2);
+        let a = by_task.iter().find(|(id, _)| *id == task_a.id).unwrap();
+        assert_eq!(a.1.average, dec!(4.50));
+        let b = by_task.iter().find(|(id, _)| *id == task_b.id).unwrap();
+        assert_eq!(b.1.rated_count, 1);
+    }
+
+    #[test]
+    fn test_earnings_by_category() {
+        let kid_id = Uuid::new_v4();
+        let pets = test_task(dec!(2.00)).with_category(Some(TaskCategory::Pets));
+        let school = test_task(dec!(1.00)).with_category(Some(TaskCategory::School));
+        let uncategorized = test_task(dec!(0.50));
+
+        // Entry recorded before the task had a category
+        let mut legacy = LedgerEntry::task_completion(kid_id, &school, None);
+        legacy.category = None;
+
+        let entries = vec![
+            LedgerEntry::task_completion(kid_id, &pets, None),
+            LedgerEntry::task_completion(kid_id, &pets, None),
+            legacy,
+            LedgerEntry::task_completion(kid_id, &uncategorized, None),
+            LedgerEntry::adjusted(kid_id, dec!(10.00), "Bonus".to_string()),
+        ];
+        let ledger = Ledger::new(kid_id, entries);
+
+        let breakdown = ledger.earnings_by_category(std::slice::from_ref(&school));
+        assert_eq!(
+            breakdown,
+            vec![
+                (Some(TaskCategory::Pets), dec!(4.00)),
+                (Some(TaskCategory::School), dec!(1.00)),
+                (None, dec!(0.50)),
+            ]
+        );
+    }
 }
 
@@ -2,9 +2,24 @@ pub mod kid;
 pub mod task;
 pub mod ledger;
 pub mod user;
+pub mod attachment;
+pub mod claim;
+pub mod household;
+pub mod invite;
+pub mod idempotency;
+pub mod withdrawal;
+pub mod transfer;
 
 pub use kid::Kid;
-pub use task::{Task, Cadence};
-pub use ledger::{LedgerEntry, EntryType, Ledger};
-pub use user::{User, AccountType};
-
+pub use task::{Task, TaskPatch, Cadence, TaskCategory, normalize_tags};
+pub use ledger::{LedgerEntry, EntryType, Ledger, CompletionReview, QualityStat};
+pub use user::{User, AccountType, MAX_FAILED_PIN_ATTEMPTS, pin_lockout_duration};
+pub use attachment::Attachment;
+pub use claim::{TaskClaim, ClaimStatus};
+pub use household::{
+    BudgetEnforcement, BudgetPeriod, Household, HouseholdMember, HouseholdRole, KidBudget, PayoutBudget,
+};
+pub use invite::{Invite, default_invite_ttl};
+pub use idempotency::{IdempotencyKey, MAX_IDEMPOTENCY_KEY_LEN};
+pub use withdrawal::{Withdrawal, WithdrawalStatus, PaymentMethod};
+pub use transfer::{Transfer, TransferStatus};
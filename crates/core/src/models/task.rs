@@ -3,6 +3,7 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc, Duration};
 use rust_decimal::Decimal;
 use crate::error::Result;
+use crate::models::kid::check_restorable;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
@@ -12,12 +13,25 @@ pub struct Task {
     pub description: String,
     pub value: Decimal,
     pub cadence: Cadence,
-    /// Owner of this task (user_id as string, or "admin" for admin-created)
+    #[serde(default)]
+    pub category: Option<TaskCategory>,
+    /// Free-form labels, stored lowercase without duplicates
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Household that owns this task (see `Household::owner_id`)
     #[serde(default)]
     pub owner_id: String,
     pub last_reset: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set when the task is archived; archived tasks are hidden but past
+    /// completions still refer to them
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Bumped on every save; saving a task read at an older version fails
+    /// with `Error::Conflict` instead of overwriting someone else's edit
+    #[serde(default)]
+    pub version: u64,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -27,6 +41,86 @@ pub enum Cadence {
     OneTime,
 }
 
+impl Cadence {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "daily" => Some(Cadence::Daily),
+            "weekly" => Some(Cadence::Weekly),
+            "onetime" | "one-time" | "one_time" | "once" => Some(Cadence::OneTime),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Cadence::Daily => "daily",
+            Cadence::Weekly => "weekly",
+            Cadence::OneTime => "onetime",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskCategory {
+    Room,
+    Kitchen,
+    School,
+    Pets,
+    Yard,
+    Other,
+}
+
+impl TaskCategory {
+    pub const ALL: [TaskCategory; 6] = [
+        TaskCategory::Room,
+        TaskCategory::Kitchen,
+        TaskCategory::School,
+        TaskCategory::Pets,
+        TaskCategory::Yard,
+        TaskCategory::Other,
+    ];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "room" => Some(TaskCategory::Room),
+            "kitchen" => Some(TaskCategory::Kitchen),
+            "school" => Some(TaskCategory::School),
+            "pets" | "pet" => Some(TaskCategory::Pets),
+            "yard" => Some(TaskCategory::Yard),
+            "other" => Some(TaskCategory::Other),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskCategory::Room => "room",
+            TaskCategory::Kitchen => "kitchen",
+            TaskCategory::School => "school",
+            TaskCategory::Pets => "pets",
+            TaskCategory::Yard => "yard",
+            TaskCategory::Other => "other",
+        }
+    }
+}
+
+/// Normalize tags: trimmed, lowercase, no empties or duplicates
+pub fn normalize_tags<I, S>(tags: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.as_ref().trim().to_lowercase();
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
 impl Task {
     pub fn new(name: String, description: String, value: Decimal, cadence: Cadence, owner_id: String) -> Result<Self> {
         let now = Utc::now();
@@ -36,15 +130,38 @@ impl Task {
             description: description.trim().to_string(),
             value,
             cadence,
+            category: None,
+            tags: Vec::new(),
             owner_id,
             last_reset: now,
             created_at: now,
             updated_at: now,
+            archived_at: None,
+            version: 0,
         };
         task.validate()?;
         Ok(task)
     }
 
+    pub fn with_category(mut self, category: Option<TaskCategory>) -> Self {
+        self.category = category;
+        self
+    }
+
+    pub fn with_tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.tags = normalize_tags(tags);
+        self
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.trim().to_lowercase();
+        self.tags.contains(&tag)
+    }
+
     pub fn validate(&self) -> Result<()> {
         if self.name.trim().is_empty() {
             return Err(crate::error::Error::Validation("Task name cannot be empty".to_string()));
@@ -73,6 +190,79 @@ impl Task {
         self.last_reset = Utc::now();
         self.updated_at = Utc::now();
     }
+
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
+    pub fn archive(&mut self, now: DateTime<Utc>) {
+        self.archived_at.get_or_insert(now);
+        self.updated_at = now;
+    }
+
+    /// Bring an archived task back, if still within the restore window
+    pub fn restore(&mut self, window: Duration, now: DateTime<Utc>) -> Result<()> {
+        check_restorable(self.archived_at, window, now)?;
+        self.archived_at = None;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Change only the fields set in `patch`, leaving the task as it was if
+    /// the result isn't valid
+    pub fn apply(&mut self, patch: &TaskPatch, now: DateTime<Utc>) -> Result<()> {
+        let mut next = self.clone();
+        if let Some(name) = &patch.name {
+            next.name = name.trim().to_string();
+        }
+        if let Some(description) = &patch.description {
+            next.description = description.trim().to_string();
+        }
+        if let Some(value) = patch.value {
+            next.value = value;
+        }
+        if let Some(cadence) = patch.cadence {
+            next.cadence = cadence;
+        }
+        if let Some(category) = patch.category {
+            next.category = category;
+        }
+        if let Some(tags) = &patch.tags {
+            next.tags = normalize_tags(tags);
+        }
+        next.validate()?;
+        next.updated_at = now;
+        *self = next;
+        Ok(())
+    }
+}
+
+/// A change to some of a task's fields
+///
+/// Unlike saving a whole `Task`, a patch only touches the fields it sets, so
+/// two people editing different fields of the same task don't undo each
+/// other's changes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskPatch {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub value: Option<Decimal>,
+    pub cadence: Option<Cadence>,
+    /// `Some(None)` clears the category
+    pub category: Option<Option<TaskCategory>>,
+    /// Replaces all tags
+    pub tags: Option<Vec<String>>,
+}
+
+impl TaskPatch {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.description.is_none()
+            && self.value.is_none()
+            && self.cadence.is_none()
+            && self.category.is_none()
+            && self.tags.is_none()
+    }
 }
 
 #[cfg(test)]
@@ -142,5 +332,63 @@ mod tests {
         ).unwrap();
         assert!(!task.needs_reset());
     }
+
+    #[test]
+    fn test_tags_normalized() {
+        let task = Task::new(
+            "Test".to_string(),
+            "".to_string(),
+            dec!(1.0),
+            Cadence::Daily,
+            "test-owner".to_string(),
+        )
+        .unwrap()
+        .with_category(Some(TaskCategory::Pets))
+        .with_tags(["Dog", " dog ", "", "Morning"]);
+
+        assert_eq!(task.tags, vec!["dog".to_string(), "morning".to_string()]);
+        assert!(task.has_tag("DOG"));
+        assert_eq!(task.category, Some(TaskCategory::Pets));
+    }
+
+    #[test]
+    fn test_apply_patch() {
+        let mut task = Task::new(
+            "Test".to_string(),
+            "Keep me".to_string(),
+            dec!(1.0),
+            Cadence::Daily,
+            "test-owner".to_string(),
+        )
+        .unwrap()
+        .with_category(Some(TaskCategory::Pets));
+
+        let patch = TaskPatch {
+            name: Some(" Feed cat ".to_string()),
+            category: Some(None),
+            tags: Some(vec!["Morning".to_string()]),
+            ..Default::default()
+        };
+        task.apply(&patch, Utc::now()).unwrap();
+        assert_eq!(task.name, "Feed cat");
+        assert_eq!(task.description, "Keep me");
+        assert_eq!(task.category, None);
+        assert_eq!(task.tags, vec!["morning".to_string()]);
+
+        let invalid = TaskPatch { value: Some(dec!(0)), ..Default::default() };
+        assert!(task.apply(&invalid, Utc::now()).is_err());
+        assert_eq!(task.value, dec!(1.0));
+        assert!(TaskPatch::default().is_empty());
+    }
+
+    #[test]
+    fn test_category_parse() {
+        assert_eq!(TaskCategory::parse("School"), Some(TaskCategory::School));
+        assert_eq!(TaskCategory::parse("pet"), Some(TaskCategory::Pets));
+        assert_eq!(TaskCategory::parse("garage"), None);
+        for category in TaskCategory::ALL {
+            assert_eq!(TaskCategory::parse(category.as_str()), Some(category));
+        }
+    }
 }
 
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use crate::error::{Error, Result};
+use crate::models::ledger::{serialize_uuid_as_string, deserialize_uuid_from_string};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TransferStatus {
+    /// Waiting for a parent to approve
+    Pending,
+    /// Moved: both ledger entries are written
+    Completed,
+    /// Turned down by a parent; nothing was moved
+    Declined,
+}
+
+impl TransferStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferStatus::Pending => "pending",
+            TransferStatus::Completed => "completed",
+            TransferStatus::Declined => "declined",
+        }
+    }
+}
+
+/// Money moved from one kid's balance to another's in the same household
+///
+/// Completing it writes a debit on the sender's ledger and a credit on the
+/// receiver's, both carrying the transfer's ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transfer {
+    #[serde(skip)]
+    pub id: Uuid,
+    /// Household of both kids
+    pub owner_id: String,
+    #[serde(serialize_with = "serialize_uuid_as_string", deserialize_with = "deserialize_uuid_from_string")]
+    pub from_kid_id: Uuid,
+    #[serde(serialize_with = "serialize_uuid_as_string", deserialize_with = "deserialize_uuid_from_string")]
+    pub to_kid_id: Uuid,
+    pub amount: Decimal,
+    /// Optional note ("my half of the video game")
+    #[serde(default)]
+    pub note: Option<String>,
+    pub status: TransferStatus,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl Transfer {
+    /// A transfer waiting for approval
+    pub fn new(owner_id: String, from_kid_id: Uuid, to_kid_id: Uuid, amount: Decimal, note: Option<String>) -> Result<Self> {
+        if from_kid_id == to_kid_id {
+            return Err(Error::Validation("A kid can't transfer money to themselves".to_string()));
+        }
+        if amount <= Decimal::ZERO {
+            return Err(Error::Validation("A transfer must be for more than $0".to_string()));
+        }
+        if amount.round_dp(2) != amount {
+            return Err(Error::Validation("A transfer can't include fractions of a cent".to_string()));
+        }
+        Ok(Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            from_kid_id,
+            to_kid_id,
+            amount,
+            note: note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+            status: TransferStatus::Pending,
+            created_at: Utc::now(),
+            resolved_at: None,
+        })
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.status == TransferStatus::Pending
+    }
+
+    pub fn complete(&mut self) -> Result<()> {
+        self.ensure_pending()?;
+        self.status = TransferStatus::Completed;
+        self.resolved_at = Some(Utc::now());
+        Ok(())
+    }
+
+    pub fn decline(&mut self) -> Result<()> {
+        self.ensure_pending()?;
+        self.status = TransferStatus::Declined;
+        self.resolved_at = Some(Utc::now());
+        Ok(())
+    }
+
+    fn ensure_pending(&self) -> Result<()> {
+        if !self.is_pending() {
+            return Err(Error::Validation("Transfer has already been reviewed".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_transfer_validation_and_review() {
+        let (kai, mia) = (Uuid::new_v4(), Uuid::new_v4());
+        assert!(Transfer::new("owner".to_string(), kai, kai, dec!(1.00), None).is_err());
+        assert!(Transfer::new("owner".to_string(), kai, mia, dec!(-1.00), None).is_err());
+
+        let mut transfer = Transfer::new("owner".to_string(), kai, mia, dec!(2.50), Some("  ".to_string())).unwrap();
+        assert!(transfer.is_pending() && transfer.note.is_none());
+        transfer.complete().unwrap();
+        assert_eq!(transfer.status, TransferStatus::Completed);
+        assert!(transfer.decline().is_err());
+    }
+}
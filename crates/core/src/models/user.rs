@@ -1,7 +1,8 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::error::{Error, Result};
+use crate::models::ledger::{serialize_optional_uuid_as_string, deserialize_optional_uuid_from_string};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum AccountType {
@@ -10,6 +11,8 @@ pub enum AccountType {
     /// Regular user account - sees dashboard with their own data
     #[default]
     User,
+    /// Kid account - logs in with a PIN, sees only their own tasks and ledger
+    Kid,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,8 +23,32 @@ pub struct User {
     pub password_hash: String,
     #[serde(default)]
     pub account_type: AccountType,
+    /// Kid record this account logs in as (kid accounts only)
+    #[serde(
+        default,
+        serialize_with = "serialize_optional_uuid_as_string",
+        deserialize_with = "deserialize_optional_uuid_from_string"
+    )]
+    pub kid_id: Option<Uuid>,
+    /// Consecutive failed logins since the last success
+    #[serde(default)]
+    pub failed_logins: u32,
+    /// Logins are refused until this time after too many failures
+    #[serde(default)]
+    pub locked_until: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Bumped on every save (see `Task::version`)
+    #[serde(default)]
+    pub version: u64,
+}
+
+/// Failed PIN attempts allowed before a kid account is locked
+pub const MAX_FAILED_PIN_ATTEMPTS: u32 = 5;
+
+/// How long a kid account stays locked after too many failed PINs
+pub fn pin_lockout_duration() -> Duration {
+    Duration::minutes(15)
 }
 
 impl User {
@@ -37,8 +64,30 @@ impl User {
             username,
             password_hash: String::new(),  // Must be set before saving
             account_type,
+            kid_id: None,
+            failed_logins: 0,
+            locked_until: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            version: 0,
+        };
+        user.validate()?;
+        Ok(user)
+    }
+
+    /// Create a login account for a kid (without PIN hash - that must be set separately)
+    pub fn new_kid(username: String, kid_id: Uuid) -> Result<Self> {
+        let user = Self {
+            id: Uuid::new_v4(),
+            username,
+            password_hash: String::new(),  // Must be set before saving
+            account_type: AccountType::Kid,
+            kid_id: Some(kid_id),
+            failed_logins: 0,
+            locked_until: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 0,
         };
         user.validate()?;
         Ok(user)
@@ -49,6 +98,33 @@ impl User {
         self.account_type == AccountType::Admin
     }
 
+    /// Check if this is a kid account
+    pub fn is_kid(&self) -> bool {
+        self.account_type == AccountType::Kid
+    }
+
+    /// Whether logins are currently refused because of failed attempts
+    pub fn is_locked(&self, now: DateTime<Utc>) -> bool {
+        self.locked_until.is_some_and(|until| now < until)
+    }
+
+    /// Count a failed login, locking the account once the limit is reached
+    pub fn record_failed_login(&mut self, now: DateTime<Utc>) {
+        self.failed_logins += 1;
+        if self.failed_logins >= MAX_FAILED_PIN_ATTEMPTS {
+            self.locked_until = Some(now + pin_lockout_duration());
+            self.failed_logins = 0;
+        }
+        self.updated_at = now;
+    }
+
+    /// Clear failed attempts after a successful login
+    pub fn record_successful_login(&mut self, now: DateTime<Utc>) {
+        self.failed_logins = 0;
+        self.locked_until = None;
+        self.updated_at = now;
+    }
+
     /// Validate user data
     pub fn validate(&self) -> Result<()> {
         if self.username.trim().is_empty() {
@@ -76,6 +152,12 @@ impl User {
             ));
         }
 
+        if self.account_type == AccountType::Kid && self.kid_id.is_none() {
+            return Err(Error::Validation(
+                "Kid accounts must be linked to a kid".to_string()
+            ));
+        }
+
         Ok(())
     }
 }
@@ -115,4 +197,31 @@ mod tests {
         let user = User::new("test_user-123".to_string()).unwrap();
         assert_eq!(user.username, "test_user-123");
     }
+
+    #[test]
+    fn test_kid_account_requires_kid() {
+        let mut user = User::new_kid("maya".to_string(), Uuid::new_v4()).unwrap();
+        assert!(user.is_kid());
+        user.kid_id = None;
+        assert!(user.validate().is_err());
+    }
+
+    #[test]
+    fn test_lockout_after_max_failures() {
+        let mut user = User::new_kid("maya".to_string(), Uuid::new_v4()).unwrap();
+        let now = Utc::now();
+
+        for _ in 0..MAX_FAILED_PIN_ATTEMPTS - 1 {
+            user.record_failed_login(now);
+        }
+        assert!(!user.is_locked(now));
+
+        user.record_failed_login(now);
+        assert!(user.is_locked(now));
+        assert!(!user.is_locked(now + pin_lockout_duration()));
+
+        user.record_successful_login(now);
+        assert!(!user.is_locked(now));
+        assert_eq!(user.failed_logins, 0);
+    }
 }
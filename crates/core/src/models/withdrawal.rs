@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use crate::error::{Error, Result};
+use crate::models::ledger::{serialize_uuid_as_string, deserialize_uuid_from_string};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WithdrawalStatus {
+    /// Waiting for a parent to approve
+    Requested,
+    /// Approved; the parent owes the kid the money
+    Approved,
+    /// Handed over
+    Paid,
+    /// Turned down, and the amount returned to the kid's balance
+    Declined,
+}
+
+impl WithdrawalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WithdrawalStatus::Requested => "requested",
+            WithdrawalStatus::Approved => "approved",
+            WithdrawalStatus::Paid => "paid",
+            WithdrawalStatus::Declined => "declined",
+        }
+    }
+}
+
+/// How a withdrawal was handed over
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PaymentMethod {
+    Cash,
+    BankTransfer,
+    GiftCard,
+}
+
+impl PaymentMethod {
+    pub const ALL: [PaymentMethod; 3] = [PaymentMethod::Cash, PaymentMethod::BankTransfer, PaymentMethod::GiftCard];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentMethod::Cash => "cash",
+            PaymentMethod::BankTransfer => "bank_transfer",
+            PaymentMethod::GiftCard => "gift_card",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaymentMethod::Cash => "cash",
+            PaymentMethod::BankTransfer => "bank transfer",
+            PaymentMethod::GiftCard => "gift card",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().replace([' ', '-'], "_").as_str() {
+            "cash" => Some(PaymentMethod::Cash),
+            "bank_transfer" | "bank" | "transfer" => Some(PaymentMethod::BankTransfer),
+            "gift_card" | "giftcard" => Some(PaymentMethod::GiftCard),
+            _ => None,
+        }
+    }
+}
+
+/// A kid's request to take money out of their balance
+///
+/// Requesting takes the amount off the balance straight away, so it can't be
+/// spent twice; declining puts it back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Withdrawal {
+    #[serde(skip)]
+    pub id: Uuid,
+    /// Household of the kid
+    pub owner_id: String,
+    #[serde(serialize_with = "serialize_uuid_as_string", deserialize_with = "deserialize_uuid_from_string")]
+    pub kid_id: Uuid,
+    pub amount: Decimal,
+    /// Optional note from the kid ("for the school trip")
+    #[serde(default)]
+    pub note: Option<String>,
+    pub status: WithdrawalStatus,
+    /// Set once paid
+    #[serde(default)]
+    pub method: Option<PaymentMethod>,
+    pub requested_at: DateTime<Utc>,
+    #[serde(default)]
+    pub approved_at: Option<DateTime<Utc>>,
+    /// When it was paid or declined
+    #[serde(default)]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl Withdrawal {
+    pub fn new(owner_id: String, kid_id: Uuid, amount: Decimal, note: Option<String>) -> Result<Self> {
+        if amount <= Decimal::ZERO {
+            return Err(Error::Validation("A withdrawal must be for more than $0".to_string()));
+        }
+        if amount.round_dp(2) != amount {
+            return Err(Error::Validation("A withdrawal can't include fractions of a cent".to_string()));
+        }
+        Ok(Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            kid_id,
+            amount,
+            note: note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+            status: WithdrawalStatus::Requested,
+            method: None,
+            requested_at: Utc::now(),
+            approved_at: None,
+            resolved_at: None,
+        })
+    }
+
+    /// Requested or approved, but not paid yet
+    pub fn is_open(&self) -> bool {
+        matches!(self.status, WithdrawalStatus::Requested | WithdrawalStatus::Approved)
+    }
+
+    pub fn approve(&mut self) -> Result<()> {
+        if self.status != WithdrawalStatus::Requested {
+            return Err(Error::Validation("Withdrawal has already been reviewed".to_string()));
+        }
+        self.status = WithdrawalStatus::Approved;
+        self.approved_at = Some(Utc::now());
+        Ok(())
+    }
+
+    /// Record that the parent handed the money over
+    pub fn mark_paid(&mut self, method: PaymentMethod) -> Result<()> {
+        if self.status != WithdrawalStatus::Approved {
+            return Err(Error::Validation("Only approved withdrawals can be marked paid".to_string()));
+        }
+        self.status = WithdrawalStatus::Paid;
+        self.method = Some(method);
+        self.resolved_at = Some(Utc::now());
+        Ok(())
+    }
+
+    pub fn decline(&mut self) -> Result<()> {
+        if !self.is_open() {
+            return Err(Error::Validation("Withdrawal has already been paid or declined".to_string()));
+        }
+        self.status = WithdrawalStatus::Declined;
+        self.resolved_at = Some(Utc::now());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_withdrawal_steps() {
+        let mut withdrawal = Withdrawal::new("owner".to_string(), Uuid::new_v4(), dec!(5.00), Some(" ".to_string())).unwrap();
+        assert!(withdrawal.is_open() && withdrawal.note.is_none());
+        assert!(withdrawal.mark_paid(PaymentMethod::Cash).is_err());
+
+        withdrawal.approve().unwrap();
+        assert!(withdrawal.approve().is_err());
+        withdrawal.mark_paid(PaymentMethod::GiftCard).unwrap();
+        assert_eq!((withdrawal.status, withdrawal.method), (WithdrawalStatus::Paid, Some(PaymentMethod::GiftCard)));
+        assert!(withdrawal.decline().is_err());
+
+        assert!(Withdrawal::new("owner".to_string(), Uuid::new_v4(), dec!(0), None).is_err());
+        assert!(Withdrawal::new("owner".to_string(), Uuid::new_v4(), dec!(1.005), None).is_err());
+        assert_eq!(PaymentMethod::parse("Bank transfer"), Some(PaymentMethod::BankTransfer));
+    }
+}
@@ -0,0 +1,270 @@
+use crate::db::{patch_task, KidRepository, LedgerRepository, Repository, TaskRepository};
+use crate::models::{HouseholdRole, Kid, Ledger, LedgerEntry, Task, TaskPatch};
+use crate::workflows::HouseholdWorkflow;
+use crate::error::{Error, Result};
+use rust_decimal::Decimal;
+use std::fmt::Display;
+use uuid::Uuid;
+
+/// Who is performing an operation
+///
+/// Every read or change of household data is checked against an actor, so
+/// callers have to say who they are acting for rather than passing bare IDs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Actor {
+    /// A parent account acting in its household
+    Member {
+        user_id: String,
+        household_id: String,
+        role: HouseholdRole,
+    },
+    /// A kid logged in with a PIN, limited to their own records
+    Kid {
+        household_id: String,
+        kid_id: Uuid,
+    },
+    /// The server itself (migrations, retention jobs); never built from a request
+    System,
+}
+
+impl Actor {
+    pub fn member(user_id: impl Into<String>, household_id: impl Into<String>, role: HouseholdRole) -> Self {
+        Actor::Member {
+            user_id: user_id.into(),
+            household_id: household_id.into(),
+            role,
+        }
+    }
+
+    pub fn kid(household_id: impl Into<String>, kid_id: Uuid) -> Self {
+        Actor::Kid {
+            household_id: household_id.into(),
+            kid_id,
+        }
+    }
+
+    /// The household this actor acts in, i.e. the `owner_id` of its records
+    pub fn household_id(&self) -> Option<&str> {
+        match self {
+            Actor::Member { household_id, .. } | Actor::Kid { household_id, .. } => Some(household_id),
+            Actor::System => None,
+        }
+    }
+
+    pub fn can_edit(&self) -> bool {
+        match self {
+            Actor::Member { role, .. } => role.can_edit(),
+            Actor::Kid { .. } => false,
+            Actor::System => true,
+        }
+    }
+
+    /// Allow reading a record owned by `owner_id`
+    pub fn authorize_read(&self, owner_id: &str, what: impl Display) -> Result<()> {
+        match self.household_id() {
+            None => Ok(()),
+            Some(household_id) => check_owner(household_id, owner_id, what),
+        }
+    }
+
+    /// Allow changing a record owned by `owner_id`
+    pub fn authorize_edit(&self, owner_id: &str, what: impl Display) -> Result<()> {
+        self.authorize_read(owner_id, &what)?;
+        match self {
+            Actor::Member { .. } if !self.can_edit() => {
+                Err(Error::Forbidden("Household viewers can't make changes".to_string()))
+            }
+            Actor::Kid { .. } => Err(Error::Forbidden(format!("Kid logins can't change {}", what))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Allow reading a kid's records; kid logins only see themselves
+    pub fn authorize_kid(&self, kid: &Kid) -> Result<()> {
+        let what = format!("Kid {}", kid.id);
+        self.authorize_read(&kid.owner_id, &what)?;
+        match self {
+            Actor::Kid { kid_id, .. } if *kid_id != kid.id => {
+                Err(Error::Forbidden(format!("{} is another kid's", what)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Allow asking for something on a kid's behalf (a claim, a withdrawal):
+    /// the kid themselves, or a parent who can make changes
+    pub fn authorize_request(&self, kid: &Kid) -> Result<()> {
+        self.authorize_kid(kid)?;
+        match self {
+            Actor::Kid { .. } => Ok(()),
+            _ => self.authorize_edit(&kid.owner_id, format!("Kid {}", kid.id)),
+        }
+    }
+
+    /// The household whose records a member lists, e.g. every kid's ledger
+    ///
+    /// Kid logins only see their own records and the server has no household
+    /// of its own, so both are refused.
+    pub fn readable_household(&self) -> Result<&str> {
+        match self {
+            Actor::Member { household_id, .. } => Ok(household_id),
+            Actor::Kid { .. } => Err(Error::Forbidden("Kid logins can only see their own records".to_string())),
+            Actor::System => Err(Error::Validation("No household to list".to_string())),
+        }
+    }
+
+    /// The household a member adds records to, such as imported tasks
+    pub fn editable_household(&self) -> Result<&str> {
+        let household_id = self.readable_household()?;
+        self.authorize_edit(household_id, "this household")?;
+        Ok(household_id)
+    }
+}
+
+/// Fail with `Error::Forbidden` unless a record owned by `owner_id` belongs
+/// to `household_id`
+fn check_owner(household_id: &str, owner_id: &str, what: impl Display) -> Result<()> {
+    if household_id != owner_id {
+        return Err(Error::Forbidden(format!("{} belongs to another household", what)));
+    }
+    Ok(())
+}
+
+/// Repository access on behalf of an actor
+///
+/// Entry points (MCP tools, server functions) that work on a single record by
+/// ID go through here, so a guessed ID from another household is refused.
+///
+/// Kids and tasks can come from any `Repository`, so the checks can be tested
+/// in memory.
+pub struct Policy<K = KidRepository, T = TaskRepository> {
+    kid_repo: K,
+    task_repo: T,
+    ledger_repo: LedgerRepository,
+    households: HouseholdWorkflow,
+}
+
+impl<K: Repository<Kid>, T: Repository<Task>> Policy<K, T> {
+    pub fn new(
+        kid_repo: K,
+        task_repo: T,
+        ledger_repo: LedgerRepository,
+        households: HouseholdWorkflow,
+    ) -> Self {
+        Self {
+            kid_repo,
+            task_repo,
+            ledger_repo,
+            households,
+        }
+    }
+
+    /// The actor for a parent account, acting in its household
+    pub async fn actor_for_user(&self, user_id: &str) -> Result<Actor> {
+        let (household, role) = self.households.membership(user_id).await?;
+        Ok(Actor::member(user_id, household.owner_id(), role))
+    }
+
+    pub async fn kid(&self, actor: &Actor, kid_id: Uuid) -> Result<Kid> {
+        let kid = self.kid_repo.get(kid_id).await?;
+        actor.authorize_kid(&kid)?;
+        Ok(kid)
+    }
+
+    pub async fn task(&self, actor: &Actor, task_id: Uuid) -> Result<Task> {
+        let task = self.task_repo.get(task_id).await?;
+        actor.authorize_read(&task.owner_id, format!("Task {}", task_id))?;
+        Ok(task)
+    }
+
+    pub async fn ledger(&self, actor: &Actor, kid_id: Uuid) -> Result<Ledger> {
+        self.kid(actor, kid_id).await?;
+        self.ledger_repo.get_ledger(kid_id).await
+    }
+
+    /// Save changes to a task; the stored task decides who may change it
+    pub async fn update_task(&self, actor: &Actor, mut task: Task) -> Result<Task> {
+        let existing = self.task_repo.get(task.id).await?;
+        actor.authorize_edit(&existing.owner_id, format!("Task {}", task.id))?;
+        // Tasks can't be moved into another household this way
+        task.owner_id = existing.owner_id;
+        self.task_repo.update(task).await
+    }
+
+    /// Change some of a task's fields (see `patch_task`)
+    pub async fn patch_task(
+        &self,
+        actor: &Actor,
+        task_id: Uuid,
+        patch: &TaskPatch,
+        expected_version: Option<u64>,
+    ) -> Result<Task> {
+        let existing = self.task_repo.get(task_id).await?;
+        actor.authorize_edit(&existing.owner_id, format!("Task {}", task_id))?;
+        patch_task(&self.task_repo, task_id, patch, expected_version).await
+    }
+
+    /// Add a manual adjustment to a kid's balance
+    pub async fn adjust_balance(
+        &self,
+        actor: &Actor,
+        kid_id: Uuid,
+        amount: Decimal,
+        description: String,
+    ) -> Result<LedgerEntry> {
+        let kid = self.kid_repo.get(kid_id).await?;
+        actor.authorize_edit(&kid.owner_id, format!("Kid {}", kid_id))?;
+        self.ledger_repo
+            .create_entry(LedgerEntry::adjusted(kid_id, amount, description))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kid_in(household_id: &str) -> Kid {
+        Kid::new("Keala".to_string(), household_id.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_members_only_reach_their_household() {
+        let parent = Actor::member("mom", "house-a", HouseholdRole::Parent);
+        assert!(parent.authorize_read("house-a", "Task").is_ok());
+        assert!(parent.authorize_edit("house-a", "Task").is_ok());
+        assert!(matches!(parent.authorize_read("house-b", "Task"), Err(Error::Forbidden(_))));
+        assert!(matches!(parent.authorize_edit("house-b", "Task"), Err(Error::Forbidden(_))));
+    }
+
+    #[test]
+    fn test_viewers_and_kids_are_read_only() {
+        let viewer = Actor::member("grandma", "house-a", HouseholdRole::Viewer);
+        assert!(viewer.authorize_read("house-a", "Task").is_ok());
+        assert!(matches!(viewer.authorize_edit("house-a", "Task"), Err(Error::Forbidden(_))));
+
+        let kid = kid_in("house-a");
+        let actor = Actor::kid("house-a", kid.id);
+        assert!(actor.authorize_kid(&kid).is_ok());
+        assert!(matches!(actor.authorize_edit("house-a", "Task"), Err(Error::Forbidden(_))));
+    }
+
+    #[test]
+    fn test_kids_only_see_themselves() {
+        let kid = kid_in("house-a");
+        let sibling = kid_in("house-a");
+        let actor = Actor::kid("house-a", kid.id);
+        assert!(matches!(actor.authorize_kid(&sibling), Err(Error::Forbidden(_))));
+
+        // Parents see every kid in their household, and only those
+        let parent = Actor::member("mom", "house-a", HouseholdRole::Parent);
+        assert!(parent.authorize_kid(&sibling).is_ok());
+        assert!(matches!(parent.authorize_kid(&kid_in("house-b")), Err(Error::Forbidden(_))));
+    }
+
+    #[test]
+    fn test_system_is_unrestricted() {
+        assert!(Actor::System.authorize_edit("house-a", "Task").is_ok());
+        assert!(Actor::System.authorize_kid(&kid_in("house-b")).is_ok());
+    }
+}
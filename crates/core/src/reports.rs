@@ -0,0 +1,446 @@
+//! Earnings reports: what each kid earned per day, week or month
+//!
+//! The database sums the ledger into [`EarningsTotal`]s, one per kid, period,
+//! task, category and entry type, so a report never loads individual
+//! entries. [`EarningsReport::new`] arranges those totals into periods,
+//! breakdowns and comparisons with the span before.
+
+use crate::models::{EntryType, Kid, Task, TaskCategory};
+use crate::error::{Error, Result};
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The most periods one report covers
+pub const MAX_PERIODS: usize = 366;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportPeriod {
+    Day,
+    Week,
+    Month,
+}
+
+impl ReportPeriod {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "day" | "daily" => Some(ReportPeriod::Day),
+            "week" | "weekly" => Some(ReportPeriod::Week),
+            "month" | "monthly" => Some(ReportPeriod::Month),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportPeriod::Day => "day",
+            ReportPeriod::Week => "week",
+            ReportPeriod::Month => "month",
+        }
+    }
+
+    /// How many periods a report shows when no dates are given
+    pub fn default_count(&self) -> usize {
+        match self {
+            ReportPeriod::Day => 14,
+            ReportPeriod::Week => 8,
+            ReportPeriod::Month => 6,
+        }
+    }
+
+    /// First day of the period `day` falls in; weeks start on Monday
+    pub fn start_of(&self, day: NaiveDate) -> NaiveDate {
+        match self {
+            ReportPeriod::Day => day,
+            ReportPeriod::Week => day.week(Weekday::Mon).first_day(),
+            ReportPeriod::Month => day.with_day(1).unwrap_or(day),
+        }
+    }
+
+    /// First day of the period after the one starting on `start`
+    pub fn next(&self, start: NaiveDate) -> NaiveDate {
+        self.shift(start, 1, true)
+    }
+
+    /// First day of the period `count` periods before the one starting on
+    /// `start`
+    pub fn back(&self, start: NaiveDate, count: usize) -> NaiveDate {
+        self.shift(start, count, false)
+    }
+
+    fn shift(&self, start: NaiveDate, count: usize, forward: bool) -> NaiveDate {
+        let count = count as u64;
+        let shifted = match (self, forward) {
+            (ReportPeriod::Day, true) => start.checked_add_days(Days::new(count)),
+            (ReportPeriod::Day, false) => start.checked_sub_days(Days::new(count)),
+            (ReportPeriod::Week, true) => start.checked_add_days(Days::new(7 * count)),
+            (ReportPeriod::Week, false) => start.checked_sub_days(Days::new(7 * count)),
+            (ReportPeriod::Month, true) => start.checked_add_months(Months::new(count as u32)),
+            (ReportPeriod::Month, false) => start.checked_sub_months(Months::new(count as u32)),
+        };
+        shifted.unwrap_or(start)
+    }
+
+    /// "Sep 14", "Week of Sep 14" or "Sep 2026"
+    pub fn label(&self, start: NaiveDate) -> String {
+        match self {
+            ReportPeriod::Day => start.format("%a %b %-d").to_string(),
+            ReportPeriod::Week => start.format("Week of %b %-d").to_string(),
+            ReportPeriod::Month => start.format("%b %Y").to_string(),
+        }
+    }
+
+    /// How the database names the period an entry falls in
+    pub(crate) fn bucket_format(&self) -> &'static str {
+        match self {
+            ReportPeriod::Day => "%Y-%m-%d",
+            ReportPeriod::Week => "%G-W%V",
+            ReportPeriod::Month => "%Y-%m",
+        }
+    }
+
+    /// First day of the period the database named `bucket`
+    pub(crate) fn parse_bucket(&self, bucket: &str) -> Option<NaiveDate> {
+        match self {
+            ReportPeriod::Day => NaiveDate::parse_from_str(bucket, "%Y-%m-%d").ok(),
+            ReportPeriod::Week => {
+                let (year, week) = bucket.split_once("-W")?;
+                NaiveDate::from_isoywd_opt(year.parse().ok()?, week.parse().ok()?, Weekday::Mon)
+            }
+            ReportPeriod::Month => NaiveDate::parse_from_str(&format!("{}-01", bucket), "%Y-%m-%d").ok(),
+        }
+    }
+}
+
+/// Which kids and periods a report covers
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportQuery {
+    /// One kid, or all of the household's active kids
+    pub kid_id: Option<Uuid>,
+    pub period: ReportPeriod,
+    /// First day of the first period
+    pub from: NaiveDate,
+    /// Last day to include; the period it falls in is covered in full
+    pub to: NaiveDate,
+}
+
+impl ReportQuery {
+    pub fn new(kid_id: Option<Uuid>, period: ReportPeriod, from: NaiveDate, to: NaiveDate) -> Result<Self> {
+        if from > to {
+            return Err(Error::Validation("The report's start date is after its end date".to_string()));
+        }
+        let query = Self { kid_id, period, from: period.start_of(from), to };
+        if query.period_starts().len() > MAX_PERIODS {
+            return Err(Error::Validation(format!(
+                "A report can cover at most {} {}s",
+                MAX_PERIODS,
+                period.as_str()
+            )));
+        }
+        Ok(query)
+    }
+
+    /// The last `count` periods, up to and including the one `today` is in
+    pub fn recent(kid_id: Option<Uuid>, period: ReportPeriod, count: usize, today: NaiveDate) -> Result<Self> {
+        let from = period.back(period.start_of(today), count.max(1) - 1);
+        Self::new(kid_id, period, from, today)
+    }
+
+    /// First days of the periods covered
+    pub fn period_starts(&self) -> Vec<NaiveDate> {
+        let mut starts = Vec::new();
+        let mut start = self.from;
+        while start <= self.to && starts.len() <= MAX_PERIODS {
+            starts.push(start);
+            start = self.period.next(start);
+        }
+        starts
+    }
+
+    /// The day after the last period ends
+    pub fn end(&self) -> NaiveDate {
+        self.period.next(self.period.start_of(self.to))
+    }
+
+    /// First day of the span just as long right before this one, which
+    /// the report compares against
+    pub fn comparison_start(&self) -> NaiveDate {
+        self.period.back(self.from, self.period_starts().len())
+    }
+}
+
+/// Ledger entries of one kid, period, task, category and entry type, summed
+/// by the database
+#[derive(Debug, Clone, PartialEq)]
+pub struct EarningsTotal {
+    pub kid_id: Uuid,
+    /// First day of the period
+    pub period_start: NaiveDate,
+    pub task_id: Option<Uuid>,
+    pub category: Option<TaskCategory>,
+    pub entry_type: EntryType,
+    pub entries: u64,
+    pub total: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EarningsReport {
+    pub period: ReportPeriod,
+    pub from: NaiveDate,
+    /// Last day of the last period
+    pub to: NaiveDate,
+    pub kids: Vec<KidEarnings>,
+}
+
+/// One kid's part of a report
+///
+/// "Earned" counts task earnings only; "net" also counts adjustments, but not
+/// payouts or transfers, which move earnings rather than change them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KidEarnings {
+    pub kid_id: Uuid,
+    pub name: String,
+    pub earned: Decimal,
+    pub net: Decimal,
+    /// Earned over the same number of periods right before the report's
+    pub previous_earned: Decimal,
+    /// Percentage change from `previous_earned`, when there was any
+    pub change_percent: Option<Decimal>,
+    pub periods: Vec<PeriodEarnings>,
+    /// Task earnings per task, largest first
+    pub by_task: Vec<EarningsBreakdown>,
+    /// Task earnings per category, largest first
+    pub by_category: Vec<EarningsBreakdown>,
+    /// Every entry, per entry type
+    pub by_entry_type: Vec<EarningsBreakdown>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PeriodEarnings {
+    pub start: NaiveDate,
+    pub label: String,
+    pub earned: Decimal,
+    pub net: Decimal,
+    pub entries: u64,
+    /// Difference in earnings from the period before
+    pub change: Decimal,
+    pub change_percent: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EarningsBreakdown {
+    /// Task ID, category or entry type
+    pub key: String,
+    pub label: String,
+    pub total: Decimal,
+    pub entries: u64,
+}
+
+/// Percentage change from `previous` to `current`, to one decimal place
+pub fn change_percent(current: Decimal, previous: Decimal) -> Option<Decimal> {
+    if previous.is_zero() {
+        return None;
+    }
+    Some(((current - previous) / previous.abs() * Decimal::ONE_HUNDRED).round_dp(1))
+}
+
+impl EarningsReport {
+    /// Arrange the database's totals for `kids`
+    ///
+    /// `totals` should start at `query.comparison_start()`. `tasks` name the
+    /// tasks, and give the category of earnings recorded before entries
+    /// carried one.
+    pub fn new(query: &ReportQuery, kids: &[Kid], tasks: &[Task], totals: &[EarningsTotal]) -> Self {
+        let starts = query.period_starts();
+        let kids = kids
+            .iter()
+            .map(|kid| {
+                let kid_totals: Vec<&EarningsTotal> = totals.iter().filter(|t| t.kid_id == kid.id).collect();
+                kid_earnings(query, &starts, kid, tasks, &kid_totals)
+            })
+            .collect();
+
+        Self {
+            period: query.period,
+            from: query.from,
+            to: query.end().pred_opt().unwrap_or(query.to),
+            kids,
+        }
+    }
+}
+
+fn counts_toward_net(entry_type: EntryType) -> bool {
+    matches!(entry_type, EntryType::Earned | EntryType::Adjusted)
+}
+
+fn kid_earnings(
+    query: &ReportQuery,
+    starts: &[NaiveDate],
+    kid: &Kid,
+    tasks: &[Task],
+    totals: &[&EarningsTotal],
+) -> KidEarnings {
+    let earned_in = |from: NaiveDate, to: NaiveDate| -> Decimal {
+        totals
+            .iter()
+            .filter(|t| t.entry_type == EntryType::Earned && t.period_start >= from && t.period_start < to)
+            .map(|t| t.total)
+            .sum()
+    };
+
+    let mut previous = earned_in(query.period.back(query.from, 1), query.from);
+    let periods: Vec<PeriodEarnings> = starts
+        .iter()
+        .map(|&start| {
+            let in_period: Vec<&&EarningsTotal> = totals.iter().filter(|t| t.period_start == start).collect();
+            let earned = earned_in(start, query.period.next(start));
+            let period = PeriodEarnings {
+                start,
+                label: query.period.label(start),
+                earned,
+                net: in_period.iter().filter(|t| counts_toward_net(t.entry_type)).map(|t| t.total).sum(),
+                entries: in_period.iter().map(|t| t.entries).sum(),
+                change: earned - previous,
+                change_percent: change_percent(earned, previous),
+            };
+            previous = earned;
+            period
+        })
+        .collect();
+
+    let in_range: Vec<&EarningsTotal> = totals
+        .iter()
+        .copied()
+        .filter(|t| t.period_start >= query.from && t.period_start < query.end())
+        .collect();
+    let earnings: Vec<&EarningsTotal> = in_range.iter().copied().filter(|t| t.entry_type == EntryType::Earned).collect();
+
+    let task_of = |id: Uuid| tasks.iter().find(|t| t.id == id);
+    let by_task = breakdown(&earnings, |t| match t.task_id {
+        Some(id) => (id.to_string(), task_of(id).map(|t| t.name.clone()).unwrap_or_else(|| "Deleted task".to_string())),
+        None => ("none".to_string(), "No task".to_string()),
+    });
+    let by_category = breakdown(&earnings, |t| {
+        let category = t.category.or_else(|| t.task_id.and_then(task_of).and_then(|t| t.category));
+        let name = category.map(|c| c.as_str()).unwrap_or("uncategorized");
+        (name.to_string(), capitalize(name))
+    });
+    let by_entry_type = breakdown(&in_range, |t| (t.entry_type.as_str().to_string(), capitalize(t.entry_type.as_str())));
+
+    let earned = periods.iter().map(|p| p.earned).sum();
+    let previous_earned = earned_in(query.comparison_start(), query.from);
+    KidEarnings {
+        kid_id: kid.id,
+        name: kid.name.clone(),
+        earned,
+        net: periods.iter().map(|p| p.net).sum(),
+        previous_earned,
+        change_percent: change_percent(earned, previous_earned),
+        periods,
+        by_task,
+        by_category,
+        by_entry_type,
+    }
+}
+
+/// Totals grouped under the key and label `group` gives them, largest first
+fn breakdown<F>(totals: &[&EarningsTotal], group: F) -> Vec<EarningsBreakdown>
+where
+    F: Fn(&EarningsTotal) -> (String, String),
+{
+    let mut groups: Vec<EarningsBreakdown> = Vec::new();
+    for total in totals {
+        let (key, label) = group(total);
+        match groups.iter_mut().find(|g| g.key == key) {
+            Some(group) => {
+                group.total += total.total;
+                group.entries += total.entries;
+            }
+            None => groups.push(EarningsBreakdown { key, label, total: total.total, entries: total.entries }),
+        }
+    }
+    groups.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.label.cmp(&b.label)));
+    groups
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Cadence;
+    use rust_decimal_macros::dec;
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_periods() {
+        // 2026-09-16 is a Wednesday
+        assert_eq!(ReportPeriod::Week.start_of(day(2026, 9, 16)), day(2026, 9, 14));
+        assert_eq!(ReportPeriod::Week.parse_bucket("2026-W38"), Some(day(2026, 9, 14)));
+        assert_eq!(ReportPeriod::Month.parse_bucket("2026-09"), Some(day(2026, 9, 1)));
+        assert_eq!(ReportPeriod::Day.parse_bucket("2026-09-16"), Some(day(2026, 9, 16)));
+        assert_eq!(ReportPeriod::Month.back(day(2026, 1, 1), 2), day(2025, 11, 1));
+
+        let query = ReportQuery::recent(None, ReportPeriod::Month, 3, day(2026, 10, 18)).unwrap();
+        assert_eq!(query.period_starts(), vec![day(2026, 8, 1), day(2026, 9, 1), day(2026, 10, 1)]);
+        assert_eq!(query.end(), day(2026, 11, 1));
+        assert_eq!(query.comparison_start(), day(2026, 5, 1));
+
+        assert!(ReportQuery::new(None, ReportPeriod::Day, day(2026, 9, 2), day(2026, 9, 1)).is_err());
+        assert!(ReportQuery::new(None, ReportPeriod::Day, day(2020, 1, 1), day(2026, 1, 1)).is_err());
+    }
+
+    #[test]
+    fn test_report_from_totals() {
+        let kai = Kid::new("Kai".to_string(), "owner".to_string()).unwrap();
+        let dishes = Task::new("Dishes".to_string(), String::new(), dec!(1.00), Cadence::Daily, "owner".to_string())
+            .unwrap()
+            .with_category(Some(TaskCategory::Kitchen));
+        let total = |month: u32, task_id: Option<Uuid>, entry_type: EntryType, total: Decimal| EarningsTotal {
+            kid_id: kai.id,
+            period_start: day(2026, month, 1),
+            task_id,
+            category: None,
+            entry_type,
+            entries: 2,
+            total,
+        };
+        let totals = vec![
+            total(7, Some(dishes.id), EntryType::Earned, dec!(4.00)),
+            total(8, Some(dishes.id), EntryType::Earned, dec!(5.00)),
+            total(9, Some(dishes.id), EntryType::Earned, dec!(6.00)),
+            total(9, Some(Uuid::new_v4()), EntryType::Earned, dec!(1.50)),
+            total(9, None, EntryType::Adjusted, dec!(-2.00)),
+        ];
+
+        let query = ReportQuery::new(None, ReportPeriod::Month, day(2026, 8, 1), day(2026, 9, 30)).unwrap();
+        let report = EarningsReport::new(&query, std::slice::from_ref(&kai), std::slice::from_ref(&dishes), &totals);
+        assert_eq!(report.to, day(2026, 9, 30));
+        let kid = &report.kids[0];
+        assert_eq!((kid.earned, kid.net, kid.previous_earned), (dec!(12.50), dec!(10.50), dec!(4.00)));
+        assert_eq!(kid.change_percent, Some(dec!(212.5)));
+
+        let september = &kid.periods[1];
+        assert_eq!((september.earned, september.net, september.entries), (dec!(7.50), dec!(5.50), 6));
+        assert_eq!((september.change, september.change_percent), (dec!(2.50), Some(dec!(50.0))));
+        assert_eq!(kid.periods[0].change, dec!(1.00));
+
+        let tasks: Vec<(&str, Decimal)> = kid.by_task.iter().map(|b| (b.label.as_str(), b.total)).collect();
+        assert_eq!(tasks, vec![("Dishes", dec!(11.00)), ("Deleted task", dec!(1.50))]);
+        assert_eq!(kid.by_category[0].label, "Kitchen");
+        assert_eq!(kid.by_category[1].key, "uncategorized");
+        let types: Vec<&str> = kid.by_entry_type.iter().map(|b| b.key.as_str()).collect();
+        assert_eq!(types, vec!["earned", "adjusted"]);
+    }
+}
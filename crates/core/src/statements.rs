@@ -0,0 +1,439 @@
+//! Bank-style monthly statements of a kid's ledger
+//!
+//! A [`Statement`] is built from ledger entries and rendered either as a
+//! standalone HTML page or as a PDF. PDFs use the standard Helvetica font,
+//! which every viewer has, so no font files are embedded.
+
+use crate::models::{EntryType, Kid, LedgerEntry};
+use crate::error::{Error, Result};
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+use rust_decimal::Decimal;
+use std::fmt::Write;
+use uuid::Uuid;
+
+/// One month of a kid's ledger
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    pub kid_id: Uuid,
+    pub kid_name: String,
+    /// First day of the month covered
+    pub month: NaiveDate,
+    pub opening_balance: Decimal,
+    pub lines: Vec<StatementLine>,
+    /// The month's entries totalled per type, leaving out types with none
+    pub totals: Vec<(EntryType, Decimal)>,
+    pub closing_balance: Decimal,
+}
+
+/// An entry on a statement, with the balance after it
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementLine {
+    pub date: DateTime<Utc>,
+    pub description: String,
+    pub entry_type: EntryType,
+    pub amount: Decimal,
+    pub balance: Decimal,
+}
+
+/// The first day of a month, refusing months that don't exist
+pub fn month_start(year: i32, month: u32) -> Result<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .ok_or_else(|| Error::Validation(format!("{}-{:02} isn't a month", year, month)))
+}
+
+/// Parse a month written as `2026-09`
+pub fn parse_month(month: &str) -> Result<NaiveDate> {
+    let invalid = || Error::Validation(format!("Month '{}' should look like 2026-09", month));
+    let (year, number) = month.trim().split_once('-').ok_or_else(invalid)?;
+    month_start(year.parse().map_err(|_| invalid())?, number.parse().map_err(|_| invalid())?)
+}
+
+/// The month before the one `now` falls in, which is the latest one that's over
+pub fn previous_month(now: DateTime<Utc>) -> NaiveDate {
+    let this_month = now.date_naive().with_day(1).unwrap_or_default();
+    this_month - Months::new(1)
+}
+
+/// When a month ends, which is when the next one starts (UTC)
+pub fn month_end(month: NaiveDate) -> DateTime<Utc> {
+    (month + Months::new(1)).and_time(chrono::NaiveTime::MIN).and_utc()
+}
+
+impl Statement {
+    /// The statement for `month` (its first day), from all of the kid's
+    /// entries; the ones before the month make up the opening balance
+    pub fn new(kid: &Kid, entries: &[LedgerEntry], month: NaiveDate) -> Self {
+        let start = month.and_time(chrono::NaiveTime::MIN).and_utc();
+        let end = month_end(month);
+
+        let mut entries: Vec<&LedgerEntry> = entries.iter().filter(|e| e.created_at < end).collect();
+        entries.sort_by_key(|e| e.created_at);
+        let opening_balance: Decimal = entries.iter().filter(|e| e.created_at < start).map(|e| e.amount).sum();
+
+        let mut balance = opening_balance;
+        let mut lines = Vec::new();
+        let mut totals: Vec<(EntryType, Decimal)> = Vec::new();
+        for entry in entries.into_iter().filter(|e| e.created_at >= start) {
+            balance += entry.amount;
+            lines.push(StatementLine {
+                date: entry.created_at,
+                description: entry.description.clone(),
+                entry_type: entry.entry_type,
+                amount: entry.amount,
+                balance,
+            });
+            match totals.iter_mut().find(|(entry_type, _)| *entry_type == entry.entry_type) {
+                Some((_, total)) => *total += entry.amount,
+                None => totals.push((entry.entry_type, entry.amount)),
+            }
+        }
+        totals.sort_by_key(|(entry_type, _)| EntryType::ALL.iter().position(|t| t == entry_type));
+
+        Self {
+            kid_id: kid.id,
+            kid_name: kid.name.clone(),
+            month,
+            opening_balance,
+            lines,
+            totals,
+            closing_balance: balance,
+        }
+    }
+
+    /// "September 2026"
+    pub fn period(&self) -> String {
+        self.month.format("%B %Y").to_string()
+    }
+
+    /// A file name without extension, e.g. `statement-2026-09-kai-<id>`
+    pub fn file_stem(&self) -> String {
+        let name: String = self.kid_name
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        format!("statement-{}-{}-{}", self.month.format("%Y-%m"), name.trim_matches('-'), self.kid_id)
+    }
+
+    /// A standalone HTML page, with its styles inline
+    pub fn to_html(&self) -> String {
+        let title = format!("{} — statement for {}", self.kid_name, self.period());
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n",
+            escape_html(&title),
+            HTML_STYLE
+        );
+        let _ = write!(
+            html,
+            "<h1>{}</h1>\n<p class=\"period\">Statement for {}</p>\n<table class=\"summary\">\n<tr><th>Opening balance</th><td>{}</td></tr>\n",
+            escape_html(&self.kid_name),
+            self.period(),
+            money(self.opening_balance)
+        );
+        for (entry_type, total) in &self.totals {
+            let _ = writeln!(html, "<tr><th>Total {}</th><td>{}</td></tr>", entry_type.as_str(), money(*total));
+        }
+        let _ = write!(
+            html,
+            "<tr class=\"closing\"><th>Closing balance</th><td>{}</td></tr>\n</table>\n",
+            money(self.closing_balance)
+        );
+
+        if self.lines.is_empty() {
+            html.push_str("<p class=\"empty\">No entries this month.</p>\n");
+        } else {
+            html.push_str("<table class=\"entries\">\n<thead><tr><th>Date</th><th>Description</th><th>Type</th><th>Amount</th><th>Balance</th></tr></thead>\n<tbody>\n");
+            for line in &self.lines {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"amount\">{}</td><td class=\"amount\">{}</td></tr>",
+                    line.date.format("%Y-%m-%d"),
+                    escape_html(&line.description),
+                    line.entry_type.as_str(),
+                    money(line.amount),
+                    money(line.balance)
+                );
+            }
+            html.push_str("</tbody>\n</table>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    /// An A4 PDF, continued over as many pages as the entries need
+    pub fn to_pdf(&self) -> Vec<u8> {
+        let mut pages = vec![PdfPage::new()];
+        let page = pages.last_mut().unwrap();
+        page.text(BOLD, 20.0, LEFT, TOP, &self.kid_name);
+        page.text(REGULAR, 11.0, LEFT, TOP - 20.0, &format!("Statement for {}", self.period()));
+
+        let mut y = TOP - 55.0;
+        let mut summary = vec![("Opening balance".to_string(), self.opening_balance)];
+        summary.extend(self.totals.iter().map(|(t, total)| (format!("Total {}", t.as_str()), *total)));
+        summary.push(("Closing balance".to_string(), self.closing_balance));
+        for (i, (label, amount)) in summary.iter().enumerate() {
+            let font = if i == summary.len() - 1 { BOLD } else { REGULAR };
+            page.text(font, 10.0, LEFT, y, label);
+            page.amount(font, 10.0, LEFT + 220.0, y, *amount);
+            y -= ROW;
+        }
+
+        y -= ROW;
+        if self.lines.is_empty() {
+            page.text(REGULAR, 10.0, LEFT, y, "No entries this month.");
+        } else {
+            page.table_header(y);
+            y -= ROW + 4.0;
+        }
+        for line in &self.lines {
+            if y < BOTTOM {
+                pages.push(PdfPage::new());
+                y = TOP;
+                pages.last_mut().unwrap().table_header(y);
+                y -= ROW + 4.0;
+            }
+            let page = pages.last_mut().unwrap();
+            page.text(REGULAR, 9.0, COLUMNS[0], y, &line.date.format("%Y-%m-%d").to_string());
+            page.text(REGULAR, 9.0, COLUMNS[1], y, &truncate(&line.description, 48));
+            page.text(REGULAR, 9.0, COLUMNS[2], y, line.entry_type.as_str());
+            page.amount(REGULAR, 9.0, COLUMNS[3], y, line.amount);
+            page.amount(REGULAR, 9.0, COLUMNS[4], y, line.balance);
+            y -= ROW;
+        }
+
+        let count = pages.len();
+        for (i, page) in pages.iter_mut().enumerate() {
+            page.text(REGULAR, 8.0, LEFT, 30.0, &format!("{} · {} · page {} of {}", self.kid_name, self.period(), i + 1, count));
+        }
+        write_pdf(&format!("{} — statement for {}", self.kid_name, self.period()), pages)
+    }
+}
+
+const HTML_STYLE: &str = "body{font-family:Helvetica,Arial,sans-serif;color:#2c3e50;max-width:720px;margin:40px auto;padding:0 16px}\
+h1{margin-bottom:0}.period{color:#64748b;margin-top:4px}\
+table{border-collapse:collapse;width:100%;margin:20px 0}th,td{padding:6px 8px;text-align:left;border-bottom:1px solid #e2e8f0}\
+.summary{width:auto}.summary td,.amount{text-align:right;font-variant-numeric:tabular-nums}.closing{font-weight:bold}\
+.empty{color:#94a3b8;font-style:italic}@media print{body{margin:0}}";
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// "$1.50", "-$2.00"
+fn money(amount: Decimal) -> String {
+    let amount = amount.round_dp(2);
+    if amount.is_sign_negative() && !amount.is_zero() {
+        format!("-${:.2}", amount.abs())
+    } else {
+        format!("${:.2}", amount.abs())
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max - 3).collect();
+    truncated.push_str("...");
+    truncated
+}
+
+// A4 in points, and where things go on it
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const LEFT: f32 = 50.0;
+const TOP: f32 = 790.0;
+const BOTTOM: f32 = 60.0;
+const ROW: f32 = 15.0;
+/// Date, description and type start at these; amount and balance end at them
+const COLUMNS: [f32; 5] = [LEFT, 120.0, 370.0, 480.0, 545.0];
+
+const REGULAR: Name<'static> = Name(b"F1");
+const BOLD: Name<'static> = Name(b"F2");
+
+/// The text drawn on one page
+struct PdfPage {
+    content: Content,
+}
+
+impl PdfPage {
+    fn new() -> Self {
+        Self { content: Content::new() }
+    }
+
+    fn text(&mut self, font: Name, size: f32, x: f32, y: f32, text: &str) {
+        self.content
+            .begin_text()
+            .set_font(font, size)
+            .next_line(x, y)
+            .show(Str(&win_ansi(text)))
+            .end_text();
+    }
+
+    /// An amount ending at `right`, so columns of them line up
+    fn amount(&mut self, font: Name, size: f32, right: f32, y: f32, amount: Decimal) {
+        let text = money(amount);
+        self.text(font, size, right - text_width(&text, size), y, &text);
+    }
+
+    fn table_header(&mut self, y: f32) {
+        for (i, label) in ["Date", "Description", "Type"].iter().enumerate() {
+            self.text(BOLD, 9.0, COLUMNS[i], y, label);
+        }
+        self.text(BOLD, 9.0, COLUMNS[3] - text_width("Amount", 9.0), y, "Amount");
+        self.text(BOLD, 9.0, COLUMNS[4] - text_width("Balance", 9.0), y, "Balance");
+        self.content
+            .set_line_width(0.5)
+            .move_to(LEFT, y - 4.0)
+            .line_to(COLUMNS[4], y - 4.0)
+            .stroke();
+    }
+}
+
+/// Width of text in Helvetica; exact for amounts, an estimate otherwise
+fn text_width(text: &str, size: f32) -> f32 {
+    let units: u32 = text
+        .chars()
+        .map(|c| match c {
+            '0'..='9' | '$' => 556,
+            '.' | ',' => 278,
+            '-' => 333,
+            'A'..='Z' => 667,
+            _ => 500,
+        })
+        .sum();
+    units as f32 * size / 1000.0
+}
+
+/// Encode text for the standard fonts' WinAnsi encoding, replacing
+/// characters it doesn't have
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' => c as u8,
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            '€' => 0x80,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            _ => b'?',
+        })
+        .collect()
+}
+
+fn write_pdf(title: &str, pages: Vec<PdfPage>) -> Vec<u8> {
+    let catalog_id = Ref::new(1);
+    let tree_id = Ref::new(2);
+    let regular_id = Ref::new(3);
+    let bold_id = Ref::new(4);
+    let info_id = Ref::new(5);
+    let page_ids: Vec<(Ref, Ref)> = (0..pages.len() as i32)
+        .map(|i| (Ref::new(6 + 2 * i), Ref::new(7 + 2 * i)))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(tree_id);
+    pdf.pages(tree_id)
+        .kids(page_ids.iter().map(|(page_id, _)| *page_id))
+        .count(pages.len() as i32);
+    pdf.type1_font(regular_id).base_font(Name(b"Helvetica")).encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.type1_font(bold_id).base_font(Name(b"Helvetica-Bold")).encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.document_info(info_id).title(TextStr(title)).producer(TextStr("Loa'a"));
+
+    for (page, (page_id, content_id)) in pages.into_iter().zip(page_ids) {
+        let mut writer = pdf.page(page_id);
+        writer
+            .parent(tree_id)
+            .media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+            .contents(content_id);
+        let mut resources = writer.resources();
+        resources.fonts().pair(REGULAR, regular_id).pair(BOLD, bold_id);
+        resources.finish();
+        writer.finish();
+        pdf.stream(content_id, &page.content.finish());
+    }
+    pdf.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+
+    fn entry(kid: &Kid, day: (i32, u32, u32), amount: Decimal, entry_type: EntryType, description: &str) -> LedgerEntry {
+        let mut entry = LedgerEntry::new(kid.id, amount, entry_type, description.to_string());
+        entry.created_at = Utc.with_ymd_and_hms(day.0, day.1, day.2, 12, 0, 0).unwrap();
+        entry
+    }
+
+    #[test]
+    fn test_statement_balances() {
+        let kid = Kid::new("Kai".to_string(), "test-owner".to_string()).unwrap();
+        let entries = vec![
+            entry(&kid, (2026, 9, 3), dec!(2.00), EntryType::Earned, "Dishes"),
+            entry(&kid, (2026, 8, 30), dec!(5.00), EntryType::Earned, "Lawn"),
+            entry(&kid, (2026, 9, 10), dec!(-1.50), EntryType::Adjusted, "Candy <3"),
+            entry(&kid, (2026, 9, 20), dec!(1.00), EntryType::Earned, "Feed cat"),
+            entry(&kid, (2026, 10, 1), dec!(9.00), EntryType::Earned, "Next month"),
+        ];
+
+        let statement = Statement::new(&kid, &entries, month_start(2026, 9).unwrap());
+        assert_eq!(statement.opening_balance, dec!(5.00));
+        assert_eq!(statement.totals, vec![(EntryType::Earned, dec!(3.00)), (EntryType::Adjusted, dec!(-1.50))]);
+        assert_eq!(statement.closing_balance, dec!(6.50));
+        let balances: Vec<Decimal> = statement.lines.iter().map(|l| l.balance).collect();
+        assert_eq!(balances, vec![dec!(7.00), dec!(5.50), dec!(6.50)]);
+
+        let html = statement.to_html();
+        assert!(html.contains("Candy &lt;3"));
+        assert!(html.contains("<td>-$1.50</td>") || html.contains(">-$1.50<"));
+        assert!(statement.file_stem().starts_with("statement-2026-09-kai-"));
+    }
+
+    #[test]
+    fn test_pdf_pages() {
+        let kid = Kid::new("Kai".to_string(), "test-owner".to_string()).unwrap();
+        let entries: Vec<LedgerEntry> = (1..=28)
+            .flat_map(|day| (0..3).map(move |_| day))
+            .map(|day| entry(&kid, (2026, 2, day), dec!(1.00), EntryType::Earned, "Chore"))
+            .collect();
+
+        let pdf = Statement::new(&kid, &entries, month_start(2026, 2).unwrap()).to_pdf();
+        assert!(pdf.starts_with(b"%PDF-"));
+        // 84 rows don't fit on one page
+        assert!(String::from_utf8_lossy(&pdf).contains("/Count 2"));
+
+        let empty = Statement::new(&kid, &[], month_start(2026, 2).unwrap());
+        assert!(String::from_utf8_lossy(&empty.to_pdf()).contains("/Count 1"));
+    }
+
+    #[test]
+    fn test_months() {
+        assert_eq!(parse_month("2026-09").unwrap(), NaiveDate::from_ymd_opt(2026, 9, 1).unwrap());
+        assert!(parse_month("2026-13").is_err());
+        assert!(parse_month("September").is_err());
+        let now = Utc.with_ymd_and_hms(2026, 1, 15, 0, 0, 0).unwrap();
+        assert_eq!(previous_month(now), NaiveDate::from_ymd_opt(2025, 12, 1).unwrap());
+        assert_eq!(money(dec!(-0.5)), "-$0.50");
+        assert_eq!(win_ansi("Kai — café ✓"), b"Kai \x97 caf\xe9 ?".to_vec());
+    }
+}
@@ -0,0 +1,304 @@
+//! Library of chore templates grouped into packs
+//!
+//! Packs are JSON documents describing a set of suggested tasks for an age
+//! band and room. A handful of packs are built into the binary, and more can
+//! be loaded from a directory of `*.json` files (see `LOAA_TEMPLATE_DIR`).
+
+use serde::{Deserialize, Serialize};
+use rust_decimal::Decimal;
+use std::path::Path;
+use crate::models::{Cadence, Task, TaskCategory};
+use crate::error::{Error, Result};
+
+/// Packs shipped with Loa'a, embedded at compile time
+const BUILTIN_PACKS: &[&str] = &[
+    include_str!("../packs/preschool-bedroom.json"),
+    include_str!("../packs/elementary-kitchen.json"),
+    include_str!("../packs/elementary-pets.json"),
+    include_str!("../packs/tween-laundry.json"),
+    include_str!("../packs/teen-kitchen.json"),
+    include_str!("../packs/teen-yard.json"),
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AgeBand {
+    /// Ages 3-5
+    Preschool,
+    /// Ages 6-9
+    Elementary,
+    /// Ages 10-12
+    Tween,
+    /// Ages 13+
+    Teen,
+}
+
+impl AgeBand {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "preschool" => Some(AgeBand::Preschool),
+            "elementary" => Some(AgeBand::Elementary),
+            "tween" => Some(AgeBand::Tween),
+            "teen" => Some(AgeBand::Teen),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AgeBand::Preschool => "Ages 3-5",
+            AgeBand::Elementary => "Ages 6-9",
+            AgeBand::Tween => "Ages 10-12",
+            AgeBand::Teen => "Ages 13+",
+        }
+    }
+}
+
+/// A suggested task within a pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub suggested_value: Decimal,
+    pub cadence: Cadence,
+}
+
+/// A named group of task templates for an age band and room
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskPack {
+    /// Stable identifier used to import the pack (e.g. "elementary-kitchen")
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub age_band: AgeBand,
+    pub room: String,
+    /// Category given to tasks created from this pack
+    #[serde(default)]
+    pub category: Option<TaskCategory>,
+    pub templates: Vec<TaskTemplate>,
+}
+
+impl TaskPack {
+    pub fn from_json(json: &str) -> Result<Self> {
+        let pack: TaskPack = serde_json::from_str(json)
+            .map_err(|e| Error::Validation(format!("Invalid task pack: {}", e)))?;
+        pack.validate()?;
+        Ok(pack)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.id.trim().is_empty() {
+            return Err(Error::Validation("Task pack id cannot be empty".to_string()));
+        }
+        if self.templates.is_empty() {
+            return Err(Error::Validation(format!("Task pack '{}' has no templates", self.id)));
+        }
+        for template in &self.templates {
+            if template.name.trim().is_empty() {
+                return Err(Error::Validation(format!(
+                    "Task pack '{}' has a template with an empty name",
+                    self.id
+                )));
+            }
+            if template.suggested_value <= Decimal::ZERO {
+                return Err(Error::Validation(format!(
+                    "Template '{}' in pack '{}' must have a positive value",
+                    template.name, self.id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Build tasks for an owner from this pack's templates
+    ///
+    /// Each suggested value is multiplied by `value_scale` and rounded to
+    /// cents, so a family can import a pack at e.g. half or double the
+    /// suggested rates. Tasks get the pack's category and are tagged with
+    /// its room.
+    pub fn instantiate(&self, owner_id: &str, value_scale: Decimal) -> Result<Vec<Task>> {
+        if value_scale <= Decimal::ZERO {
+            return Err(Error::Validation("Value scale must be positive".to_string()));
+        }
+
+        self.templates
+            .iter()
+            .map(|template| {
+                let value = (template.suggested_value * value_scale).round_dp(2);
+                Task::new(
+                    template.name.clone(),
+                    template.description.clone(),
+                    value,
+                    template.cadence,
+                    owner_id.to_string(),
+                )
+                .map(|task| task.with_category(self.category).with_tags([&self.room]))
+            })
+            .collect()
+    }
+}
+
+/// Collection of available task packs
+#[derive(Debug, Clone, Default)]
+pub struct TemplateLibrary {
+    packs: Vec<TaskPack>,
+}
+
+impl TemplateLibrary {
+    /// Library containing only the built-in packs
+    pub fn builtin() -> Self {
+        let packs = BUILTIN_PACKS
+            .iter()
+            .map(|json| TaskPack::from_json(json).expect("built-in task pack is invalid"))
+            .collect();
+        Self { packs }
+    }
+
+    /// Built-in packs plus any packs found in `dir`
+    ///
+    /// Packs on disk replace built-in packs with the same id.
+    pub fn load(dir: Option<&Path>) -> Result<Self> {
+        let mut library = Self::builtin();
+        if let Some(dir) = dir {
+            for pack in load_packs_from_dir(dir)? {
+                library.insert(pack);
+            }
+        }
+        Ok(library)
+    }
+
+    /// Load the library using `LOAA_TEMPLATE_DIR` for extra packs
+    ///
+    /// Falls back to the built-in packs if the directory can't be read.
+    pub fn from_env() -> Self {
+        let dir = std::env::var("LOAA_TEMPLATE_DIR").ok().map(std::path::PathBuf::from);
+        match Self::load(dir.as_deref()) {
+            Ok(library) => library,
+            Err(e) => {
+                eprintln!("WARNING: Failed to load task packs from LOAA_TEMPLATE_DIR: {}", e);
+                Self::builtin()
+            }
+        }
+    }
+
+    /// Add a pack, replacing any existing pack with the same id
+    pub fn insert(&mut self, pack: TaskPack) {
+        self.packs.retain(|p| p.id != pack.id);
+        self.packs.push(pack);
+    }
+
+    pub fn packs(&self) -> &[TaskPack] {
+        &self.packs
+    }
+
+    pub fn get(&self, id: &str) -> Option<&TaskPack> {
+        self.packs.iter().find(|p| p.id == id)
+    }
+
+    /// Packs matching the optional age band and room filters
+    pub fn filter(&self, age_band: Option<AgeBand>, room: Option<&str>) -> Vec<&TaskPack> {
+        self.packs
+            .iter()
+            .filter(|p| age_band.is_none_or(|band| p.age_band == band))
+            .filter(|p| room.is_none_or(|room| p.room.eq_ignore_ascii_case(room)))
+            .collect()
+    }
+}
+
+/// Read every `*.json` file in `dir` as a task pack
+pub fn load_packs_from_dir(dir: &Path) -> Result<Vec<TaskPack>> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| Error::Internal(format!("Failed to read template directory {}: {}", dir.display(), e)))?;
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            let json = std::fs::read_to_string(path)
+                .map_err(|e| Error::Internal(format!("Failed to read {}: {}", path.display(), e)))?;
+            TaskPack::from_json(&json)
+                .map_err(|e| Error::Validation(format!("{}: {}", path.display(), e)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_builtin_packs_are_valid() {
+        let library = TemplateLibrary::builtin();
+        assert_eq!(library.packs().len(), BUILTIN_PACKS.len());
+        assert!(library.get("elementary-kitchen").is_some());
+    }
+
+    #[test]
+    fn test_filter_by_age_band_and_room() {
+        let library = TemplateLibrary::builtin();
+        let teen = library.filter(Some(AgeBand::Teen), None);
+        assert!(teen.iter().all(|p| p.age_band == AgeBand::Teen));
+        assert!(!teen.is_empty());
+
+        let kitchens = library.filter(None, Some("Kitchen"));
+        assert_eq!(kitchens.len(), 2);
+    }
+
+    #[test]
+    fn test_instantiate_with_scaling() {
+        let library = TemplateLibrary::builtin();
+        let pack = library.get("elementary-pets").unwrap();
+        let tasks = pack.instantiate("owner-1", dec!(1.5)).unwrap();
+
+        assert_eq!(tasks.len(), pack.templates.len());
+        let feed = tasks.iter().find(|t| t.name == "Feed pets").unwrap();
+        assert_eq!(feed.value, dec!(1.50));
+        assert_eq!(feed.owner_id, "owner-1");
+        assert_eq!(feed.cadence, Cadence::Daily);
+        assert_eq!(feed.category, Some(TaskCategory::Pets));
+        assert!(feed.has_tag("pets"));
+    }
+
+    #[test]
+    fn test_instantiate_rejects_non_positive_scale() {
+        let library = TemplateLibrary::builtin();
+        let pack = library.get("teen-yard").unwrap();
+        assert!(pack.instantiate("owner-1", dec!(0)).is_err());
+    }
+
+    #[test]
+    fn test_load_from_dir_overrides_builtin() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let json = r#"{
+            "id": "teen-yard",
+            "name": "Custom Yard",
+            "age_band": "teen",
+            "room": "yard",
+            "templates": [
+                { "name": "Pull weeds", "suggested_value": "3.00", "cadence": "Weekly" }
+            ]
+        }"#;
+        std::fs::write(dir.path().join("yard.json"), json).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let library = TemplateLibrary::load(Some(dir.path())).unwrap();
+        assert_eq!(library.packs().len(), BUILTIN_PACKS.len());
+        let yard = library.get("teen-yard").unwrap();
+        assert_eq!(yard.name, "Custom Yard");
+        assert_eq!(yard.templates.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_pack_rejected() {
+        let json = r#"{ "id": "empty", "name": "Empty", "age_band": "teen", "room": "x", "templates": [] }"#;
+        assert!(TaskPack::from_json(json).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::attachments::AttachmentStore;
+use crate::db::{AttachmentRepository, KidRepository, Repository, TaskRepository};
+use crate::models::{Kid, Task};
+use crate::policy::Actor;
+use crate::error::{Error, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Deleting a kid or task archives it: it disappears from lists but its
+/// history stays intact and it can be restored for a while. Once the restore
+/// window has passed (or a parent purges it) it is removed for good.
+pub struct ArchiveWorkflow {
+    kid_repo: KidRepository,
+    task_repo: TaskRepository,
+    attachment_repo: AttachmentRepository,
+    store: Arc<dyn AttachmentStore>,
+    restore_window: Duration,
+}
+
+impl ArchiveWorkflow {
+    pub fn new(
+        kid_repo: KidRepository,
+        task_repo: TaskRepository,
+        attachment_repo: AttachmentRepository,
+        store: Arc<dyn AttachmentStore>,
+        restore_window: Duration,
+    ) -> Self {
+        Self {
+            kid_repo,
+            task_repo,
+            attachment_repo,
+            store,
+            restore_window,
+        }
+    }
+
+    pub async fn archive_kid(&self, actor: &Actor, kid_id: Uuid, now: DateTime<Utc>) -> Result<Kid> {
+        let mut kid = self.editable_kid(actor, kid_id).await?;
+        kid.archive(now);
+        self.kid_repo.update(kid).await
+    }
+
+    pub async fn restore_kid(&self, actor: &Actor, kid_id: Uuid, now: DateTime<Utc>) -> Result<Kid> {
+        let mut kid = self.editable_kid(actor, kid_id).await?;
+        kid.restore(self.restore_window, now)?;
+        self.kid_repo.update(kid).await
+    }
+
+    pub async fn archive_task(&self, actor: &Actor, task_id: Uuid, now: DateTime<Utc>) -> Result<Task> {
+        let mut task = self.editable_task(actor, task_id).await?;
+        task.archive(now);
+        self.task_repo.update(task).await
+    }
+
+    pub async fn restore_task(&self, actor: &Actor, task_id: Uuid, now: DateTime<Utc>) -> Result<Task> {
+        let mut task = self.editable_task(actor, task_id).await?;
+        task.restore(self.restore_window, now)?;
+        self.task_repo.update(task).await
+    }
+
+    /// The actor's archived kids and tasks, most recently archived first
+    pub async fn list_archived(&self, actor: &Actor) -> Result<(Vec<Kid>, Vec<Task>)> {
+        let household_id = actor.household_id()
+            .ok_or_else(|| Error::Validation("No household to list".to_string()))?;
+        Ok((
+            self.kid_repo.list_archived_by_owner(household_id).await?,
+            self.task_repo.list_archived_by_owner(household_id).await?,
+        ))
+    }
+
+    /// Permanently remove an archived kid and everything that belongs to them
+    pub async fn purge_kid(&self, actor: &Actor, kid_id: Uuid) -> Result<()> {
+        let kid = self.editable_kid(actor, kid_id).await?;
+        if !kid.is_archived() {
+            return Err(Error::Validation("Archive the kid before purging them".to_string()));
+        }
+        self.purge_kid_records(kid.id).await
+    }
+
+    /// Permanently remove an archived task; completions keep their description
+    pub async fn purge_task(&self, actor: &Actor, task_id: Uuid) -> Result<()> {
+        let task = self.editable_task(actor, task_id).await?;
+        if !task.is_archived() {
+            return Err(Error::Validation("Archive the task before purging it".to_string()));
+        }
+        self.task_repo.delete(task.id).await
+    }
+
+    /// Purge every kid and task whose restore window has passed at `now`
+    ///
+    /// Returns the number of kids and tasks removed.
+    pub async fn purge_expired(&self, now: DateTime<Utc>) -> Result<usize> {
+        let cutoff = now - self.restore_window;
+        let kids = self.kid_repo.list_archived_before(cutoff).await?;
+        let tasks = self.task_repo.list_archived_before(cutoff).await?;
+
+        for kid in &kids {
+            self.purge_kid_records(kid.id).await?;
+        }
+        for task in &tasks {
+            self.task_repo.delete(task.id).await?;
+        }
+        Ok(kids.len() + tasks.len())
+    }
+
+    async fn purge_kid_records(&self, kid_id: Uuid) -> Result<()> {
+        // Photo files live outside the database, so note them before the
+        // records go and remove them once the purge has committed
+        let attachments = self.attachment_repo.list_by_kid(kid_id).await?;
+        self.kid_repo.purge(kid_id).await?;
+
+        for attachment in &attachments {
+            for key in [attachment.storage_key(), attachment.thumbnail_key()] {
+                if let Err(e) = self.store.delete(&key) {
+                    eprintln!("WARNING: Failed to delete attachment data {}: {}", key, e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn editable_kid(&self, actor: &Actor, kid_id: Uuid) -> Result<Kid> {
+        let kid = self.kid_repo.get(kid_id).await?;
+        actor.authorize_edit(&kid.owner_id, format!("Kid {}", kid_id))?;
+        Ok(kid)
+    }
+
+    async fn editable_task(&self, actor: &Actor, task_id: Uuid) -> Result<Task> {
+        let task = self.task_repo.get(task_id).await?;
+        actor.authorize_edit(&task.owner_id, format!("Task {}", task_id))?;
+        Ok(task)
+    }
+}
@@ -0,0 +1,169 @@
+use crate::attachments::{detect_content_type, make_thumbnail, AttachmentStore};
+use crate::db::{AttachmentRepository, KidRepository, LedgerRepository, Repository};
+use crate::models::Attachment;
+use crate::policy::Actor;
+use crate::error::{Error, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Handles photo proof uploads, linking them to completions, serving them
+/// to the owning account, and purging them after the retention period
+pub struct AttachmentWorkflow {
+    attachment_repo: AttachmentRepository,
+    kid_repo: KidRepository,
+    ledger_repo: LedgerRepository,
+    store: Arc<dyn AttachmentStore>,
+    max_bytes: u64,
+}
+
+impl AttachmentWorkflow {
+    pub fn new(
+        attachment_repo: AttachmentRepository,
+        kid_repo: KidRepository,
+        ledger_repo: LedgerRepository,
+        store: Arc<dyn AttachmentStore>,
+        max_bytes: u64,
+    ) -> Self {
+        Self {
+            attachment_repo,
+            kid_repo,
+            ledger_repo,
+            store,
+            max_bytes,
+        }
+    }
+
+    /// Store a photo for a kid, by the kid or a parent
+    ///
+    /// The image must be a JPEG or PNG no larger than the configured limit.
+    /// A thumbnail is generated alongside the original. If
+    /// `ledger_entry_id` is given the photo is linked to that completion
+    /// straight away.
+    pub async fn upload(
+        &self,
+        actor: &Actor,
+        kid_id: Uuid,
+        task_id: Option<Uuid>,
+        ledger_entry_id: Option<Uuid>,
+        bytes: Vec<u8>,
+    ) -> Result<Attachment> {
+        if bytes.len() as u64 > self.max_bytes {
+            return Err(Error::Validation(format!(
+                "Photo is too large (limit is {} bytes)",
+                self.max_bytes
+            )));
+        }
+        let content_type = detect_content_type(&bytes)?;
+        let kid = self.kid_repo.get(kid_id).await?;
+        actor.authorize_request(&kid)?;
+
+        let mut attachment = Attachment::new(
+            kid.owner_id,
+            kid_id,
+            task_id,
+            content_type.to_string(),
+            bytes.len() as u64,
+        )?;
+        if let Some(entry_id) = ledger_entry_id {
+            self.check_entry(&attachment, entry_id).await?;
+            attachment.link_to_entry(entry_id);
+        }
+
+        // Decoding and resizing is CPU-bound, keep it off the async workers
+        let (bytes, thumbnail) = tokio::task::spawn_blocking(move || {
+            make_thumbnail(&bytes).map(|thumbnail| (bytes, thumbnail))
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Thumbnail task failed: {}", e)))??;
+
+        self.store.put(&attachment.storage_key(), &bytes)?;
+        self.store.put(&attachment.thumbnail_key(), &thumbnail)?;
+
+        match self.attachment_repo.create(attachment.clone()).await {
+            Ok(created) => Ok(created),
+            Err(e) => {
+                self.delete_blobs(&attachment);
+                Err(e)
+            }
+        }
+    }
+
+    /// Link an uploaded photo to a completion of the same kid
+    pub async fn link_to_completion(
+        &self,
+        actor: &Actor,
+        attachment_id: Uuid,
+        ledger_entry_id: Uuid,
+    ) -> Result<Attachment> {
+        let mut attachment = self.attachment_repo.get(attachment_id).await?;
+        actor.authorize_edit(&attachment.owner_id, format!("Attachment {}", attachment_id))?;
+        self.check_entry(&attachment, ledger_entry_id).await?;
+        attachment.link_to_entry(ledger_entry_id);
+        self.attachment_repo.update(attachment).await
+    }
+
+    /// Look up an attachment, refusing attachments from other households
+    /// and, for kid logins, other kids' photos
+    pub async fn get(&self, actor: &Actor, attachment_id: Uuid) -> Result<Attachment> {
+        let attachment = self.attachment_repo.get(attachment_id).await?;
+        actor.authorize_read(&attachment.owner_id, format!("Attachment {}", attachment_id))?;
+        actor.authorize_kid(&self.kid_repo.get(attachment.kid_id).await?)?;
+        Ok(attachment)
+    }
+
+    /// Original image bytes, for the owning account only
+    pub async fn read(&self, actor: &Actor, attachment_id: Uuid) -> Result<(Attachment, Vec<u8>)> {
+        let attachment = self.get(actor, attachment_id).await?;
+        let bytes = self.store.get(&attachment.storage_key())?;
+        Ok((attachment, bytes))
+    }
+
+    /// Thumbnail bytes (always JPEG), for the owning account only
+    pub async fn read_thumbnail(&self, actor: &Actor, attachment_id: Uuid) -> Result<Vec<u8>> {
+        let attachment = self.get(actor, attachment_id).await?;
+        self.store.get(&attachment.thumbnail_key())
+    }
+
+    /// Photos for a kid, oldest first
+    pub async fn list_for_kid(&self, actor: &Actor, kid_id: Uuid) -> Result<Vec<Attachment>> {
+        actor.authorize_kid(&self.kid_repo.get(kid_id).await?)?;
+        self.attachment_repo.list_by_kid(kid_id).await
+    }
+
+    /// Delete every attachment whose retention period has passed at `now`
+    ///
+    /// Returns the number of attachments removed.
+    pub async fn purge_expired(&self, retention: Duration, now: DateTime<Utc>) -> Result<usize> {
+        let expired: Vec<Attachment> = self.attachment_repo
+            .list()
+            .await?
+            .into_iter()
+            .filter(|a| a.is_expired(retention, now))
+            .collect();
+
+        for attachment in &expired {
+            self.attachment_repo.delete(attachment.id).await?;
+            self.delete_blobs(attachment);
+        }
+        Ok(expired.len())
+    }
+
+    async fn check_entry(&self, attachment: &Attachment, ledger_entry_id: Uuid) -> Result<()> {
+        let entry = self.ledger_repo.get_entry(ledger_entry_id).await?;
+        if entry.kid_id != attachment.kid_id {
+            return Err(Error::Validation(
+                "Photo and completion belong to different kids".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn delete_blobs(&self, attachment: &Attachment) {
+        for key in [attachment.storage_key(), attachment.thumbnail_key()] {
+            if let Err(e) = self.store.delete(&key) {
+                eprintln!("WARNING: Failed to delete attachment data {}: {}", key, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,624 @@
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use crate::db::{Entity, Repository, SurrealRepository, Transaction, MIGRATIONS};
+use crate::config::BackupConfig;
+use crate::models::{Attachment, Household, Invite, Kid, LedgerEntry, Task, TaskClaim, Transfer, User, Withdrawal};
+use crate::error::{Error, Result};
+use chrono::{DateTime, Datelike, IsoWeek, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Identifies a Loa'a backup, in the manifest's `format`
+const FORMAT: &str = "loaa-backup";
+
+/// Bumped when the archive layout changes
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Tables a backup holds, in the order they are restored
+///
+/// A new table must be added here or to `SKIPPED_TABLES`.
+pub const BACKUP_TABLES: &[&str] = &[
+    "household",
+    "user",
+    "kid",
+    "task",
+    "ledger_entry",
+    "task_claim",
+    "attachment",
+    "withdrawal",
+    "transfer",
+    "invite",
+];
+
+/// Tables deliberately left out: request replay records expire within a day,
+/// and the migration history belongs to the database rather than the family
+pub const SKIPPED_TABLES: &[&str] = &["idempotency_key", "schema_migration"];
+
+/// Scheduled backups are named `loaa-backup-20261018T030000Z.jsonl`
+const FILE_PREFIX: &str = "loaa-backup-";
+const FILE_SUFFIX: &str = ".jsonl";
+const FILE_TIME: &str = "%Y%m%dT%H%M%SZ";
+
+/// Where the outcome of the last scheduled backup is kept, next to the
+/// backups
+const STATUS_FILE: &str = "last-backup.json";
+
+/// Tables whose records belong to a kid, and the fields naming the kid
+const KID_FIELDS: &[(&str, &str)] = &[
+    ("ledger_entry", "kid_id"),
+    ("task_claim", "kid_id"),
+    ("attachment", "kid_id"),
+    ("withdrawal", "kid_id"),
+    ("transfer", "from_kid_id"),
+    ("transfer", "to_kid_id"),
+];
+
+/// The first line of a backup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format: String,
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Latest migration of the database it was taken from
+    pub schema_version: u32,
+    pub tables: Vec<BackupTable>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupTable {
+    pub name: String,
+    pub records: usize,
+    /// SHA-256 of the table's lines, each followed by a newline
+    pub sha256: String,
+}
+
+/// Every line after the manifest is one record
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupLine {
+    table: String,
+    id: Uuid,
+    data: Value,
+}
+
+/// What a restore does with records already in the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestoreMode {
+    /// Keep existing records. Backed-up records that are already there are
+    /// skipped; one whose ID is taken by a different record is restored
+    /// under a new ID, and records referring to it follow.
+    Merge,
+    /// Delete everything in the backed-up tables first
+    Replace,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestoreSummary {
+    pub restored: usize,
+    /// Already in the database (merge only)
+    pub skipped: usize,
+    /// Restored under a new ID (merge only)
+    pub remapped: usize,
+}
+
+/// The outcome of the last scheduled backup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupStatus {
+    pub attempted_at: DateTime<Utc>,
+    /// File name of the backup, if it was written and verified
+    pub file: Option<String>,
+    pub records: usize,
+    pub error: Option<String>,
+    /// When a backup last succeeded, even if this one failed
+    pub last_success_at: Option<DateTime<Utc>>,
+}
+
+impl BackupStatus {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Exports a whole database to a JSON-lines archive and restores it, to take
+/// backups or move a family between database modes
+///
+/// Photo files live outside the database; copy the attachment directory
+/// along with the archive.
+pub struct BackupWorkflow {
+    db: Arc<Surreal<Any>>,
+}
+
+impl BackupWorkflow {
+    pub fn new(db: Arc<Surreal<Any>>) -> Self {
+        Self { db }
+    }
+
+    /// Write every backed-up table as a manifest line followed by one line
+    /// per record
+    pub async fn export(&self, now: DateTime<Utc>) -> Result<String> {
+        let mut tables = Vec::new();
+        let mut body = String::new();
+        for &table in BACKUP_TABLES {
+            let records = self.export_table(table).await?;
+            let mut hasher = Sha256::new();
+            for (id, data) in &records {
+                let line = serde_json::to_string(&BackupLine { table: table.to_string(), id: *id, data: data.clone() })?;
+                hasher.update(line.as_bytes());
+                hasher.update(b"\n");
+                body.push_str(&line);
+                body.push('\n');
+            }
+            tables.push(BackupTable {
+                name: table.to_string(),
+                records: records.len(),
+                sha256: hex(&hasher.finalize()),
+            });
+        }
+
+        let manifest = BackupManifest {
+            format: FORMAT.to_string(),
+            version: BACKUP_VERSION,
+            created_at: now,
+            schema_version: MIGRATIONS.last().map_or(0, |m| m.version),
+            tables,
+        };
+        Ok(format!("{}\n{}", serde_json::to_string(&manifest)?, body))
+    }
+
+    /// Check an archive without restoring it
+    pub fn verify(&self, archive: &str) -> Result<BackupManifest> {
+        Ok(parse(archive)?.0)
+    }
+
+    /// Restore an archive in one transaction, so a failed restore changes
+    /// nothing
+    ///
+    /// The whole archive is checked first: its manifest, checksums, that
+    /// every record reads as its model, and that records belonging to a kid
+    /// refer to one that exists.
+    pub async fn restore(&self, archive: &str, mode: RestoreMode) -> Result<RestoreSummary> {
+        let (_, lines) = parse(archive)?;
+        let mut summary = RestoreSummary::default();
+        let mut tx = Transaction::new(self.db.clone());
+
+        let lines = match mode {
+            RestoreMode::Replace => {
+                for &table in BACKUP_TABLES {
+                    tx.push(format!("DELETE {}", table));
+                }
+                check_kids(&lines, &HashSet::new())?;
+                lines
+            }
+            RestoreMode::Merge => {
+                let existing = self.existing().await?;
+                let lines = merge(lines, &existing, &mut summary)?;
+                let kids = existing.get("kid").map(|records| records.keys().copied().collect()).unwrap_or_default();
+                check_kids(&lines, &kids)?;
+                lines
+            }
+        };
+
+        for line in &lines {
+            let id = tx.bind(line.id.to_string())?;
+            let content = tx.bind(line.data.clone())?;
+            tx.push(format!("CREATE type::thing('{}', {}) CONTENT {}", line.table, id, content));
+        }
+        summary.restored = lines.len();
+        tx.commit().await?;
+        Ok(summary)
+    }
+
+    /// Write a backup to `config.dir`, read it back to verify it, delete the
+    /// ones the retention policy no longer keeps, and record the outcome
+    ///
+    /// A failed backup is recorded rather than returned as an error; errors
+    /// are for when not even the status can be written.
+    pub async fn run_scheduled(&self, config: &BackupConfig, now: DateTime<Utc>) -> Result<BackupStatus> {
+        let dir = config.dir.as_deref()
+            .ok_or_else(|| Error::Validation("No backup directory is configured (LOAA_BACKUP_DIR)".to_string()))?;
+        std::fs::create_dir_all(dir).map_err(|e| io_error(dir, e))?;
+
+        let status = match self.write_backup(dir, now).await {
+            Ok((path, records)) => {
+                rotate(dir, config.keep_daily, config.keep_weekly)?;
+                BackupStatus {
+                    attempted_at: now,
+                    file: path.file_name().map(|name| name.to_string_lossy().into_owned()),
+                    records,
+                    error: None,
+                    last_success_at: Some(now),
+                }
+            }
+            Err(e) => BackupStatus {
+                attempted_at: now,
+                file: None,
+                records: 0,
+                error: Some(e.to_string()),
+                last_success_at: Self::last_status(dir)?.and_then(|s| s.last_success_at),
+            },
+        };
+        write_atomically(&dir.join(STATUS_FILE), serde_json::to_string_pretty(&status)?.as_bytes())?;
+        Ok(status)
+    }
+
+    /// The outcome of the last scheduled backup into `dir`, if there was one
+    pub fn last_status(dir: &Path) -> Result<Option<BackupStatus>> {
+        let path = dir.join(STATUS_FILE);
+        match std::fs::read_to_string(&path) {
+            Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error(&path, e)),
+        }
+    }
+
+    async fn write_backup(&self, dir: &Path, now: DateTime<Utc>) -> Result<(PathBuf, usize)> {
+        let archive = self.export(now).await?;
+        let path = dir.join(format!("{}{}{}", FILE_PREFIX, now.format(FILE_TIME), FILE_SUFFIX));
+        write_atomically(&path, archive.as_bytes())?;
+
+        // Read it back, so a bad disk shows up now rather than at restore time
+        let verified = std::fs::read_to_string(&path)
+            .map_err(|e| io_error(&path, e))
+            .and_then(|written| {
+                if written != archive {
+                    return Err(Error::Internal(format!("{} doesn't match what was written", path.display())));
+                }
+                parse(&written)
+            });
+        match verified {
+            Ok((manifest, _)) => Ok((path, manifest.tables.iter().map(|t| t.records).sum())),
+            Err(e) => {
+                let _ = std::fs::remove_file(&path);
+                Err(e)
+            }
+        }
+    }
+
+    async fn export_table(&self, table: &str) -> Result<Vec<(Uuid, Value)>> {
+        match table {
+            "household" => self.export_entities::<Household>().await,
+            "user" => self.export_entities::<User>().await,
+            "kid" => self.export_entities::<Kid>().await,
+            "task" => self.export_entities::<Task>().await,
+            "ledger_entry" => self.export_entities::<LedgerEntry>().await,
+            "task_claim" => self.export_entities::<TaskClaim>().await,
+            "attachment" => self.export_entities::<Attachment>().await,
+            "withdrawal" => self.export_entities::<Withdrawal>().await,
+            "transfer" => self.export_entities::<Transfer>().await,
+            "invite" => self.export_entities::<Invite>().await,
+            _ => Err(Error::Internal(format!("Table {} can't be backed up", table))),
+        }
+    }
+
+    async fn export_entities<T: Entity>(&self) -> Result<Vec<(Uuid, Value)>> {
+        let mut entities = SurrealRepository::<T>::new(self.db.clone()).list().await?;
+        // Stable output, so the same data gives the same archive
+        entities.sort_by_key(|e| e.id());
+        entities
+            .into_iter()
+            .map(|e| Ok((e.id(), serde_json::to_value(&e)?)))
+            .collect()
+    }
+
+    /// Records already in the database, by table and ID
+    async fn existing(&self) -> Result<HashMap<&'static str, HashMap<Uuid, Value>>> {
+        let mut existing = HashMap::new();
+        for &table in BACKUP_TABLES {
+            existing.insert(table, self.export_table(table).await?.into_iter().collect());
+        }
+        Ok(existing)
+    }
+}
+
+/// Read and check an archive, returning its records in restore order
+fn parse(archive: &str) -> Result<(BackupManifest, Vec<BackupLine>)> {
+    let invalid = |msg: String| Error::Validation(format!("Invalid backup: {}", msg));
+    let mut lines = archive.lines();
+
+    let manifest: BackupManifest = serde_json::from_str(lines.next().unwrap_or_default())
+        .map_err(|e| invalid(format!("unreadable manifest ({})", e)))?;
+    if manifest.format != FORMAT {
+        return Err(invalid("not a Loa'a backup".to_string()));
+    }
+    if manifest.version > BACKUP_VERSION {
+        return Err(invalid(format!("archive version {} is newer than this app; upgrade it first", manifest.version)));
+    }
+    if manifest.schema_version > MIGRATIONS.last().map_or(0, |m| m.version) {
+        return Err(invalid(format!("it was taken by a newer version of the app (schema {})", manifest.schema_version)));
+    }
+
+    let mut by_table: BTreeMap<&str, (Sha256, Vec<BackupLine>)> = BTreeMap::new();
+    let mut seen = HashSet::new();
+    for (number, text) in lines.enumerate().map(|(i, text)| (i + 2, text)) {
+        if text.trim().is_empty() {
+            continue;
+        }
+        let mut line: BackupLine = serde_json::from_str(text)
+            .map_err(|e| invalid(format!("line {} is unreadable ({})", number, e)))?;
+        let table = BACKUP_TABLES
+            .iter()
+            .find(|&&t| t == line.table)
+            .ok_or_else(|| invalid(format!("line {} is for unknown table {}", number, line.table)))?;
+        if !seen.insert((*table, line.id)) {
+            return Err(invalid(format!("line {} repeats {} {}", number, line.table, line.id)));
+        }
+        line.data = normalize(table, &line.data)
+            .map_err(|e| invalid(format!("line {} isn't a valid {} ({})", number, line.table, e)))?;
+
+        let (hasher, records) = by_table.entry(table).or_insert_with(|| (Sha256::new(), Vec::new()));
+        hasher.update(text.as_bytes());
+        hasher.update(b"\n");
+        records.push(line);
+    }
+
+    for table in &manifest.tables {
+        let (checksum, count) = match by_table.get(table.name.as_str()) {
+            Some((hasher, records)) => (hex(&hasher.clone().finalize()), records.len()),
+            None => (hex(&Sha256::new().finalize()), 0),
+        };
+        if count != table.records || checksum != table.sha256 {
+            return Err(invalid(format!("table {} doesn't match its checksum; the file is damaged", table.name)));
+        }
+    }
+    if let Some(missing) = by_table.keys().find(|t| !manifest.tables.iter().any(|m| &m.name == *t)) {
+        return Err(invalid(format!("table {} isn't in the manifest", missing)));
+    }
+
+    let mut records = Vec::new();
+    for table in BACKUP_TABLES {
+        if let Some((_, lines)) = by_table.remove(table) {
+            records.extend(lines);
+        }
+    }
+    Ok((manifest, records))
+}
+
+/// Read a record as its model and write it back, which fails on records that
+/// aren't valid and fills in fields added since the backup was taken
+fn normalize(table: &str, data: &Value) -> Result<Value> {
+    fn read<T: Entity>(data: &Value) -> Result<Value> {
+        Ok(serde_json::to_value(T::deserialize(data)?)?)
+    }
+    match table {
+        "household" => read::<Household>(data),
+        "user" => read::<User>(data),
+        "kid" => read::<Kid>(data),
+        "task" => read::<Task>(data),
+        "ledger_entry" => read::<LedgerEntry>(data),
+        "task_claim" => read::<TaskClaim>(data),
+        "attachment" => read::<Attachment>(data),
+        "withdrawal" => read::<Withdrawal>(data),
+        "transfer" => read::<Transfer>(data),
+        "invite" => read::<Invite>(data),
+        _ => Err(Error::Internal(format!("Table {} can't be restored", table))),
+    }
+}
+
+/// Leave out records that are already in the database and give new IDs to
+/// ones whose ID is taken, rewriting references to them
+///
+/// New IDs are derived from the old ones, so merging the same backup twice
+/// finds the records restored the first time.
+fn merge(
+    lines: Vec<BackupLine>,
+    existing: &HashMap<&'static str, HashMap<Uuid, Value>>,
+    summary: &mut RestoreSummary,
+) -> Result<Vec<BackupLine>> {
+    let stored = |table: &str, id: &Uuid| existing.get(table).and_then(|records| records.get(id));
+
+    // A record whose references were remapped may now differ from the stored
+    // one too, so repeat until nothing else needs a new ID
+    let mut remap: HashMap<String, String> = HashMap::new();
+    loop {
+        let mut changed = false;
+        for line in &lines {
+            if remap.contains_key(&line.id.to_string()) {
+                continue;
+            }
+            let mut data = line.data.clone();
+            rewrite_ids(&mut data, &remap);
+            if stored(&line.table, &line.id).is_some_and(|stored| *stored != data) {
+                let new_id = Uuid::new_v5(&line.id, b"loaa-restore");
+                remap.insert(line.id.to_string(), new_id.to_string());
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let usernames: HashSet<&str> = existing
+        .get("user")
+        .into_iter()
+        .flat_map(|users| users.values())
+        .filter_map(|user| user["username"].as_str())
+        .collect();
+
+    let mut merged = Vec::new();
+    for mut line in lines {
+        let remapped = match remap.get(&line.id.to_string()) {
+            Some(new_id) => {
+                line.id = Uuid::parse_str(new_id).map_err(|e| Error::Internal(e.to_string()))?;
+                true
+            }
+            None => false,
+        };
+        rewrite_ids(&mut line.data, &remap);
+
+        match stored(&line.table, &line.id) {
+            Some(stored) if *stored == line.data => {
+                summary.skipped += 1;
+                continue;
+            }
+            Some(_) => {
+                return Err(Error::Validation(format!(
+                    "Can't merge the backup: {} {} was restored before and has changed since", line.table, line.id
+                )));
+            }
+            None => {}
+        }
+        if line.table == "user" {
+            if let Some(username) = line.data["username"].as_str().filter(|u| usernames.contains(u)) {
+                return Err(Error::Validation(format!(
+                    "Can't merge the backup: username '{}' is already taken", username
+                )));
+            }
+        }
+        if remapped {
+            summary.remapped += 1;
+        }
+        merged.push(line);
+    }
+    Ok(merged)
+}
+
+/// Replace every string in `value` that is a remapped ID
+fn rewrite_ids(value: &mut Value, remap: &HashMap<String, String>) {
+    match value {
+        Value::String(s) => {
+            if let Some(new_id) = remap.get(s.as_str()) {
+                *s = new_id.clone();
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| rewrite_ids(item, remap)),
+        Value::Object(fields) => fields.values_mut().for_each(|field| rewrite_ids(field, remap)),
+        _ => {}
+    }
+}
+
+/// Records that belong to a kid must refer to one in the backup or, when
+/// merging, in the database
+fn check_kids(lines: &[BackupLine], existing: &HashSet<Uuid>) -> Result<()> {
+    let kids: HashSet<Uuid> = lines.iter().filter(|l| l.table == "kid").map(|l| l.id).collect();
+    for line in lines {
+        for (_, field) in KID_FIELDS.iter().filter(|(table, _)| *table == line.table) {
+            let kid_id = line.data[*field].as_str().and_then(|id| Uuid::parse_str(id).ok());
+            if !kid_id.is_some_and(|id| kids.contains(&id) || existing.contains(&id)) {
+                return Err(Error::Validation(format!(
+                    "Invalid backup: {} {} belongs to a kid that isn't in it", line.table, line.id
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write a file under a temporary name and rename it into place, so a crash
+/// never leaves a half-written file under the real name
+pub(crate) fn write_atomically(path: &Path, contents: &[u8]) -> Result<()> {
+    let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    let temp = path.with_file_name(format!(".{}.tmp", name));
+    let written = std::fs::File::create(&temp).and_then(|mut file| {
+        file.write_all(contents)?;
+        file.sync_all()
+    });
+    if let Err(e) = written.and_then(|()| std::fs::rename(&temp, path)) {
+        let _ = std::fs::remove_file(&temp);
+        return Err(io_error(path, e));
+    }
+    Ok(())
+}
+
+/// Delete scheduled backups in `dir` that the retention policy no longer
+/// keeps, returning how many were deleted
+fn rotate(dir: &Path, keep_daily: u32, keep_weekly: u32) -> Result<usize> {
+    let mut backups = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(|e| io_error(dir, e))? {
+        let path = entry.map_err(|e| io_error(dir, e))?.path();
+        let taken_at = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(FILE_PREFIX)?.strip_suffix(FILE_SUFFIX))
+            .and_then(|stamp| NaiveDateTime::parse_from_str(stamp, FILE_TIME).ok());
+        if let Some(taken_at) = taken_at {
+            backups.push((taken_at.and_utc(), path));
+        }
+    }
+
+    let keep = retained(backups.iter().map(|(taken_at, _)| *taken_at), keep_daily, keep_weekly);
+    let mut deleted = 0;
+    for (taken_at, path) in backups {
+        if !keep.contains(&taken_at) {
+            std::fs::remove_file(&path).map_err(|e| io_error(&path, e))?;
+            deleted += 1;
+        }
+    }
+    Ok(deleted)
+}
+
+/// Backups to keep: the newest of each of the last `keep_daily` days and of
+/// each of the last `keep_weekly` weeks that have one, and always the newest
+fn retained(taken: impl Iterator<Item = DateTime<Utc>>, keep_daily: u32, keep_weekly: u32) -> HashSet<DateTime<Utc>> {
+    let mut taken: Vec<DateTime<Utc>> = taken.collect();
+    taken.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut keep: HashSet<DateTime<Utc>> = taken.first().copied().into_iter().collect();
+    let mut days: Vec<NaiveDate> = Vec::new();
+    let mut weeks: Vec<IsoWeek> = Vec::new();
+    for taken_at in taken {
+        let day = taken_at.date_naive();
+        if !days.contains(&day) && days.len() < keep_daily as usize {
+            days.push(day);
+            keep.insert(taken_at);
+        }
+        let week = taken_at.iso_week();
+        if !weeks.contains(&week) && weeks.len() < keep_weekly as usize {
+            weeks.push(week);
+            keep.insert(taken_at);
+        }
+    }
+    keep
+}
+
+pub(crate) fn io_error(path: &Path, e: std::io::Error) -> Error {
+    Error::Internal(format!("{}: {}", path.display(), e))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rewrite_ids() {
+        let old = Uuid::new_v4().to_string();
+        let new = Uuid::new_v4().to_string();
+        let remap = HashMap::from([(old.clone(), new.clone())]);
+
+        let mut data = json!({"kid_id": old, "members": [{"user_id": old}], "name": "Kai"});
+        rewrite_ids(&mut data, &remap);
+        assert_eq!(data, json!({"kid_id": new, "members": [{"user_id": new}], "name": "Kai"}));
+    }
+
+    #[test]
+    fn test_retained() {
+        let at = |day: u32, hour: u32| chrono::NaiveDate::from_ymd_opt(2026, 10, day).unwrap().and_hms_opt(hour, 0, 0).unwrap().and_utc();
+        // Two a day for three weeks, through Sunday 2026-10-18
+        let taken: Vec<_> = (1..=18).flat_map(|day| [at(day, 3), at(day, 15)]).collect();
+
+        let keep = retained(taken.iter().copied(), 3, 2);
+        // The newest of the last three days, and of this week and last
+        let mut expected = HashSet::from([at(18, 15), at(17, 15), at(16, 15), at(11, 15)]);
+        assert_eq!(keep, expected);
+
+        // The newest is kept even with nothing else
+        expected = HashSet::from([at(18, 15)]);
+        assert_eq!(retained(taken.into_iter(), 0, 0), expected);
+    }
+
+    #[test]
+    fn test_rejects_other_files() {
+        assert!(matches!(parse(""), Err(Error::Validation(_))));
+        assert!(matches!(parse("{\"format\":\"zip\"}"), Err(Error::Validation(_))));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::budget::{report_period, BudgetStatus};
+use crate::db::{HouseholdRepository, KidRepository, LedgerRepository};
+use crate::models::{Household, Kid, PayoutBudget};
+use crate::policy::Actor;
+use crate::error::Result;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+/// Sums payouts against the household's and kids' budgets
+pub struct BudgetWorkflow {
+    household_repo: HouseholdRepository,
+    kid_repo: KidRepository,
+    ledger_repo: LedgerRepository,
+}
+
+impl BudgetWorkflow {
+    pub fn new(household_repo: HouseholdRepository, kid_repo: KidRepository, ledger_repo: LedgerRepository) -> Self {
+        Self { household_repo, kid_repo, ledger_repo }
+    }
+
+    /// Every budget the household has set, household's first, as of `today`
+    pub async fn statuses(&self, actor: &Actor, today: NaiveDate) -> Result<Vec<BudgetStatus>> {
+        let owner_id = actor.readable_household()?;
+        let Some(household) = self.household_repo.find_by_owner_id(owner_id).await? else {
+            return Ok(Vec::new());
+        };
+        let mut statuses = Vec::new();
+        if let Some(budget) = household.budget {
+            statuses.push(self.household_status(&household, budget, today).await?);
+        }
+        for kid in self.kid_repo.list_by_owner(owner_id).await? {
+            if let Some(budget) = household.kid_budget(kid.id) {
+                statuses.push(self.status(Some(kid.id), kid.name, vec![kid.id], budget, today).await?);
+            }
+        }
+        Ok(statuses)
+    }
+
+    /// The budgets a payout to `kid` on `today` counts toward: the
+    /// household's and the kid's own, where set
+    pub async fn for_kid(&self, kid: &Kid, today: NaiveDate) -> Result<Vec<BudgetStatus>> {
+        let Some(household) = self.household_repo.find_by_owner_id(&kid.owner_id).await? else {
+            return Ok(Vec::new());
+        };
+        let mut statuses = Vec::new();
+        if let Some(budget) = household.budget {
+            statuses.push(self.household_status(&household, budget, today).await?);
+        }
+        if let Some(budget) = household.kid_budget(kid.id) {
+            statuses.push(self.status(Some(kid.id), kid.name.clone(), vec![kid.id], budget, today).await?);
+        }
+        Ok(statuses)
+    }
+
+    // Payouts to archived kids still count toward the household's budget
+    async fn household_status(&self, household: &Household, budget: PayoutBudget, today: NaiveDate) -> Result<BudgetStatus> {
+        let owner_id = household.owner_id();
+        let mut kid_ids: Vec<Uuid> = self.kid_repo.list_by_owner(&owner_id).await?.iter().map(|kid| kid.id).collect();
+        kid_ids.extend(self.kid_repo.list_archived_by_owner(&owner_id).await?.iter().map(|kid| kid.id));
+        self.status(None, household.name.clone(), kid_ids, budget, today).await
+    }
+
+    async fn status(
+        &self,
+        kid_id: Option<Uuid>,
+        name: String,
+        kid_ids: Vec<Uuid>,
+        budget: PayoutBudget,
+        today: NaiveDate,
+    ) -> Result<BudgetStatus> {
+        let period = report_period(budget.period);
+        let start = period.start_of(today);
+        let totals = self.ledger_repo.earnings_totals(&kid_ids, period, start, period.next(start)).await?;
+        Ok(BudgetStatus::new(kid_id, name, budget, start, &totals))
+    }
+}
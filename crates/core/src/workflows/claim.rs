@@ -0,0 +1,139 @@
+use crate::db::{AttachmentRepository, ClaimRepository, KidRepository, Repository, TaskRepository};
+use crate::models::{CompletionReview, LedgerEntry, TaskClaim};
+use crate::workflows::TaskCompletionWorkflow;
+use crate::policy::Actor;
+use crate::error::{Error, Result};
+use uuid::Uuid;
+
+/// Kids claim tasks they've done; parents approve (paying out through the
+/// task completion workflow) or reject the claim
+pub struct ClaimWorkflow {
+    claim_repo: ClaimRepository,
+    kid_repo: KidRepository,
+    task_repo: TaskRepository,
+    attachment_repo: AttachmentRepository,
+    completion: TaskCompletionWorkflow,
+}
+
+impl ClaimWorkflow {
+    pub fn new(
+        claim_repo: ClaimRepository,
+        kid_repo: KidRepository,
+        task_repo: TaskRepository,
+        attachment_repo: AttachmentRepository,
+        completion: TaskCompletionWorkflow,
+    ) -> Self {
+        Self {
+            claim_repo,
+            kid_repo,
+            task_repo,
+            attachment_repo,
+            completion,
+        }
+    }
+
+    /// Record a kid's claim that they finished a task
+    ///
+    /// The task must belong to the kid's household, and a kid can only have
+    /// one pending claim per task. An optional photo must be one of the kid's
+    /// own attachments.
+    pub async fn claim_task(
+        &self,
+        actor: &Actor,
+        kid_id: Uuid,
+        task_id: Uuid,
+        note: Option<String>,
+        attachment_id: Option<Uuid>,
+    ) -> Result<TaskClaim> {
+        let kid = self.kid_repo.get(kid_id).await?;
+        actor.authorize_request(&kid)?;
+        let task = self.task_repo.get(task_id).await?;
+        if task.owner_id != kid.owner_id {
+            return Err(Error::Forbidden(format!("Task {} belongs to another household", task_id)));
+        }
+        if kid.is_archived() || task.is_archived() {
+            return Err(Error::Validation("Archived kids and tasks can't be claimed".to_string()));
+        }
+
+        if let Some(attachment_id) = attachment_id {
+            let attachment = self.attachment_repo.get(attachment_id).await?;
+            if attachment.kid_id != kid_id {
+                return Err(Error::NotFound(format!("Attachment with id {}", attachment_id)));
+            }
+        }
+
+        let already_claimed = self.claim_repo
+            .list_by_kid(kid_id)
+            .await?
+            .iter()
+            .any(|c| c.task_id == task_id && c.is_pending());
+        if already_claimed {
+            return Err(Error::Validation(format!(
+                "'{}' is already waiting for approval",
+                task.name
+            )));
+        }
+
+        let claim = TaskClaim::new(kid.owner_id, kid_id, task_id, note, attachment_id);
+        self.claim_repo.create(claim).await
+    }
+
+    /// Approve a pending claim, completing the task for the kid
+    ///
+    /// Any photo on the claim is linked to the resulting ledger entry. The
+    /// payout, photo link and claim update commit together, and only if the
+    /// claim is still pending then, so a claim can't be paid twice.
+    pub async fn approve(
+        &self,
+        actor: &Actor,
+        claim_id: Uuid,
+        review: Option<CompletionReview>,
+    ) -> Result<(TaskClaim, LedgerEntry)> {
+        let mut claim = self.editable_claim(actor, claim_id).await?;
+        if !claim.is_pending() {
+            return Err(Error::Validation("Claim has already been reviewed".to_string()));
+        }
+
+        let mut tx = self.claim_repo.begin();
+        let entry = self.completion
+            .complete_in(&mut tx, claim.task_id, claim.kid_id, review)
+            .await?;
+
+        if let Some(attachment_id) = claim.attachment_id {
+            let mut attachment = self.attachment_repo.get(attachment_id).await?;
+            attachment.link_to_entry(entry.id);
+            self.attachment_repo.update_in(&mut tx, &attachment)?;
+        }
+
+        claim.approve(entry.id)?;
+        self.claim_repo.review_in(&mut tx, &claim)?;
+        tx.commit().await?;
+        Ok((claim, entry))
+    }
+
+    /// Turn down a pending claim without paying anything
+    pub async fn reject(&self, actor: &Actor, claim_id: Uuid) -> Result<TaskClaim> {
+        let mut claim = self.editable_claim(actor, claim_id).await?;
+        claim.reject()?;
+
+        let mut tx = self.claim_repo.begin();
+        self.claim_repo.review_in(&mut tx, &claim)?;
+        tx.commit().await?;
+        Ok(claim)
+    }
+
+    pub async fn list_pending(&self, actor: &Actor) -> Result<Vec<TaskClaim>> {
+        self.claim_repo.list_pending(actor.readable_household()?).await
+    }
+
+    pub async fn list_for_kid(&self, actor: &Actor, kid_id: Uuid) -> Result<Vec<TaskClaim>> {
+        actor.authorize_kid(&self.kid_repo.get(kid_id).await?)?;
+        self.claim_repo.list_by_kid(kid_id).await
+    }
+
+    async fn editable_claim(&self, actor: &Actor, claim_id: Uuid) -> Result<TaskClaim> {
+        let claim = self.claim_repo.get(claim_id).await?;
+        actor.authorize_edit(&claim.owner_id, format!("Task claim {}", claim_id))?;
+        Ok(claim)
+    }
+}
@@ -0,0 +1,551 @@
+use crate::db::{KidRepository, LedgerRepository, TaskRepository};
+use crate::models::{Cadence, EntryType, Kid, LedgerEntry, Task, TaskCategory};
+use crate::policy::Actor;
+use crate::error::{Error, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Columns of a task CSV file, in the order they are exported
+pub const TASK_COLUMNS: &[&str] = &["name", "description", "value", "cadence", "category", "tags"];
+
+/// Columns of a ledger CSV file, in the order they are exported
+///
+/// On import `kid` may hold a kid's name or ID; `kid_id` wins when both are
+/// filled in.
+pub const LEDGER_COLUMNS: &[&str] = &["date", "kid", "kid_id", "type", "amount", "description", "category"];
+
+const REQUIRED_TASK_COLUMNS: &[&str] = &["name", "value", "cadence"];
+const REQUIRED_LEDGER_COLUMNS: &[&str] = &["date", "amount"];
+
+/// Tags share one cell, separated by semicolons
+const TAG_SEPARATOR: char = ';';
+
+/// Which column of an imported file holds each field
+///
+/// Fields that aren't mapped are read from the column named after the
+/// field, so files exported from Loa'a import without a mapping. Column
+/// names are matched ignoring case.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnMapping {
+    columns: HashMap<String, String>,
+}
+
+impl ColumnMapping {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read `field` from `column`
+    pub fn map(mut self, field: &str, column: &str) -> Self {
+        self.columns.insert(field.trim().to_lowercase(), column.trim().to_string());
+        self
+    }
+
+    /// Parse comma-separated `field=Column` pairs, e.g. `name=Chore, value=Pay`
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut mapping = Self::new();
+        for pair in s.split(',').filter(|pair| !pair.trim().is_empty()) {
+            let (field, column) = pair.split_once('=').ok_or_else(|| {
+                Error::Validation(format!("Column mapping '{}' should look like field=Column", pair.trim()))
+            })?;
+            mapping = mapping.map(field, column);
+        }
+        Ok(mapping)
+    }
+
+    fn column<'a>(&'a self, field: &'a str) -> &'a str {
+        self.columns.get(field).map(String::as_str).unwrap_or(field)
+    }
+
+    /// Find each field's column in `headers`, refusing mappings for fields
+    /// this file type doesn't have and files missing a required column
+    fn resolve(&self, headers: &csv::StringRecord, fields: &[&'static str], required: &[&str]) -> Result<Columns> {
+        if let Some(unknown) = self.columns.keys().find(|field| !fields.contains(&field.as_str())) {
+            return Err(Error::Validation(format!(
+                "Unknown field '{}' in column mapping; expected one of: {}",
+                unknown,
+                fields.join(", ")
+            )));
+        }
+
+        let mut index = HashMap::new();
+        for &field in fields {
+            let column = self.column(field);
+            match headers.iter().position(|header| header.trim().eq_ignore_ascii_case(column)) {
+                Some(i) => {
+                    index.insert(field, i);
+                }
+                None if required.contains(&field) => {
+                    return Err(Error::Validation(format!("The file has no '{}' column for {}", column, field)));
+                }
+                None => {}
+            }
+        }
+        Ok(Columns { index })
+    }
+}
+
+/// Where each field is in a row
+struct Columns {
+    index: HashMap<&'static str, usize>,
+}
+
+impl Columns {
+    /// The field's cell, or `None` if it's missing or blank
+    fn get<'r>(&self, row: &'r csv::StringRecord, field: &str) -> Option<&'r str> {
+        self.index
+            .get(field)
+            .and_then(|&i| row.get(i))
+            .map(|cell| unguard(cell.trim()))
+            .filter(|cell| !cell.is_empty())
+    }
+}
+
+/// A row of an import that can't be used
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CsvRowError {
+    /// Line in the file, counting the header as line 1
+    pub line: u64,
+    pub message: String,
+}
+
+/// What an import did, or would do on a dry run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CsvImportReport {
+    /// Data rows in the file
+    pub rows: usize,
+    /// Rows that were imported, or would be
+    pub imported: usize,
+    /// Rows left out because the same task or entry already exists
+    pub skipped: Vec<String>,
+    pub errors: Vec<CsvRowError>,
+    /// Whether anything was written; not on a dry run, and not if any row
+    /// has an error
+    pub written: bool,
+}
+
+/// Which ledger entries to export
+#[derive(Debug, Clone, Default)]
+pub struct LedgerFilter {
+    /// One kid, or all of the owner's kids
+    pub kid_id: Option<Uuid>,
+    /// First day to include (UTC)
+    pub from: Option<NaiveDate>,
+    /// Last day to include (UTC)
+    pub to: Option<NaiveDate>,
+}
+
+impl LedgerFilter {
+    fn includes(&self, entry: &LedgerEntry) -> bool {
+        let day = entry.created_at.date_naive();
+        self.from.is_none_or(|from| day >= from) && self.to.is_none_or(|to| day <= to)
+    }
+}
+
+/// Exports ledgers and tasks as CSV for spreadsheets, and imports tasks and
+/// past ledger entries from them
+///
+/// Imports check every row before writing and then write all of them in one
+/// transaction, so a file with a bad row changes nothing.
+pub struct CsvWorkflow {
+    kid_repo: KidRepository,
+    task_repo: TaskRepository,
+    ledger_repo: LedgerRepository,
+}
+
+impl CsvWorkflow {
+    pub fn new(kid_repo: KidRepository, task_repo: TaskRepository, ledger_repo: LedgerRepository) -> Self {
+        Self { kid_repo, task_repo, ledger_repo }
+    }
+
+    /// A household's tasks, one per row
+    pub async fn export_tasks(&self, actor: &Actor) -> Result<String> {
+        let owner_id = actor.readable_household()?;
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(TASK_COLUMNS).map_err(csv_error)?;
+        for task in self.task_repo.list_by_owner(owner_id).await? {
+            writer
+                .write_record([
+                    guard(&task.name),
+                    guard(&task.description),
+                    task.value.to_string(),
+                    task.cadence.as_str().to_string(),
+                    task.category.map(|c| c.as_str()).unwrap_or_default().to_string(),
+                    guard(&task.tags.join("; ")),
+                ])
+                .map_err(csv_error)?;
+        }
+        into_string(writer)
+    }
+
+    /// Ledger entries of a household's kids, oldest first
+    pub async fn export_ledger(&self, actor: &Actor, filter: &LedgerFilter) -> Result<String> {
+        let mut kids = self.kid_repo.list_by_owner(actor.readable_household()?).await?;
+        if let Some(kid_id) = filter.kid_id {
+            kids.retain(|kid| kid.id == kid_id);
+            if kids.is_empty() {
+                return Err(Error::NotFound(format!("Kid with id {}", kid_id)));
+            }
+        }
+
+        let mut rows = Vec::new();
+        for kid in &kids {
+            let ledger = self.ledger_repo.get_ledger(kid.id).await?;
+            rows.extend(ledger.entries.into_iter().filter(|e| filter.includes(e)).map(|e| (kid, e)));
+        }
+        rows.sort_by(|(a_kid, a), (b_kid, b)| a.created_at.cmp(&b.created_at).then(a_kid.name.cmp(&b_kid.name)));
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(LEDGER_COLUMNS).map_err(csv_error)?;
+        for (kid, entry) in rows {
+            writer
+                .write_record([
+                    entry.created_at.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+                    guard(&kid.name),
+                    kid.id.to_string(),
+                    entry.entry_type.as_str().to_string(),
+                    entry.amount.to_string(),
+                    guard(&entry.description),
+                    entry.category.map(|c| c.as_str()).unwrap_or_default().to_string(),
+                ])
+                .map_err(csv_error)?;
+        }
+        into_string(writer)
+    }
+
+    /// Create a task for each row; rows named like an existing task are
+    /// skipped
+    pub async fn import_tasks(&self, actor: &Actor, csv: &str, mapping: &ColumnMapping, dry_run: bool) -> Result<CsvImportReport> {
+        let owner_id = actor.editable_household()?;
+        let mut reader = reader(csv);
+        let headers = reader.headers().map_err(csv_error)?.clone();
+        let columns = mapping.resolve(&headers, TASK_COLUMNS, REQUIRED_TASK_COLUMNS)?;
+        let mut names: Vec<String> = self.task_repo
+            .list_by_owner(owner_id)
+            .await?
+            .into_iter()
+            .map(|task| task.name.to_lowercase())
+            .collect();
+
+        let mut report = CsvImportReport::default();
+        let mut tasks = Vec::new();
+        for row in reader.records() {
+            report.rows += 1;
+            let task = row
+                .map_err(|e| row_error(&e))
+                .and_then(|row| task_from_row(&columns, &row, owner_id).map_err(|message| (line(&row), message)));
+            match task {
+                Ok(task) if names.contains(&task.name.to_lowercase()) => report.skipped.push(task.name),
+                Ok(task) => {
+                    names.push(task.name.to_lowercase());
+                    tasks.push(task);
+                }
+                Err((line, message)) => report.errors.push(CsvRowError { line, message }),
+            }
+        }
+
+        report.imported = tasks.len();
+        if dry_run || !report.errors.is_empty() || tasks.is_empty() {
+            return Ok(report);
+        }
+        let mut tx = self.ledger_repo.begin();
+        for task in &tasks {
+            self.task_repo.create_in(&mut tx, task)?;
+        }
+        tx.commit().await?;
+        report.written = true;
+        Ok(report)
+    }
+
+    /// Record past ledger entries for a household's kids; rows matching an
+    /// entry already in the ledger are skipped
+    ///
+    /// A row matches an entry of the same kid, type, amount and description
+    /// at the same time, or on the same day if the row only gives a date (as
+    /// spreadsheets often leave an exported timestamp).
+    pub async fn import_ledger(
+        &self,
+        actor: &Actor,
+        csv: &str,
+        mapping: &ColumnMapping,
+        dry_run: bool,
+        now: DateTime<Utc>,
+    ) -> Result<CsvImportReport> {
+        let mut reader = reader(csv);
+        let headers = reader.headers().map_err(csv_error)?.clone();
+        let columns = mapping.resolve(&headers, LEDGER_COLUMNS, REQUIRED_LEDGER_COLUMNS)?;
+        if !columns.index.contains_key("kid") && !columns.index.contains_key("kid_id") {
+            return Err(Error::Validation(format!(
+                "The file has no '{}' or '{}' column for the kid",
+                mapping.column("kid"),
+                mapping.column("kid_id")
+            )));
+        }
+        let kids = self.kid_repo.list_by_owner(actor.editable_household()?).await?;
+
+        let mut existing: HashMap<Uuid, Vec<LedgerEntry>> = HashMap::new();
+        let mut report = CsvImportReport::default();
+        let mut entries = Vec::new();
+        for row in reader.records() {
+            report.rows += 1;
+            let entry = row
+                .map_err(|e| row_error(&e))
+                .and_then(|row| entry_from_row(&columns, &row, &kids, now).map_err(|message| (line(&row), message)));
+            let (entry, date_only) = match entry {
+                Ok(entry) => entry,
+                Err((line, message)) => {
+                    report.errors.push(CsvRowError { line, message });
+                    continue;
+                }
+            };
+
+            if let Entry::Vacant(slot) = existing.entry(entry.kid_id) {
+                slot.insert(self.ledger_repo.get_ledger(entry.kid_id).await?.entries);
+            }
+            let same = |e: &LedgerEntry| {
+                let same_time = if date_only {
+                    e.created_at.date_naive() == entry.created_at.date_naive()
+                } else {
+                    e.created_at == entry.created_at
+                };
+                e.kid_id == entry.kid_id
+                    && same_time
+                    && e.entry_type == entry.entry_type
+                    && e.amount == entry.amount
+                    && e.description == entry.description
+            };
+            if existing[&entry.kid_id].iter().any(same) || entries.iter().any(same) {
+                report.skipped.push(format!("{} {} {}", entry.created_at.date_naive(), entry.amount, entry.description));
+                continue;
+            }
+            entries.push(entry);
+        }
+
+        report.imported = entries.len();
+        if dry_run || !report.errors.is_empty() || entries.is_empty() {
+            return Ok(report);
+        }
+        let mut tx = self.ledger_repo.begin();
+        let mut kid_ids: Vec<Uuid> = entries.iter().map(|e| e.kid_id).collect();
+        kid_ids.sort();
+        kid_ids.dedup();
+        for kid_id in kid_ids {
+            self.kid_repo.ensure_active_in(&mut tx, kid_id)?;
+        }
+        for entry in &entries {
+            self.ledger_repo.create_entry_in(&mut tx, entry)?;
+        }
+        tx.commit().await?;
+        report.written = true;
+        Ok(report)
+    }
+}
+
+fn task_from_row(columns: &Columns, row: &csv::StringRecord, owner_id: &str) -> std::result::Result<Task, String> {
+    let name = columns.get(row, "name").ok_or("Name is missing")?;
+    let value = columns.get(row, "value").ok_or("Value is missing")?;
+    let value = parse_amount(value)?;
+    let cadence = columns.get(row, "cadence").ok_or("Cadence is missing")?;
+    let cadence = Cadence::parse(cadence)
+        .ok_or_else(|| format!("Cadence '{}' should be daily, weekly or onetime", cadence))?;
+    let category = columns.get(row, "category").map(parse_category).transpose()?;
+    let tags = columns.get(row, "tags").unwrap_or_default().split(TAG_SEPARATOR);
+
+    let description = columns.get(row, "description").unwrap_or_default().to_string();
+    let task = Task::new(name.to_string(), description, value, cadence, owner_id.to_string())
+        .map_err(message)?
+        .with_category(category)
+        .with_tags(tags);
+    task.validate().map_err(message)?;
+    Ok(task)
+}
+
+/// A row's entry, and whether the row gave only a date for it
+fn entry_from_row(
+    columns: &Columns,
+    row: &csv::StringRecord,
+    kids: &[Kid],
+    now: DateTime<Utc>,
+) -> std::result::Result<(LedgerEntry, bool), String> {
+    let date = columns.get(row, "date").ok_or("Date is missing")?;
+    let created_at = parse_date(date)?;
+    if created_at > now {
+        return Err(format!("Date {} is in the future", date));
+    }
+
+    let kid = match (columns.get(row, "kid_id"), columns.get(row, "kid")) {
+        (Some(id), _) => find_kid(kids, id)?,
+        (None, Some(kid)) => find_kid(kids, kid)?,
+        (None, None) => return Err("Kid is missing".to_string()),
+    };
+
+    let amount = columns.get(row, "amount").ok_or("Amount is missing")?;
+    let amount = parse_amount(amount)?;
+    let entry_type = match columns.get(row, "type") {
+        Some(entry_type) => match entry_type.to_lowercase().as_str() {
+            "earned" => EntryType::Earned,
+            "adjusted" => EntryType::Adjusted,
+            "payout" => EntryType::Payout,
+            "transfer" => EntryType::Transfer,
+            _ => return Err(format!("Type '{}' should be earned, adjusted, payout or transfer", entry_type)),
+        },
+        // Without a type, money in is earned and money out an adjustment
+        None if amount > Decimal::ZERO => EntryType::Earned,
+        None => EntryType::Adjusted,
+    };
+    // Withdrawals record their approval and payment as zero-amount payouts
+    if amount.is_zero() && entry_type != EntryType::Payout {
+        return Err("Amount can't be zero".to_string());
+    }
+    if entry_type == EntryType::Earned && amount < Decimal::ZERO {
+        return Err("Earnings can't be negative; use type adjusted".to_string());
+    }
+
+    let description = columns.get(row, "description").unwrap_or("Imported");
+    let mut entry = LedgerEntry::new(kid.id, amount, entry_type, description.to_string());
+    entry.category = columns.get(row, "category").map(parse_category).transpose()?;
+    entry.created_at = created_at;
+    Ok((entry, NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()))
+}
+
+/// A kid by ID or, failing that, by name
+fn find_kid<'k>(kids: &'k [Kid], kid: &str) -> std::result::Result<&'k Kid, String> {
+    if let Ok(id) = Uuid::parse_str(kid) {
+        return kids.iter().find(|k| k.id == id).ok_or_else(|| format!("No kid has ID {}", id));
+    }
+    let mut named = kids.iter().filter(|k| k.name.eq_ignore_ascii_case(kid));
+    match (named.next(), named.next()) {
+        (Some(found), None) => Ok(found),
+        (Some(_), Some(_)) => Err(format!("More than one kid is named {}; use their ID instead", kid)),
+        (None, _) => Err(format!("No kid is named {}", kid)),
+    }
+}
+
+/// A timestamp as exported (RFC 3339), or a plain date or date and time in UTC
+fn parse_date(date: &str) -> std::result::Result<DateTime<Utc>, String> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(date) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    if let Ok(at) = NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S") {
+        return Ok(at.and_utc());
+    }
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|day| day.and_time(chrono::NaiveTime::MIN).and_utc())
+        .map_err(|_| format!("Date '{}' should look like 2026-10-18", date))
+}
+
+/// An amount, allowing a leading currency sign as spreadsheets often add
+fn parse_amount(amount: &str) -> std::result::Result<Decimal, String> {
+    let (sign, digits) = match amount.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", amount),
+    };
+    let digits = digits.trim_start_matches('$').replace(',', "");
+    Decimal::from_str(&format!("{}{}", sign, digits))
+        .map_err(|_| format!("Amount '{}' isn't a number", amount))
+}
+
+fn parse_category(category: &str) -> std::result::Result<TaskCategory, String> {
+    TaskCategory::parse(category).ok_or_else(|| {
+        format!("Category '{}' should be room, kitchen, school, pets, yard or other", category)
+    })
+}
+
+/// Spreadsheets run cells starting with these as formulas
+const FORMULA_PREFIXES: &[char] = &['=', '+', '-', '@', '\t', '\r'];
+
+/// Quote text that a spreadsheet would otherwise treat as a formula
+fn guard(text: &str) -> String {
+    if text.starts_with(FORMULA_PREFIXES) {
+        format!("'{}", text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Undo [`guard`] on imported text
+fn unguard(cell: &str) -> &str {
+    match cell.strip_prefix('\'') {
+        Some(rest) if rest.starts_with(FORMULA_PREFIXES) => rest,
+        _ => cell,
+    }
+}
+
+fn reader(csv: &str) -> csv::Reader<&[u8]> {
+    // Spreadsheets often save with a byte order mark
+    let csv = csv.strip_prefix('\u{feff}').unwrap_or(csv);
+    csv::ReaderBuilder::new().flexible(true).from_reader(csv.as_bytes())
+}
+
+fn line(row: &csv::StringRecord) -> u64 {
+    row.position().map(|p| p.line()).unwrap_or_default()
+}
+
+fn row_error(e: &csv::Error) -> (u64, String) {
+    (e.position().map(|p| p.line()).unwrap_or_default(), e.to_string())
+}
+
+fn message(e: Error) -> String {
+    match e {
+        Error::Validation(msg) => msg,
+        e => e.to_string(),
+    }
+}
+
+fn csv_error(e: csv::Error) -> Error {
+    Error::Validation(format!("Invalid CSV: {}", e))
+}
+
+fn into_string(writer: csv::Writer<Vec<u8>>) -> Result<String> {
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| Error::Internal(format!("Failed to write CSV: {}", e)))?;
+    String::from_utf8(bytes).map_err(|e| Error::Internal(format!("Failed to write CSV: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_column_mapping() {
+        let mapping = ColumnMapping::parse("name=Chore, value = Pay").unwrap();
+        let headers = csv::StringRecord::from(vec!["chore", "PAY", "cadence"]);
+        let columns = mapping.resolve(&headers, TASK_COLUMNS, REQUIRED_TASK_COLUMNS).unwrap();
+        assert_eq!(columns.index["name"], 0);
+        assert_eq!(columns.index["value"], 1);
+        assert!(!columns.index.contains_key("tags"));
+
+        assert!(ColumnMapping::parse("name").is_err());
+        let unknown = ColumnMapping::new().map("colour", "Colour");
+        assert!(matches!(unknown.resolve(&headers, TASK_COLUMNS, REQUIRED_TASK_COLUMNS), Err(Error::Validation(_))));
+        let missing = csv::StringRecord::from(vec!["name", "cadence"]);
+        assert!(ColumnMapping::new().resolve(&missing, TASK_COLUMNS, REQUIRED_TASK_COLUMNS).is_err());
+    }
+
+    #[test]
+    fn test_parse_cells() {
+        assert_eq!(parse_amount("$1,250.50"), Ok(dec!(1250.50)));
+        assert_eq!(parse_amount("-$2"), Ok(dec!(-2)));
+        assert!(parse_amount("two").is_err());
+
+        let midnight = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap().and_time(chrono::NaiveTime::MIN).and_utc();
+        assert_eq!(parse_date("2026-10-18"), Ok(midnight));
+        assert_eq!(parse_date("2026-10-18T10:00:00+10:00"), Ok(midnight));
+        assert!(parse_date("18/10/2026").is_err());
+    }
+
+    #[test]
+    fn test_formulas_are_guarded() {
+        assert_eq!(guard("=SUM(A1:A9)"), "'=SUM(A1:A9)");
+        assert_eq!(guard("Dishes"), "Dishes");
+        assert_eq!(unguard(&guard("-1 for candy")), "-1 for candy");
+        assert_eq!(unguard("'quoted'"), "'quoted'");
+    }
+}
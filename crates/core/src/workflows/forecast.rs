@@ -0,0 +1,65 @@
+use crate::db::{HouseholdRepository, KidRepository, LedgerRepository, TaskRepository};
+use crate::forecast::{Forecast, LOOKBACK_DAYS};
+use crate::models::Cadence;
+use crate::reports::ReportPeriod;
+use crate::policy::Actor;
+use crate::error::Result;
+use chrono::{Days, NaiveDate};
+use uuid::Uuid;
+
+/// Projects a household's chore payouts from its tasks and recent history
+pub struct ForecastWorkflow {
+    household_repo: HouseholdRepository,
+    kid_repo: KidRepository,
+    task_repo: TaskRepository,
+    ledger_repo: LedgerRepository,
+}
+
+impl ForecastWorkflow {
+    pub fn new(
+        household_repo: HouseholdRepository,
+        kid_repo: KidRepository,
+        task_repo: TaskRepository,
+        ledger_repo: LedgerRepository,
+    ) -> Self {
+        Self { household_repo, kid_repo, task_repo, ledger_repo }
+    }
+
+    /// Payouts the household's active kids and tasks are expected to come
+    /// to over the week and month after `today`
+    pub async fn forecast(&self, actor: &Actor, today: NaiveDate) -> Result<Forecast> {
+        let owner_id = actor.readable_household()?;
+        let budget = self.household_repo.find_by_owner_id(owner_id).await?.and_then(|h| h.budget);
+
+        let kids = self.kid_repo.list_by_owner(owner_id).await?;
+        let tasks = self.task_repo.list_by_owner(owner_id).await?;
+        let kid_ids: Vec<Uuid> = kids.iter().map(|kid| kid.id).collect();
+
+        // The database sums the history; only its totals are loaded
+        let tomorrow = today + Days::new(1);
+        let since = today - Days::new(LOOKBACK_DAYS as u64 - 1);
+        let history = self.ledger_repo.earnings_totals(&kid_ids, ReportPeriod::Month, since, tomorrow).await?;
+
+        // One-time tasks are open until anyone, archived kids included, did them
+        let first_one_time = tasks
+            .iter()
+            .filter(|t| t.cadence == Cadence::OneTime)
+            .map(|t| t.created_at.date_naive())
+            .min();
+        let done_one_time = match first_one_time {
+            Some(first) => {
+                let mut all_kids = kid_ids.clone();
+                all_kids.extend(self.kid_repo.list_archived_by_owner(owner_id).await?.iter().map(|kid| kid.id));
+                self.ledger_repo
+                    .earnings_totals(&all_kids, ReportPeriod::Month, first, tomorrow)
+                    .await?
+                    .into_iter()
+                    .filter_map(|total| total.task_id)
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+
+        Ok(Forecast::new(&kids, &tasks, &history, &done_one_time, budget, today))
+    }
+}
@@ -0,0 +1,176 @@
+use crate::db::{HouseholdRepository, Repository};
+use crate::models::{Household, HouseholdRole, Kid, PayoutBudget};
+use crate::error::{Error, Result};
+use uuid::Uuid;
+
+/// Resolves users to their household and manages its members
+pub struct HouseholdWorkflow {
+    household_repo: HouseholdRepository,
+}
+
+impl HouseholdWorkflow {
+    pub fn new(household_repo: HouseholdRepository) -> Self {
+        Self { household_repo }
+    }
+
+    /// The household a user belongs to, creating their personal one on first use
+    pub async fn household_for_user(&self, user_id: &str) -> Result<Household> {
+        if let Some(household) = self.household_repo.find_by_member(user_id).await? {
+            return Ok(household);
+        }
+
+        let personal = Household::personal(user_id.to_string());
+        let personal_id = personal.id;
+        match self.household_repo.create(personal).await {
+            Ok(household) => Ok(household),
+            Err(Error::Database(_)) => {
+                // Either another process created it first, or the user was
+                // removed from the household that started out as theirs
+                let existing = self.household_repo.get(personal_id).await?;
+                if existing.member(user_id).is_some() {
+                    return Ok(existing);
+                }
+                self.household_repo.create(Household::new("My Family".to_string(), user_id.to_string())?).await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The user's household together with their role in it
+    pub async fn membership(&self, user_id: &str) -> Result<(Household, HouseholdRole)> {
+        let household = self.household_for_user(user_id).await?;
+        let role = household.role_of(user_id)
+            .ok_or_else(|| Error::Internal("User missing from their own household".to_string()))?;
+        Ok((household, role))
+    }
+
+    /// Re-key records whose `owner_id` is still a user ID (from before
+    /// households existed) to that user's household
+    ///
+    /// Safe to run on every startup; returns how many owners were migrated.
+    pub async fn migrate_owner_ids(&self) -> Result<usize> {
+        let household_ids: Vec<String> = self.household_repo
+            .list()
+            .await?
+            .iter()
+            .map(|h| h.owner_id())
+            .collect();
+
+        let mut migrated = 0;
+        for owner_id in self.household_repo.owner_ids_in_use().await? {
+            if owner_id.is_empty() || household_ids.contains(&owner_id) {
+                continue;
+            }
+            let household = self.household_for_user(&owner_id).await?;
+            self.household_repo.reassign_owner(&owner_id, &household.owner_id()).await?;
+            migrated += 1;
+        }
+        Ok(migrated)
+    }
+
+    /// Move a user into another household with the given role
+    ///
+    /// See `ensure_can_join`; if the user was alone in their previous
+    /// household, that household is removed.
+    pub async fn join(&self, user_id: &str, household_id: Uuid, role: HouseholdRole) -> Result<Household> {
+        let mut target = self.ensure_can_join(user_id, household_id).await?;
+
+        if let Some(mut current) = self.household_repo.find_by_member(user_id).await? {
+            if current.members.len() == 1 {
+                self.household_repo.delete(current.id).await?;
+            } else {
+                current.remove_member(user_id)?;
+                self.household_repo.update(current).await?;
+            }
+        }
+
+        target.add_member(user_id.to_string(), role)?;
+        self.household_repo.update(target).await
+    }
+
+    /// Fail unless the user can join the household, returning it
+    ///
+    /// A user alone in a household that still has kids, tasks or other
+    /// records is refused, rather than have them handed to the household
+    /// they join.
+    pub async fn ensure_can_join(&self, user_id: &str, household_id: Uuid) -> Result<Household> {
+        let target = self.household_repo.get(household_id).await?;
+        if target.member(user_id).is_some() {
+            return Err(Error::Validation("Already a member of this household".to_string()));
+        }
+
+        if let Some(current) = self.household_repo.find_by_member(user_id).await? {
+            if current.members.len() == 1 && self.household_repo.owns_records(&current.owner_id()).await? {
+                return Err(Error::Validation(format!(
+                    "'{}' still has kids or tasks; remove them before joining another household",
+                    current.name
+                )));
+            }
+        }
+        Ok(target)
+    }
+
+    /// Add an existing user to the actor's household (owners only)
+    pub async fn add_member(&self, actor_user_id: &str, user_id: &str, role: HouseholdRole) -> Result<Household> {
+        let household = self.managed_household(actor_user_id).await?;
+        self.join(user_id, household.id, role).await
+    }
+
+    pub async fn set_role(&self, actor_user_id: &str, user_id: &str, role: HouseholdRole) -> Result<Household> {
+        let mut household = self.managed_household(actor_user_id).await?;
+        household.set_role(user_id, role)?;
+        self.household_repo.update(household).await
+    }
+
+    /// Remove a member; they start over in a fresh personal household
+    ///
+    /// Members can always remove themselves (leave); removing others is for owners.
+    pub async fn remove_member(&self, actor_user_id: &str, user_id: &str) -> Result<Household> {
+        let mut household = if actor_user_id == user_id {
+            self.household_for_user(actor_user_id).await?
+        } else {
+            self.managed_household(actor_user_id).await?
+        };
+        household.remove_member(user_id)?;
+        self.household_repo.update(household).await
+    }
+
+    pub async fn rename(&self, actor_user_id: &str, name: String) -> Result<Household> {
+        let mut household = self.managed_household(actor_user_id).await?;
+        household.name = name.trim().to_string();
+        household.validate()?;
+        self.household_repo.update(household).await
+    }
+
+    /// Set or clear the household's payout budget (owners and parents)
+    pub async fn set_budget(&self, actor_user_id: &str, budget: Option<PayoutBudget>) -> Result<Household> {
+        let (mut household, role) = self.membership(actor_user_id).await?;
+        if !role.can_edit() {
+            return Err(Error::Forbidden("Viewers can't change the budget".to_string()));
+        }
+        household.budget = budget;
+        household.updated_at = chrono::Utc::now();
+        self.household_repo.update(household).await
+    }
+
+    /// Set or clear one kid's payout budget (owners and parents)
+    pub async fn set_kid_budget(&self, actor_user_id: &str, kid: &Kid, budget: Option<PayoutBudget>) -> Result<Household> {
+        let (mut household, role) = self.membership(actor_user_id).await?;
+        if kid.owner_id != household.owner_id() {
+            return Err(Error::NotFound(format!("Kid with id {}", kid.id)));
+        }
+        if !role.can_edit() {
+            return Err(Error::Forbidden("Viewers can't change budgets".to_string()));
+        }
+        household.set_kid_budget(kid.id, budget);
+        self.household_repo.update(household).await
+    }
+
+    async fn managed_household(&self, actor_user_id: &str) -> Result<Household> {
+        let (household, role) = self.membership(actor_user_id).await?;
+        if !role.can_manage_members() {
+            return Err(Error::Validation("Only household owners can manage members".to_string()));
+        }
+        Ok(household)
+    }
+}
@@ -0,0 +1,113 @@
+use crate::db::{IdempotencyRepository, Repository};
+use crate::models::IdempotencyKey;
+use crate::error::{Error, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::future::Future;
+
+/// Makes requests that change data safe to retry
+///
+/// Clients (MCP tool calls in particular) retry on flaky connections. When a
+/// request carries an idempotency key, the first request runs and its result
+/// is stored under the key; a retry with the same key and arguments gets that
+/// result back instead of, say, paying a kid twice. Keys are kept for a
+/// retention window.
+pub struct IdempotencyWorkflow {
+    repo: IdempotencyRepository,
+    retention: Duration,
+}
+
+impl IdempotencyWorkflow {
+    pub fn new(repo: IdempotencyRepository, retention: Duration) -> Self {
+        Self { repo, retention }
+    }
+
+    /// Run `operation` once per `key` within `scope`
+    ///
+    /// Without a key this simply runs it. The outer result reports problems
+    /// with the key itself (reused for another operation or other
+    /// `arguments`, or the first request is still running); the inner one is
+    /// the operation's own.
+    /// Failed operations aren't stored, so they can be retried with the same
+    /// key.
+    pub async fn run<A, T, E, F, Fut>(
+        &self,
+        scope: &str,
+        key: Option<&str>,
+        operation: &str,
+        arguments: &A,
+        f: F,
+    ) -> Result<std::result::Result<T, E>>
+    where
+        A: Serialize,
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = std::result::Result<T, E>>,
+    {
+        let Some(key) = key else {
+            return Ok(f().await);
+        };
+
+        let now = Utc::now();
+        let record = IdempotencyKey::new(scope, key, operation, arguments, now)?;
+        let id = record.id;
+        let request_hash = record.request_hash.clone();
+        if !self.reserve(record).await? {
+            return self.replay(id, operation, &request_hash).await.map(Ok);
+        }
+
+        let result = f().await;
+        match &result {
+            Ok(value) => {
+                let response = serde_json::to_string(value)
+                    .map_err(|e| Error::Internal(format!("Failed to store result: {}", e)))?;
+                // The change is done either way; without a stored result a
+                // retry is refused as still in progress rather than repeated
+                if let Err(e) = self.repo.set_response(id, response).await {
+                    eprintln!("WARNING: Failed to store result for idempotency key {}: {}", id, e);
+                }
+            }
+            Err(_) => self.repo.delete(id).await?,
+        }
+        Ok(result)
+    }
+
+    /// Delete keys past the retention window, returning how many were removed
+    pub async fn purge_expired(&self, now: DateTime<Utc>) -> Result<usize> {
+        self.repo.delete_before(now - self.retention).await
+    }
+
+    async fn reserve(&self, record: IdempotencyKey) -> Result<bool> {
+        if let Some(existing) = self.repo.find(record.id).await? {
+            // An expired key that hasn't been purged yet counts as new
+            if !existing.is_expired(self.retention, record.created_at) {
+                return Ok(false);
+            }
+            self.repo.delete(existing.id).await?;
+        }
+        self.repo.reserve(record).await
+    }
+
+    async fn replay<T: DeserializeOwned>(&self, id: uuid::Uuid, operation: &str, request_hash: &str) -> Result<T> {
+        let existing = self.repo.find(id).await?
+            .ok_or_else(|| Error::Validation("The first request with this idempotency key failed; retry".to_string()))?;
+        if existing.operation != operation {
+            return Err(Error::Validation(format!(
+                "Idempotency key '{}' was already used for {}",
+                existing.key, existing.operation
+            )));
+        }
+        if existing.request_hash != request_hash {
+            return Err(Error::Validation(format!(
+                "Idempotency key '{}' was already used for a {} request with different arguments",
+                existing.key, existing.operation
+            )));
+        }
+
+        let response = existing.response
+            .ok_or_else(|| Error::Validation("A request with this idempotency key is still in progress".to_string()))?;
+        serde_json::from_str(&response)
+            .map_err(|e| Error::Internal(format!("Failed to read stored result: {}", e)))
+    }
+}
@@ -0,0 +1,145 @@
+use crate::auth::{hash_password, sign_invite_code, validate_password, verify_invite_code};
+use crate::db::{InviteRepository, Repository, UserRepository};
+use crate::models::{Household, HouseholdRole, Invite, User};
+use crate::workflows::HouseholdWorkflow;
+use crate::error::{Error, Result};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// Invites let household members bring in a co-parent or grandparent, who
+/// registers their own account with the invite code
+pub struct InviteWorkflow {
+    invite_repo: InviteRepository,
+    user_repo: UserRepository,
+    households: HouseholdWorkflow,
+    /// Server secret used to sign invite codes
+    secret: String,
+}
+
+impl InviteWorkflow {
+    pub fn new(
+        invite_repo: InviteRepository,
+        user_repo: UserRepository,
+        households: HouseholdWorkflow,
+        secret: String,
+    ) -> Self {
+        Self {
+            invite_repo,
+            user_repo,
+            households,
+            secret,
+        }
+    }
+
+    /// Create an invite into the actor's household, returning it with its code
+    ///
+    /// Parents can invite parents and viewers; only owners can invite owners.
+    pub async fn create(
+        &self,
+        actor_user_id: &str,
+        role: HouseholdRole,
+        ttl: Duration,
+    ) -> Result<(Invite, String)> {
+        let (household, actor_role) = self.editable_household(actor_user_id).await?;
+        if role == HouseholdRole::Owner && actor_role != HouseholdRole::Owner {
+            return Err(Error::Validation("Only owners can invite another owner".to_string()));
+        }
+        if ttl <= Duration::zero() {
+            return Err(Error::Validation("Invite lifetime must be positive".to_string()));
+        }
+
+        let invite = self.invite_repo
+            .create(Invite::new(household.id, role, actor_user_id.to_string(), ttl))
+            .await?;
+        let code = sign_invite_code(invite.id, &self.secret);
+        Ok((invite, code))
+    }
+
+    /// Invites into the actor's household that can still be used
+    pub async fn list_pending(&self, actor_user_id: &str, now: DateTime<Utc>) -> Result<Vec<Invite>> {
+        let (household, _) = self.editable_household(actor_user_id).await?;
+        Ok(self.invite_repo
+            .list_by_household(household.id)
+            .await?
+            .into_iter()
+            .filter(|i| i.is_pending(now))
+            .collect())
+    }
+
+    pub async fn revoke(&self, actor_user_id: &str, invite_id: Uuid) -> Result<Invite> {
+        let (household, _) = self.editable_household(actor_user_id).await?;
+        let mut invite = self.invite_repo.get(invite_id).await?;
+        if invite.household_id != household.id {
+            return Err(Error::NotFound(format!("Invite with id {}", invite_id)));
+        }
+        if invite.used_at.is_some() {
+            return Err(Error::Validation("Invite has already been used".to_string()));
+        }
+
+        invite.revoked_at.get_or_insert_with(Utc::now);
+        self.invite_repo.update(invite).await
+    }
+
+    /// Look up a code's invite without using it (e.g. to show the household role)
+    pub async fn check(&self, code: &str, now: DateTime<Utc>) -> Result<Invite> {
+        let invite_id = verify_invite_code(code, &self.secret)
+            .ok_or_else(|| Error::Validation("Invalid invite code".to_string()))?;
+        let invite = self.invite_repo.get(invite_id).await
+            .map_err(|_| Error::Validation("Invalid invite code".to_string()))?;
+        if !invite.is_pending(now) {
+            return Err(Error::Validation("This invite has expired or was already used".to_string()));
+        }
+        Ok(invite)
+    }
+
+    /// Register a new account with an invite and join the household
+    pub async fn register(
+        &self,
+        code: &str,
+        username: String,
+        password: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(User, Household)> {
+        let invite = self.check(code, now).await?;
+        validate_password(password)?;
+        if self.user_repo.get_by_username(&username).await.is_ok() {
+            return Err(Error::Validation(format!("Username '{}' already exists", username)));
+        }
+
+        let mut user = User::new(username)?;
+        user.password_hash = hash_password(password)?;
+        let user = self.user_repo.create(user).await?;
+
+        match self.redeem(invite, &user.id.to_string(), now).await {
+            Ok(household) => Ok((user, household)),
+            Err(e) => {
+                // Someone else used the code first; don't leave a stray account behind
+                self.user_repo.delete(user.id).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Join a household with an invite as an already registered user
+    pub async fn accept(&self, code: &str, user_id: &str, now: DateTime<Utc>) -> Result<Household> {
+        let invite = self.check(code, now).await?;
+        self.redeem(invite, user_id, now).await
+    }
+
+    async fn redeem(&self, invite: Invite, user_id: &str, now: DateTime<Utc>) -> Result<Household> {
+        // A refused join leaves the invite for another try
+        self.households.ensure_can_join(user_id, invite.household_id).await?;
+        if !self.invite_repo.mark_used(invite.id, user_id, now).await? {
+            return Err(Error::Validation("This invite has expired or was already used".to_string()));
+        }
+        self.households.join(user_id, invite.household_id, invite.role).await
+    }
+
+    async fn editable_household(&self, actor_user_id: &str) -> Result<(Household, HouseholdRole)> {
+        let (household, role) = self.households.membership(actor_user_id).await?;
+        if !role.can_edit() {
+            return Err(Error::Validation("Viewers can't manage invites".to_string()));
+        }
+        Ok((household, role))
+    }
+}
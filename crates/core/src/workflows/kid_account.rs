@@ -0,0 +1,130 @@
+use crate::auth::{hash_pin, verify_pin};
+use crate::db::{KidRepository, Repository, UserRepository};
+use crate::models::{Kid, User};
+use crate::policy::Actor;
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Result of a kid trying to log in with a PIN
+#[derive(Debug, Clone)]
+pub enum KidLogin {
+    Success { user: Box<User>, kid: Box<Kid> },
+    /// Unknown username, not a kid account, or wrong PIN
+    InvalidCredentials,
+    /// Too many wrong PINs; try again after `until`
+    Locked { until: DateTime<Utc> },
+}
+
+/// Manages PIN logins for kids
+pub struct KidAccountWorkflow {
+    user_repo: UserRepository,
+    kid_repo: KidRepository,
+}
+
+impl KidAccountWorkflow {
+    pub fn new(user_repo: UserRepository, kid_repo: KidRepository) -> Self {
+        Self { user_repo, kid_repo }
+    }
+
+    /// Create a PIN login for one of the actor's kids
+    pub async fn create_account(
+        &self,
+        actor: &Actor,
+        kid_id: Uuid,
+        username: String,
+        pin: &str,
+    ) -> Result<User> {
+        self.editable_kid(actor, kid_id).await?;
+
+        if self.user_repo.get_by_username(&username).await.is_ok() {
+            return Err(Error::Validation(format!("Username '{}' already exists", username)));
+        }
+        if self.find_by_kid(kid_id).await?.is_some() {
+            return Err(Error::Validation("This kid already has a login".to_string()));
+        }
+
+        let mut user = User::new_kid(username, kid_id)?;
+        user.password_hash = hash_pin(pin)?;
+        self.user_repo.create(user).await
+    }
+
+    /// Check a kid's PIN, enforcing the lockout after repeated failures
+    pub async fn login(&self, username: &str, pin: &str, now: DateTime<Utc>) -> Result<KidLogin> {
+        let mut user = match self.user_repo.get_by_username(username).await {
+            Ok(user) if user.is_kid() => user,
+            Ok(_) | Err(Error::NotFound(_)) => return Ok(KidLogin::InvalidCredentials),
+            Err(e) => return Err(e),
+        };
+
+        if let Some(until) = user.locked_until.filter(|_| user.is_locked(now)) {
+            return Ok(KidLogin::Locked { until });
+        }
+
+        if !verify_pin(pin, &user.password_hash)? {
+            user.record_failed_login(now);
+            let locked_until = user.locked_until.filter(|_| user.is_locked(now));
+            self.user_repo.update(user).await?;
+            return Ok(match locked_until {
+                Some(until) => KidLogin::Locked { until },
+                None => KidLogin::InvalidCredentials,
+            });
+        }
+
+        let kid_id = user.kid_id
+            .ok_or_else(|| Error::Internal("Kid account is not linked to a kid".to_string()))?;
+        let kid = self.kid_repo.get(kid_id).await?;
+        if kid.is_archived() {
+            return Ok(KidLogin::InvalidCredentials);
+        }
+
+        if user.failed_logins > 0 || user.locked_until.is_some() {
+            user.record_successful_login(now);
+            user = self.user_repo.update(user).await?;
+        }
+
+        Ok(KidLogin::Success { user: Box::new(user), kid: Box::new(kid) })
+    }
+
+    /// Set a new PIN for a kid's login and clear any lockout
+    pub async fn reset_pin(&self, actor: &Actor, kid_id: Uuid, pin: &str) -> Result<User> {
+        self.editable_kid(actor, kid_id).await?;
+        let mut user = self.find_by_kid(kid_id).await?
+            .ok_or_else(|| Error::NotFound("Login for this kid".to_string()))?;
+
+        user.password_hash = hash_pin(pin)?;
+        user.record_successful_login(Utc::now());
+        self.user_repo.update(user).await
+    }
+
+    /// Kid logins belonging to the actor's household's kids
+    pub async fn list_accounts(&self, actor: &Actor) -> Result<Vec<User>> {
+        let kid_ids: Vec<Uuid> = self.kid_repo
+            .list_by_owner(actor.readable_household()?)
+            .await?
+            .into_iter()
+            .map(|k| k.id)
+            .collect();
+
+        Ok(self.user_repo
+            .list()
+            .await?
+            .into_iter()
+            .filter(|u| u.kid_id.is_some_and(|id| kid_ids.contains(&id)))
+            .collect())
+    }
+
+    async fn find_by_kid(&self, kid_id: Uuid) -> Result<Option<User>> {
+        Ok(self.user_repo
+            .list()
+            .await?
+            .into_iter()
+            .find(|u| u.kid_id == Some(kid_id)))
+    }
+
+    async fn editable_kid(&self, actor: &Actor, kid_id: Uuid) -> Result<Kid> {
+        let kid = self.kid_repo.get(kid_id).await?;
+        actor.authorize_edit(&kid.owner_id, format!("Kid {}", kid_id))?;
+        Ok(kid)
+    }
+}
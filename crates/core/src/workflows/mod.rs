@@ -1,3 +1,35 @@
 mod task_completion;
+mod pack_import;
+mod attachment;
+mod claim;
+mod kid_account;
+mod household;
+mod invite;
+mod archive;
+mod idempotency;
+mod backup;
+mod csv_io;
+mod statement;
+mod report;
+mod forecast;
+mod budget;
+mod withdrawal;
+mod transfer;
 
-pub use task_completion::TaskCompletionWorkflow;
+pub use task_completion::{CompletionStore, TaskCompletionWorkflow};
+pub use pack_import::{PackImportWorkflow, PackImportResult};
+pub use attachment::AttachmentWorkflow;
+pub use claim::ClaimWorkflow;
+pub use kid_account::{KidAccountWorkflow, KidLogin};
+pub use household::HouseholdWorkflow;
+pub use invite::InviteWorkflow;
+pub use archive::ArchiveWorkflow;
+pub use idempotency::IdempotencyWorkflow;
+pub use backup::{BackupWorkflow, BackupManifest, BackupStatus, BackupTable, RestoreMode, RestoreSummary, BACKUP_TABLES, BACKUP_VERSION, SKIPPED_TABLES};
+pub use csv_io::{CsvWorkflow, ColumnMapping, CsvImportReport, CsvRowError, LedgerFilter, LEDGER_COLUMNS, TASK_COLUMNS};
+pub use statement::StatementWorkflow;
+pub use report::ReportWorkflow;
+pub use forecast::ForecastWorkflow;
+pub use budget::BudgetWorkflow;
+pub use withdrawal::{PayoutSummary, WithdrawalWorkflow};
+pub use transfer::TransferWorkflow;
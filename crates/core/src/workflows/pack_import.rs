@@ -0,0 +1,54 @@
+use crate::db::{Repository, TaskRepository};
+use crate::models::Task;
+use crate::templates::TaskPack;
+use crate::policy::Actor;
+use crate::error::Result;
+use rust_decimal::Decimal;
+
+/// Outcome of importing a task pack
+#[derive(Debug, Clone)]
+pub struct PackImportResult {
+    /// Tasks created from the pack
+    pub created: Vec<Task>,
+    /// Names of templates skipped because the owner already has a task with that name
+    pub skipped: Vec<String>,
+}
+
+/// Instantiates task packs as real tasks for a household
+pub struct PackImportWorkflow {
+    task_repo: TaskRepository,
+}
+
+impl PackImportWorkflow {
+    pub fn new(task_repo: TaskRepository) -> Self {
+        Self { task_repo }
+    }
+
+    /// Create a task for each template in the pack
+    ///
+    /// Values are scaled by `value_scale`. Templates whose name matches an
+    /// existing task of the household (case-insensitive) are skipped, so
+    /// importing the same pack twice doesn't create duplicates.
+    pub async fn import_pack(
+        &self,
+        pack: &TaskPack,
+        actor: &Actor,
+        value_scale: Decimal,
+    ) -> Result<PackImportResult> {
+        let owner_id = actor.editable_household()?;
+        let tasks = pack.instantiate(owner_id, value_scale)?;
+        let existing = self.task_repo.list_by_owner(owner_id).await?;
+
+        let mut created = Vec::new();
+        let mut skipped = Vec::new();
+        for task in tasks {
+            if existing.iter().any(|t| t.name.eq_ignore_ascii_case(&task.name)) {
+                skipped.push(task.name);
+                continue;
+            }
+            created.push(self.task_repo.create(task).await?);
+        }
+
+        Ok(PackImportResult { created, skipped })
+    }
+}
@@ -0,0 +1,41 @@
+use crate::db::{KidRepository, LedgerRepository, Repository, TaskRepository};
+use crate::reports::{EarningsReport, ReportQuery};
+use crate::policy::Actor;
+use crate::error::Result;
+
+/// Earnings reports for a household, summed by the database
+pub struct ReportWorkflow {
+    kid_repo: KidRepository,
+    task_repo: TaskRepository,
+    ledger_repo: LedgerRepository,
+}
+
+impl ReportWorkflow {
+    pub fn new(kid_repo: KidRepository, task_repo: TaskRepository, ledger_repo: LedgerRepository) -> Self {
+        Self { kid_repo, task_repo, ledger_repo }
+    }
+
+    /// Earnings of the household's active kids, or of the one kid the query
+    /// names (which may be archived)
+    pub async fn earnings(&self, actor: &Actor, query: &ReportQuery) -> Result<EarningsReport> {
+        let owner_id = actor.readable_household()?;
+        let kids = match query.kid_id {
+            Some(kid_id) => {
+                let kid = self.kid_repo.get(kid_id).await?;
+                actor.authorize_kid(&kid)?;
+                vec![kid]
+            }
+            None => self.kid_repo.list_by_owner(owner_id).await?,
+        };
+
+        // Archived tasks still name the earnings they paid
+        let mut tasks = self.task_repo.list_by_owner(owner_id).await?;
+        tasks.extend(self.task_repo.list_archived_by_owner(owner_id).await?);
+
+        let kid_ids: Vec<_> = kids.iter().map(|kid| kid.id).collect();
+        let totals = self.ledger_repo
+            .earnings_totals(&kid_ids, query.period, query.comparison_start(), query.end())
+            .await?;
+        Ok(EarningsReport::new(query, &kids, &tasks, &totals))
+    }
+}
@@ -0,0 +1,62 @@
+use crate::db::{KidRepository, LedgerRepository, Repository};
+use crate::models::Kid;
+use crate::policy::Actor;
+use crate::statements::{month_end, Statement};
+use crate::workflows::backup::{io_error, write_atomically};
+use crate::error::{Error, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Monthly statements of kids' ledgers, on demand and archived once a month
+/// is over
+pub struct StatementWorkflow {
+    kid_repo: KidRepository,
+    ledger_repo: LedgerRepository,
+}
+
+impl StatementWorkflow {
+    pub fn new(kid_repo: KidRepository, ledger_repo: LedgerRepository) -> Self {
+        Self { kid_repo, ledger_repo }
+    }
+
+    /// A kid's statement for `month` (its first day)
+    pub async fn statement(&self, actor: &Actor, kid_id: Uuid, month: NaiveDate) -> Result<Statement> {
+        let kid = self.kid_repo.get(kid_id).await?;
+        actor.authorize_kid(&kid)?;
+        self.build(&kid, month).await
+    }
+
+    /// Write every active kid's statement for `month` to `dir/YYYY-MM/`, as
+    /// PDF and HTML, returning how many statements were written
+    ///
+    /// Statements already archived are left alone, so running this again for
+    /// the same month only fills in what's missing. A month that isn't over
+    /// yet is refused, since its statement would change.
+    pub async fn archive_month(&self, dir: &Path, month: NaiveDate, now: DateTime<Utc>) -> Result<usize> {
+        if month_end(month) > now {
+            return Err(Error::Validation(format!("{} isn't over yet", month.format("%B %Y"))));
+        }
+        let month_dir = dir.join(month.format("%Y-%m").to_string());
+        std::fs::create_dir_all(&month_dir).map_err(|e| io_error(&month_dir, e))?;
+
+        let mut written = 0;
+        for kid in self.kid_repo.list().await?.into_iter().filter(|kid| !kid.is_archived()) {
+            let statement = self.build(&kid, month).await?;
+            let pdf = month_dir.join(format!("{}.pdf", statement.file_stem()));
+            let html = month_dir.join(format!("{}.html", statement.file_stem()));
+            if pdf.exists() && html.exists() {
+                continue;
+            }
+            write_atomically(&pdf, &statement.to_pdf())?;
+            write_atomically(&html, statement.to_html().as_bytes())?;
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    async fn build(&self, kid: &Kid, month: NaiveDate) -> Result<Statement> {
+        let entries = self.ledger_repo.list_entries(kid.id).await?;
+        Ok(Statement::new(kid, &entries, month))
+    }
+}
@@ -1,27 +1,101 @@
-use crate::db::{TaskRepository, KidRepository, LedgerRepository};
-use crate::models::LedgerEntry;
-use crate::error::Result;
+use crate::budget::BudgetStatus;
+use crate::db::{InMemoryRepository, KidRepository, LedgerRepository, Repository, TaskRepository, Transaction};
+use crate::models::{BudgetEnforcement, CompletionReview, Kid, LedgerEntry, Task};
+use crate::workflows::BudgetWorkflow;
+use crate::policy::Actor;
+use crate::error::{Error, Result};
+use std::future::Future;
 use uuid::Uuid;
 
+/// Where task completions are saved: the payout together with the task,
+/// whose version is bumped so that of two completions racing on the same
+/// task only the first is saved
+///
+/// On SurrealDB both go into one transaction (see
+/// `TaskCompletionWorkflow::complete_in`), which also re-checks the blocking
+/// `budgets` the payout was allowed by. In memory they're saved one after
+/// the other, which is enough for tests.
+pub trait CompletionStore<K, T>: Send + Sync {
+    fn save_completion(
+        &self,
+        kids: &K,
+        tasks: &T,
+        task: &Task,
+        entry: &LedgerEntry,
+        budgets: &[BudgetStatus],
+    ) -> impl Future<Output = Result<()>> + Send;
+}
+
+impl CompletionStore<KidRepository, TaskRepository> for LedgerRepository {
+    async fn save_completion(
+        &self,
+        kids: &KidRepository,
+        tasks: &TaskRepository,
+        task: &Task,
+        entry: &LedgerEntry,
+        budgets: &[BudgetStatus],
+    ) -> Result<()> {
+        let mut tx = self.begin();
+        completion_in(&mut tx, kids, tasks, self, task, entry, budgets)?;
+        tx.commit().await
+    }
+}
+
+impl<K: Repository<Kid>, T: Repository<Task>> CompletionStore<K, T> for InMemoryRepository<LedgerEntry> {
+    async fn save_completion(
+        &self,
+        _kids: &K,
+        tasks: &T,
+        task: &Task,
+        entry: &LedgerEntry,
+        _budgets: &[BudgetStatus],
+    ) -> Result<()> {
+        tasks.update(task.clone()).await?;
+        self.create(entry.clone()).await?;
+        Ok(())
+    }
+}
+
 /// Coordinates task completion workflow:
 /// 1. Mark task as complete (create ledger entry)
 /// 2. Reset task if it's a recurring task
-pub struct TaskCompletionWorkflow {
-    task_repo: TaskRepository,
-    kid_repo: KidRepository,
-    ledger_repo: LedgerRepository,
+///
+/// Both steps are saved together (see `CompletionStore`). With budgets (see
+/// `with_budgets`), payouts a blocking budget has no room for are refused.
+pub struct TaskCompletionWorkflow<K = KidRepository, T = TaskRepository, L = LedgerRepository> {
+    task_repo: T,
+    kid_repo: K,
+    ledger_repo: L,
+    budgets: Option<BudgetWorkflow>,
 }
 
-impl TaskCompletionWorkflow {
-    pub fn new(
-        task_repo: TaskRepository,
-        kid_repo: KidRepository,
-        ledger_repo: LedgerRepository,
-    ) -> Self {
+impl<K, T, L> TaskCompletionWorkflow<K, T, L>
+where
+    K: Repository<Kid>,
+    T: Repository<Task>,
+    L: CompletionStore<K, T>,
+{
+    pub fn new(task_repo: T, kid_repo: K, ledger_repo: L) -> Self {
         Self {
             task_repo,
             kid_repo,
             ledger_repo,
+            budgets: None,
+        }
+    }
+
+    /// Check payouts against the household's and kids' budgets
+    pub fn with_budgets(mut self, budgets: BudgetWorkflow) -> Self {
+        self.budgets = Some(budgets);
+        self
+    }
+
+    /// The budgets a payout to the kid counts toward, as of `today`; empty
+    /// without budgets
+    pub async fn budget_statuses(&self, kid_id: Uuid, today: chrono::NaiveDate) -> Result<Vec<BudgetStatus>> {
+        match self.budgets {
+            Some(ref budgets) => budgets.for_kid(&self.kid_repo.get(kid_id).await?, today).await,
+            None => Ok(Vec::new()),
         }
     }
 
@@ -32,23 +106,129 @@ impl TaskCompletionWorkflow {
     ///
     /// Returns the created ledger entry
     pub async fn complete_task(&self, task_id: Uuid, kid_id: Uuid) -> Result<LedgerEntry> {
+        self.complete_task_with_review(task_id, kid_id, None).await
+    }
+
+    /// Complete a task for a kid, applying a parent's review
+    ///
+    /// The review can pay a percentage of the task's value and attach a
+    /// quality rating and comment. The ledger entry records the original
+    /// task value alongside the review.
+    pub async fn complete_task_with_review(
+        &self,
+        task_id: Uuid,
+        kid_id: Uuid,
+        review: Option<CompletionReview>,
+    ) -> Result<LedgerEntry> {
+        let (task, entry, budgets) = self.prepare(task_id, kid_id, review).await?;
+        self.ledger_repo
+            .save_completion(&self.kid_repo, &self.task_repo, &task, &entry, &budgets)
+            .await?;
+        Ok(entry)
+    }
+
+    /// Check a completion and work out what it changes: the task as it's to
+    /// be saved, and the payout, along with the blocking budgets it has to
+    /// stay within when saved
+    async fn prepare(
+        &self,
+        task_id: Uuid,
+        kid_id: Uuid,
+        review: Option<CompletionReview>,
+    ) -> Result<(Task, LedgerEntry, Vec<BudgetStatus>)> {
+        if let Some(ref review) = review {
+            review.validate()?;
+        }
+
         // 1. Verify the kid exists
-        let _kid = self.kid_repo.get(kid_id).await?;
+        let kid = self.kid_repo.get(kid_id).await?;
 
         // 2. Get the task
         let mut task = self.task_repo.get(task_id).await?;
+        if kid.is_archived() || task.is_archived() {
+            return Err(Error::Validation("Archived kids and tasks can't be completed".to_string()));
+        }
 
-        // 3. Create ledger entry for the earnings
-        let description = format!("Completed: {}", task.name);
-        let entry = LedgerEntry::earned(kid_id, task.value, description);
-        let created_entry = self.ledger_repo.create_entry(entry).await?;
+        // 3. Create ledger entry for the earnings, if the budgets allow it
+        let entry = LedgerEntry::task_completion(kid_id, &task, review);
+        let mut blocking = Vec::new();
+        if let Some(ref budgets) = self.budgets {
+            for status in budgets.for_kid(&kid, entry.created_at.date_naive()).await? {
+                if status.blocks(entry.amount) {
+                    return Err(Error::Validation(format!(
+                        "{}, which has ${:.2} left",
+                        status.refusal(entry.amount),
+                        status.remaining.max(rust_decimal::Decimal::ZERO)
+                    )));
+                }
+                if status.budget.enforcement == BudgetEnforcement::Block {
+                    blocking.push(status);
+                }
+            }
+        }
 
-        // 4. Reset task if it needs resetting (recurring tasks)
+        // 4. Reset task if it needs resetting (recurring tasks). The task is
+        // saved either way, bumping its version.
         if task.needs_reset() {
             task.reset();
-            self.task_repo.update(task).await?;
         }
 
-        Ok(created_entry)
+        Ok((task, entry, blocking))
+    }
+
+    /// Complete a task for a kid on behalf of an actor, who must be able to
+    /// change both the kid's and the task's household
+    pub async fn complete_task_as(
+        &self,
+        actor: &Actor,
+        task_id: Uuid,
+        kid_id: Uuid,
+        review: Option<CompletionReview>,
+    ) -> Result<LedgerEntry> {
+        let kid = self.kid_repo.get(kid_id).await?;
+        actor.authorize_edit(&kid.owner_id, format!("Kid {}", kid_id))?;
+        let task = self.task_repo.get(task_id).await?;
+        actor.authorize_edit(&task.owner_id, format!("Task {}", task_id))?;
+
+        self.complete_task_with_review(task_id, kid_id, review).await
+    }
+}
+
+impl TaskCompletionWorkflow {
+    /// Add a task completion to `tx` without committing it, so callers can
+    /// make further changes that succeed or fail together with the payout
+    ///
+    /// Returns the ledger entry the transaction will create.
+    pub async fn complete_in(
+        &self,
+        tx: &mut Transaction,
+        task_id: Uuid,
+        kid_id: Uuid,
+        review: Option<CompletionReview>,
+    ) -> Result<LedgerEntry> {
+        let (task, entry, budgets) = self.prepare(task_id, kid_id, review).await?;
+        completion_in(tx, &self.kid_repo, &self.task_repo, &self.ledger_repo, &task, &entry, &budgets)?;
+        Ok(entry)
+    }
+}
+
+/// Add saving a checked completion to `tx`, which fails if the kid or the
+/// task was archived, the task saved by someone else, or one of the blocking
+/// `budgets` used up by other payouts, before it commits
+fn completion_in(
+    tx: &mut Transaction,
+    kids: &KidRepository,
+    tasks: &TaskRepository,
+    ledger: &LedgerRepository,
+    task: &Task,
+    entry: &LedgerEntry,
+    budgets: &[BudgetStatus],
+) -> Result<()> {
+    kids.ensure_active_in(tx, entry.kid_id)?;
+    tasks.ensure_active_in(tx, task.id)?;
+    for status in budgets {
+        ledger.ensure_within_budget_in(tx, entry.kid_id, status, entry.amount)?;
     }
+    ledger.create_entry_in(tx, entry)?;
+    tasks.update_in(tx, task)
 }
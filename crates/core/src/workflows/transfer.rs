@@ -0,0 +1,129 @@
+use crate::db::{KidRepository, LedgerRepository, Repository, Transaction, TransferRepository};
+use crate::models::{Kid, LedgerEntry, Transfer};
+use crate::policy::Actor;
+use crate::error::{Error, Result};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// Moves money between two kids of a household
+///
+/// A transfer writes a debit on the sender's ledger and a credit on the
+/// receiver's in one transaction, and only if the sender's balance covers it
+/// then. Transfers that need a parent's approval are saved as pending and
+/// moved when approved.
+pub struct TransferWorkflow {
+    transfer_repo: TransferRepository,
+    kid_repo: KidRepository,
+    ledger_repo: LedgerRepository,
+}
+
+impl TransferWorkflow {
+    pub fn new(transfer_repo: TransferRepository, kid_repo: KidRepository, ledger_repo: LedgerRepository) -> Self {
+        Self { transfer_repo, kid_repo, ledger_repo }
+    }
+
+    /// Move `amount` from one of a household's kids to another, or, with
+    /// `needs_approval`, save it for a parent to approve
+    ///
+    /// Kid logins can only send their own money, and always need approval.
+    pub async fn transfer(
+        &self,
+        actor: &Actor,
+        from_kid_id: Uuid,
+        to_kid_id: Uuid,
+        amount: Decimal,
+        note: Option<String>,
+        needs_approval: bool,
+    ) -> Result<Transfer> {
+        let sender = self.kid_repo.get(from_kid_id).await?;
+        actor.authorize_request(&sender)?;
+        let needs_approval = needs_approval || matches!(actor, Actor::Kid { .. });
+        let mut transfer = Transfer::new(sender.owner_id, from_kid_id, to_kid_id, amount, note)?;
+        let (from, to) = self.kids(&transfer).await?;
+
+        let mut tx = self.transfer_repo.begin();
+        if needs_approval {
+            // Checked again when it's approved
+            if self.ledger_repo.get_ledger(from.id).await?.balance < amount {
+                return Err(Error::Validation(format!("{}'s balance doesn't cover ${:.2}", from.name, amount)));
+            }
+            self.transfer_repo.create_in(&mut tx, &transfer)?;
+        } else {
+            transfer.complete()?;
+            self.transfer_repo.create_in(&mut tx, &transfer)?;
+            self.move_in(&mut tx, &transfer, &from, &to)?;
+        }
+        tx.commit().await?;
+        Ok(transfer)
+    }
+
+    /// Approve a pending transfer, moving the money
+    pub async fn approve(&self, actor: &Actor, transfer_id: Uuid) -> Result<Transfer> {
+        let mut transfer = self.editable_transfer(actor, transfer_id).await?;
+        transfer.complete()?;
+        let (from, to) = self.kids(&transfer).await?;
+
+        let mut tx = self.transfer_repo.begin();
+        self.transfer_repo.review_in(&mut tx, &transfer)?;
+        self.move_in(&mut tx, &transfer, &from, &to)?;
+        tx.commit().await?;
+        Ok(transfer)
+    }
+
+    /// Turn down a pending transfer without moving anything
+    pub async fn decline(&self, actor: &Actor, transfer_id: Uuid) -> Result<Transfer> {
+        let mut transfer = self.editable_transfer(actor, transfer_id).await?;
+        transfer.decline()?;
+
+        let mut tx = self.transfer_repo.begin();
+        self.transfer_repo.review_in(&mut tx, &transfer)?;
+        tx.commit().await?;
+        Ok(transfer)
+    }
+
+    pub async fn list_pending(&self, actor: &Actor) -> Result<Vec<Transfer>> {
+        self.transfer_repo.list_pending(actor.readable_household()?).await
+    }
+
+    pub async fn list_for_kid(&self, actor: &Actor, kid_id: Uuid) -> Result<Vec<Transfer>> {
+        actor.authorize_kid(&self.kid_repo.get(kid_id).await?)?;
+        self.transfer_repo.list_by_kid(kid_id).await
+    }
+
+    /// The sending and receiving kids
+    async fn kids(&self, transfer: &Transfer) -> Result<(Kid, Kid)> {
+        Ok((self.kid(transfer, transfer.from_kid_id).await?, self.kid(transfer, transfer.to_kid_id).await?))
+    }
+
+    // Both kids must be active and in the transfer's household
+    async fn kid(&self, transfer: &Transfer, kid_id: Uuid) -> Result<Kid> {
+        let kid = self.kid_repo.get(kid_id).await?;
+        if kid.owner_id != transfer.owner_id {
+            return Err(Error::NotFound(format!("Kid with id {}", kid_id)));
+        }
+        if kid.is_archived() {
+            return Err(Error::Validation(format!("{} is archived", kid.name)));
+        }
+        Ok(kid)
+    }
+
+    fn move_in(&self, tx: &mut Transaction, transfer: &Transfer, from: &Kid, to: &Kid) -> Result<()> {
+        let note = transfer.note.as_ref().map(|n| format!(": {}", n)).unwrap_or_default();
+        self.kid_repo.ensure_active_in(tx, from.id)?;
+        self.kid_repo.ensure_active_in(tx, to.id)?;
+        self.ledger_repo.ensure_balance_in(tx, from.id, transfer.amount)?;
+        for entry in [
+            LedgerEntry::transfer(from.id, -transfer.amount, format!("Transfer to {}{}", to.name, note), transfer.id),
+            LedgerEntry::transfer(to.id, transfer.amount, format!("Transfer from {}{}", from.name, note), transfer.id),
+        ] {
+            self.ledger_repo.create_entry_in(tx, &entry)?;
+        }
+        Ok(())
+    }
+
+    async fn editable_transfer(&self, actor: &Actor, transfer_id: Uuid) -> Result<Transfer> {
+        let transfer = self.transfer_repo.get(transfer_id).await?;
+        actor.authorize_edit(&transfer.owner_id, format!("Transfer {}", transfer_id))?;
+        Ok(transfer)
+    }
+}
@@ -0,0 +1,151 @@
+use crate::db::{KidRepository, LedgerRepository, Repository, Transaction, WithdrawalRepository};
+use crate::models::{LedgerEntry, PaymentMethod, Withdrawal, WithdrawalStatus};
+use crate::policy::Actor;
+use crate::error::{Error, Result};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// What a kid has taken out of their balance: still owed by the parents, and
+/// already handed over
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PayoutSummary {
+    pub kid_id: Uuid,
+    pub name: String,
+    /// Balance left after open withdrawals
+    pub balance: Decimal,
+    /// Requested, waiting for approval
+    pub requested: Decimal,
+    /// Approved, waiting to be paid
+    pub approved: Decimal,
+    /// Requested and approved: what the parents owe
+    pub owed: Decimal,
+    /// Handed over, all time
+    pub paid: Decimal,
+}
+
+/// Kids ask to take money out of their balance; parents approve it, then
+/// mark it paid (or decline it)
+///
+/// Every step posts a payout entry to the kid's ledger. Requesting takes the
+/// amount off the balance, so it can't be spent twice while a parent gets to
+/// it; declining puts it back. Approving and paying post zero-amount entries
+/// that record when it happened.
+pub struct WithdrawalWorkflow {
+    withdrawal_repo: WithdrawalRepository,
+    kid_repo: KidRepository,
+    ledger_repo: LedgerRepository,
+}
+
+impl WithdrawalWorkflow {
+    pub fn new(withdrawal_repo: WithdrawalRepository, kid_repo: KidRepository, ledger_repo: LedgerRepository) -> Self {
+        Self { withdrawal_repo, kid_repo, ledger_repo }
+    }
+
+    /// Ask to withdraw `amount` of a kid's balance
+    ///
+    /// Fails if the balance doesn't cover it when the request is saved.
+    pub async fn request(&self, actor: &Actor, kid_id: Uuid, amount: Decimal, note: Option<String>) -> Result<Withdrawal> {
+        let kid = self.kid_repo.get(kid_id).await?;
+        actor.authorize_request(&kid)?;
+        if kid.is_archived() {
+            return Err(Error::Validation("Archived kids can't withdraw money".to_string()));
+        }
+        let withdrawal = Withdrawal::new(kid.owner_id, kid_id, amount, note)?;
+
+        let mut tx = self.withdrawal_repo.begin();
+        self.kid_repo.ensure_active_in(&mut tx, kid_id)?;
+        self.ledger_repo.ensure_balance_in(&mut tx, kid_id, amount)?;
+        self.withdrawal_repo.create_in(&mut tx, &withdrawal)?;
+        self.post_in(&mut tx, &withdrawal, -amount, format!("Withdrawal of ${:.2} requested", amount))?;
+        tx.commit().await?;
+        Ok(withdrawal)
+    }
+
+    pub async fn approve(&self, actor: &Actor, withdrawal_id: Uuid) -> Result<Withdrawal> {
+        let mut withdrawal = self.editable_withdrawal(actor, withdrawal_id).await?;
+        let from = withdrawal.status;
+        withdrawal.approve()?;
+
+        let description = format!("Withdrawal of ${:.2} approved", withdrawal.amount);
+        self.save(&withdrawal, from, Decimal::ZERO, description).await?;
+        Ok(withdrawal)
+    }
+
+    /// Record that an approved withdrawal was handed over
+    pub async fn mark_paid(&self, actor: &Actor, withdrawal_id: Uuid, method: PaymentMethod) -> Result<Withdrawal> {
+        let mut withdrawal = self.editable_withdrawal(actor, withdrawal_id).await?;
+        let from = withdrawal.status;
+        withdrawal.mark_paid(method)?;
+
+        let description = format!("Withdrawal of ${:.2} paid by {}", withdrawal.amount, method.label());
+        self.save(&withdrawal, from, Decimal::ZERO, description).await?;
+        Ok(withdrawal)
+    }
+
+    /// Turn down an unpaid withdrawal, returning the amount to the balance
+    pub async fn decline(&self, actor: &Actor, withdrawal_id: Uuid) -> Result<Withdrawal> {
+        let mut withdrawal = self.editable_withdrawal(actor, withdrawal_id).await?;
+        let from = withdrawal.status;
+        withdrawal.decline()?;
+
+        let description = format!("Withdrawal of ${:.2} declined", withdrawal.amount);
+        self.save(&withdrawal, from, withdrawal.amount, description).await?;
+        Ok(withdrawal)
+    }
+
+    /// Withdrawals waiting for a parent to approve or pay them, oldest first
+    pub async fn list_open(&self, actor: &Actor) -> Result<Vec<Withdrawal>> {
+        self.withdrawal_repo.list_open(actor.readable_household()?).await
+    }
+
+    pub async fn list_for_kid(&self, actor: &Actor, kid_id: Uuid) -> Result<Vec<Withdrawal>> {
+        actor.authorize_kid(&self.kid_repo.get(kid_id).await?)?;
+        self.withdrawal_repo.list_by_kid(kid_id).await
+    }
+
+    /// Owed and paid amounts for each of the household's kids
+    pub async fn summary(&self, actor: &Actor) -> Result<Vec<PayoutSummary>> {
+        let owner_id = actor.readable_household()?;
+        let withdrawals = self.withdrawal_repo.list_by_owner(owner_id).await?;
+        let mut summaries = Vec::new();
+        for kid in self.kid_repo.list_by_owner(owner_id).await? {
+            let total = |status: WithdrawalStatus| -> Decimal {
+                withdrawals
+                    .iter()
+                    .filter(|w| w.kid_id == kid.id && w.status == status)
+                    .map(|w| w.amount)
+                    .sum()
+            };
+            let (requested, approved) = (total(WithdrawalStatus::Requested), total(WithdrawalStatus::Approved));
+            summaries.push(PayoutSummary {
+                kid_id: kid.id,
+                balance: self.ledger_repo.get_ledger(kid.id).await?.balance,
+                requested,
+                approved,
+                owed: requested + approved,
+                paid: total(WithdrawalStatus::Paid),
+                name: kid.name,
+            });
+        }
+        Ok(summaries)
+    }
+
+    async fn save(&self, withdrawal: &Withdrawal, from: WithdrawalStatus, amount: Decimal, description: String) -> Result<()> {
+        let mut tx = self.withdrawal_repo.begin();
+        self.withdrawal_repo.update_in(&mut tx, withdrawal, from)?;
+        self.post_in(&mut tx, withdrawal, amount, description)?;
+        tx.commit().await
+    }
+
+    fn post_in(&self, tx: &mut Transaction, withdrawal: &Withdrawal, amount: Decimal, description: String) -> Result<()> {
+        let entry = LedgerEntry::payout(withdrawal.kid_id, amount, description, withdrawal.id);
+        self.ledger_repo.create_entry_in(tx, &entry)
+    }
+
+    async fn editable_withdrawal(&self, actor: &Actor, withdrawal_id: Uuid) -> Result<Withdrawal> {
+        let withdrawal = self.withdrawal_repo.get(withdrawal_id).await?;
+        actor.authorize_edit(&withdrawal.owner_id, format!("Withdrawal {}", withdrawal_id))?;
+        Ok(withdrawal)
+    }
+}
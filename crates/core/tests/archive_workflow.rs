@@ -0,0 +1,184 @@
+use loaa_core::attachments::LocalFsStore;
+use loaa_core::db::{
+    AttachmentRepository, ClaimRepository, KidRepository, LedgerRepository, Repository, TaskRepository,
+    TransferRepository, UserRepository,
+};
+use loaa_core::models::{Cadence, EntryType, HouseholdRole, Kid, Task, TaskClaim, User};
+use loaa_core::policy::Actor;
+use loaa_core::workflows::{ArchiveWorkflow, AttachmentWorkflow, TaskCompletionWorkflow, TransferWorkflow};
+use loaa_core::AttachmentStore;
+use chrono::{Duration, Utc};
+use rust_decimal_macros::dec;
+use std::io::Cursor;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+mod common;
+use common::init_database;
+
+// NOTE: Tests require a running SurrealDB server on 127.0.0.1:8000
+// Run `just db` before running tests, or run them on SQLite with
+// LOAA_TEST_DB_MODE=sqlite
+
+struct TestContext {
+    _dir: TempDir,
+    archive: ArchiveWorkflow,
+    attachments: AttachmentWorkflow,
+    completion: TaskCompletionWorkflow,
+    transfers: TransferWorkflow,
+    kid_repo: KidRepository,
+    task_repo: TaskRepository,
+    ledger_repo: LedgerRepository,
+    claim_repo: ClaimRepository,
+    user_repo: UserRepository,
+    store: LocalFsStore,
+}
+
+async fn setup_test() -> TestContext {
+    let db_url = std::env::var("LOAA_DB_URL").unwrap_or_else(|_| "127.0.0.1:8000".to_string());
+    let database = init_database(&db_url)
+        .await
+        .expect("Failed to initialize database");
+
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let store = LocalFsStore::new(dir.path()).unwrap();
+
+    TestContext {
+        archive: ArchiveWorkflow::new(
+            KidRepository::new(database.client.clone()),
+            TaskRepository::new(database.client.clone()),
+            AttachmentRepository::new(database.client.clone()),
+            Arc::new(store.clone()),
+            Duration::days(30),
+        ),
+        attachments: AttachmentWorkflow::new(
+            AttachmentRepository::new(database.client.clone()),
+            KidRepository::new(database.client.clone()),
+            LedgerRepository::new(database.client.clone()),
+            Arc::new(store.clone()),
+            1024 * 1024,
+        ),
+        completion: TaskCompletionWorkflow::new(
+            TaskRepository::new(database.client.clone()),
+            KidRepository::new(database.client.clone()),
+            LedgerRepository::new(database.client.clone()),
+        ),
+        transfers: TransferWorkflow::new(
+            TransferRepository::new(database.client.clone()),
+            KidRepository::new(database.client.clone()),
+            LedgerRepository::new(database.client.clone()),
+        ),
+        kid_repo: KidRepository::new(database.client.clone()),
+        task_repo: TaskRepository::new(database.client.clone()),
+        ledger_repo: LedgerRepository::new(database.client.clone()),
+        claim_repo: ClaimRepository::new(database.client.clone()),
+        user_repo: UserRepository::new(database.client.clone()),
+        _dir: dir,
+        store,
+    }
+}
+
+fn photo() -> Vec<u8> {
+    let image = image::RgbImage::from_pixel(64, 48, image::Rgb([30, 144, 255]));
+    let mut out = Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgb8(image)
+        .write_to(&mut out, image::ImageFormat::Png)
+        .unwrap();
+    out.into_inner()
+}
+
+async fn family(ctx: &TestContext) -> (Actor, Kid, Task) {
+    let household_id = uuid::Uuid::new_v4().to_string();
+    let actor = Actor::member("mom", household_id.clone(), HouseholdRole::Owner);
+    let kid = ctx.kid_repo.create(Kid::new("Pua".to_string(), household_id.clone()).unwrap()).await.unwrap();
+    let task = ctx.task_repo.create(
+        Task::new("Water plants".to_string(), "".to_string(), dec!(1.00), Cadence::Daily, household_id).unwrap()
+    ).await.unwrap();
+    (actor, kid, task)
+}
+
+#[tokio::test]
+async fn test_archive_hides_but_keeps_history() {
+    let ctx = setup_test().await;
+    let (actor, kid, task) = family(&ctx).await;
+    let household_id = actor.household_id().unwrap();
+    ctx.completion.complete_task(task.id, kid.id).await.unwrap();
+
+    ctx.archive.archive_task(&actor, task.id, Utc::now()).await.unwrap();
+    ctx.archive.archive_kid(&actor, kid.id, Utc::now()).await.unwrap();
+
+    assert!(ctx.kid_repo.list_by_owner(household_id).await.unwrap().is_empty());
+    assert!(ctx.task_repo.list_by_owner(household_id).await.unwrap().is_empty());
+    let (kids, tasks) = ctx.archive.list_archived(&actor).await.unwrap();
+    assert_eq!((kids.len(), tasks.len()), (1, 1));
+
+    // History is untouched, but archived records can't earn anything new
+    assert_eq!(ctx.ledger_repo.get_ledger(kid.id).await.unwrap().balance, dec!(1.00));
+    assert!(ctx.completion.complete_task(task.id, kid.id).await.is_err());
+
+    ctx.archive.restore_kid(&actor, kid.id, Utc::now()).await.unwrap();
+    ctx.archive.restore_task(&actor, task.id, Utc::now()).await.unwrap();
+    assert_eq!(ctx.kid_repo.list_by_owner(household_id).await.unwrap().len(), 1);
+    assert_eq!(ctx.task_repo.list_by_owner(household_id).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_restore_window() {
+    let ctx = setup_test().await;
+    let (actor, kid, _) = family(&ctx).await;
+
+    let archived_at = Utc::now() - Duration::days(31);
+    ctx.archive.archive_kid(&actor, kid.id, archived_at).await.unwrap();
+    assert!(ctx.archive.restore_kid(&actor, kid.id, Utc::now()).await.is_err());
+
+    // The scheduled purge removes it once the window has passed
+    let purged = ctx.archive.purge_expired(Utc::now()).await.unwrap();
+    assert!(purged >= 1);
+    assert!(ctx.kid_repo.get(kid.id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_purge_removes_kid_and_dependents() {
+    let ctx = setup_test().await;
+    let (actor, kid, task) = family(&ctx).await;
+    let household_id = actor.household_id().unwrap().to_string();
+
+    let entry = ctx.completion.complete_task(task.id, kid.id).await.unwrap();
+    let attachment = ctx.attachments
+        .upload(&actor, kid.id, Some(task.id), Some(entry.id), photo())
+        .await
+        .unwrap();
+    ctx.claim_repo
+        .create(TaskClaim::new(household_id.clone(), kid.id, task.id, None, None))
+        .await
+        .unwrap();
+    let login = ctx.user_repo
+        .create(User::new_kid(format!("pua-{}", kid.id.simple()), kid.id).unwrap())
+        .await
+        .unwrap();
+
+    let sister = ctx.kid_repo.create(Kid::new("Lani".to_string(), household_id.clone()).unwrap()).await.unwrap();
+    let gift = ctx.transfers.transfer(&actor, kid.id, sister.id, dec!(0.50), None, false).await.unwrap();
+
+    // Only archived kids can be purged
+    assert!(ctx.archive.purge_kid(&actor, kid.id).await.is_err());
+    ctx.archive.archive_kid(&actor, kid.id, Utc::now()).await.unwrap();
+    ctx.archive.purge_kid(&actor, kid.id).await.unwrap();
+
+    assert!(ctx.kid_repo.get(kid.id).await.is_err());
+    assert!(ctx.ledger_repo.list_entries(kid.id).await.unwrap().is_empty());
+    assert!(ctx.claim_repo.list_by_kid(kid.id).await.unwrap().is_empty());
+    assert!(ctx.user_repo.get(login.id).await.is_err());
+    assert!(ctx.store.get(&attachment.storage_key()).is_err());
+    assert!(ctx.store.get(&attachment.thumbnail_key()).is_err());
+
+    // The sister keeps the money, no longer linked to the deleted transfer
+    let sisters = ctx.ledger_repo.get_ledger(sister.id).await.unwrap();
+    assert_eq!(sisters.balance, dec!(0.50));
+    assert_eq!(sisters.entries[0].entry_type, EntryType::Transfer);
+    assert_eq!(sisters.entries[0].transfer_id, None);
+    assert!(ctx.transfers.list_for_kid(&actor, sister.id).await.unwrap().iter().all(|t| t.id != gift.id));
+
+    // The task belongs to the household, not the kid
+    assert!(ctx.task_repo.get(task.id).await.is_ok());
+}
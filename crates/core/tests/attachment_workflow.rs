@@ -0,0 +1,167 @@
+use loaa_core::attachments::LocalFsStore;
+use loaa_core::db::{AttachmentRepository, KidRepository, LedgerRepository, Repository, TaskRepository};
+use loaa_core::models::{Cadence, HouseholdRole, Kid, Task};
+use loaa_core::workflows::{AttachmentWorkflow, TaskCompletionWorkflow};
+use loaa_core::{Actor, Error};
+use chrono::{Duration, Utc};
+use rust_decimal_macros::dec;
+use std::io::Cursor;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+mod common;
+use common::init_database;
+
+// NOTE: Tests require a running SurrealDB server on 127.0.0.1:8000
+// Run `just db` before running tests, or run them on SQLite with
+// LOAA_TEST_DB_MODE=sqlite
+
+struct TestContext {
+    _dir: TempDir,
+    workflow: AttachmentWorkflow,
+    completion: TaskCompletionWorkflow,
+    kid_repo: KidRepository,
+    task_repo: TaskRepository,
+    store: LocalFsStore,
+}
+
+async fn setup_test() -> TestContext {
+    let db_url = std::env::var("LOAA_DB_URL").unwrap_or_else(|_| "127.0.0.1:8000".to_string());
+    let database = init_database(&db_url)
+        .await
+        .expect("Failed to initialize database");
+
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let store = LocalFsStore::new(dir.path()).unwrap();
+
+    let workflow = AttachmentWorkflow::new(
+        AttachmentRepository::new(database.client.clone()),
+        KidRepository::new(database.client.clone()),
+        LedgerRepository::new(database.client.clone()),
+        Arc::new(store.clone()),
+        1024 * 1024,
+    );
+    let completion = TaskCompletionWorkflow::new(
+        TaskRepository::new(database.client.clone()),
+        KidRepository::new(database.client.clone()),
+        LedgerRepository::new(database.client.clone()),
+    );
+
+    TestContext {
+        _dir: dir,
+        workflow,
+        completion,
+        kid_repo: KidRepository::new(database.client.clone()),
+        task_repo: TaskRepository::new(database.client.clone()),
+        store,
+    }
+}
+
+fn parent(owner_id: &str) -> Actor {
+    Actor::member("parent", owner_id, HouseholdRole::Owner)
+}
+
+fn photo() -> Vec<u8> {
+    let image = image::RgbImage::from_pixel(640, 480, image::Rgb([30, 144, 255]));
+    let mut out = Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgb8(image)
+        .write_to(&mut out, image::ImageFormat::Png)
+        .unwrap();
+    out.into_inner()
+}
+
+#[tokio::test]
+async fn test_upload_link_and_read() {
+    let ctx = setup_test().await;
+    let owner_id = uuid::Uuid::new_v4().to_string();
+
+    let kid = ctx.kid_repo.create(Kid::new("Maya".to_string(), owner_id.clone()).unwrap()).await.unwrap();
+    let task = ctx.task_repo.create(
+        Task::new("Clean room".to_string(), "".to_string(), dec!(2.00), Cadence::Daily, owner_id.clone()).unwrap()
+    ).await.unwrap();
+
+    let attachment = ctx.workflow
+        .upload(&parent(&owner_id), kid.id, Some(task.id), None, photo())
+        .await
+        .unwrap();
+    assert_eq!(attachment.content_type, "image/png");
+    assert!(attachment.ledger_entry_id.is_none());
+
+    let entry = ctx.completion.complete_task(task.id, kid.id).await.unwrap();
+    let linked = ctx.workflow
+        .link_to_completion(&parent(&owner_id), attachment.id, entry.id)
+        .await
+        .unwrap();
+    assert_eq!(linked.ledger_entry_id, Some(entry.id));
+    assert!(linked.linked_at.is_some());
+
+    let (_, bytes) = ctx.workflow.read(&parent(&owner_id), attachment.id).await.unwrap();
+    assert_eq!(bytes, photo());
+    let thumbnail = ctx.workflow.read_thumbnail(&parent(&owner_id), attachment.id).await.unwrap();
+    assert!(thumbnail.len() < bytes.len());
+
+    let listed = ctx.workflow.list_for_kid(&parent(&owner_id), kid.id).await.unwrap();
+    assert_eq!(listed.len(), 1);
+}
+
+#[tokio::test]
+async fn test_other_owners_cannot_read_or_upload() {
+    let ctx = setup_test().await;
+    let owner_id = uuid::Uuid::new_v4().to_string();
+    let intruder = uuid::Uuid::new_v4().to_string();
+
+    let kid = ctx.kid_repo.create(Kid::new("Noa".to_string(), owner_id.clone()).unwrap()).await.unwrap();
+    let attachment = ctx.workflow.upload(&parent(&owner_id), kid.id, None, None, photo()).await.unwrap();
+
+    let read = ctx.workflow.read(&parent(&intruder), attachment.id).await;
+    assert!(matches!(read, Err(Error::Forbidden(_))));
+    let thumb = ctx.workflow.read_thumbnail(&parent(&intruder), attachment.id).await;
+    assert!(matches!(thumb, Err(Error::Forbidden(_))));
+
+    let upload = ctx.workflow.upload(&parent(&intruder), kid.id, None, None, photo()).await;
+    assert!(matches!(upload, Err(Error::Forbidden(_))));
+
+    // Kid logins only see their own photos
+    let sibling = ctx.kid_repo.create(Kid::new("Kai".to_string(), owner_id.clone()).unwrap()).await.unwrap();
+    let peek = ctx.workflow.read(&Actor::kid(owner_id.clone(), sibling.id), attachment.id).await;
+    assert!(matches!(peek, Err(Error::Forbidden(_))));
+    assert!(ctx.workflow.read(&Actor::kid(owner_id.clone(), kid.id), attachment.id).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_rejects_non_images_and_oversized_uploads() {
+    let ctx = setup_test().await;
+    let owner_id = uuid::Uuid::new_v4().to_string();
+    let kid = ctx.kid_repo.create(Kid::new("Kai".to_string(), owner_id.clone()).unwrap()).await.unwrap();
+
+    let text = ctx.workflow.upload(&parent(&owner_id), kid.id, None, None, b"hello".to_vec()).await;
+    assert!(matches!(text, Err(Error::Validation(_))));
+
+    let huge = ctx.workflow.upload(&parent(&owner_id), kid.id, None, None, vec![0; 2 * 1024 * 1024]).await;
+    assert!(matches!(huge, Err(Error::Validation(_))));
+}
+
+#[tokio::test]
+async fn test_purge_expired_removes_record_and_files() {
+    let ctx = setup_test().await;
+    let owner_id = uuid::Uuid::new_v4().to_string();
+    let kid = ctx.kid_repo.create(Kid::new("Leilani".to_string(), owner_id.clone()).unwrap()).await.unwrap();
+    let task = ctx.task_repo.create(
+        Task::new("Feed fish".to_string(), "".to_string(), dec!(0.50), Cadence::Daily, owner_id.clone()).unwrap()
+    ).await.unwrap();
+    let entry = ctx.completion.complete_task(task.id, kid.id).await.unwrap();
+
+    let attachment = ctx.workflow
+        .upload(&parent(&owner_id), kid.id, Some(task.id), Some(entry.id), photo())
+        .await
+        .unwrap();
+
+    // Nothing expires within the retention window
+    ctx.workflow.purge_expired(Duration::days(30), Utc::now()).await.unwrap();
+    assert!(ctx.workflow.get(&parent(&owner_id), attachment.id).await.is_ok());
+
+    ctx.workflow.purge_expired(Duration::days(30), Utc::now() + Duration::days(31)).await.unwrap();
+    assert!(matches!(ctx.workflow.get(&parent(&owner_id), attachment.id).await, Err(Error::NotFound(_))));
+    assert!(loaa_core::AttachmentStore::get(&ctx.store, &attachment.storage_key()).is_err());
+    assert!(loaa_core::AttachmentStore::get(&ctx.store, &attachment.thumbnail_key()).is_err());
+}
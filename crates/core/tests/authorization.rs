@@ -0,0 +1,188 @@
+use loaa_core::attachments::LocalFsStore;
+use loaa_core::db::{
+    AttachmentRepository, HouseholdRepository, KidRepository, LedgerRepository, Repository, TaskRepository,
+};
+use loaa_core::models::{Cadence, HouseholdRole, Kid, Task, TaskPatch};
+use loaa_core::policy::{Actor, Policy};
+use loaa_core::workflows::{ArchiveWorkflow, HouseholdWorkflow, TaskCompletionWorkflow};
+use loaa_core::Error;
+use chrono::{Duration, Utc};
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+mod common;
+use common::init_database;
+
+// NOTE: Tests require a running SurrealDB server on 127.0.0.1:8000
+// Run `just db` before running tests, or run them on SQLite with
+// LOAA_TEST_DB_MODE=sqlite
+//
+// Each test covers the checks behind an MCP tool / web server function that
+// takes a record ID: a parent from another household must get Forbidden.
+
+struct TestContext {
+    _dir: TempDir,
+    policy: Policy,
+    archive: ArchiveWorkflow,
+    completion: TaskCompletionWorkflow,
+    households: HouseholdWorkflow,
+    kid_repo: KidRepository,
+    task_repo: TaskRepository,
+    ledger_repo: LedgerRepository,
+}
+
+async fn setup_test() -> TestContext {
+    let db_url = std::env::var("LOAA_DB_URL").unwrap_or_else(|_| "127.0.0.1:8000".to_string());
+    let database = init_database(&db_url)
+        .await
+        .expect("Failed to initialize database");
+
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let store = LocalFsStore::new(dir.path()).unwrap();
+
+    TestContext {
+        archive: ArchiveWorkflow::new(
+            KidRepository::new(database.client.clone()),
+            TaskRepository::new(database.client.clone()),
+            AttachmentRepository::new(database.client.clone()),
+            Arc::new(store),
+            Duration::days(30),
+        ),
+        _dir: dir,
+        policy: Policy::new(
+            KidRepository::new(database.client.clone()),
+            TaskRepository::new(database.client.clone()),
+            LedgerRepository::new(database.client.clone()),
+            HouseholdWorkflow::new(HouseholdRepository::new(database.client.clone())),
+        ),
+        completion: TaskCompletionWorkflow::new(
+            TaskRepository::new(database.client.clone()),
+            KidRepository::new(database.client.clone()),
+            LedgerRepository::new(database.client.clone()),
+        ),
+        households: HouseholdWorkflow::new(HouseholdRepository::new(database.client.clone())),
+        kid_repo: KidRepository::new(database.client.clone()),
+        task_repo: TaskRepository::new(database.client.clone()),
+        ledger_repo: LedgerRepository::new(database.client.clone()),
+    }
+}
+
+/// A kid and a task in a fresh household, plus a parent of another household
+async fn two_families(ctx: &TestContext) -> (Actor, Actor, Kid, Task) {
+    let owner = ctx.policy.actor_for_user(&uuid::Uuid::new_v4().to_string()).await.unwrap();
+    let intruder = ctx.policy.actor_for_user(&uuid::Uuid::new_v4().to_string()).await.unwrap();
+    let household_id = owner.household_id().unwrap().to_string();
+
+    let kid = ctx.kid_repo.create(Kid::new("Malia".to_string(), household_id.clone()).unwrap()).await.unwrap();
+    let task = ctx.task_repo.create(
+        Task::new("Sweep lanai".to_string(), "".to_string(), dec!(2.00), Cadence::Daily, household_id).unwrap()
+    ).await.unwrap();
+    (owner, intruder, kid, task)
+}
+
+#[tokio::test]
+async fn test_get_ledger_is_denied_across_households() {
+    let ctx = setup_test().await;
+    let (owner, intruder, kid, _) = two_families(&ctx).await;
+
+    assert!(ctx.policy.ledger(&owner, kid.id).await.is_ok());
+    assert!(matches!(ctx.policy.ledger(&intruder, kid.id).await, Err(Error::Forbidden(_))));
+    assert!(matches!(ctx.policy.kid(&intruder, kid.id).await, Err(Error::Forbidden(_))));
+}
+
+#[tokio::test]
+async fn test_complete_task_is_denied_across_households() {
+    let ctx = setup_test().await;
+    let (owner, intruder, kid, task) = two_families(&ctx).await;
+
+    let denied = ctx.completion.complete_task_as(&intruder, task.id, kid.id, None).await;
+    assert!(matches!(denied, Err(Error::Forbidden(_))));
+    assert!(ctx.ledger_repo.list_entries(kid.id).await.unwrap().is_empty());
+
+    // A task from another household can't be paid to this household's kid either
+    let (_, _, _, other_task) = two_families(&ctx).await;
+    let mixed = ctx.completion.complete_task_as(&owner, other_task.id, kid.id, None).await;
+    assert!(matches!(mixed, Err(Error::Forbidden(_))));
+
+    ctx.completion.complete_task_as(&owner, task.id, kid.id, None).await.unwrap();
+    assert_eq!(ctx.ledger_repo.get_ledger(kid.id).await.unwrap().balance, dec!(2.00));
+}
+
+#[tokio::test]
+async fn test_adjust_balance_is_denied_across_households() {
+    let ctx = setup_test().await;
+    let (owner, intruder, kid, _) = two_families(&ctx).await;
+
+    let denied = ctx.policy.adjust_balance(&intruder, kid.id, dec!(100.00), "Gift".to_string()).await;
+    assert!(matches!(denied, Err(Error::Forbidden(_))));
+
+    ctx.policy.adjust_balance(&owner, kid.id, dec!(5.00), "Birthday".to_string()).await.unwrap();
+    assert_eq!(ctx.ledger_repo.get_ledger(kid.id).await.unwrap().balance, dec!(5.00));
+}
+
+#[tokio::test]
+async fn test_task_changes_are_denied_across_households() {
+    let ctx = setup_test().await;
+    let (owner, intruder, _, task) = two_families(&ctx).await;
+
+    assert!(matches!(ctx.policy.task(&intruder, task.id).await, Err(Error::Forbidden(_))));
+
+    let mut renamed = task.clone();
+    renamed.name = "Hijacked".to_string();
+    assert!(matches!(ctx.policy.update_task(&intruder, renamed).await, Err(Error::Forbidden(_))));
+    let patch = TaskPatch { name: Some("Hijacked".to_string()), ..Default::default() };
+    assert!(matches!(ctx.policy.patch_task(&intruder, task.id, &patch, None).await, Err(Error::Forbidden(_))));
+    assert!(matches!(ctx.archive.archive_task(&intruder, task.id, Utc::now()).await, Err(Error::Forbidden(_))));
+    assert_eq!(ctx.task_repo.get(task.id).await.unwrap().name, "Sweep lanai");
+
+    // Updates can't move a task into another household
+    let mut moved = task.clone();
+    moved.owner_id = intruder.household_id().unwrap().to_string();
+    let updated = ctx.policy.update_task(&owner, moved).await.unwrap();
+    assert_eq!(updated.owner_id, owner.household_id().unwrap());
+
+    ctx.archive.archive_task(&owner, task.id, Utc::now()).await.unwrap();
+    assert!(matches!(ctx.archive.restore_task(&intruder, task.id, Utc::now()).await, Err(Error::Forbidden(_))));
+    assert!(matches!(ctx.archive.purge_task(&intruder, task.id).await, Err(Error::Forbidden(_))));
+    ctx.archive.purge_task(&owner, task.id).await.unwrap();
+    assert!(ctx.task_repo.get(task.id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_delete_kid_is_denied_across_households() {
+    let ctx = setup_test().await;
+    let (owner, intruder, kid, _) = two_families(&ctx).await;
+
+    assert!(matches!(ctx.archive.archive_kid(&intruder, kid.id, Utc::now()).await, Err(Error::Forbidden(_))));
+    assert!(!ctx.kid_repo.get(kid.id).await.unwrap().is_archived());
+
+    ctx.archive.archive_kid(&owner, kid.id, Utc::now()).await.unwrap();
+    assert!(matches!(ctx.archive.restore_kid(&intruder, kid.id, Utc::now()).await, Err(Error::Forbidden(_))));
+    assert!(matches!(ctx.archive.purge_kid(&intruder, kid.id).await, Err(Error::Forbidden(_))));
+    ctx.archive.purge_kid(&owner, kid.id).await.unwrap();
+    assert!(ctx.kid_repo.get(kid.id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_viewers_can_read_but_not_change() {
+    let ctx = setup_test().await;
+    let (owner, _, kid, task) = two_families(&ctx).await;
+    let Actor::Member { user_id: owner_user_id, .. } = &owner else { unreachable!() };
+
+    let grandma = uuid::Uuid::new_v4().to_string();
+    let household = ctx.households.household_for_user(owner_user_id).await.unwrap();
+    ctx.households.join(&grandma, household.id, HouseholdRole::Viewer).await.unwrap();
+    let viewer = ctx.policy.actor_for_user(&grandma).await.unwrap();
+
+    assert!(ctx.policy.ledger(&viewer, kid.id).await.is_ok());
+    assert!(matches!(
+        ctx.policy.adjust_balance(&viewer, kid.id, dec!(1.00), "Treat".to_string()).await,
+        Err(Error::Forbidden(_))
+    ));
+    assert!(matches!(
+        ctx.completion.complete_task_as(&viewer, task.id, kid.id, None).await,
+        Err(Error::Forbidden(_))
+    ));
+    assert!(matches!(ctx.archive.archive_kid(&viewer, kid.id, Utc::now()).await, Err(Error::Forbidden(_))));
+}
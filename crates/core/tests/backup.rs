@@ -0,0 +1,292 @@
+use loaa_core::config::{BackupConfig, DatabaseConfig, DatabaseMode};
+use loaa_core::db::{
+    AttachmentRepository, ClaimRepository, Database, HouseholdRepository, InviteRepository, KidRepository, LedgerRepository,
+    Repository, TaskRepository, TransferRepository, UserRepository, WithdrawalRepository,
+};
+use loaa_core::models::{
+    Attachment, Cadence, Household, HouseholdRole, Invite, Kid, LedgerEntry, Task, TaskClaim, TransferStatus, User,
+    WithdrawalStatus,
+};
+use loaa_core::workflows::{
+    BackupWorkflow, RestoreMode, RestoreSummary, TransferWorkflow, WithdrawalWorkflow, BACKUP_TABLES, SKIPPED_TABLES,
+};
+use loaa_core::{Actor, Error};
+use chrono::{Duration, TimeZone, Utc};
+use rust_decimal_macros::dec;
+use tempfile::TempDir;
+
+mod common;
+
+// These tests use fresh in-memory databases, so they don't need a running
+// SurrealDB server
+
+fn parent() -> Actor {
+    Actor::member("parent", "test-owner", HouseholdRole::Owner)
+}
+
+async fn fresh_database() -> Database {
+    common::init_database_with_config(&DatabaseConfig {
+        mode: DatabaseMode::Memory,
+        url: None,
+        path: None,
+        namespace: None,
+        database: None,
+        username: None,
+        password: None,
+        token: None,
+    })
+    .await
+    .expect("Failed to initialize database")
+}
+
+/// A family with a parent, a kid with two ledger entries, and a task
+async fn family(database: &Database) -> Kid {
+    let kid = KidRepository::new(database.client.clone())
+        .create(Kid::new("Kai".to_string(), "test-owner".to_string()).unwrap())
+        .await
+        .unwrap();
+    let ledger = LedgerRepository::new(database.client.clone());
+    ledger.create_entry(LedgerEntry::earned(kid.id, dec!(2.50), "Dishes".to_string())).await.unwrap();
+    ledger.create_entry(LedgerEntry::adjusted(kid.id, dec!(-1.00), "Candy".to_string())).await.unwrap();
+    TaskRepository::new(database.client.clone())
+        .create(Task::new("Dishes".to_string(), "".to_string(), dec!(2.50), Cadence::Daily, "test-owner".to_string()).unwrap())
+        .await
+        .unwrap();
+    UserRepository::new(database.client.clone())
+        .create(User::new("parent".to_string()).unwrap())
+        .await
+        .unwrap();
+    kid
+}
+
+#[tokio::test]
+async fn test_round_trip() {
+    let source = fresh_database().await;
+    let kid = family(&source).await;
+    let now = Utc::now();
+    let archive = BackupWorkflow::new(source.client.clone()).export(now).await.unwrap();
+
+    let target = fresh_database().await;
+    let workflow = BackupWorkflow::new(target.client.clone());
+    let manifest = workflow.verify(&archive).unwrap();
+    assert_eq!(manifest.tables.iter().find(|t| t.name == "ledger_entry").unwrap().records, 2);
+
+    let summary = workflow.restore(&archive, RestoreMode::Replace).await.unwrap();
+    assert_eq!(summary, RestoreSummary { restored: 5, skipped: 0, remapped: 0 });
+
+    let ledger = LedgerRepository::new(target.client.clone()).get_ledger(kid.id).await.unwrap();
+    assert_eq!(ledger.balance, dec!(1.50));
+    assert_eq!(UserRepository::new(target.client.clone()).get_by_username("parent").await.unwrap().username, "parent");
+    assert_eq!(workflow.export(now).await.unwrap(), archive);
+}
+
+#[tokio::test]
+async fn test_every_table_round_trips() {
+    let source = fresh_database().await;
+    let kai = family(&source).await;
+    let client = source.client.clone();
+    let mia = KidRepository::new(client.clone())
+        .create(Kid::new("Mia".to_string(), "test-owner".to_string()).unwrap())
+        .await
+        .unwrap();
+    let household = HouseholdRepository::new(client.clone())
+        .create(Household::new("Home".to_string(), "parent".to_string()).unwrap())
+        .await
+        .unwrap();
+    InviteRepository::new(client.clone())
+        .create(Invite::new(household.id, HouseholdRole::Viewer, "parent".to_string(), Duration::days(1)))
+        .await
+        .unwrap();
+    let task = TaskRepository::new(client.clone()).list().await.unwrap().remove(0);
+    ClaimRepository::new(client.clone())
+        .create(TaskClaim::new("test-owner".to_string(), kai.id, task.id, None, None))
+        .await
+        .unwrap();
+    AttachmentRepository::new(client.clone())
+        .create(Attachment::new("test-owner".to_string(), kai.id, Some(task.id), "image/png".to_string(), 10).unwrap())
+        .await
+        .unwrap();
+    let withdrawal = WithdrawalWorkflow::new(
+        WithdrawalRepository::new(client.clone()),
+        KidRepository::new(client.clone()),
+        LedgerRepository::new(client.clone()),
+    )
+    .request(&Actor::kid("test-owner", kai.id), kai.id, dec!(0.50), Some("Stickers".to_string()))
+    .await
+    .unwrap();
+    TransferWorkflow::new(
+        TransferRepository::new(client.clone()),
+        KidRepository::new(client.clone()),
+        LedgerRepository::new(client.clone()),
+    )
+    .transfer(&parent(), kai.id, mia.id, dec!(0.25), None, false)
+    .await
+    .unwrap();
+
+    let now = Utc::now();
+    let archive = BackupWorkflow::new(client).export(now).await.unwrap();
+    let target = fresh_database().await;
+    let workflow = BackupWorkflow::new(target.client.clone());
+    let manifest = workflow.verify(&archive).unwrap();
+    for table in BACKUP_TABLES {
+        let records = manifest.tables.iter().find(|t| t.name == *table).map(|t| t.records);
+        assert!(records.is_some_and(|n| n > 0), "Table {} has no records in the backup", table);
+    }
+
+    workflow.restore(&archive, RestoreMode::Replace).await.unwrap();
+    let restored = WithdrawalRepository::new(target.client.clone()).get(withdrawal.id).await.unwrap();
+    assert_eq!((restored.status, restored.note.as_deref()), (WithdrawalStatus::Requested, Some("Stickers")));
+    assert_eq!(LedgerRepository::new(target.client.clone()).get_ledger(kai.id).await.unwrap().balance, dec!(0.75));
+    assert_eq!(workflow.export(now).await.unwrap(), archive);
+}
+
+#[tokio::test]
+async fn test_transfers_round_trip() {
+    let source = fresh_database().await;
+    let kai = family(&source).await;
+    let mia = KidRepository::new(source.client.clone())
+        .create(Kid::new("Mia".to_string(), "test-owner".to_string()).unwrap())
+        .await
+        .unwrap();
+    let transfers = TransferWorkflow::new(
+        TransferRepository::new(source.client.clone()),
+        KidRepository::new(source.client.clone()),
+        LedgerRepository::new(source.client.clone()),
+    );
+    let gift = transfers.transfer(&parent(), kai.id, mia.id, dec!(1.00), None, false).await.unwrap();
+    let archive = BackupWorkflow::new(source.client.clone()).export(Utc::now()).await.unwrap();
+
+    let target = fresh_database().await;
+    BackupWorkflow::new(target.client.clone()).restore(&archive, RestoreMode::Replace).await.unwrap();
+    let restored = TransferRepository::new(target.client.clone()).get(gift.id).await.unwrap();
+    assert_eq!((restored.from_kid_id, restored.status), (kai.id, TransferStatus::Completed));
+    assert_eq!(LedgerRepository::new(target.client.clone()).get_ledger(mia.id).await.unwrap().balance, dec!(1.00));
+
+    // A transfer to a kid that isn't in the backup is refused
+    transfers.transfer(&parent(), kai.id, mia.id, dec!(0.50), None, true).await.unwrap();
+    source.client.query("DELETE ledger_entry WHERE kid_id = type::thing('kid', $id)").bind(("id", mia.id.to_string())).await.unwrap();
+    KidRepository::new(source.client.clone()).delete(mia.id).await.unwrap();
+    let orphaned = BackupWorkflow::new(source.client.clone()).export(Utc::now()).await.unwrap();
+    let result = BackupWorkflow::new(fresh_database().await.client.clone()).restore(&orphaned, RestoreMode::Replace).await;
+    assert!(matches!(result, Err(Error::Validation(msg)) if msg.contains("transfer")));
+}
+
+#[tokio::test]
+async fn test_damaged_archive_is_refused() {
+    let source = fresh_database().await;
+    family(&source).await;
+    let archive = BackupWorkflow::new(source.client.clone()).export(Utc::now()).await.unwrap();
+
+    let target = fresh_database().await;
+    let kid = family(&target).await;
+    let workflow = BackupWorkflow::new(target.client.clone());
+
+    let tampered = archive.replace("\"2.50\"", "\"250.00\"");
+    assert!(matches!(workflow.restore(&tampered, RestoreMode::Replace).await, Err(Error::Validation(_))));
+    let truncated: String = archive.lines().take(3).map(|line| format!("{}\n", line)).collect();
+    assert!(matches!(workflow.restore(&truncated, RestoreMode::Replace).await, Err(Error::Validation(_))));
+
+    // Nothing was replaced
+    let ledger = LedgerRepository::new(target.client.clone()).get_ledger(kid.id).await.unwrap();
+    assert_eq!(ledger.entries.len(), 2);
+}
+
+#[tokio::test]
+async fn test_merge_remaps_taken_ids() {
+    let source = fresh_database().await;
+    let kid = family(&source).await;
+    let archive = BackupWorkflow::new(source.client.clone()).export(Utc::now()).await.unwrap();
+
+    // Merging into the same data changes nothing
+    let workflow = BackupWorkflow::new(source.client.clone());
+    let summary = workflow.restore(&archive, RestoreMode::Merge).await.unwrap();
+    assert_eq!(summary, RestoreSummary { restored: 0, skipped: 5, remapped: 0 });
+
+    // Another database where the kid's ID belongs to someone else
+    let target = fresh_database().await;
+    let mut other = Kid::new("Noa".to_string(), "test-owner".to_string()).unwrap();
+    other.id = kid.id;
+    KidRepository::new(target.client.clone()).create(other).await.unwrap();
+
+    let summary = BackupWorkflow::new(target.client.clone()).restore(&archive, RestoreMode::Merge).await.unwrap();
+    assert_eq!(summary, RestoreSummary { restored: 5, skipped: 0, remapped: 1 });
+
+    let kids = KidRepository::new(target.client.clone()).list().await.unwrap();
+    assert_eq!(kids.len(), 2);
+    let restored = kids.iter().find(|k| k.name == "Kai").unwrap();
+    assert_ne!(restored.id, kid.id);
+    let ledger = LedgerRepository::new(target.client.clone());
+    assert_eq!(ledger.get_ledger(restored.id).await.unwrap().balance, dec!(1.50));
+    assert!(ledger.get_ledger(kid.id).await.unwrap().entries.is_empty());
+
+    // Merging again finds the remapped records
+    let summary = BackupWorkflow::new(target.client.clone()).restore(&archive, RestoreMode::Merge).await.unwrap();
+    assert_eq!(summary, RestoreSummary { restored: 0, skipped: 5, remapped: 0 });
+
+    // Usernames stay unique
+    let taken = fresh_database().await;
+    UserRepository::new(taken.client.clone()).create(User::new("parent".to_string()).unwrap()).await.unwrap();
+    let result = BackupWorkflow::new(taken.client.clone()).restore(&archive, RestoreMode::Merge).await;
+    assert!(matches!(result, Err(Error::Validation(_))));
+}
+
+#[tokio::test]
+async fn test_every_table_is_backed_up_or_skipped() {
+    let database = fresh_database().await;
+    let info: Option<serde_json::Value> = database.client
+        .query("INFO FOR DB")
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+    let tables = info.unwrap()["tables"].as_object().unwrap().clone();
+    assert!(tables.contains_key("ledger_entry"));
+    for table in tables.keys() {
+        assert!(
+            BACKUP_TABLES.contains(&table.as_str()) || SKIPPED_TABLES.contains(&table.as_str()),
+            "Table {} is neither backed up nor skipped",
+            table
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_scheduled_backups_rotate() {
+    let database = fresh_database().await;
+    family(&database).await;
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config = BackupConfig {
+        dir: Some(temp_dir.path().join("backups")),
+        interval_hours: 12,
+        keep_daily: 2,
+        keep_weekly: 1,
+    };
+    let dir = config.dir.as_deref().unwrap();
+    assert_eq!(BackupWorkflow::last_status(dir).unwrap(), None);
+
+    // Twice a day for three days
+    let workflow = BackupWorkflow::new(database.client.clone());
+    let start = Utc.with_ymd_and_hms(2026, 10, 14, 3, 0, 0).unwrap();
+    let mut status = None;
+    for run in 0..6 {
+        status = Some(workflow.run_scheduled(&config, start + Duration::hours(12 * run)).await.unwrap());
+    }
+    let status = status.unwrap();
+    assert!(status.is_ok());
+    assert_eq!(status.records, 5);
+    assert_eq!(BackupWorkflow::last_status(dir).unwrap(), Some(status.clone()));
+
+    // The newest of each of the last two days
+    let mut files: Vec<String> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".jsonl"))
+        .collect();
+    files.sort();
+    assert_eq!(files, vec!["loaa-backup-20261015T150000Z.jsonl", "loaa-backup-20261016T150000Z.jsonl"]);
+    assert_eq!(status.file.as_deref(), Some("loaa-backup-20261016T150000Z.jsonl"));
+
+    // Every kept file restores
+    let archive = std::fs::read_to_string(dir.join(&files[0])).unwrap();
+    assert_eq!(workflow.verify(&archive).unwrap().created_at, start + Duration::hours(36));
+}
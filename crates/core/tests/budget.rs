@@ -0,0 +1,134 @@
+use loaa_core::config::{DatabaseConfig, DatabaseMode};
+use loaa_core::db::{Database, HouseholdRepository, KidRepository, LedgerRepository, Repository, TaskRepository};
+use loaa_core::models::{BudgetEnforcement, BudgetPeriod, Cadence, HouseholdRole, Kid, PayoutBudget, Task};
+use loaa_core::workflows::{BudgetWorkflow, HouseholdWorkflow, TaskCompletionWorkflow};
+use loaa_core::{Actor, BudgetThreshold, Error};
+use rust_decimal_macros::dec;
+
+mod common;
+
+// These tests use fresh in-memory databases, so they don't need a running
+// SurrealDB server
+
+async fn fresh_database() -> Database {
+    common::init_database_with_config(&DatabaseConfig {
+        mode: DatabaseMode::Memory,
+        url: None,
+        path: None,
+        namespace: None,
+        database: None,
+        username: None,
+        password: None,
+        token: None,
+    })
+    .await
+    .expect("Failed to initialize database")
+}
+
+fn budgets(database: &Database) -> BudgetWorkflow {
+    BudgetWorkflow::new(
+        HouseholdRepository::new(database.client.clone()),
+        KidRepository::new(database.client.clone()),
+        LedgerRepository::new(database.client.clone()),
+    )
+}
+
+#[tokio::test]
+async fn test_completions_are_checked_against_budgets() {
+    let database = fresh_database().await;
+    let households = HouseholdWorkflow::new(HouseholdRepository::new(database.client.clone()));
+    let kids = KidRepository::new(database.client.clone());
+    let tasks = TaskRepository::new(database.client.clone());
+    let workflow = TaskCompletionWorkflow::new(
+        TaskRepository::new(database.client.clone()),
+        KidRepository::new(database.client.clone()),
+        LedgerRepository::new(database.client.clone()),
+    )
+    .with_budgets(budgets(&database));
+
+    let parent = uuid::Uuid::new_v4().to_string();
+    let owner_id = households.household_for_user(&parent).await.unwrap().owner_id();
+    let kai = kids.create(Kid::new("Kai".to_string(), owner_id.clone()).unwrap()).await.unwrap();
+    let mia = kids.create(Kid::new("Mia".to_string(), owner_id.clone()).unwrap()).await.unwrap();
+    let chore = tasks
+        .create(Task::new("Dishes".to_string(), String::new(), dec!(4.00), Cadence::Daily, owner_id.clone()).unwrap())
+        .await
+        .unwrap();
+
+    // A warning budget lets payouts through and reports the crossing
+    let weekly = PayoutBudget::new(dec!(10.00), BudgetPeriod::Week).unwrap();
+    households.set_budget(&parent, Some(weekly)).await.unwrap();
+    let today = chrono::Utc::now().date_naive();
+    for expected in [None, Some(BudgetThreshold::NearlySpent), Some(BudgetThreshold::Spent)] {
+        let entry = workflow.complete_task(chore.id, kai.id).await.unwrap();
+        let statuses = workflow.budget_statuses(kai.id, today).await.unwrap();
+        assert_eq!(statuses[0].crossed(entry.amount), expected);
+    }
+    let statuses = workflow.budget_statuses(kai.id, today).await.unwrap();
+    assert_eq!((statuses[0].spent, statuses[0].remaining), (dec!(12.00), dec!(-2.00)));
+    assert!(statuses[0].warning().is_some());
+
+    // A blocking kid budget refuses the payout that would go over it
+    let strict = PayoutBudget::new(dec!(6.00), BudgetPeriod::Month)
+        .unwrap()
+        .with_enforcement(BudgetEnforcement::Block);
+    households.set_kid_budget(&parent, &mia, Some(strict)).await.unwrap();
+    workflow.complete_task(chore.id, mia.id).await.unwrap();
+    let refused = workflow.complete_task(chore.id, mia.id).await;
+    assert!(matches!(refused, Err(Error::Validation(_))));
+
+    let actor = Actor::member(parent.clone(), owner_id.clone(), HouseholdRole::Owner);
+    let all = budgets(&database).statuses(&actor, today).await.unwrap();
+    assert_eq!(all.len(), 2);
+    assert_eq!((all[1].kid_id, all[1].spent, all[1].remaining), (Some(mia.id), dec!(4.00), dec!(2.00)));
+
+    // Kids of other households can't be given budgets
+    let stranger = kids.create(Kid::new("Stranger".to_string(), "other-household".to_string()).unwrap()).await.unwrap();
+    assert!(matches!(
+        households.set_kid_budget(&parent, &stranger, Some(strict)).await,
+        Err(Error::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_completions_racing_for_the_last_of_a_budget() {
+    let database = fresh_database().await;
+    let households = HouseholdWorkflow::new(HouseholdRepository::new(database.client.clone()));
+    let kids = KidRepository::new(database.client.clone());
+    let tasks = TaskRepository::new(database.client.clone());
+    let ledger = LedgerRepository::new(database.client.clone());
+    let workflow = TaskCompletionWorkflow::new(
+        TaskRepository::new(database.client.clone()),
+        KidRepository::new(database.client.clone()),
+        LedgerRepository::new(database.client.clone()),
+    )
+    .with_budgets(budgets(&database));
+
+    let parent = uuid::Uuid::new_v4().to_string();
+    let owner_id = households.household_for_user(&parent).await.unwrap().owner_id();
+    let kai = kids.create(Kid::new("Kai".to_string(), owner_id.clone()).unwrap()).await.unwrap();
+    let mia = kids.create(Kid::new("Mia".to_string(), owner_id.clone()).unwrap()).await.unwrap();
+    let mut chores = Vec::new();
+    for name in ["Dishes", "Laundry"] {
+        let task = Task::new(name.to_string(), String::new(), dec!(4.00), Cadence::Daily, owner_id.clone()).unwrap();
+        chores.push(tasks.create(task).await.unwrap());
+    }
+    let weekly = PayoutBudget::new(dec!(6.00), BudgetPeriod::Week)
+        .unwrap()
+        .with_enforcement(BudgetEnforcement::Block);
+    households.set_budget(&parent, Some(weekly)).await.unwrap();
+
+    // Both completions fit the budget when checked, but only one is saved
+    let mut tx = ledger.begin();
+    workflow.complete_in(&mut tx, chores[0].id, kai.id, None).await.unwrap();
+    workflow.complete_task(chores[1].id, mia.id).await.unwrap();
+    match tx.commit().await {
+        Err(Error::Validation(message)) => assert!(message.contains("weekly budget")),
+        other => panic!("Expected the budget to refuse the payout, got {:?}", other),
+    }
+
+    assert!(ledger.list_entries(kai.id).await.unwrap().is_empty());
+    let today = chrono::Utc::now().date_naive();
+    let statuses = workflow.budget_statuses(kai.id, today).await.unwrap();
+    assert_eq!(statuses[0].spent, dec!(4.00));
+}
@@ -0,0 +1,144 @@
+use loaa_core::db::{
+    AttachmentRepository, ClaimRepository, KidRepository, LedgerRepository, Repository, TaskRepository,
+};
+use loaa_core::models::{Attachment, Cadence, ClaimStatus, CompletionReview, HouseholdRole, Kid, Task};
+use loaa_core::workflows::{ClaimWorkflow, TaskCompletionWorkflow};
+use loaa_core::Actor;
+use rust_decimal_macros::dec;
+
+mod common;
+use common::init_database;
+
+// NOTE: Tests require a running SurrealDB server on 127.0.0.1:8000
+// Run `just db` before running tests, or run them on SQLite with
+// LOAA_TEST_DB_MODE=sqlite
+
+struct TestContext {
+    workflow: ClaimWorkflow,
+    kid_repo: KidRepository,
+    task_repo: TaskRepository,
+    ledger_repo: LedgerRepository,
+    attachment_repo: AttachmentRepository,
+}
+
+async fn setup_test() -> TestContext {
+    let db_url = std::env::var("LOAA_DB_URL").unwrap_or_else(|_| "127.0.0.1:8000".to_string());
+    let database = init_database(&db_url)
+        .await
+        .expect("Failed to initialize database");
+    let client = database.client.clone();
+
+    let completion = TaskCompletionWorkflow::new(
+        TaskRepository::new(client.clone()),
+        KidRepository::new(client.clone()),
+        LedgerRepository::new(client.clone()),
+    );
+    let workflow = ClaimWorkflow::new(
+        ClaimRepository::new(client.clone()),
+        KidRepository::new(client.clone()),
+        TaskRepository::new(client.clone()),
+        AttachmentRepository::new(client.clone()),
+        completion,
+    );
+
+    TestContext {
+        workflow,
+        kid_repo: KidRepository::new(client.clone()),
+        task_repo: TaskRepository::new(client.clone()),
+        ledger_repo: LedgerRepository::new(client.clone()),
+        attachment_repo: AttachmentRepository::new(client),
+    }
+}
+
+fn parent(owner_id: &str) -> Actor {
+    Actor::member("parent", owner_id, HouseholdRole::Owner)
+}
+
+fn login(kid: &Kid) -> Actor {
+    Actor::kid(kid.owner_id.clone(), kid.id)
+}
+
+async fn kid_and_task(ctx: &TestContext, owner_id: &str) -> (Kid, Task) {
+    let kid = ctx.kid_repo.create(Kid::new("Maya".to_string(), owner_id.to_string()).unwrap()).await.unwrap();
+    let task = ctx.task_repo.create(
+        Task::new("Clean room".to_string(), "".to_string(), dec!(4.00), Cadence::Daily, owner_id.to_string()).unwrap()
+    ).await.unwrap();
+    (kid, task)
+}
+
+#[tokio::test]
+async fn test_claim_and_approve_pays_kid() {
+    let ctx = setup_test().await;
+    let owner_id = uuid::Uuid::new_v4().to_string();
+    let (kid, task) = kid_and_task(&ctx, &owner_id).await;
+
+    let attachment = ctx.attachment_repo.create(
+        Attachment::new(owner_id.clone(), kid.id, Some(task.id), "image/png".to_string(), 100).unwrap()
+    ).await.unwrap();
+
+    let claim = ctx.workflow
+        .claim_task(&login(&kid), kid.id, task.id, Some("Vacuumed too".to_string()), Some(attachment.id))
+        .await
+        .unwrap();
+    assert_eq!(claim.status, ClaimStatus::Pending);
+    assert_eq!(claim.owner_id, owner_id);
+
+    // Nothing is paid until a parent approves
+    assert_eq!(ctx.ledger_repo.get_ledger(kid.id).await.unwrap().balance, dec!(0));
+
+    let pending = ctx.workflow.list_pending(&parent(&owner_id)).await.unwrap();
+    assert_eq!(pending.len(), 1);
+
+    let review = CompletionReview::new(Some(4), 50, None).unwrap();
+    let (approved, entry) = ctx.workflow.approve(&parent(&owner_id), claim.id, Some(review)).await.unwrap();
+    assert_eq!(approved.status, ClaimStatus::Approved);
+    assert_eq!(approved.ledger_entry_id, Some(entry.id));
+    assert_eq!(entry.amount, dec!(2.00));
+    assert_eq!(ctx.ledger_repo.get_ledger(kid.id).await.unwrap().balance, dec!(2.00));
+
+    let linked = ctx.attachment_repo.get(attachment.id).await.unwrap();
+    assert_eq!(linked.ledger_entry_id, Some(entry.id));
+
+    assert!(ctx.workflow.list_pending(&parent(&owner_id)).await.unwrap().is_empty());
+    assert!(ctx.workflow.approve(&parent(&owner_id), claim.id, None).await.is_err());
+}
+
+#[tokio::test]
+async fn test_reject_claim() {
+    let ctx = setup_test().await;
+    let owner_id = uuid::Uuid::new_v4().to_string();
+    let (kid, task) = kid_and_task(&ctx, &owner_id).await;
+
+    let claim = ctx.workflow.claim_task(&login(&kid), kid.id, task.id, None, None).await.unwrap();
+
+    // Other owners can't see or review the claim
+    let intruder = uuid::Uuid::new_v4().to_string();
+    assert!(ctx.workflow.reject(&parent(&intruder), claim.id).await.is_err());
+
+    let rejected = ctx.workflow.reject(&parent(&owner_id), claim.id).await.unwrap();
+    assert_eq!(rejected.status, ClaimStatus::Rejected);
+    assert_eq!(ctx.ledger_repo.get_ledger(kid.id).await.unwrap().balance, dec!(0));
+}
+
+#[tokio::test]
+async fn test_claim_rules() {
+    let ctx = setup_test().await;
+    let owner_id = uuid::Uuid::new_v4().to_string();
+    let (kid, task) = kid_and_task(&ctx, &owner_id).await;
+
+    // Only one pending claim per task
+    ctx.workflow.claim_task(&login(&kid), kid.id, task.id, None, None).await.unwrap();
+    assert!(ctx.workflow.claim_task(&login(&kid), kid.id, task.id, None, None).await.is_err());
+
+    // Tasks from another family can't be claimed
+    let other_owner = uuid::Uuid::new_v4().to_string();
+    let (_, other_task) = kid_and_task(&ctx, &other_owner).await;
+    assert!(ctx.workflow.claim_task(&login(&kid), kid.id, other_task.id, None, None).await.is_err());
+
+    // A sibling's login can't claim on the kid's behalf
+    let sibling = ctx.kid_repo.create(Kid::new("Kai".to_string(), owner_id.clone()).unwrap()).await.unwrap();
+    assert!(ctx.workflow.claim_task(&login(&sibling), kid.id, task.id, None, None).await.is_err());
+
+    let claims = ctx.workflow.list_for_kid(&login(&kid), kid.id).await.unwrap();
+    assert_eq!(claims.len(), 1);
+}
@@ -0,0 +1,57 @@
+//! Lets the suites run on SQLite mode as well as the database they're written
+//! for
+//!
+//! Suites open their database through these rather than `loaa_core::db`'s.
+//! With `LOAA_TEST_DB_MODE=sqlite` set, each test gets a SQLite database file
+//! of its own under the target directory instead; `just test` runs the suites
+//! both ways.
+
+#![allow(dead_code)]
+
+use loaa_core::config::{DatabaseConfig, DatabaseMode};
+use loaa_core::db::{self, Database};
+use loaa_core::Result;
+use std::path::Path;
+use uuid::Uuid;
+
+fn sqlite_mode() -> bool {
+    std::env::var("LOAA_TEST_DB_MODE").is_ok_and(|mode| mode == "sqlite")
+}
+
+fn sqlite_config() -> DatabaseConfig {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("sqlite")
+        .join(format!("{}.sqlite", Uuid::new_v4()));
+    DatabaseConfig {
+        mode: DatabaseMode::Sqlite,
+        url: None,
+        path: Some(path),
+        namespace: None,
+        database: None,
+        username: None,
+        password: None,
+        token: None,
+    }
+}
+
+/// `db::init_database`, for suites that run against a SurrealDB server
+pub async fn init_database(url: &str) -> Result<Database> {
+    if sqlite_mode() {
+        return Database::init_with_config(&sqlite_config()).await;
+    }
+    db::init_database(url).await
+}
+
+pub async fn init_database_with_config(config: &DatabaseConfig) -> Result<Database> {
+    if sqlite_mode() {
+        return Database::init_with_config(&sqlite_config()).await;
+    }
+    Database::init_with_config(config).await
+}
+
+pub async fn connect(config: &DatabaseConfig) -> Result<Database> {
+    if sqlite_mode() {
+        return Database::connect(&sqlite_config()).await;
+    }
+    Database::connect(config).await
+}
@@ -0,0 +1,207 @@
+use loaa_core::config::{DatabaseConfig, DatabaseMode};
+use loaa_core::db::{Database, KidRepository, LedgerRepository, Repository, TaskRepository};
+use loaa_core::models::{Cadence, EntryType, HouseholdRole, Kid, LedgerEntry, Task, TaskCategory};
+use loaa_core::workflows::{ColumnMapping, CsvWorkflow, LedgerFilter};
+use loaa_core::Actor;
+use chrono::{NaiveDate, TimeZone, Utc};
+use rust_decimal_macros::dec;
+
+mod common;
+
+// These tests use fresh in-memory databases, so they don't need a running
+// SurrealDB server
+
+async fn fresh_database() -> Database {
+    common::init_database_with_config(&DatabaseConfig {
+        mode: DatabaseMode::Memory,
+        url: None,
+        path: None,
+        namespace: None,
+        database: None,
+        username: None,
+        password: None,
+        token: None,
+    })
+    .await
+    .expect("Failed to initialize database")
+}
+
+fn parent(owner_id: &str) -> Actor {
+    Actor::member("parent", owner_id, HouseholdRole::Owner)
+}
+
+fn workflow(database: &Database) -> CsvWorkflow {
+    CsvWorkflow::new(
+        KidRepository::new(database.client.clone()),
+        TaskRepository::new(database.client.clone()),
+        LedgerRepository::new(database.client.clone()),
+    )
+}
+
+#[tokio::test]
+async fn test_tasks_round_trip() {
+    let database = fresh_database().await;
+    let tasks = TaskRepository::new(database.client.clone());
+    tasks
+        .create(
+            Task::new("Dishes".to_string(), "=after dinner".to_string(), dec!(2.50), Cadence::Daily, "owner-a".to_string())
+                .unwrap()
+                .with_category(Some(TaskCategory::Kitchen))
+                .with_tags(["evening", "quick"]),
+        )
+        .await
+        .unwrap();
+
+    let csv = workflow(&database).export_tasks(&parent("owner-a")).await.unwrap();
+    assert!(csv.starts_with("name,description,value,cadence,category,tags\n"));
+    assert!(csv.contains("Dishes,'=after dinner,2.50,daily,kitchen,evening; quick"));
+
+    // A dry run writes nothing
+    let report = workflow(&database).import_tasks(&parent("owner-b"), &csv, &ColumnMapping::new(), true).await.unwrap();
+    assert_eq!((report.rows, report.imported, report.written), (1, 1, false));
+    assert!(tasks.list_by_owner("owner-b").await.unwrap().is_empty());
+
+    let report = workflow(&database).import_tasks(&parent("owner-b"), &csv, &ColumnMapping::new(), false).await.unwrap();
+    assert!(report.written);
+    let imported = tasks.list_by_owner("owner-b").await.unwrap();
+    assert_eq!(imported.len(), 1);
+    assert_eq!(imported[0].description, "=after dinner");
+    assert_eq!(imported[0].category, Some(TaskCategory::Kitchen));
+    assert_eq!(imported[0].tags, vec!["evening", "quick"]);
+
+    // Importing again skips what's there
+    let report = workflow(&database).import_tasks(&parent("owner-b"), &csv, &ColumnMapping::new(), false).await.unwrap();
+    assert_eq!(report.skipped, vec!["Dishes"]);
+    assert!(!report.written);
+}
+
+#[tokio::test]
+async fn test_bad_rows_are_reported_and_nothing_is_written() {
+    let database = fresh_database().await;
+    let csv = "Chore,Pay,How often\nFeed cat,1.00,daily\n,1.00,daily\nMow lawn,-5,weekly\nRead,1,sometimes\n";
+    let mapping = ColumnMapping::parse("name=Chore, value=Pay, cadence=How often").unwrap();
+
+    let report = workflow(&database).import_tasks(&parent("owner-a"), csv, &mapping, false).await.unwrap();
+    assert_eq!(report.rows, 4);
+    assert_eq!(report.imported, 1);
+    assert!(!report.written);
+    let lines: Vec<u64> = report.errors.iter().map(|e| e.line).collect();
+    assert_eq!(lines, vec![3, 4, 5]);
+    assert_eq!(report.errors[1].message, "Task value must be positive");
+    assert!(TaskRepository::new(database.client.clone()).list_by_owner("owner-a").await.unwrap().is_empty());
+
+    // A missing column fails the whole file
+    assert!(workflow(&database).import_tasks(&parent("owner-a"), csv, &ColumnMapping::new(), true).await.is_err());
+
+    // Viewers can't import, not even as a dry run
+    let viewer = Actor::member("viewer", "owner-a", HouseholdRole::Viewer);
+    let refused = workflow(&database).import_tasks(&viewer, csv, &mapping, true).await;
+    assert!(matches!(refused, Err(loaa_core::Error::Forbidden(_))));
+}
+
+#[tokio::test]
+async fn test_ledger_import_and_export() {
+    let database = fresh_database().await;
+    let kids = KidRepository::new(database.client.clone());
+    let kai = kids.create(Kid::new("Kai".to_string(), "owner-a".to_string()).unwrap()).await.unwrap();
+    let noa = kids.create(Kid::new("Noa".to_string(), "owner-a".to_string()).unwrap()).await.unwrap();
+    kids.create(Kid::new("Other".to_string(), "owner-b".to_string()).unwrap()).await.unwrap();
+    let now = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+
+    let csv = format!(
+        "Date,Who,Amount,Note\n2026-09-01,Kai,$3.00,Lawn\n2026-09-02,{},-1.00,Candy\n2026-09-03,Other,1.00,Not ours\n2027-01-01,Kai,1.00,Later\n",
+        noa.id
+    );
+    let mapping = ColumnMapping::parse("kid=Who, description=Note").unwrap();
+    let report = workflow(&database).import_ledger(&parent("owner-a"), &csv, &mapping, false, now).await.unwrap();
+    assert_eq!(report.errors.len(), 2);
+    assert_eq!(report.errors[0].message, "No kid is named Other");
+    assert!(!report.written);
+
+    let csv: String = csv.lines().take(3).map(|line| format!("{}\n", line)).collect();
+    let report = workflow(&database).import_ledger(&parent("owner-a"), &csv, &mapping, false, now).await.unwrap();
+    assert_eq!((report.imported, report.written), (2, true));
+
+    let ledger = LedgerRepository::new(database.client.clone());
+    let entries = ledger.get_ledger(kai.id).await.unwrap().entries;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].entry_type, EntryType::Earned);
+    assert_eq!(entries[0].created_at, Utc.with_ymd_and_hms(2026, 9, 1, 0, 0, 0).unwrap());
+    assert_eq!(ledger.get_ledger(noa.id).await.unwrap().entries[0].entry_type, EntryType::Adjusted);
+
+    // Re-importing the same history adds nothing
+    let report = workflow(&database).import_ledger(&parent("owner-a"), &csv, &mapping, false, now).await.unwrap();
+    assert_eq!(report.skipped.len(), 2);
+    assert!(!report.written);
+
+    ledger.create_entry(LedgerEntry::earned(kai.id, dec!(1.00), "Today".to_string())).await.unwrap();
+    let filter = LedgerFilter {
+        kid_id: None,
+        from: NaiveDate::from_ymd_opt(2026, 9, 2),
+        to: NaiveDate::from_ymd_opt(2026, 9, 30),
+    };
+    let export = workflow(&database).export_ledger(&parent("owner-a"), &filter).await.unwrap();
+    assert_eq!(
+        export,
+        format!(
+            "date,kid,kid_id,type,amount,description,category\n2026-09-02T00:00:00Z,Noa,{},adjusted,-1.00,Candy,\n",
+            noa.id
+        )
+    );
+
+    // The export imports back as the same entries
+    let all = workflow(&database).export_ledger(&parent("owner-a"), &LedgerFilter::default()).await.unwrap();
+    let report = workflow(&database).import_ledger(&parent("owner-a"), &all, &ColumnMapping::new(), true, Utc::now()).await.unwrap();
+    assert_eq!((report.imported, report.skipped.len()), (0, 3));
+
+    let other = LedgerFilter { kid_id: Some(kids.list_by_owner("owner-b").await.unwrap()[0].id), ..LedgerFilter::default() };
+    assert!(workflow(&database).export_ledger(&parent("owner-a"), &other).await.is_err());
+}
+
+#[tokio::test]
+async fn test_identical_rows_for_different_kids_are_both_imported() {
+    let database = fresh_database().await;
+    let kids = KidRepository::new(database.client.clone());
+    let kai = kids.create(Kid::new("Kai".to_string(), "owner-a".to_string()).unwrap()).await.unwrap();
+    let noa = kids.create(Kid::new("Noa".to_string(), "owner-a".to_string()).unwrap()).await.unwrap();
+    let now = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+
+    // Both kids got the same allowance on the same day
+    let csv = "date,kid,amount,description\n2026-09-01,Kai,5.00,Allowance\n2026-09-01,Noa,5.00,Allowance\n";
+    let report = workflow(&database).import_ledger(&parent("owner-a"), csv, &ColumnMapping::new(), false, now).await.unwrap();
+    assert_eq!((report.imported, report.skipped.len()), (2, 0));
+
+    let ledger = LedgerRepository::new(database.client.clone());
+    assert_eq!(ledger.get_ledger(kai.id).await.unwrap().balance, dec!(5.00));
+    assert_eq!(ledger.get_ledger(noa.id).await.unwrap().balance, dec!(5.00));
+
+    // Each row is still a duplicate of that kid's own entry
+    let report = workflow(&database).import_ledger(&parent("owner-a"), csv, &ColumnMapping::new(), false, now).await.unwrap();
+    assert_eq!((report.imported, report.skipped.len()), (0, 2));
+}
+
+#[tokio::test]
+async fn test_ledger_reimport_matches_type_and_day() {
+    let database = fresh_database().await;
+    let kids = KidRepository::new(database.client.clone());
+    let kai = kids.create(Kid::new("Kai".to_string(), "owner-a".to_string()).unwrap()).await.unwrap();
+    let now = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+
+    let ledger = LedgerRepository::new(database.client.clone());
+    let mut allowance = LedgerEntry::earned(kai.id, dec!(5.00), "Allowance".to_string());
+    allowance.created_at = Utc.with_ymd_and_hms(2026, 9, 1, 15, 30, 0).unwrap();
+    ledger.create_entry(allowance).await.unwrap();
+
+    // A spreadsheet that cut the export's timestamp down to the day
+    let csv = "date,kid,type,amount,description\n2026-09-01,Kai,earned,5.00,Allowance\n";
+    let report = workflow(&database).import_ledger(&parent("owner-a"), csv, &ColumnMapping::new(), false, now).await.unwrap();
+    assert_eq!((report.imported, report.skipped.len()), (0, 1));
+
+    // Another day, or another type on the same day, is a different entry
+    let csv = "date,kid,type,amount,description\n\
+               2026-09-02,Kai,earned,5.00,Allowance\n\
+               2026-09-01T15:30:00Z,Kai,adjusted,5.00,Allowance\n";
+    let report = workflow(&database).import_ledger(&parent("owner-a"), csv, &ColumnMapping::new(), false, now).await.unwrap();
+    assert_eq!((report.imported, report.skipped.len()), (2, 0));
+    assert_eq!(ledger.get_ledger(kai.id).await.unwrap().balance, dec!(15.00));
+}
@@ -0,0 +1,90 @@
+use loaa_core::config::{DatabaseConfig, DatabaseMode};
+use loaa_core::db::{Database, HouseholdRepository, KidRepository, LedgerRepository, Repository, TaskRepository};
+use loaa_core::models::{BudgetPeriod, Cadence, HouseholdRole, Kid, LedgerEntry, PayoutBudget, Task};
+use loaa_core::workflows::{ForecastWorkflow, HouseholdWorkflow};
+use loaa_core::Actor;
+use chrono::{Duration, Utc};
+use rust_decimal_macros::dec;
+
+mod common;
+
+// These tests use fresh in-memory databases, so they don't need a running
+// SurrealDB server
+
+async fn fresh_database() -> Database {
+    common::init_database_with_config(&DatabaseConfig {
+        mode: DatabaseMode::Memory,
+        url: None,
+        path: None,
+        namespace: None,
+        database: None,
+        username: None,
+        password: None,
+        token: None,
+    })
+    .await
+    .expect("Failed to initialize database")
+}
+
+#[tokio::test]
+async fn test_forecast_from_ledger_history_and_budget() {
+    let database = fresh_database().await;
+    let households = HouseholdWorkflow::new(HouseholdRepository::new(database.client.clone()));
+    let kids = KidRepository::new(database.client.clone());
+    let tasks = TaskRepository::new(database.client.clone());
+    let ledger = LedgerRepository::new(database.client.clone());
+    let workflow = ForecastWorkflow::new(
+        HouseholdRepository::new(database.client.clone()),
+        KidRepository::new(database.client.clone()),
+        TaskRepository::new(database.client.clone()),
+        LedgerRepository::new(database.client.clone()),
+    );
+
+    let parent = uuid::Uuid::new_v4().to_string();
+    let household = households.household_for_user(&parent).await.unwrap();
+    let owner_id = household.owner_id();
+    let actor = Actor::member(parent.clone(), owner_id.clone(), HouseholdRole::Owner);
+    let now = Utc::now();
+
+    let kai = kids.create(Kid::new("Kai".to_string(), owner_id.clone()).unwrap()).await.unwrap();
+    let mut lawn = Task::new("Mow lawn".to_string(), String::new(), dec!(5.00), Cadence::Weekly, owner_id.clone()).unwrap();
+    lawn.created_at = now - Duration::days(60);
+    let lawn = tasks.create(lawn).await.unwrap();
+    let mut garage = Task::new("Garage".to_string(), String::new(), dec!(20.00), Cadence::OneTime, owner_id.clone()).unwrap();
+    garage.created_at = now - Duration::days(60);
+    let garage = tasks.create(garage).await.unwrap();
+
+    // Mowed two of the last four weeks; an older mow is outside the lookback
+    for days_ago in [3, 17, 40] {
+        let mut entry = LedgerEntry::task_completion(kai.id, &lawn, None);
+        entry.created_at = now - Duration::days(days_ago);
+        ledger.create_entry(entry).await.unwrap();
+    }
+
+    let forecast = workflow.forecast(&actor, now.date_naive()).await.unwrap();
+    let kid = &forecast.kids[0];
+    assert_eq!((kid.tasks[0].completions, kid.tasks[0].rate), (2, dec!(0.5)));
+    assert_eq!((kid.next_week, kid.next_month), (dec!(2.50), dec!(10.71)));
+    assert_eq!(forecast.open_one_time, dec!(20.00));
+    assert!(forecast.warning.is_none());
+
+    // Once done, the one-time task drops out of the projection
+    ledger.create_entry(LedgerEntry::task_completion(kai.id, &garage, None)).await.unwrap();
+    let weekly = PayoutBudget::new(dec!(2.00), BudgetPeriod::Week).unwrap();
+    let saved = households.set_budget(&parent, Some(weekly)).await.unwrap();
+    assert_eq!(saved.budget, Some(weekly));
+
+    let forecast = workflow.forecast(&actor, now.date_naive()).await.unwrap();
+    assert_eq!(forecast.open_one_time, dec!(0));
+    assert_eq!(forecast.next_week, dec!(2.50));
+    assert_eq!(
+        forecast.warning.as_deref(),
+        Some("Projected payouts of $2.50 next week are over the weekly budget of $2.00")
+    );
+
+    // Viewers can't change the budget
+    let grandma = uuid::Uuid::new_v4().to_string();
+    households.add_member(&parent, &grandma, HouseholdRole::Viewer).await.unwrap();
+    assert!(households.set_budget(&grandma, None).await.is_err());
+    assert!(households.set_budget(&parent, None).await.unwrap().budget.is_none());
+}
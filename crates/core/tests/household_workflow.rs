@@ -0,0 +1,116 @@
+use loaa_core::db::{Database, HouseholdRepository, KidRepository, Repository, TaskRepository, TransferRepository, WithdrawalRepository};
+use loaa_core::models::{Cadence, HouseholdRole, Kid, Task, Transfer, Withdrawal};
+use loaa_core::workflows::HouseholdWorkflow;
+use loaa_core::Error;
+use rust_decimal_macros::dec;
+
+mod common;
+use common::init_database;
+
+// NOTE: Tests require a running SurrealDB server on 127.0.0.1:8000
+// Run `just db` before running tests, or run them on SQLite with
+// LOAA_TEST_DB_MODE=sqlite
+
+async fn setup_test() -> (HouseholdWorkflow, KidRepository, TaskRepository, Database) {
+    let db_url = std::env::var("LOAA_DB_URL").unwrap_or_else(|_| "127.0.0.1:8000".to_string());
+    let database = init_database(&db_url)
+        .await
+        .expect("Failed to initialize database");
+
+    (
+        HouseholdWorkflow::new(HouseholdRepository::new(database.client.clone())),
+        KidRepository::new(database.client.clone()),
+        TaskRepository::new(database.client.clone()),
+        database,
+    )
+}
+
+fn new_user_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+#[tokio::test]
+async fn test_personal_household_created_once() {
+    let (workflow, _, _, _) = setup_test().await;
+    let user = new_user_id();
+
+    let first = workflow.household_for_user(&user).await.unwrap();
+    let second = workflow.household_for_user(&user).await.unwrap();
+    assert_eq!(first.id, second.id);
+
+    let (_, role) = workflow.membership(&user).await.unwrap();
+    assert_eq!(role, HouseholdRole::Owner);
+}
+
+#[tokio::test]
+async fn test_migrate_legacy_owner_ids() {
+    let (workflow, kid_repo, task_repo, database) = setup_test().await;
+    let user = new_user_id();
+
+    // Data written before households existed is keyed by the user ID
+    let kid = kid_repo.create(Kid::new("Maya".to_string(), user.clone()).unwrap()).await.unwrap();
+    let task = task_repo.create(
+        Task::new("Dishes".to_string(), "".to_string(), dec!(1.00), Cadence::Daily, user.clone()).unwrap()
+    ).await.unwrap();
+    let withdrawals = WithdrawalRepository::new(database.client.clone());
+    let withdrawal = Withdrawal::new(user.clone(), kid.id, dec!(2.00), None).unwrap();
+    let mut tx = withdrawals.begin();
+    withdrawals.create_in(&mut tx, &withdrawal).unwrap();
+    tx.commit().await.unwrap();
+    let sister = kid_repo.create(Kid::new("Lani".to_string(), user.clone()).unwrap()).await.unwrap();
+    let transfers = TransferRepository::new(database.client.clone());
+    let transfer = Transfer::new(user.clone(), kid.id, sister.id, dec!(1.00), None).unwrap();
+    let mut tx = transfers.begin();
+    transfers.create_in(&mut tx, &transfer).unwrap();
+    tx.commit().await.unwrap();
+
+    assert!(workflow.migrate_owner_ids().await.unwrap() >= 1);
+
+    let household = workflow.household_for_user(&user).await.unwrap();
+    assert_eq!(kid_repo.get(kid.id).await.unwrap().owner_id, household.owner_id());
+    assert_eq!(task_repo.get(task.id).await.unwrap().owner_id, household.owner_id());
+    assert_eq!(withdrawals.get(withdrawal.id).await.unwrap().owner_id, household.owner_id());
+    assert_eq!(transfers.get(transfer.id).await.unwrap().owner_id, household.owner_id());
+    assert!(kid_repo.list_by_owner(&user).await.unwrap().is_empty());
+
+    // Running again leaves migrated data alone
+    workflow.migrate_owner_ids().await.unwrap();
+    assert_eq!(kid_repo.get(kid.id).await.unwrap().owner_id, household.owner_id());
+}
+
+#[tokio::test]
+async fn test_two_parents_share_kids() {
+    let (workflow, kid_repo, _, _) = setup_test().await;
+    let mom = new_user_id();
+    let dad = new_user_id();
+
+    let household = workflow.household_for_user(&mom).await.unwrap();
+    kid_repo.create(Kid::new("Noa".to_string(), household.owner_id()).unwrap()).await.unwrap();
+
+    // Dad already tracked a kid on his own; he can't join until it's gone
+    let dads_household = workflow.household_for_user(&dad).await.unwrap();
+    let kai = kid_repo.create(Kid::new("Kai".to_string(), dads_household.owner_id()).unwrap()).await.unwrap();
+    let refused = workflow.add_member(&mom, &dad, HouseholdRole::Parent).await;
+    assert!(matches!(refused, Err(Error::Validation(_))));
+    kid_repo.delete(kai.id).await.unwrap();
+
+    let joined = workflow.add_member(&mom, &dad, HouseholdRole::Parent).await.unwrap();
+    assert_eq!(joined.id, household.id);
+
+    let (dads_view, role) = workflow.membership(&dad).await.unwrap();
+    assert_eq!(dads_view.id, household.id);
+    assert_eq!(role, HouseholdRole::Parent);
+    assert_eq!(kid_repo.list_by_owner(&household.owner_id()).await.unwrap().len(), 1);
+
+    // Only owners manage members
+    let grandma = new_user_id();
+    assert!(workflow.add_member(&dad, &grandma, HouseholdRole::Viewer).await.is_err());
+    workflow.add_member(&mom, &grandma, HouseholdRole::Viewer).await.unwrap();
+    assert!(workflow.set_role(&dad, &grandma, HouseholdRole::Parent).await.is_err());
+
+    // Removed members start over in a household of their own
+    workflow.remove_member(&mom, &grandma).await.unwrap();
+    let (grandmas_household, role) = workflow.membership(&grandma).await.unwrap();
+    assert_ne!(grandmas_household.id, household.id);
+    assert_eq!(role, HouseholdRole::Owner);
+}
@@ -0,0 +1,155 @@
+use loaa_core::db::{IdempotencyRepository, KidRepository, LedgerRepository, Repository};
+use loaa_core::models::{IdempotencyKey, Kid, LedgerEntry};
+use loaa_core::workflows::IdempotencyWorkflow;
+use loaa_core::Error;
+use chrono::{Duration, Utc};
+use rust_decimal_macros::dec;
+
+mod common;
+use common::init_database;
+
+// NOTE: Tests require a running SurrealDB server on 127.0.0.1:8000
+// Run `just db` before running tests, or run them on SQLite with
+// LOAA_TEST_DB_MODE=sqlite
+
+struct TestContext {
+    workflow: IdempotencyWorkflow,
+    repo: IdempotencyRepository,
+    kid_repo: KidRepository,
+    ledger_repo: LedgerRepository,
+}
+
+async fn setup_test() -> TestContext {
+    let db_url = std::env::var("LOAA_DB_URL").unwrap_or_else(|_| "127.0.0.1:8000".to_string());
+    let database = init_database(&db_url)
+        .await
+        .expect("Failed to initialize database");
+
+    TestContext {
+        workflow: IdempotencyWorkflow::new(
+            IdempotencyRepository::new(database.client.clone()),
+            Duration::hours(24),
+        ),
+        repo: IdempotencyRepository::new(database.client.clone()),
+        kid_repo: KidRepository::new(database.client.clone()),
+        ledger_repo: LedgerRepository::new(database.client.clone()),
+    }
+}
+
+async fn kid(ctx: &TestContext) -> Kid {
+    ctx.kid_repo
+        .create(Kid::new("Kai".to_string(), uuid::Uuid::new_v4().to_string()).unwrap())
+        .await
+        .unwrap()
+}
+
+/// Pay a kid $5, returning the new entry's ID
+async fn pay(ctx: &TestContext, kid: &Kid) -> loaa_core::Result<uuid::Uuid> {
+    let entry = ctx.ledger_repo
+        .create_entry(LedgerEntry::adjusted(kid.id, dec!(5.00), "Birthday".to_string()))
+        .await?;
+    Ok(entry.id)
+}
+
+#[tokio::test]
+async fn test_replay_returns_first_result() {
+    let ctx = setup_test().await;
+    let kid = kid(&ctx).await;
+    let scope = uuid::Uuid::new_v4().to_string();
+
+    let first = ctx.workflow.run(&scope, Some("pay-1"), "adjust_balance", &kid.id, || pay(&ctx, &kid)).await.unwrap().unwrap();
+    let retry = ctx.workflow.run(&scope, Some("pay-1"), "adjust_balance", &kid.id, || pay(&ctx, &kid)).await.unwrap().unwrap();
+    assert_eq!(first, retry);
+    assert_eq!(ctx.ledger_repo.get_ledger(kid.id).await.unwrap().balance, dec!(5.00));
+
+    // A new key, or no key at all, pays again
+    ctx.workflow.run(&scope, Some("pay-2"), "adjust_balance", &kid.id, || pay(&ctx, &kid)).await.unwrap().unwrap();
+    ctx.workflow.run(&scope, None, "adjust_balance", &kid.id, || pay(&ctx, &kid)).await.unwrap().unwrap();
+    assert_eq!(ctx.ledger_repo.get_ledger(kid.id).await.unwrap().balance, dec!(15.00));
+
+    // Keys belong to whoever sent them
+    let other_scope = uuid::Uuid::new_v4().to_string();
+    ctx.workflow.run(&other_scope, Some("pay-1"), "adjust_balance", &kid.id, || pay(&ctx, &kid)).await.unwrap().unwrap();
+    assert_eq!(ctx.ledger_repo.get_ledger(kid.id).await.unwrap().balance, dec!(20.00));
+}
+
+#[tokio::test]
+async fn test_key_reused_for_another_operation() {
+    let ctx = setup_test().await;
+    let kid = kid(&ctx).await;
+    let scope = uuid::Uuid::new_v4().to_string();
+
+    ctx.workflow.run(&scope, Some("k"), "adjust_balance", &kid.id, || pay(&ctx, &kid)).await.unwrap().unwrap();
+    let reused = ctx.workflow.run(&scope, Some("k"), "complete_task", &kid.id, || pay(&ctx, &kid)).await;
+    assert!(matches!(reused, Err(Error::Validation(_))));
+    assert_eq!(ctx.ledger_repo.list_entries(kid.id).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_key_reused_with_other_arguments() {
+    let ctx = setup_test().await;
+    let (kid, sibling) = (kid(&ctx).await, kid(&ctx).await);
+    let scope = uuid::Uuid::new_v4().to_string();
+
+    ctx.workflow.run(&scope, Some("k"), "adjust_balance", &kid.id, || pay(&ctx, &kid)).await.unwrap().unwrap();
+    // Paying the sibling with the same key doesn't answer with the first payout
+    let reused = ctx.workflow.run(&scope, Some("k"), "adjust_balance", &sibling.id, || pay(&ctx, &sibling)).await;
+    match reused {
+        Err(Error::Validation(message)) => assert!(message.contains("different arguments")),
+        other => panic!("Expected the key to be refused, got {:?}", other.map(|r| r.is_ok())),
+    }
+    assert!(ctx.ledger_repo.list_entries(sibling.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_failures_are_not_stored() {
+    let ctx = setup_test().await;
+    let kid = kid(&ctx).await;
+    let scope = uuid::Uuid::new_v4().to_string();
+
+    let failed: Result<uuid::Uuid, String> = ctx.workflow
+        .run(&scope, Some("k"), "adjust_balance", &kid.id, || async { Err("connection reset".to_string()) })
+        .await
+        .unwrap();
+    assert!(failed.is_err());
+
+    // The retry runs for real
+    ctx.workflow.run(&scope, Some("k"), "adjust_balance", &kid.id, || pay(&ctx, &kid)).await.unwrap().unwrap();
+    assert_eq!(ctx.ledger_repo.list_entries(kid.id).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_concurrent_retries_run_once() {
+    let ctx = setup_test().await;
+    let kid = kid(&ctx).await;
+    let scope = uuid::Uuid::new_v4().to_string();
+
+    let (first, second) = tokio::join!(
+        ctx.workflow.run(&scope, Some("k"), "adjust_balance", &kid.id, || pay(&ctx, &kid)),
+        ctx.workflow.run(&scope, Some("k"), "adjust_balance", &kid.id, || pay(&ctx, &kid)),
+    );
+    // The loser either replays the result or is told the first is still running
+    assert!(first.is_ok() || second.is_ok());
+    assert_eq!(ctx.ledger_repo.list_entries(kid.id).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_expired_keys() {
+    let ctx = setup_test().await;
+    let kid = kid(&ctx).await;
+    let scope = uuid::Uuid::new_v4().to_string();
+
+    let mut old = IdempotencyKey::new(&scope, "k", "adjust_balance", &kid.id, Utc::now() - Duration::hours(25)).unwrap();
+    old.response = Some("\"00000000-0000-0000-0000-000000000000\"".to_string());
+    ctx.repo.reserve(old.clone()).await.unwrap();
+
+    // Past the retention window the key counts as new
+    ctx.workflow.run(&scope, Some("k"), "adjust_balance", &kid.id, || pay(&ctx, &kid)).await.unwrap().unwrap();
+    assert_eq!(ctx.ledger_repo.list_entries(kid.id).await.unwrap().len(), 1);
+
+    let stale = IdempotencyKey::new(&scope, "stale", "adjust_balance", &kid.id, Utc::now() - Duration::hours(25)).unwrap();
+    ctx.repo.reserve(stale.clone()).await.unwrap();
+    assert!(ctx.workflow.purge_expired(Utc::now()).await.unwrap() >= 1);
+    assert!(ctx.repo.find(stale.id).await.unwrap().is_none());
+    assert!(ctx.repo.find(old.id).await.unwrap().is_some());
+}
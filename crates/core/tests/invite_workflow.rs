@@ -0,0 +1,134 @@
+use loaa_core::db::{HouseholdRepository, InviteRepository, KidRepository, Repository, UserRepository};
+use loaa_core::models::{default_invite_ttl, HouseholdRole, Kid};
+use loaa_core::workflows::{HouseholdWorkflow, InviteWorkflow};
+use loaa_core::Error;
+use chrono::{Duration, Utc};
+
+mod common;
+use common::init_database;
+
+// NOTE: Tests require a running SurrealDB server on 127.0.0.1:8000
+// Run `just db` before running tests, or run them on SQLite with
+// LOAA_TEST_DB_MODE=sqlite
+
+const SECRET: &str = "test-invite-secret";
+
+async fn setup_test() -> (InviteWorkflow, HouseholdWorkflow, KidRepository) {
+    let db_url = std::env::var("LOAA_DB_URL").unwrap_or_else(|_| "127.0.0.1:8000".to_string());
+    let database = init_database(&db_url)
+        .await
+        .expect("Failed to initialize database");
+
+    let workflow = InviteWorkflow::new(
+        InviteRepository::new(database.client.clone()),
+        UserRepository::new(database.client.clone()),
+        HouseholdWorkflow::new(HouseholdRepository::new(database.client.clone())),
+        SECRET.to_string(),
+    );
+    (
+        workflow,
+        HouseholdWorkflow::new(HouseholdRepository::new(database.client.clone())),
+        KidRepository::new(database.client.clone()),
+    )
+}
+
+fn unique_username() -> String {
+    format!("parent-{}", uuid::Uuid::new_v4().simple())
+}
+
+#[tokio::test]
+async fn test_register_with_invite_joins_household() {
+    let (workflow, households, _) = setup_test().await;
+    let mom = uuid::Uuid::new_v4().to_string();
+    let household = households.household_for_user(&mom).await.unwrap();
+
+    let (invite, code) = workflow.create(&mom, HouseholdRole::Parent, default_invite_ttl()).await.unwrap();
+    assert_eq!(workflow.list_pending(&mom, Utc::now()).await.unwrap().len(), 1);
+
+    let (user, joined) = workflow
+        .register(&code, unique_username(), "correct horse", Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(joined.id, household.id);
+    assert_eq!(joined.role_of(&user.id.to_string()), Some(HouseholdRole::Parent));
+
+    // Single use
+    let again = workflow.register(&code, unique_username(), "correct horse", Utc::now()).await;
+    assert!(again.is_err());
+    assert!(workflow.list_pending(&mom, Utc::now()).await.unwrap().is_empty());
+    assert!(workflow.revoke(&mom, invite.id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_invite_expiry_revocation_and_forgery() {
+    let (workflow, households, _) = setup_test().await;
+    let mom = uuid::Uuid::new_v4().to_string();
+    households.household_for_user(&mom).await.unwrap();
+
+    let (_, code) = workflow.create(&mom, HouseholdRole::Viewer, Duration::hours(1)).await.unwrap();
+    let later = Utc::now() + Duration::hours(2);
+    assert!(workflow.register(&code, unique_username(), "correct horse", later).await.is_err());
+
+    let (invite, code) = workflow.create(&mom, HouseholdRole::Viewer, default_invite_ttl()).await.unwrap();
+    workflow.revoke(&mom, invite.id).await.unwrap();
+    assert!(workflow.check(&code, Utc::now()).await.is_err());
+
+    // Codes signed with a different secret are rejected
+    let forged = loaa_core::sign_invite_code(uuid::Uuid::new_v4(), "not-the-secret");
+    assert!(workflow.check(&forged, Utc::now()).await.is_err());
+
+    // Other households can't revoke the invite
+    let (invite, _) = workflow.create(&mom, HouseholdRole::Viewer, default_invite_ttl()).await.unwrap();
+    let stranger = uuid::Uuid::new_v4().to_string();
+    assert!(workflow.revoke(&stranger, invite.id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_invite_role_limits() {
+    let (workflow, households, _) = setup_test().await;
+    let mom = uuid::Uuid::new_v4().to_string();
+    households.household_for_user(&mom).await.unwrap();
+
+    let (_, code) = workflow.create(&mom, HouseholdRole::Viewer, default_invite_ttl()).await.unwrap();
+    let (grandma, _) = workflow.register(&code, unique_username(), "correct horse", Utc::now()).await.unwrap();
+    let grandma_id = grandma.id.to_string();
+
+    // Viewers can't invite, parents can't hand out ownership
+    assert!(workflow.create(&grandma_id, HouseholdRole::Viewer, default_invite_ttl()).await.is_err());
+
+    let (_, code) = workflow.create(&mom, HouseholdRole::Parent, default_invite_ttl()).await.unwrap();
+    let (dad, _) = workflow.register(&code, unique_username(), "correct horse", Utc::now()).await.unwrap();
+    let dad_id = dad.id.to_string();
+    assert!(workflow.create(&dad_id, HouseholdRole::Owner, default_invite_ttl()).await.is_err());
+    assert!(workflow.create(&dad_id, HouseholdRole::Parent, default_invite_ttl()).await.is_ok());
+
+    // Weak passwords are refused before the invite is used up
+    let (_, code) = workflow.create(&mom, HouseholdRole::Parent, default_invite_ttl()).await.unwrap();
+    assert!(workflow.register(&code, unique_username(), "short", Utc::now()).await.is_err());
+    assert!(workflow.check(&code, Utc::now()).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_accepting_keeps_the_users_own_kids() {
+    let (workflow, households, kid_repo) = setup_test().await;
+    let mom = uuid::Uuid::new_v4().to_string();
+    let household = households.household_for_user(&mom).await.unwrap();
+
+    // Grandpa tracks a kid of his own, then is invited to look on
+    let grandpa = uuid::Uuid::new_v4().to_string();
+    let own = households.household_for_user(&grandpa).await.unwrap();
+    let kid = kid_repo.create(Kid::new("Ola".to_string(), own.owner_id()).unwrap()).await.unwrap();
+    let (_, code) = workflow.create(&mom, HouseholdRole::Viewer, default_invite_ttl()).await.unwrap();
+
+    let refused = workflow.accept(&code, &grandpa, Utc::now()).await;
+    assert!(matches!(refused, Err(Error::Validation(_))));
+    let (current, role) = households.membership(&grandpa).await.unwrap();
+    assert_eq!((current.id, role), (own.id, HouseholdRole::Owner));
+    assert_eq!(kid_repo.get(kid.id).await.unwrap().owner_id, own.owner_id());
+
+    // The invite wasn't used up, and works once his household is empty
+    kid_repo.delete(kid.id).await.unwrap();
+    let joined = workflow.accept(&code, &grandpa, Utc::now()).await.unwrap();
+    assert_eq!(joined.id, household.id);
+    assert_eq!(joined.role_of(&grandpa), Some(HouseholdRole::Viewer));
+}
@@ -0,0 +1,119 @@
+use loaa_core::db::{KidRepository, Repository, UserRepository};
+use loaa_core::models::{HouseholdRole, Kid, User, MAX_FAILED_PIN_ATTEMPTS};
+use loaa_core::workflows::{KidAccountWorkflow, KidLogin};
+use loaa_core::Actor;
+use chrono::{Duration, Utc};
+
+mod common;
+use common::init_database;
+
+// NOTE: Tests require a running SurrealDB server on 127.0.0.1:8000
+// Run `just db` before running tests, or run them on SQLite with
+// LOAA_TEST_DB_MODE=sqlite
+
+async fn setup_test() -> (KidAccountWorkflow, KidRepository) {
+    let db_url = std::env::var("LOAA_DB_URL").unwrap_or_else(|_| "127.0.0.1:8000".to_string());
+    let database = init_database(&db_url)
+        .await
+        .expect("Failed to initialize database");
+
+    let workflow = KidAccountWorkflow::new(
+        UserRepository::new(database.client.clone()),
+        KidRepository::new(database.client.clone()),
+    );
+    (workflow, KidRepository::new(database.client.clone()))
+}
+
+fn parent(owner_id: &str) -> Actor {
+    Actor::member("parent", owner_id, HouseholdRole::Owner)
+}
+
+fn unique_username() -> String {
+    format!("kid-{}", uuid::Uuid::new_v4().simple())
+}
+
+async fn create_kid_with_login(workflow: &KidAccountWorkflow, kid_repo: &KidRepository) -> (String, Kid, User) {
+    let owner_id = uuid::Uuid::new_v4().to_string();
+    let kid = kid_repo.create(Kid::new("Maya".to_string(), owner_id.clone()).unwrap()).await.unwrap();
+    let user = workflow
+        .create_account(&parent(&owner_id), kid.id, unique_username(), "4821")
+        .await
+        .unwrap();
+    (owner_id, kid, user)
+}
+
+#[tokio::test]
+async fn test_kid_login_with_pin() {
+    let (workflow, kid_repo) = setup_test().await;
+    let (_, kid, user) = create_kid_with_login(&workflow, &kid_repo).await;
+
+    assert!(user.is_kid());
+    assert_eq!(user.kid_id, Some(kid.id));
+
+    match workflow.login(&user.username, "4821", Utc::now()).await.unwrap() {
+        KidLogin::Success { kid: logged_in, .. } => assert_eq!(logged_in.id, kid.id),
+        other => panic!("Expected successful login, got {:?}", other),
+    }
+
+    let wrong = workflow.login(&user.username, "0000", Utc::now()).await.unwrap();
+    assert!(matches!(wrong, KidLogin::InvalidCredentials));
+}
+
+#[tokio::test]
+async fn test_kid_login_locks_after_failed_attempts() {
+    let (workflow, kid_repo) = setup_test().await;
+    let (owner_id, kid, user) = create_kid_with_login(&workflow, &kid_repo).await;
+    let now = Utc::now();
+
+    for _ in 0..MAX_FAILED_PIN_ATTEMPTS - 1 {
+        let result = workflow.login(&user.username, "1111", now).await.unwrap();
+        assert!(matches!(result, KidLogin::InvalidCredentials));
+    }
+    let result = workflow.login(&user.username, "1111", now).await.unwrap();
+    assert!(matches!(result, KidLogin::Locked { .. }));
+
+    // Even the right PIN is refused while locked
+    let result = workflow.login(&user.username, "4821", now).await.unwrap();
+    assert!(matches!(result, KidLogin::Locked { .. }));
+
+    // The lock expires
+    let later = now + Duration::minutes(16);
+    let result = workflow.login(&user.username, "4821", later).await.unwrap();
+    assert!(matches!(result, KidLogin::Success { .. }));
+
+    // A parent resetting the PIN also clears the lock
+    for _ in 0..MAX_FAILED_PIN_ATTEMPTS {
+        workflow.login(&user.username, "1111", later).await.unwrap();
+    }
+    workflow.reset_pin(&parent(&owner_id), kid.id, "9999").await.unwrap();
+    let result = workflow.login(&user.username, "9999", later).await.unwrap();
+    assert!(matches!(result, KidLogin::Success { .. }));
+}
+
+#[tokio::test]
+async fn test_create_account_requires_owned_kid() {
+    let (workflow, kid_repo) = setup_test().await;
+    let owner_id = uuid::Uuid::new_v4().to_string();
+    let kid = kid_repo.create(Kid::new("Noa".to_string(), owner_id.clone()).unwrap()).await.unwrap();
+
+    let other_owner = uuid::Uuid::new_v4().to_string();
+    let result = workflow.create_account(&parent(&other_owner), kid.id, unique_username(), "1234").await;
+    assert!(result.is_err());
+
+    let bad_pin = workflow.create_account(&parent(&owner_id), kid.id, unique_username(), "12").await;
+    assert!(bad_pin.is_err());
+
+    workflow.create_account(&parent(&owner_id), kid.id, unique_username(), "1234").await.unwrap();
+    let duplicate = workflow.create_account(&parent(&owner_id), kid.id, unique_username(), "1234").await;
+    assert!(duplicate.is_err());
+
+    let accounts = workflow.list_accounts(&parent(&owner_id)).await.unwrap();
+    assert_eq!(accounts.len(), 1);
+}
+
+#[tokio::test]
+async fn test_parent_accounts_cannot_use_pin_login() {
+    let (workflow, _) = setup_test().await;
+    let result = workflow.login("no-such-user", "1234", Utc::now()).await.unwrap();
+    assert!(matches!(result, KidLogin::InvalidCredentials));
+}
@@ -0,0 +1,105 @@
+use loaa_core::config::{DatabaseConfig, DatabaseMode};
+use loaa_core::db::{Database, KidRepository, LedgerRepository, Repository};
+use loaa_core::models::{Kid, LedgerEntry};
+use rust_decimal_macros::dec;
+use std::time::{Duration, Instant};
+
+mod common;
+use common::init_database_with_config;
+
+// Runs on its own in-memory database, so the entry counts are exact and no
+// SurrealDB server is needed
+
+const OTHER_KIDS: usize = 100;
+const ENTRIES_PER_OTHER_KID: usize = 200;
+
+async fn fresh_database() -> Database {
+    init_database_with_config(&DatabaseConfig {
+        mode: DatabaseMode::Memory,
+        url: None,
+        path: None,
+        namespace: None,
+        database: None,
+        username: None,
+        password: None,
+        token: None,
+    })
+    .await
+    .expect("Failed to initialize database")
+}
+
+/// Fastest of a few fetches of the kid's ledger, to smooth out scheduling noise
+async fn fetch_time(ledger_repo: &LedgerRepository, kid: &Kid) -> Duration {
+    let mut fastest = Duration::MAX;
+    for _ in 0..10 {
+        let started = Instant::now();
+        let ledger = ledger_repo.get_ledger(kid.id).await.unwrap();
+        fastest = fastest.min(started.elapsed());
+        assert_eq!(ledger.entries.len(), 10);
+    }
+    fastest
+}
+
+#[tokio::test]
+async fn test_ledger_fetch_uses_the_kid_index() {
+    let database = fresh_database().await;
+    let kid_repo = KidRepository::new(database.client.clone());
+    let ledger_repo = LedgerRepository::new(database.client.clone());
+
+    let kid = kid_repo.create(Kid::new("Keoni".to_string(), "family".to_string()).unwrap()).await.unwrap();
+    for _ in 0..10 {
+        ledger_repo
+            .create_entry(LedgerEntry::adjusted(kid.id, dec!(1.00), "Allowance".to_string()))
+            .await
+            .unwrap();
+    }
+    let small = fetch_time(&ledger_repo, &kid).await;
+
+    // Tens of thousands of entries belonging to other kids
+    database.client
+        .query(
+            "FOR $k IN 1..=$kids {
+                LET $kid = type::thing('kid', rand::uuid());
+                FOR $e IN 1..=$entries {
+                    CREATE ledger_entry CONTENT {
+                        kid_id: $kid,
+                        amount: '1.00',
+                        entry_type: 'Adjusted',
+                        description: 'Noise',
+                        created_at: time::now()
+                    };
+                };
+            }",
+        )
+        .bind(("kids", OTHER_KIDS))
+        .bind(("entries", ENTRIES_PER_OTHER_KID))
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+    let total: Option<usize> = database.client
+        .query("RETURN count(SELECT id FROM ledger_entry)")
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+    assert_eq!(total, Some(10 + OTHER_KIDS * ENTRIES_PER_OTHER_KID));
+
+    let large = fetch_time(&ledger_repo, &kid).await;
+    println!("ledger fetch: {:?} with 10 entries, {:?} with {} entries", small, large, total.unwrap());
+
+    // The lookup goes through the index rather than scanning the table
+    let plan: surrealdb::Value = database.client
+        .query("SELECT * FROM ledger_entry WHERE kid_id = $kid EXPLAIN")
+        .bind(("kid", surrealdb::sql::Thing::from(("kid", kid.id.to_string().as_str()))))
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+    let plan = format!("{:?}", plan);
+    assert!(plan.contains("ledger_entry_kid"), "Expected an index lookup, got {}", plan);
+
+    // Scanning every entry takes hundreds of times longer than the indexed
+    // fetch, so this only fails if the fetch stops using the index
+    assert!(large < small * 5, "Fetch took {:?} with {} entries but {:?} with 10", large, total.unwrap(), small);
+}
@@ -0,0 +1,159 @@
+use loaa_core::config::{DatabaseConfig, DatabaseMode};
+use loaa_core::db::{Database, KidRepository, LedgerRepository, Migration, MigrationRunner, Repository, UserRepository, MIGRATIONS};
+use loaa_core::models::{Kid, LedgerEntry, User};
+use rust_decimal_macros::dec;
+use loaa_core::Error;
+
+mod common;
+
+// These tests start from an empty in-memory database, so they don't need
+// a running SurrealDB server
+
+async fn empty_database() -> Database {
+    common::connect(&DatabaseConfig {
+        mode: DatabaseMode::Memory,
+        url: None,
+        path: None,
+        namespace: None,
+        database: None,
+        username: None,
+        password: None,
+        token: None,
+    })
+    .await
+    .expect("Failed to connect to database")
+}
+
+#[tokio::test]
+async fn test_dry_run_then_apply() {
+    let database = empty_database().await;
+    let runner = MigrationRunner::new(database.client.clone());
+
+    // Listing pending migrations changes nothing
+    assert_eq!(runner.pending().await.unwrap().len(), MIGRATIONS.len());
+    assert_eq!(runner.pending().await.unwrap().len(), MIGRATIONS.len());
+    assert!(runner.applied().await.unwrap().is_empty());
+
+    assert_eq!(runner.run().await.unwrap().len(), MIGRATIONS.len());
+    let applied = runner.applied().await.unwrap();
+    assert_eq!(applied.len(), MIGRATIONS.len());
+    assert_eq!(applied[0].checksum, MIGRATIONS[0].checksum());
+
+    // Running again is a no-op
+    assert!(runner.pending().await.unwrap().is_empty());
+    assert!(runner.run().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_legacy_records_are_backfilled() {
+    let database = empty_database().await;
+    let kid_repo = KidRepository::new(database.client.clone());
+
+    // A kid saved before owners and versions existed
+    let kid = kid_repo.create(Kid::new("Makoa".to_string(), "family".to_string()).unwrap()).await.unwrap();
+    database.client
+        .query("UPDATE type::thing('kid', $id) UNSET owner_id, version")
+        .bind(("id", kid.id.to_string()))
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+
+    MigrationRunner::new(database.client.clone()).run().await.unwrap();
+
+    let owners: Vec<String> = database.client
+        .query("SELECT VALUE owner_id FROM kid")
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+    assert_eq!(owners, vec![String::new()]);
+
+    // And it can be saved again
+    let mut migrated = kid_repo.get(kid.id).await.unwrap();
+    assert_eq!(migrated.version, 0);
+    migrated.name = "Makoa K.".to_string();
+    assert_eq!(kid_repo.update(migrated).await.unwrap().version, 1);
+}
+
+#[tokio::test]
+async fn test_ledger_kid_ids_become_links() {
+    let database = empty_database().await;
+    let kid_repo = KidRepository::new(database.client.clone());
+    let kid = kid_repo.create(Kid::new("Ikaika".to_string(), "family".to_string()).unwrap()).await.unwrap();
+
+    // Older entries held the kid's ID as a string, sometimes uppercase
+    let entry = LedgerEntry::adjusted(kid.id, dec!(3.00), "Birthday".to_string());
+    database.client
+        .query("CREATE type::thing('ledger_entry', $id) CONTENT $entry")
+        .query("UPDATE ledger_entry SET kid_id = string::uppercase(kid_id)")
+        .bind(("id", entry.id.to_string()))
+        .bind(("entry", entry.clone()))
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+
+    MigrationRunner::new(database.client.clone()).run().await.unwrap();
+
+    let linked: Vec<bool> = database.client
+        .query("SELECT VALUE type::is::record(kid_id, 'kid') FROM ledger_entry")
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+    assert_eq!(linked, vec![true]);
+
+    let ledger_repo = LedgerRepository::new(database.client.clone());
+    let ledger = ledger_repo.get_ledger(kid.id).await.unwrap();
+    assert_eq!(ledger.balance, dec!(3.00));
+    assert_eq!(ledger_repo.get_entry(entry.id).await.unwrap().kid_id, kid.id);
+}
+
+#[tokio::test]
+async fn test_usernames_are_unique() {
+    let database = empty_database().await;
+    MigrationRunner::new(database.client.clone()).run().await.unwrap();
+    let user_repo = UserRepository::new(database.client.clone());
+
+    user_repo.create(User::new("leilani".to_string()).unwrap()).await.unwrap();
+    assert!(user_repo.create(User::new("leilani".to_string()).unwrap()).await.is_err());
+}
+
+const ORIGINAL: &[Migration] = &[Migration { version: 1, name: "widgets", script: "DEFINE TABLE widget;" }];
+const EDITED: &[Migration] = &[Migration { version: 1, name: "widgets", script: "DEFINE TABLE widget SCHEMAFULL;" }];
+const FAILING: &[Migration] = &[Migration {
+    version: 1,
+    name: "half_done",
+    script: "CREATE widget:1 SET size = 1; THROW 'out of widgets'",
+}];
+
+#[tokio::test]
+async fn test_edited_migration_is_refused() {
+    let database = empty_database().await;
+    MigrationRunner::with_migrations(database.client.clone(), ORIGINAL).run().await.unwrap();
+
+    let edited = MigrationRunner::with_migrations(database.client.clone(), EDITED);
+    assert!(matches!(edited.pending().await, Err(Error::Database(_))));
+    assert!(edited.run().await.is_err());
+
+    // Neither does an older build know what to make of it
+    let older = MigrationRunner::with_migrations(database.client.clone(), &[]);
+    assert!(older.pending().await.is_err());
+}
+
+#[tokio::test]
+async fn test_failed_migration_writes_nothing() {
+    let database = empty_database().await;
+    let runner = MigrationRunner::with_migrations(database.client.clone(), FAILING);
+
+    assert!(runner.run().await.is_err());
+    assert!(runner.applied().await.unwrap().is_empty());
+    let widgets: Vec<i64> = database.client
+        .query("SELECT VALUE size FROM widget")
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+    assert!(widgets.is_empty());
+}
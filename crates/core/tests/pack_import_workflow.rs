@@ -0,0 +1,58 @@
+use loaa_core::db::{TaskRepository};
+use loaa_core::models::HouseholdRole;
+use loaa_core::templates::TemplateLibrary;
+use loaa_core::workflows::PackImportWorkflow;
+use loaa_core::Actor;
+use rust_decimal_macros::dec;
+
+mod common;
+use common::init_database;
+
+// NOTE: Tests require a running SurrealDB server on 127.0.0.1:8000
+// Run `just db` before running tests, or run them on SQLite with
+// LOAA_TEST_DB_MODE=sqlite
+
+async fn setup_test() -> (PackImportWorkflow, TaskRepository) {
+    let db_url = std::env::var("LOAA_DB_URL").unwrap_or_else(|_| "127.0.0.1:8000".to_string());
+    let database = init_database(&db_url)
+        .await
+        .expect("Failed to initialize database");
+
+    let workflow = PackImportWorkflow::new(TaskRepository::new(database.client.clone()));
+    (workflow, TaskRepository::new(database.client.clone()))
+}
+
+#[tokio::test]
+async fn test_import_pack_scales_values() {
+    let (workflow, task_repo) = setup_test().await;
+    let owner_id = uuid::Uuid::new_v4().to_string();
+    let parent = Actor::member("parent", owner_id.clone(), HouseholdRole::Owner);
+    let library = TemplateLibrary::builtin();
+    let pack = library.get("elementary-kitchen").unwrap();
+
+    let result = workflow.import_pack(pack, &parent, dec!(2)).await.unwrap();
+    assert_eq!(result.created.len(), pack.templates.len());
+    assert!(result.skipped.is_empty());
+
+    let tasks = task_repo.list_by_owner(&owner_id).await.unwrap();
+    assert_eq!(tasks.len(), pack.templates.len());
+    let unload = tasks.iter().find(|t| t.name == "Unload dishwasher").unwrap();
+    assert_eq!(unload.value, dec!(2.00));
+}
+
+#[tokio::test]
+async fn test_import_pack_twice_skips_existing() {
+    let (workflow, task_repo) = setup_test().await;
+    let owner_id = uuid::Uuid::new_v4().to_string();
+    let parent = Actor::member("parent", owner_id.clone(), HouseholdRole::Owner);
+    let library = TemplateLibrary::builtin();
+    let pack = library.get("elementary-pets").unwrap();
+
+    workflow.import_pack(pack, &parent, dec!(1)).await.unwrap();
+    let second = workflow.import_pack(pack, &parent, dec!(1)).await.unwrap();
+
+    assert!(second.created.is_empty());
+    assert_eq!(second.skipped.len(), pack.templates.len());
+    let tasks = task_repo.list_by_owner(&owner_id).await.unwrap();
+    assert_eq!(tasks.len(), pack.templates.len());
+}
@@ -0,0 +1,95 @@
+use loaa_core::config::{DatabaseConfig, DatabaseMode};
+use loaa_core::db::{Database, KidRepository, LedgerRepository, Repository, TaskRepository};
+use loaa_core::models::{Cadence, HouseholdRole, Kid, LedgerEntry, Task, TaskCategory};
+use loaa_core::reports::{ReportPeriod, ReportQuery};
+use loaa_core::{Actor, Error, ReportWorkflow};
+use chrono::{NaiveDate, TimeZone, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+mod common;
+
+// These tests use fresh in-memory databases, so they don't need a running
+// SurrealDB server
+
+async fn fresh_database() -> Database {
+    common::init_database_with_config(&DatabaseConfig {
+        mode: DatabaseMode::Memory,
+        url: None,
+        path: None,
+        namespace: None,
+        database: None,
+        username: None,
+        password: None,
+        token: None,
+    })
+    .await
+    .expect("Failed to initialize database")
+}
+
+#[tokio::test]
+async fn test_earnings_are_summed_by_the_database() {
+    let database = fresh_database().await;
+    let kids = KidRepository::new(database.client.clone());
+    let tasks = TaskRepository::new(database.client.clone());
+    let ledger = LedgerRepository::new(database.client.clone());
+    let workflow = ReportWorkflow::new(
+        KidRepository::new(database.client.clone()),
+        TaskRepository::new(database.client.clone()),
+        LedgerRepository::new(database.client.clone()),
+    );
+
+    let kai = kids.create(Kid::new("Kai".to_string(), "owner-a".to_string()).unwrap()).await.unwrap();
+    let other = kids.create(Kid::new("Other".to_string(), "owner-b".to_string()).unwrap()).await.unwrap();
+    let lawn = tasks
+        .create(
+            Task::new("Mow lawn".to_string(), String::new(), dec!(5.00), Cadence::Weekly, "owner-a".to_string())
+                .unwrap()
+                .with_category(Some(TaskCategory::Yard)),
+        )
+        .await
+        .unwrap();
+
+    let entries = [
+        ((8, 20), LedgerEntry::task_completion(kai.id, &lawn, None)),
+        ((9, 14), LedgerEntry::task_completion(kai.id, &lawn, None)),
+        ((9, 16), LedgerEntry::task_completion(kai.id, &lawn, None)),
+        ((9, 16), LedgerEntry::earned(kai.id, dec!(1.25), "Helped out".to_string())),
+        ((9, 20), LedgerEntry::adjusted(kai.id, dec!(-3.00), "Toy".to_string())),
+        ((9, 21), LedgerEntry::earned(other.id, dec!(100.00), "Not ours".to_string())),
+    ];
+    for ((month, day), mut entry) in entries {
+        entry.created_at = Utc.with_ymd_and_hms(2026, month, day, 9, 30, 0).unwrap();
+        ledger.create_entry(entry).await.unwrap();
+    }
+
+    let september = |period| {
+        ReportQuery::new(
+            None,
+            period,
+            NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 9, 30).unwrap(),
+        )
+        .unwrap()
+    };
+    let parent = Actor::member("parent", "owner-a", HouseholdRole::Owner);
+    let report = workflow.earnings(&parent, &september(ReportPeriod::Month)).await.unwrap();
+    assert_eq!(report.kids.len(), 1);
+    let kid = &report.kids[0];
+    assert_eq!((kid.earned, kid.net, kid.previous_earned), (dec!(11.25), dec!(8.25), dec!(5.00)));
+    assert_eq!(kid.by_task[0].label, "Mow lawn");
+    assert_eq!((kid.by_task[0].total, kid.by_task[0].entries), (dec!(10.00), 2));
+    assert_eq!(kid.by_category[0].key, "yard");
+
+    // Weeks start on Monday the 14th and 21st; Aug 31 starts the first
+    let report = workflow.earnings(&parent, &september(ReportPeriod::Week)).await.unwrap();
+    let weeks: Vec<(NaiveDate, Decimal)> = report.kids[0].periods.iter().map(|p| (p.start, p.earned)).collect();
+    assert_eq!(weeks[0].0, NaiveDate::from_ymd_opt(2026, 8, 31).unwrap());
+    assert_eq!(weeks[2], (NaiveDate::from_ymd_opt(2026, 9, 14).unwrap(), dec!(11.25)));
+    assert_eq!(report.kids[0].periods[2].net, dec!(8.25));
+
+    let mut other_kid = september(ReportPeriod::Month);
+    other_kid.kid_id = Some(other.id);
+    let refused = workflow.earnings(&parent, &other_kid).await;
+    assert!(matches!(refused, Err(Error::Forbidden(_))));
+}
@@ -0,0 +1,62 @@
+use loaa_core::db::{InMemoryRepository, KidRepository, Repository, SqliteDatabase};
+use loaa_core::models::Kid;
+use loaa_core::Error;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+mod common;
+use common::init_database;
+
+async fn setup_test_db() -> (TempDir, KidRepository) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("test.db");
+    let database = init_database(db_path.to_str().unwrap())
+        .await
+        .expect("Failed to initialize database");
+    let repo = KidRepository::new(database.client.clone());
+    (temp_dir, repo)
+}
+
+/// What every `Repository` has to do, so the in-memory one can stand in for
+/// the database in tests
+async fn check_repository(repo: &impl Repository<Kid>) {
+    assert!(repo.list().await.unwrap().is_empty());
+    assert!(matches!(repo.get(Uuid::new_v4()).await, Err(Error::NotFound(_))));
+
+    let kid = repo.create(Kid::new("Ada".to_string(), "test-owner".to_string()).unwrap()).await.unwrap();
+    assert_eq!(repo.get(kid.id).await.unwrap().name, "Ada");
+    assert!(repo.create(kid.clone()).await.is_err());
+
+    // Saves bump the version and refuse stale copies
+    let mut renamed = kid.clone();
+    renamed.name = "Ada L".to_string();
+    let saved = repo.update(renamed).await.unwrap();
+    assert_eq!(saved.version, 1);
+    assert!(matches!(repo.update(kid.clone()).await, Err(Error::Conflict(_))));
+    assert_eq!(repo.get(kid.id).await.unwrap().name, "Ada L");
+
+    let stranger = Kid::new("Bo".to_string(), "test-owner".to_string()).unwrap();
+    assert!(matches!(repo.update(stranger).await, Err(Error::NotFound(_))));
+    assert_eq!(repo.list().await.unwrap().len(), 1);
+
+    repo.delete(kid.id).await.unwrap();
+    repo.delete(kid.id).await.unwrap();
+    assert!(matches!(repo.get(kid.id).await, Err(Error::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_surreal_repository() {
+    let (_temp_dir, repo) = setup_test_db().await;
+    check_repository(&repo).await;
+}
+
+#[tokio::test]
+async fn test_in_memory_repository() {
+    check_repository(&InMemoryRepository::<Kid>::new()).await;
+}
+
+#[tokio::test]
+async fn test_sqlite_repository() {
+    let database = SqliteDatabase::memory().unwrap();
+    check_repository(&database.repository::<Kid>()).await;
+}
@@ -0,0 +1,241 @@
+use loaa_core::config::{DatabaseConfig, DatabaseMode};
+use loaa_core::db::{
+    AttachmentRepository, ClaimRepository, Database, HouseholdRepository, IdempotencyRepository, InviteRepository,
+    KidRepository, LedgerRepository, MigrationRunner, Repository, SqliteDatabase, TaskRepository, TransferRepository,
+    UserRepository, WithdrawalRepository,
+};
+use loaa_core::models::{
+    Attachment, Cadence, Household, HouseholdRole, IdempotencyKey, Invite, Kid, LedgerEntry, Task, TaskClaim, User,
+};
+use loaa_core::workflows::{BackupWorkflow, TransferWorkflow, WithdrawalWorkflow, BACKUP_TABLES};
+use loaa_core::{Actor, Error};
+use chrono::{Duration, Utc};
+use rust_decimal_macros::dec;
+use std::future::Future;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn sqlite_config(path: &Path) -> DatabaseConfig {
+    DatabaseConfig {
+        mode: DatabaseMode::Sqlite,
+        url: None,
+        path: Some(path.to_path_buf()),
+        namespace: None,
+        database: None,
+        username: None,
+        password: None,
+        token: None,
+    }
+}
+
+/// Run as if the app had just started: once the runtime shuts down, SQLite
+/// mode lets go of the file
+fn run<T>(future: impl Future<Output = T>) -> T {
+    tokio::runtime::Runtime::new().expect("Failed to start runtime").block_on(future)
+}
+
+async fn open(path: &Path) -> Database {
+    Database::init_with_config(&sqlite_config(path))
+        .await
+        .expect("Failed to open SQLite database")
+}
+
+/// Records in every table, made through the same workflows the servers use
+async fn fill_every_table(database: &Database) -> Kid {
+    let client = database.client.clone();
+    let kids = KidRepository::new(client.clone());
+    let kai = kids.create(Kid::new("Kai".to_string(), "test-owner".to_string()).unwrap()).await.unwrap();
+    let mia = kids.create(Kid::new("Mia".to_string(), "test-owner".to_string()).unwrap()).await.unwrap();
+    LedgerRepository::new(client.clone())
+        .create_entry(LedgerEntry::earned(kai.id, dec!(2.50), "Dishes".to_string()))
+        .await
+        .unwrap();
+    let task = TaskRepository::new(client.clone())
+        .create(Task::new("Dishes".to_string(), "".to_string(), dec!(2.50), Cadence::Daily, "test-owner".to_string()).unwrap())
+        .await
+        .unwrap();
+    UserRepository::new(client.clone()).create(User::new("parent".to_string()).unwrap()).await.unwrap();
+    let household = HouseholdRepository::new(client.clone())
+        .create(Household::new("Home".to_string(), "parent".to_string()).unwrap())
+        .await
+        .unwrap();
+    InviteRepository::new(client.clone())
+        .create(Invite::new(household.id, HouseholdRole::Viewer, "parent".to_string(), Duration::days(1)))
+        .await
+        .unwrap();
+    ClaimRepository::new(client.clone())
+        .create(TaskClaim::new("test-owner".to_string(), kai.id, task.id, None, None))
+        .await
+        .unwrap();
+    AttachmentRepository::new(client.clone())
+        .create(Attachment::new("test-owner".to_string(), kai.id, Some(task.id), "image/png".to_string(), 10).unwrap())
+        .await
+        .unwrap();
+    WithdrawalWorkflow::new(WithdrawalRepository::new(client.clone()), KidRepository::new(client.clone()), LedgerRepository::new(client.clone()))
+        .request(&Actor::kid("test-owner", kai.id), kai.id, dec!(0.50), None)
+        .await
+        .unwrap();
+    TransferWorkflow::new(TransferRepository::new(client.clone()), kids, LedgerRepository::new(client.clone()))
+        .transfer(&Actor::member("parent", "test-owner", HouseholdRole::Owner), kai.id, mia.id, dec!(0.25), None, false)
+        .await
+        .unwrap();
+    IdempotencyRepository::new(client)
+        .reserve(IdempotencyKey::new("parent", "key-1", "create_kid", &["Kai"], Utc::now()).unwrap())
+        .await
+        .unwrap();
+    kai
+}
+
+#[test]
+fn test_sqlite_mode_keeps_every_table_across_restarts() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("loaa.sqlite");
+    let now = Utc::now();
+
+    let (kai, archive) = run(async {
+        let database = open(&path).await;
+        let kai = fill_every_table(&database).await;
+        let archive = BackupWorkflow::new(database.client.clone()).export(now).await.unwrap();
+        database.flush().await.unwrap();
+        (kai, archive)
+    });
+
+    run(async {
+        let database = open(&path).await;
+        let client = database.client.clone();
+        assert_eq!(BackupWorkflow::new(client.clone()).export(now).await.unwrap(), archive);
+        let manifest = BackupWorkflow::new(client.clone()).verify(&archive).unwrap();
+        assert!(manifest.tables.iter().all(|t| t.records > 0 && BACKUP_TABLES.contains(&t.name.as_str())));
+        let key = IdempotencyRepository::new(client.clone())
+            .find(IdempotencyKey::record_id("parent", "key-1"))
+            .await
+            .unwrap();
+        assert_eq!(key.map(|k| k.operation).as_deref(), Some("create_kid"));
+        assert_eq!(LedgerRepository::new(client.clone()).get_ledger(kai.id).await.unwrap().balance, dec!(1.75));
+
+        // The schema came back too: nothing left to migrate, and usernames are still unique
+        let migrations = MigrationRunner::new(client.clone());
+        assert!(migrations.pending().await.unwrap().is_empty());
+        assert_eq!(migrations.applied().await.unwrap().len(), loaa_core::db::MIGRATIONS.len());
+        assert!(UserRepository::new(client).create(User::new("parent".to_string()).unwrap()).await.is_err());
+    });
+}
+
+#[test]
+fn test_sqlite_mode_saves_changes_without_flushing() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("loaa.sqlite");
+
+    let (kept, deleted) = run(async {
+        let database = open(&path).await;
+        let kids = KidRepository::new(database.client.clone());
+        let kept = kids.create(Kid::new("Kai".to_string(), "test-owner".to_string()).unwrap()).await.unwrap();
+        let deleted = kids.create(Kid::new("Noa".to_string(), "test-owner".to_string()).unwrap()).await.unwrap();
+        let mut renamed = kept.clone();
+        renamed.name = "Kainoa".to_string();
+        kids.update(renamed).await.unwrap();
+        kids.delete(deleted.id).await.unwrap();
+        // Changes are saved in the background, a moment after they commit
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        (kept, deleted)
+    });
+
+    run(async {
+        let kids = KidRepository::new(open(&path).await.client.clone());
+        let kept = kids.get(kept.id).await.unwrap();
+        assert_eq!((kept.name.as_str(), kept.version), ("Kainoa", 1));
+        assert!(matches!(kids.get(deleted.id).await, Err(Error::NotFound(_))));
+    });
+}
+
+#[tokio::test]
+async fn test_sqlite_mode_shares_an_open_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("loaa.sqlite");
+
+    // Like the web server and the MCP server it runs, in one process
+    let web = open(&path).await;
+    let mcp = open(&path).await;
+    let kid = KidRepository::new(web.client.clone())
+        .create(Kid::new("Kai".to_string(), "test-owner".to_string()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(KidRepository::new(mcp.client.clone()).get(kid.id).await.unwrap().name, "Kai");
+}
+
+#[tokio::test]
+async fn test_records_survive_reopening() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("data").join("loaa.sqlite");
+
+    let kid = {
+        let database = SqliteDatabase::open(&path).unwrap();
+        database.repository::<Kid>()
+            .create(Kid::new("Kai".to_string(), "test-owner".to_string()).unwrap())
+            .await
+            .unwrap()
+    };
+
+    let database = SqliteDatabase::open(&path).unwrap();
+    assert_eq!(database.repository::<Kid>().get(kid.id).await.unwrap().name, "Kai");
+}
+
+#[tokio::test]
+async fn test_lists_by_owner_leave_out_archived() {
+    let database = SqliteDatabase::memory().unwrap();
+    let kids = database.repository::<Kid>();
+    let tasks = database.repository::<Task>();
+
+    let kai = kids.create(Kid::new("Kai".to_string(), "owner-a".to_string()).unwrap()).await.unwrap();
+    let mut lani = kids.create(Kid::new("Lani".to_string(), "owner-a".to_string()).unwrap()).await.unwrap();
+    kids.create(Kid::new("Noa".to_string(), "owner-b".to_string()).unwrap()).await.unwrap();
+    lani.archive(Utc::now());
+    kids.update(lani).await.unwrap();
+
+    let listed = kids.list_by_owner("owner-a").await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, kai.id);
+
+    tasks
+        .create(Task::new("Do dishes".to_string(), "".to_string(), dec!(2.00), Cadence::Daily, "owner-a".to_string()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(tasks.list_by_owner("owner-a").await.unwrap().len(), 1);
+    assert!(tasks.list_by_owner("owner-b").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_usernames_are_unique() {
+    let database = SqliteDatabase::memory().unwrap();
+    let users = database.repository::<User>();
+
+    let user = users.create(User::new("parent".to_string()).unwrap()).await.unwrap();
+    assert_eq!(users.get_by_username("parent").await.unwrap().id, user.id);
+    assert!(matches!(users.get_by_username("nobody").await, Err(Error::NotFound(_))));
+    assert!(users.create(User::new("parent".to_string()).unwrap()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_ledger() {
+    let database = SqliteDatabase::memory().unwrap();
+    let kid = database.repository::<Kid>()
+        .create(Kid::new("Kai".to_string(), "test-owner".to_string()).unwrap())
+        .await
+        .unwrap();
+    let other = database.repository::<Kid>()
+        .create(Kid::new("Noa".to_string(), "test-owner".to_string()).unwrap())
+        .await
+        .unwrap();
+    let ledger = database.repository::<LedgerEntry>();
+
+    let first = ledger.create_entry(LedgerEntry::earned(kid.id, dec!(2.50), "Dishes".to_string())).await.unwrap();
+    ledger.create_entry(LedgerEntry::adjusted(kid.id, dec!(-1.00), "Candy".to_string())).await.unwrap();
+    ledger.create_entry(LedgerEntry::earned(other.id, dec!(9.00), "Lawn".to_string())).await.unwrap();
+
+    assert_eq!(ledger.get_entry(first.id).await.unwrap().kid_id, kid.id);
+    assert_eq!(ledger.get_ledger(kid.id).await.unwrap().balance, dec!(1.50));
+
+    let entries = ledger.list_entries(kid.id).await.unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].description, "Candy");
+}
@@ -0,0 +1,71 @@
+use loaa_core::config::{DatabaseConfig, DatabaseMode};
+use loaa_core::db::{Database, KidRepository, LedgerRepository, Repository};
+use loaa_core::models::{HouseholdRole, Kid, LedgerEntry};
+use loaa_core::statements::month_start;
+use loaa_core::{Actor, StatementWorkflow};
+use chrono::{TimeZone, Utc};
+use rust_decimal_macros::dec;
+use tempfile::TempDir;
+
+mod common;
+
+// These tests use fresh in-memory databases, so they don't need a running
+// SurrealDB server
+
+async fn fresh_database() -> Database {
+    common::init_database_with_config(&DatabaseConfig {
+        mode: DatabaseMode::Memory,
+        url: None,
+        path: None,
+        namespace: None,
+        database: None,
+        username: None,
+        password: None,
+        token: None,
+    })
+    .await
+    .expect("Failed to initialize database")
+}
+
+#[tokio::test]
+async fn test_statements_are_archived_once() {
+    let database = fresh_database().await;
+    let kids = KidRepository::new(database.client.clone());
+    let ledger = LedgerRepository::new(database.client.clone());
+    let workflow = StatementWorkflow::new(
+        KidRepository::new(database.client.clone()),
+        LedgerRepository::new(database.client.clone()),
+    );
+
+    let kai = kids.create(Kid::new("Kai".to_string(), "owner-a".to_string()).unwrap()).await.unwrap();
+    let mut archived = Kid::new("Gone".to_string(), "owner-b".to_string()).unwrap();
+    archived.archive(Utc::now());
+    kids.create(archived).await.unwrap();
+    let mut entry = LedgerEntry::earned(kai.id, dec!(2.00), "Dishes".to_string());
+    entry.created_at = Utc.with_ymd_and_hms(2026, 9, 3, 12, 0, 0).unwrap();
+    ledger.create_entry(entry).await.unwrap();
+
+    let september = month_start(2026, 9).unwrap();
+    let parent = Actor::member("parent", "owner-a", HouseholdRole::Owner);
+    let statement = workflow.statement(&parent, kai.id, september).await.unwrap();
+    assert_eq!(statement.closing_balance, dec!(2.00));
+    let own = workflow.statement(&Actor::kid("owner-a", kai.id), kai.id, september).await.unwrap();
+    assert_eq!(own.closing_balance, dec!(2.00));
+    let stranger = Actor::member("stranger", "owner-b", HouseholdRole::Owner);
+    assert!(workflow.statement(&stranger, kai.id, september).await.is_err());
+
+    let dir = TempDir::new().unwrap();
+    let mid_september = Utc.with_ymd_and_hms(2026, 9, 20, 0, 0, 0).unwrap();
+    assert!(workflow.archive_month(dir.path(), september, mid_september).await.is_err());
+
+    let october = Utc.with_ymd_and_hms(2026, 10, 1, 3, 0, 0).unwrap();
+    assert_eq!(workflow.archive_month(dir.path(), september, october).await.unwrap(), 1);
+    let stem = statement.file_stem();
+    let pdf = std::fs::read(dir.path().join("2026-09").join(format!("{}.pdf", stem))).unwrap();
+    assert!(pdf.starts_with(b"%PDF-"));
+    let html = std::fs::read_to_string(dir.path().join("2026-09").join(format!("{}.html", stem))).unwrap();
+    assert!(html.contains("Dishes"));
+
+    // The next day's run finds nothing left to write
+    assert_eq!(workflow.archive_month(dir.path(), september, october).await.unwrap(), 0);
+}
@@ -1,11 +1,16 @@
-use loaa_core::db::{init_database, TaskRepository, KidRepository, LedgerRepository};
-use loaa_core::models::{Task, Kid, Cadence};
+use loaa_core::db::{InMemoryRepository, Repository, TaskRepository, KidRepository, LedgerRepository};
+use loaa_core::models::{Task, Kid, Cadence, CompletionReview, HouseholdRole, LedgerEntry};
 use loaa_core::workflows::TaskCompletionWorkflow;
+use loaa_core::{Actor, Error};
 use rust_decimal_macros::dec;
 use tempfile::TempDir;
 
+mod common;
+use common::init_database;
+
 // NOTE: Tests require a running SurrealDB server on 127.0.0.1:8000
-// Run `just db` before running tests
+// Run `just db` before running tests, or run them on SQLite with
+// LOAA_TEST_DB_MODE=sqlite
 
 async fn setup_test() -> (TempDir, TaskCompletionWorkflow, TaskRepository, KidRepository, LedgerRepository) {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -215,3 +220,78 @@ async fn test_complete_nonexistent_task() {
 
     assert!(result.is_err(), "Should fail when task doesn't exist");
 }
+
+#[tokio::test]
+async fn test_complete_task_with_partial_credit() {
+    let (_temp_dir, workflow, task_repo, kid_repo, ledger_repo) = setup_test().await;
+
+    // Create a kid
+    let kid = Kid::new("Dana".to_string(), "test-owner".to_string()).unwrap();
+    let kid_id = kid.id;
+    kid_repo.create(kid).await.unwrap();
+
+    // Create a task
+    let task = Task::new(
+        "Clean room".to_string(),
+        "Pick up and vacuum".to_string(),
+        dec!(4.00),
+        Cadence::Weekly,
+        "test-owner".to_string(),
+    )
+    .unwrap();
+    let task_id = task.id;
+    task_repo.create(task).await.unwrap();
+
+    // Approve at 50% with a rating and comment
+    let review = CompletionReview::new(Some(2), 50, Some("Floor still messy".to_string())).unwrap();
+    let entry = workflow
+        .complete_task_with_review(task_id, kid_id, Some(review))
+        .await
+        .unwrap();
+
+    assert_eq!(entry.amount, dec!(2.00));
+    assert_eq!(entry.original_value, Some(dec!(4.00)));
+    assert_eq!(entry.task_id, Some(task_id));
+
+    // Review survives the round trip through the database
+    let ledger = ledger_repo.get_ledger(kid_id).await.unwrap();
+    assert_eq!(ledger.balance, dec!(2.00));
+    let stored = &ledger.entries[0];
+    let stored_review = stored.review.as_ref().unwrap();
+    assert_eq!(stored_review.percent_paid, 50);
+    assert_eq!(stored_review.quality, Some(2));
+    assert_eq!(stored_review.comment.as_deref(), Some("Floor still messy"));
+    assert_eq!(ledger.average_quality().unwrap().rated_count, 1);
+}
+
+#[tokio::test]
+async fn test_complete_task_in_memory() {
+    let tasks = InMemoryRepository::<Task>::new();
+    let kids = InMemoryRepository::<Kid>::new();
+    let ledger = InMemoryRepository::<LedgerEntry>::new();
+    let workflow = TaskCompletionWorkflow::new(tasks.clone(), kids.clone(), ledger.clone());
+
+    let kid = kids.create(Kid::new("Eli".to_string(), "house-a".to_string()).unwrap()).await.unwrap();
+    let task = Task::new("Walk the dog".to_string(), "".to_string(), dec!(3.00), Cadence::Daily, "house-a".to_string()).unwrap();
+    let task = tasks.create(task).await.unwrap();
+
+    // Another household's parent can't pay the kid
+    let stranger = Actor::member("stranger", "house-b", HouseholdRole::Owner);
+    let refused = workflow.complete_task_as(&stranger, task.id, kid.id, None).await;
+    assert!(matches!(refused, Err(Error::Forbidden(_))));
+    assert!(ledger.list().await.unwrap().is_empty());
+
+    let parent = Actor::member("parent", "house-a", HouseholdRole::Owner);
+    let entry = workflow.complete_task_as(&parent, task.id, kid.id, None).await.unwrap();
+    assert_eq!(ledger.list().await.unwrap()[0].id, entry.id);
+    assert_eq!(entry.amount, dec!(3.00));
+    // The task was saved along with the payout
+    assert_eq!(tasks.get(task.id).await.unwrap().version, task.version + 1);
+
+    // Archived tasks can't be completed
+    let mut archived = tasks.get(task.id).await.unwrap();
+    archived.archive(chrono::Utc::now());
+    tasks.update(archived).await.unwrap();
+    assert!(matches!(workflow.complete_task(task.id, kid.id).await, Err(Error::Validation(_))));
+    assert_eq!(ledger.list().await.unwrap().len(), 1);
+}
@@ -1,9 +1,13 @@
-use loaa_core::db::{init_database, TaskRepository};
-use loaa_core::models::{Task, Cadence};
+use loaa_core::db::{patch_task, Repository, TaskRepository};
+use loaa_core::models::{Task, TaskCategory, TaskPatch, Cadence};
+use loaa_core::Error;
 use rust_decimal_macros::dec;
 use tempfile::TempDir;
 use uuid::Uuid;
 
+mod common;
+use common::init_database;
+
 async fn setup_test_db() -> (TempDir, TaskRepository) {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
     let db_path = temp_dir.path().join("test.db");
@@ -155,6 +159,73 @@ async fn test_update_nonexistent_task() {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn test_stale_update_conflicts() {
+    let (_temp_dir, repo) = setup_test_db().await;
+
+    let task = Task::new(
+        "Feed fish".to_string(),
+        "".to_string(),
+        dec!(1.00),
+        Cadence::Daily,
+        "test-owner".to_string(),
+    )
+    .unwrap();
+    let created = repo.create(task).await.unwrap();
+
+    // Two editors read the same version
+    let mut web = created.clone();
+    web.name = "Feed the fish".to_string();
+    let mut mcp = created.clone();
+    mcp.value = dec!(3.00);
+
+    let saved = repo.update(web).await.unwrap();
+    assert_eq!(saved.version, 1);
+    let stale = repo.update(mcp).await;
+    assert!(matches!(stale, Err(Error::Conflict(_))));
+
+    let current = repo.get(created.id).await.unwrap();
+    assert_eq!(current.name, "Feed the fish");
+    assert_eq!(current.value, dec!(1.00));
+    assert_eq!(current.version, 1);
+}
+
+#[tokio::test]
+async fn test_patches_keep_each_others_fields() {
+    let (_temp_dir, repo) = setup_test_db().await;
+
+    let task = Task::new(
+        "Feed fish".to_string(),
+        "".to_string(),
+        dec!(1.00),
+        Cadence::Daily,
+        "test-owner".to_string(),
+    )
+    .unwrap();
+    let created = repo.create(task).await.unwrap();
+
+    let rename = TaskPatch { name: Some("Feed the fish".to_string()), ..Default::default() };
+    let reprice = TaskPatch { value: Some(dec!(3.00)), ..Default::default() };
+    let (renamed, repriced) = tokio::join!(
+        patch_task(&repo, created.id, &rename, None),
+        patch_task(&repo, created.id, &reprice, None),
+    );
+    renamed.unwrap();
+    repriced.unwrap();
+
+    let current = repo.get(created.id).await.unwrap();
+    assert_eq!(current.name, "Feed the fish");
+    assert_eq!(current.value, dec!(3.00));
+    assert_eq!(current.version, 2);
+
+    // A patch based on an older version is refused
+    let stale = patch_task(&repo, created.id, &rename, Some(1)).await;
+    assert!(matches!(stale, Err(Error::Conflict(_))));
+    let patched = patch_task(&repo, created.id, &TaskPatch { tags: Some(vec!["Daily".to_string()]), ..Default::default() }, Some(2)).await.unwrap();
+    assert_eq!(patched.tags, vec!["daily".to_string()]);
+    assert_eq!(patched.version, 3);
+}
+
 #[tokio::test]
 async fn test_delete_task() {
     let (_temp_dir, repo) = setup_test_db().await;
@@ -199,3 +270,58 @@ async fn test_list_empty() {
     let tasks = repo.list().await.unwrap();
     assert_eq!(tasks.len(), 0);
 }
+
+#[tokio::test]
+async fn test_list_by_category_and_tag() {
+    let (_temp_dir, repo) = setup_test_db().await;
+
+    let dishes = Task::new(
+        "Do dishes".to_string(),
+        "".to_string(),
+        dec!(2.00),
+        Cadence::Daily,
+        "test-owner".to_string(),
+    )
+    .unwrap()
+    .with_category(Some(TaskCategory::Kitchen))
+    .with_tags(["Evening", "dishes"]);
+    let walk = Task::new(
+        "Walk dog".to_string(),
+        "".to_string(),
+        dec!(1.00),
+        Cadence::Daily,
+        "test-owner".to_string(),
+    )
+    .unwrap()
+    .with_category(Some(TaskCategory::Pets))
+    .with_tags(["evening"]);
+    let homework = Task::new(
+        "Homework".to_string(),
+        "".to_string(),
+        dec!(1.00),
+        Cadence::Daily,
+        "test-owner".to_string(),
+    )
+    .unwrap();
+
+    repo.create(dishes).await.unwrap();
+    repo.create(walk).await.unwrap();
+    repo.create(homework).await.unwrap();
+
+    let kitchen = repo.list_by_category("test-owner", TaskCategory::Kitchen).await.unwrap();
+    assert_eq!(kitchen.len(), 1);
+    assert_eq!(kitchen[0].name, "Do dishes");
+
+    let evening = repo.list_by_tag("test-owner", "EVENING").await.unwrap();
+    assert_eq!(evening.len(), 2);
+
+    let filtered = repo
+        .list_filtered("test-owner", Some(TaskCategory::Pets), Some("evening"))
+        .await
+        .unwrap();
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].name, "Walk dog");
+
+    let all = repo.list_filtered("test-owner", None, None).await.unwrap();
+    assert_eq!(all.len(), 3);
+}
@@ -0,0 +1,168 @@
+use loaa_core::db::{
+    AttachmentRepository, ClaimRepository, KidRepository, LedgerRepository, Repository, TaskRepository,
+};
+use loaa_core::models::{Cadence, HouseholdRole, Kid, LedgerEntry, Task};
+use loaa_core::workflows::{ClaimWorkflow, TaskCompletionWorkflow};
+use loaa_core::{Actor, Error};
+use chrono::{Duration, Utc};
+use rust_decimal_macros::dec;
+
+mod common;
+use common::init_database;
+
+// NOTE: Tests require a running SurrealDB server on 127.0.0.1:8000
+// Run `just db` before running tests, or run them on SQLite with
+// LOAA_TEST_DB_MODE=sqlite
+
+struct TestContext {
+    completion: TaskCompletionWorkflow,
+    claims: ClaimWorkflow,
+    kid_repo: KidRepository,
+    task_repo: TaskRepository,
+    ledger_repo: LedgerRepository,
+}
+
+async fn setup_test() -> TestContext {
+    let db_url = std::env::var("LOAA_DB_URL").unwrap_or_else(|_| "127.0.0.1:8000".to_string());
+    let database = init_database(&db_url)
+        .await
+        .expect("Failed to initialize database");
+    let client = database.client.clone();
+
+    let completion = || TaskCompletionWorkflow::new(
+        TaskRepository::new(client.clone()),
+        KidRepository::new(client.clone()),
+        LedgerRepository::new(client.clone()),
+    );
+
+    TestContext {
+        completion: completion(),
+        claims: ClaimWorkflow::new(
+            ClaimRepository::new(client.clone()),
+            KidRepository::new(client.clone()),
+            TaskRepository::new(client.clone()),
+            AttachmentRepository::new(client.clone()),
+            completion(),
+        ),
+        kid_repo: KidRepository::new(client.clone()),
+        task_repo: TaskRepository::new(client.clone()),
+        ledger_repo: LedgerRepository::new(client.clone()),
+    }
+}
+
+async fn kid_and_task(ctx: &TestContext) -> (Kid, Task) {
+    let owner_id = uuid::Uuid::new_v4().to_string();
+    let kid = ctx.kid_repo.create(Kid::new("Nalu".to_string(), owner_id.clone()).unwrap()).await.unwrap();
+    let mut task = Task::new("Feed the fish".to_string(), "".to_string(), dec!(2.00), Cadence::Daily, owner_id).unwrap();
+    // Due for a reset, so completing it also updates the task
+    task.last_reset = Utc::now() - Duration::days(2);
+    let task = ctx.task_repo.create(task).await.unwrap();
+    (kid, task)
+}
+
+#[tokio::test]
+async fn test_failed_statement_writes_nothing() {
+    let ctx = setup_test().await;
+    let (kid, _) = kid_and_task(&ctx).await;
+
+    let mut tx = ctx.ledger_repo.begin();
+    let entry = LedgerEntry::adjusted(kid.id, dec!(5.00), "Birthday".to_string());
+    ctx.ledger_repo.create_entry_in(&mut tx, &entry).unwrap();
+    tx.push("THROW 'disk full'");
+
+    assert!(matches!(tx.commit().await, Err(Error::Database(_))));
+    assert!(ctx.ledger_repo.list_entries(kid.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_failed_guard_returns_its_error() {
+    let ctx = setup_test().await;
+    let (kid, _) = kid_and_task(&ctx).await;
+
+    let mut tx = ctx.ledger_repo.begin();
+    let entry = LedgerEntry::adjusted(kid.id, dec!(5.00), "Birthday".to_string());
+    ctx.ledger_repo.create_entry_in(&mut tx, &entry).unwrap();
+    tx.ensure("1 = 2", Error::Validation("Not today".to_string()));
+
+    match tx.commit().await {
+        Err(Error::Validation(message)) => assert_eq!(message, "Not today"),
+        other => panic!("Expected the guard's error, got {:?}", other),
+    }
+    assert!(ctx.ledger_repo.list_entries(kid.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_completion_fails_as_a_whole() {
+    let ctx = setup_test().await;
+    let (kid, task) = kid_and_task(&ctx).await;
+
+    // The kid is archived after the workflow checked them but before the
+    // payout commits
+    let mut tx = ctx.ledger_repo.begin();
+    ctx.completion.complete_in(&mut tx, task.id, kid.id, None).await.unwrap();
+    let mut archived = kid.clone();
+    archived.archive(Utc::now());
+    ctx.kid_repo.update(archived).await.unwrap();
+
+    assert!(matches!(tx.commit().await, Err(Error::Validation(_))));
+    assert!(ctx.ledger_repo.list_entries(kid.id).await.unwrap().is_empty());
+    // The task reset was rolled back along with the payout
+    assert_eq!(ctx.task_repo.get(task.id).await.unwrap().last_reset, task.last_reset);
+}
+
+#[tokio::test]
+async fn test_claim_is_paid_once() {
+    let ctx = setup_test().await;
+    let (kid, task) = kid_and_task(&ctx).await;
+    let parent = Actor::member("parent", kid.owner_id.clone(), HouseholdRole::Owner);
+    let claim = ctx.claims.claim_task(&Actor::kid(kid.owner_id.clone(), kid.id), kid.id, task.id, None, None).await.unwrap();
+
+    let (first, second) = tokio::join!(
+        ctx.claims.approve(&parent, claim.id, None),
+        ctx.claims.approve(&parent, claim.id, None),
+    );
+    assert_eq!([first.is_ok(), second.is_ok()].iter().filter(|ok| **ok).count(), 1);
+    assert_eq!(ctx.ledger_repo.list_entries(kid.id).await.unwrap().len(), 1);
+
+    // Rejecting afterwards doesn't undo the approval
+    assert!(ctx.claims.reject(&parent, claim.id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_concurrent_completions_pay_once() {
+    let ctx = setup_test().await;
+    let (kid, _) = kid_and_task(&ctx).await;
+    let task = Task::new("Wash the car".to_string(), "".to_string(), dec!(5.00), Cadence::OneTime, kid.owner_id.clone()).unwrap();
+    let task = ctx.task_repo.create(task).await.unwrap();
+
+    // Two parents tap "done" at the same time; neither has committed yet
+    let mut first = ctx.ledger_repo.begin();
+    ctx.completion.complete_in(&mut first, task.id, kid.id, None).await.unwrap();
+    let mut second = ctx.ledger_repo.begin();
+    ctx.completion.complete_in(&mut second, task.id, kid.id, None).await.unwrap();
+
+    first.commit().await.unwrap();
+    assert!(matches!(second.commit().await, Err(Error::Conflict(_))));
+    assert_eq!(ctx.ledger_repo.list_entries(kid.id).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_completion_does_not_undo_a_concurrent_edit() {
+    let ctx = setup_test().await;
+    let (kid, task) = kid_and_task(&ctx).await;
+
+    // A parent renames the task while the completion is being prepared
+    let mut tx = ctx.ledger_repo.begin();
+    ctx.completion.complete_in(&mut tx, task.id, kid.id, None).await.unwrap();
+    let mut renamed = task.clone();
+    renamed.name = "Feed the fish and turtle".to_string();
+    ctx.task_repo.update(renamed).await.unwrap();
+
+    assert!(matches!(tx.commit().await, Err(Error::Conflict(_))));
+    assert!(ctx.ledger_repo.list_entries(kid.id).await.unwrap().is_empty());
+    assert_eq!(ctx.task_repo.get(task.id).await.unwrap().name, "Feed the fish and turtle");
+
+    // Trying again picks up the new version
+    ctx.completion.complete_task(task.id, kid.id).await.unwrap();
+    assert_eq!(ctx.ledger_repo.list_entries(kid.id).await.unwrap().len(), 1);
+}
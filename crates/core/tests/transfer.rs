@@ -0,0 +1,91 @@
+use loaa_core::config::{DatabaseConfig, DatabaseMode};
+use loaa_core::db::{Database, KidRepository, LedgerRepository, Repository, TransferRepository};
+use loaa_core::models::{EntryType, HouseholdRole, Kid, LedgerEntry, TransferStatus};
+use loaa_core::workflows::TransferWorkflow;
+use loaa_core::{Actor, Error};
+use rust_decimal_macros::dec;
+
+mod common;
+
+// These tests use fresh in-memory databases, so they don't need a running
+// SurrealDB server
+
+async fn fresh_database() -> Database {
+    common::init_database_with_config(&DatabaseConfig {
+        mode: DatabaseMode::Memory,
+        url: None,
+        path: None,
+        namespace: None,
+        database: None,
+        username: None,
+        password: None,
+        token: None,
+    })
+    .await
+    .expect("Failed to initialize database")
+}
+
+#[tokio::test]
+async fn test_transfers_move_money_between_kids() {
+    let database = fresh_database().await;
+    let kids = KidRepository::new(database.client.clone());
+    let ledger = LedgerRepository::new(database.client.clone());
+    let workflow = TransferWorkflow::new(
+        TransferRepository::new(database.client.clone()),
+        KidRepository::new(database.client.clone()),
+        LedgerRepository::new(database.client.clone()),
+    );
+
+    let kai = kids.create(Kid::new("Kai".to_string(), "parent".to_string()).unwrap()).await.unwrap();
+    let mia = kids.create(Kid::new("Mia".to_string(), "parent".to_string()).unwrap()).await.unwrap();
+    let stranger = kids.create(Kid::new("Stranger".to_string(), "other".to_string()).unwrap()).await.unwrap();
+    ledger.create_entry(LedgerEntry::earned(kai.id, dec!(10.00), "Lawn".to_string())).await.unwrap();
+    let parent = Actor::member("mom", "parent", HouseholdRole::Owner);
+
+    // A debit and a credit, linked by the transfer's ID
+    let gift = workflow.transfer(&parent, kai.id, mia.id, dec!(4.00), Some("Birthday".to_string()), false).await.unwrap();
+    assert_eq!(gift.status, TransferStatus::Completed);
+    let mia_ledger = ledger.get_ledger(mia.id).await.unwrap();
+    assert_eq!(mia_ledger.balance, dec!(4.00));
+    assert_eq!(mia_ledger.entries[0].entry_type, EntryType::Transfer);
+    assert_eq!(mia_ledger.entries[0].description, "Transfer from Kai: Birthday");
+    let kai_ledger = ledger.get_ledger(kai.id).await.unwrap();
+    assert_eq!(kai_ledger.balance, dec!(6.00));
+    assert_eq!(kai_ledger.entries[1].transfer_id, Some(gift.id));
+
+    // Nothing is written when the balance doesn't cover it
+    let overdrawn = workflow.transfer(&parent, mia.id, kai.id, dec!(5.00), None, false).await;
+    assert!(matches!(overdrawn, Err(Error::Validation(_))));
+    assert_eq!(ledger.get_ledger(mia.id).await.unwrap().entries.len(), 1);
+    assert!(matches!(
+        workflow.transfer(&parent, kai.id, stranger.id, dec!(1.00), None, false).await,
+        Err(Error::NotFound(_))
+    ));
+
+    // Pending transfers move when approved, and only if still covered then
+    let first = workflow.transfer(&parent, kai.id, mia.id, dec!(5.00), None, true).await.unwrap();
+    let second = workflow.transfer(&parent, kai.id, mia.id, dec!(5.00), None, true).await.unwrap();
+    assert_eq!(ledger.get_ledger(kai.id).await.unwrap().balance, dec!(6.00));
+    assert_eq!(workflow.list_pending(&parent).await.unwrap().len(), 2);
+    let stranger = Actor::member("stranger", "other", HouseholdRole::Owner);
+    assert!(matches!(workflow.approve(&stranger, first.id).await, Err(Error::Forbidden(_))));
+    workflow.approve(&parent, first.id).await.unwrap();
+    assert!(matches!(workflow.approve(&parent, second.id).await, Err(Error::Validation(_))));
+    workflow.decline(&parent, second.id).await.unwrap();
+    assert!(workflow.approve(&parent, second.id).await.is_err());
+
+    // Kid logins only send their own money, and always wait for a parent
+    let mia_login = Actor::kid("parent", mia.id);
+    assert!(matches!(
+        workflow.transfer(&mia_login, kai.id, mia.id, dec!(1.00), None, false).await,
+        Err(Error::Forbidden(_))
+    ));
+    let asked = workflow.transfer(&mia_login, mia.id, kai.id, dec!(1.00), None, false).await.unwrap();
+    assert_eq!(asked.status, TransferStatus::Pending);
+    workflow.decline(&parent, asked.id).await.unwrap();
+
+    assert_eq!(ledger.get_ledger(kai.id).await.unwrap().balance, dec!(1.00));
+    assert_eq!(ledger.get_ledger(mia.id).await.unwrap().balance, dec!(9.00));
+    assert_eq!(workflow.list_for_kid(&mia_login, mia.id).await.unwrap().len(), 4);
+    assert!(workflow.list_for_kid(&mia_login, kai.id).await.is_err());
+}
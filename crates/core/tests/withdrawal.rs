@@ -0,0 +1,75 @@
+use loaa_core::config::{DatabaseConfig, DatabaseMode};
+use loaa_core::db::{Database, KidRepository, LedgerRepository, Repository, WithdrawalRepository};
+use loaa_core::models::{EntryType, HouseholdRole, Kid, LedgerEntry, PaymentMethod, WithdrawalStatus};
+use loaa_core::workflows::WithdrawalWorkflow;
+use loaa_core::{Actor, Error};
+use rust_decimal_macros::dec;
+
+mod common;
+
+// These tests use fresh in-memory databases, so they don't need a running
+// SurrealDB server
+
+async fn fresh_database() -> Database {
+    common::init_database_with_config(&DatabaseConfig {
+        mode: DatabaseMode::Memory,
+        url: None,
+        path: None,
+        namespace: None,
+        database: None,
+        username: None,
+        password: None,
+        token: None,
+    })
+    .await
+    .expect("Failed to initialize database")
+}
+
+#[tokio::test]
+async fn test_withdrawal_request_approve_and_pay() {
+    let database = fresh_database().await;
+    let kids = KidRepository::new(database.client.clone());
+    let ledger = LedgerRepository::new(database.client.clone());
+    let workflow = WithdrawalWorkflow::new(
+        WithdrawalRepository::new(database.client.clone()),
+        KidRepository::new(database.client.clone()),
+        LedgerRepository::new(database.client.clone()),
+    );
+
+    let kai = kids.create(Kid::new("Kai".to_string(), "parent".to_string()).unwrap()).await.unwrap();
+    ledger.create_entry(LedgerEntry::earned(kai.id, dec!(10.00), "Lawn".to_string())).await.unwrap();
+    let parent = Actor::member("mom", "parent", HouseholdRole::Owner);
+    let login = Actor::kid("parent", kai.id);
+
+    // Requesting holds the amount, so a second request can't spend it again
+    let bike = workflow.request(&login, kai.id, dec!(6.00), Some("Bike bell".to_string())).await.unwrap();
+    assert!(matches!(workflow.request(&login, kai.id, dec!(5.00), None).await, Err(Error::Validation(_))));
+    let candy = workflow.request(&login, kai.id, dec!(4.00), None).await.unwrap();
+    assert_eq!(ledger.get_ledger(kai.id).await.unwrap().balance, dec!(0.00));
+
+    // Only the kid's household can review it, and only in order
+    let stranger = Actor::member("stranger", "someone-else", HouseholdRole::Owner);
+    assert!(matches!(workflow.approve(&stranger, bike.id).await, Err(Error::Forbidden(_))));
+    assert!(matches!(workflow.approve(&login, bike.id).await, Err(Error::Forbidden(_))));
+    assert!(workflow.mark_paid(&parent, bike.id, PaymentMethod::Cash).await.is_err());
+    workflow.approve(&parent, bike.id).await.unwrap();
+    assert!(workflow.approve(&parent, bike.id).await.is_err());
+    let paid = workflow.mark_paid(&parent, bike.id, PaymentMethod::BankTransfer).await.unwrap();
+    assert_eq!((paid.status, paid.method), (WithdrawalStatus::Paid, Some(PaymentMethod::BankTransfer)));
+
+    // Declining returns the held amount
+    workflow.decline(&parent, candy.id).await.unwrap();
+    assert!(workflow.decline(&parent, candy.id).await.is_err());
+    let entries = ledger.get_ledger(kai.id).await.unwrap();
+    assert_eq!(entries.balance, dec!(4.00));
+    let payouts: Vec<_> = entries.entries.iter().filter(|e| e.entry_type == EntryType::Payout).collect();
+    assert_eq!(payouts.len(), 5);
+    assert!(payouts.iter().all(|e| e.withdrawal_id.is_some()));
+    assert!(payouts.iter().any(|e| e.description == "Withdrawal of $6.00 paid by bank transfer"));
+
+    let open = workflow.request(&login, kai.id, dec!(1.50), None).await.unwrap();
+    assert_eq!(workflow.list_open(&parent).await.unwrap().len(), 1);
+    let summary = &workflow.summary(&parent).await.unwrap()[0];
+    assert_eq!((summary.balance, summary.owed, summary.paid), (dec!(2.50), dec!(1.50), dec!(6.00)));
+    assert_eq!(workflow.list_for_kid(&login, kai.id).await.unwrap()[0].id, open.id);
+}
@@ -84,7 +84,7 @@ pub async fn validate_jwt(
 
     // Create validation settings
     let mut validation = Validation::new(Algorithm::HS256);
-    validation.set_issuer(&[base_url.clone()]);
+    validation.set_issuer(std::slice::from_ref(&base_url));
     validation.set_audience(&["loaa-mcp"]);
 
     // Validate JWT
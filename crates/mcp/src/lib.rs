@@ -6,11 +6,27 @@
 pub mod auth;
 
 use anyhow::Result;
-use loaa_core::db::{init_database_with_config, Database, KidRepository, LedgerRepository, TaskRepository};
-use loaa_core::config::DatabaseConfig;
+use loaa_core::db::{
+    init_database_with_config, AttachmentRepository, ClaimRepository, Database, HouseholdRepository,
+    IdempotencyRepository, InviteRepository, KidRepository, LedgerRepository, Repository, TaskRepository, UserRepository,
+    TransferRepository, WithdrawalRepository,
+};
+use loaa_core::config::{ArchiveConfig, AttachmentConfig, DatabaseConfig, IdempotencyConfig};
+use loaa_core::attachments::LocalFsStore;
 use loaa_core::events::{DataEvent, EventSender, broadcast_event};
-use loaa_core::models::{Cadence, EntryType, Kid, LedgerEntry, Task};
-use loaa_core::workflows::TaskCompletionWorkflow;
+use loaa_core::policy::{Actor, Policy};
+use loaa_core::models::{
+    default_invite_ttl, BudgetEnforcement, BudgetPeriod, Cadence, CompletionReview, EntryType, HouseholdRole, Invite, Kid, LedgerEntry, QualityStat,
+    PaymentMethod, PayoutBudget, Task, TaskCategory, TaskPatch, Transfer, Withdrawal,
+};
+use loaa_core::templates::{AgeBand, TemplateLibrary};
+use loaa_core::workflows::{
+    ArchiveWorkflow, BudgetWorkflow, ClaimWorkflow, ColumnMapping, CsvImportReport, CsvWorkflow, ForecastWorkflow, HouseholdWorkflow, IdempotencyWorkflow,
+    InviteWorkflow, LedgerFilter, PackImportWorkflow, ReportWorkflow, TaskCompletionWorkflow, TransferWorkflow,
+    WithdrawalWorkflow,
+};
+use loaa_core::reports::{ReportPeriod, ReportQuery};
+use loaa_core::BudgetStatus;
 use rmcp::handler::server::router::tool::ToolRouter;
 use rmcp::handler::server::wrapper::Parameters;
 use rmcp::model::*;
@@ -20,6 +36,7 @@ use rust_decimal::Decimal;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -34,12 +51,76 @@ pub struct LoaaServer {
     kid_repo: Arc<RwLock<KidRepository>>,
     ledger_repo: Arc<RwLock<LedgerRepository>>,
     workflow: Arc<RwLock<TaskCompletionWorkflow>>,
+    pack_import: Arc<RwLock<PackImportWorkflow>>,
+    csv: Arc<RwLock<CsvWorkflow>>,
+    reports: Arc<RwLock<ReportWorkflow>>,
+    forecasts: Arc<RwLock<ForecastWorkflow>>,
+    households: Arc<RwLock<HouseholdWorkflow>>,
+    budgets: Arc<RwLock<BudgetWorkflow>>,
+    claims: Arc<RwLock<ClaimWorkflow>>,
+    withdrawals: Arc<RwLock<WithdrawalWorkflow>>,
+    transfers: Arc<RwLock<TransferWorkflow>>,
+    archive: Arc<RwLock<ArchiveWorkflow>>,
+    invites: Arc<RwLock<InviteWorkflow>>,
+    policy: Arc<RwLock<Policy>>,
+    idempotency: Arc<RwLock<IdempotencyWorkflow>>,
+    templates: Arc<TemplateLibrary>,
     event_sender: Option<EventSender>,
     /// The owner ID for this session (user_id from OAuth token)
     owner_id: String,
     tool_router: ToolRouter<Self>,
 }
 
+/// Tools that change data. Each accepts an optional `idempotency_key`, so a
+/// call Claude retries after a dropped connection isn't carried out twice.
+const MUTATING_TOOLS: &[&str] = &[
+    "create_kid",
+    "delete_kid",
+    "create_task",
+    "update_task",
+    "delete_task",
+    "restore_archived",
+    "purge_archived",
+    "import_task_pack",
+    "import_tasks_csv",
+    "import_ledger_csv",
+    "complete_task",
+    "approve_claim",
+    "reject_claim",
+    "create_invite",
+    "revoke_invite",
+    "adjust_balance",
+    "set_payout_budget",
+    "request_withdrawal",
+    "approve_withdrawal",
+    "decline_withdrawal",
+    "mark_withdrawal_paid",
+    "transfer_between_kids",
+    "approve_transfer",
+    "decline_transfer",
+];
+
+/// Advertise the `idempotency_key` argument on mutating tools
+///
+/// The key is handled in `call_tool` before a tool runs, so it is added to
+/// the schemas here rather than to every parameter struct.
+fn with_idempotency_key(mut tool: Tool) -> Tool {
+    if MUTATING_TOOLS.contains(&tool.name.as_ref()) {
+        let mut schema = (*tool.input_schema).clone();
+        let properties = schema
+            .entry("properties")
+            .or_insert_with(|| json!({}));
+        if let Some(properties) = properties.as_object_mut() {
+            properties.insert("idempotency_key".to_string(), json!({
+                "type": "string",
+                "description": "Optional unique key for this change (e.g. a UUID). Retrying with the same key returns the first call's result instead of making the change again."
+            }));
+        }
+        tool.input_schema = Arc::new(schema);
+    }
+    tool
+}
+
 // Tool parameter structures
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 struct CreateKidParams {
@@ -53,6 +134,17 @@ struct DeleteKidParams {
     id: String,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct ArchivedRecordParams {
+    #[schemars(description = "Whether the ID is a 'kid' or a 'task'")]
+    kind: String,
+    #[schemars(description = "ID of the archived kid or task")]
+    id: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct ListArchivedParams {}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 struct CreateTaskParams {
     #[schemars(description = "Name of the task")]
@@ -63,6 +155,22 @@ struct CreateTaskParams {
     value: String,
     #[schemars(description = "Cadence: 'daily', 'weekly', or 'onetime'")]
     cadence: String,
+    #[schemars(description = "Category: 'room', 'kitchen', 'school', 'pets', 'yard', or 'other' (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
+    #[schemars(description = "Free-form tags, e.g. ['morning', 'dog'] (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct ListTasksParams {
+    #[schemars(description = "Only tasks in this category (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
+    #[schemars(description = "Only tasks with this tag (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -81,6 +189,15 @@ struct UpdateTaskParams {
     #[schemars(description = "New cadence (optional)")]
     #[serde(skip_serializing_if = "Option::is_none")]
     cadence: Option<String>,
+    #[schemars(description = "New category, or 'none' to clear it (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
+    #[schemars(description = "Replacement list of tags (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<String>>,
+    #[schemars(description = "Version of the task these changes are based on, as returned by list_tasks (optional). If someone else changed the task since, the update is refused and the current task is returned instead")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected_version: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -95,6 +212,36 @@ struct CompleteTaskParams {
     task_id: String,
     #[schemars(description = "ID of the kid completing the task")]
     kid_id: String,
+    #[schemars(description = "Quality rating from 1 (poor) to 5 (excellent) (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quality: Option<u8>,
+    #[schemars(description = "Percentage of the task value to pay, 0-100 (optional, defaults to 100)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    percent: Option<u8>,
+    #[schemars(description = "Comment explaining the rating or partial credit (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct ApproveClaimParams {
+    #[schemars(description = "ID of the pending claim to approve")]
+    claim_id: String,
+    #[schemars(description = "Quality rating from 1 (poor) to 5 (excellent) (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quality: Option<u8>,
+    #[schemars(description = "Percentage of the task value to pay, 0-100 (optional, defaults to 100)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    percent: Option<u8>,
+    #[schemars(description = "Comment explaining the rating or partial credit (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct RejectClaimParams {
+    #[schemars(description = "ID of the pending claim to reject")]
+    claim_id: String,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -103,6 +250,173 @@ struct GetLedgerParams {
     kid_id: String,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct GetQualityReportParams {
+    #[schemars(description = "ID of the kid to report on (optional, defaults to all kids)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kid_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct GetEarningsByCategoryParams {
+    #[schemars(description = "ID of the kid to report on (optional, defaults to all kids)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kid_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct GetEarningsReportParams {
+    #[schemars(description = "Name or ID of the kid to report on (optional, defaults to all kids)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kid: Option<String>,
+    #[schemars(description = "Period to total by: 'day', 'week' (starting Monday) or 'month' (optional, defaults to 'month')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    period: Option<String>,
+    #[schemars(description = "First day to include as YYYY-MM-DD (optional; with to, e.g. 2026-09-01 to 2026-09-30 for September)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<String>,
+    #[schemars(description = "Last day to include as YYYY-MM-DD (optional, defaults to today)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to: Option<String>,
+    #[schemars(description = "Without from, how many periods up to today to report (optional; defaults to 14 days, 8 weeks or 6 months)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    periods: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct GetPayoutForecastParams {}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct GetPayoutBudgetsParams {}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct SetPayoutBudgetParams {
+    #[schemars(description = "Name or ID of a kid to budget for (optional, defaults to the whole household)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kid: Option<String>,
+    #[schemars(description = "Most to pay out per period, as a decimal string (e.g. '25.00'); leave out to remove the budget")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    amount: Option<String>,
+    #[schemars(description = "Whether the budget is per 'week' or per 'month' (optional, defaults to 'week')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    period: Option<String>,
+    #[schemars(description = "'warn' to pay completions that go over the budget but report it, or 'block' to refuse them (optional, defaults to 'warn')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enforcement: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct RequestWithdrawalParams {
+    #[schemars(description = "Name or ID of the kid withdrawing money")]
+    kid: String,
+    #[schemars(description = "Amount to take out of the kid's balance, as a decimal string (e.g. '5.00')")]
+    amount: String,
+    #[schemars(description = "What the money is for (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct WithdrawalParams {
+    #[schemars(description = "ID of the withdrawal")]
+    withdrawal_id: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct MarkWithdrawalPaidParams {
+    #[schemars(description = "ID of the approved withdrawal")]
+    withdrawal_id: String,
+    #[schemars(description = "How it was paid: 'cash', 'bank_transfer' or 'gift_card'")]
+    method: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct TransferBetweenKidsParams {
+    #[schemars(description = "Name or ID of the kid sending the money")]
+    from_kid: String,
+    #[schemars(description = "Name or ID of the kid receiving it")]
+    to_kid: String,
+    #[schemars(description = "Amount to move, as a decimal string (e.g. '5.00')")]
+    amount: String,
+    #[schemars(description = "What the transfer is for (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+    #[schemars(description = "Save the transfer for a parent to approve instead of moving the money now (optional, defaults to false)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    require_approval: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct TransferParams {
+    #[schemars(description = "ID of the transfer")]
+    transfer_id: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct ListTaskTemplatesParams {
+    #[schemars(description = "Filter by age band: 'preschool', 'elementary', 'tween', or 'teen' (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    age_band: Option<String>,
+    #[schemars(description = "Filter by room, e.g. 'kitchen', 'bedroom', 'pets' (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    room: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct ImportTaskPackParams {
+    #[schemars(description = "ID of the pack to import (from list_task_templates)")]
+    pack_id: String,
+    #[schemars(description = "Multiplier applied to suggested values as decimal string, e.g. '0.5' or '2' (optional, defaults to '1')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value_scale: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct ExportTasksCsvParams {}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct ExportLedgerCsvParams {
+    #[schemars(description = "ID of the kid whose ledger to export (optional, defaults to all kids)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kid_id: Option<String>,
+    #[schemars(description = "First day to include as YYYY-MM-DD (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<String>,
+    #[schemars(description = "Last day to include as YYYY-MM-DD (optional)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct ImportCsvParams {
+    #[schemars(description = "CSV text with a header row")]
+    csv: String,
+    #[schemars(description = "Which column holds each field, e.g. {\"name\": \"Chore\", \"value\": \"Pay\"} (optional; fields default to columns with their own name)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    columns: Option<HashMap<String, String>>,
+    #[schemars(description = "Only check the file and report what would be imported (optional, defaults to false)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dry_run: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct CreateInviteParams {
+    #[schemars(description = "Role the invited person gets: 'owner', 'parent' or 'viewer'")]
+    role: String,
+    #[schemars(description = "Days until the invite expires (optional, defaults to 7)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ttl_days: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct ListInvitesParams {}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+struct RevokeInviteParams {
+    #[schemars(description = "ID of the invite to revoke")]
+    invite_id: String,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 struct AdjustBalanceParams {
     #[schemars(description = "ID of the kid whose balance to adjust")]
@@ -113,6 +427,163 @@ struct AdjustBalanceParams {
     description: String,
 }
 
+/// JSON for the review recorded on a completion, or null if there is none
+fn review_json(entry: &LedgerEntry) -> serde_json::Value {
+    match &entry.review {
+        Some(review) => json!({
+            "quality": review.quality,
+            "percent_paid": review.percent_paid,
+            "comment": review.comment,
+            "original_value": entry.original_value.map(|v| v.to_string())
+        }),
+        None => serde_json::Value::Null,
+    }
+}
+
+fn parse_category(category: &str) -> Result<TaskCategory, McpError> {
+    TaskCategory::parse(category).ok_or_else(|| {
+        McpError::invalid_request(
+            "Invalid category. Must be 'room', 'kitchen', 'school', 'pets', 'yard', or 'other'",
+            None,
+        )
+    })
+}
+
+fn task_json(task: &Task) -> serde_json::Value {
+    json!({
+        "id": task.id.to_string(),
+        "name": task.name,
+        "description": task.description,
+        "value": task.value.to_string(),
+        "cadence": match task.cadence {
+            Cadence::Daily => "daily",
+            Cadence::Weekly => "weekly",
+            Cadence::OneTime => "onetime"
+        },
+        "category": task.category.map(|c| c.as_str()),
+        "tags": task.tags,
+        "created_at": task.created_at.to_rfc3339(),
+        "last_reset": task.last_reset.to_rfc3339(),
+        "needs_reset": task.needs_reset(),
+        "version": task.version
+    })
+}
+
+fn column_mapping(columns: Option<HashMap<String, String>>) -> ColumnMapping {
+    columns
+        .unwrap_or_default()
+        .iter()
+        .fold(ColumnMapping::new(), |mapping, (field, column)| mapping.map(field, column))
+}
+
+fn parse_day(field: &str, day: Option<String>) -> Result<Option<chrono::NaiveDate>, McpError> {
+    day.map(|day| {
+        chrono::NaiveDate::parse_from_str(day.trim(), "%Y-%m-%d")
+            .map_err(|e| McpError::invalid_request(format!("Invalid {} date (expected YYYY-MM-DD): {}", field, e), None))
+    })
+    .transpose()
+}
+
+fn import_report_json(report: &CsvImportReport) -> serde_json::Value {
+    json!({
+        "rows": report.rows,
+        "imported": report.imported,
+        "written": report.written,
+        "skipped": report.skipped,
+        "errors": report.errors.iter().map(|e| json!({
+            "line": e.line,
+            "message": e.message
+        })).collect::<Vec<_>>()
+    })
+}
+
+fn budget_json(status: &BudgetStatus) -> serde_json::Value {
+    json!({
+        "kid_id": status.kid_id.map(|id| id.to_string()),
+        "name": status.name,
+        "amount": status.budget.amount.to_string(),
+        "period": status.budget.period.as_str(),
+        "enforcement": status.budget.enforcement.as_str(),
+        "period_start": status.period_start.to_string(),
+        "spent": status.spent.to_string(),
+        "remaining": status.remaining.to_string(),
+        "warning": status.warning()
+    })
+}
+
+fn withdrawal_json(withdrawal: &Withdrawal) -> serde_json::Value {
+    json!({
+        "id": withdrawal.id.to_string(),
+        "kid_id": withdrawal.kid_id.to_string(),
+        "amount": withdrawal.amount.to_string(),
+        "note": withdrawal.note,
+        "status": withdrawal.status.as_str(),
+        "method": withdrawal.method.map(|m| m.as_str()),
+        "requested_at": withdrawal.requested_at.to_rfc3339(),
+        "approved_at": withdrawal.approved_at.map(|t| t.to_rfc3339()),
+        "resolved_at": withdrawal.resolved_at.map(|t| t.to_rfc3339())
+    })
+}
+
+fn invite_json(invite: &Invite) -> serde_json::Value {
+    json!({
+        "id": invite.id.to_string(),
+        "role": invite.role.as_str(),
+        "created_at": invite.created_at.to_rfc3339(),
+        "expires_at": invite.expires_at.to_rfc3339()
+    })
+}
+
+fn transfer_json(transfer: &Transfer) -> serde_json::Value {
+    json!({
+        "id": transfer.id.to_string(),
+        "from_kid_id": transfer.from_kid_id.to_string(),
+        "to_kid_id": transfer.to_kid_id.to_string(),
+        "amount": transfer.amount.to_string(),
+        "note": transfer.note,
+        "status": transfer.status.as_str(),
+        "created_at": transfer.created_at.to_rfc3339(),
+        "resolved_at": transfer.resolved_at.map(|t| t.to_rfc3339())
+    })
+}
+
+/// Map core errors from checked operations, keeping refusals distinct from failures
+fn core_error(e: loaa_core::Error) -> McpError {
+    match e {
+        loaa_core::Error::Validation(msg) => McpError::invalid_request(msg, None),
+        loaa_core::Error::Forbidden(msg) => McpError::invalid_request(format!("Forbidden: {}", msg), None),
+        loaa_core::Error::Conflict(msg) => McpError::invalid_request(format!("Conflict: {}", msg), None),
+        loaa_core::Error::NotFound(msg) => McpError::resource_not_found(msg, None),
+        e => McpError::internal_error("database_error", Some(json!({"error": e.to_string()}))),
+    }
+}
+
+fn parse_withdrawal_id(id: &str) -> Result<Uuid, McpError> {
+    Uuid::parse_str(id).map_err(|e| McpError::invalid_request(format!("Invalid withdrawal ID: {}", e), None))
+}
+
+fn parse_transfer_id(id: &str) -> Result<Uuid, McpError> {
+    Uuid::parse_str(id).map_err(|e| McpError::invalid_request(format!("Invalid transfer ID: {}", e), None))
+}
+
+fn parse_archived_kind(kind: &str) -> Result<bool, McpError> {
+    match kind.to_lowercase().as_str() {
+        "kid" => Ok(true),
+        "task" => Ok(false),
+        _ => Err(McpError::invalid_request("Invalid kind. Must be 'kid' or 'task'", None)),
+    }
+}
+
+fn quality_json(stat: &Option<QualityStat>) -> serde_json::Value {
+    match stat {
+        Some(stat) => json!({
+            "average": stat.average.to_string(),
+            "rated_count": stat.rated_count
+        }),
+        None => serde_json::Value::Null,
+    }
+}
+
 #[tool_router]
 impl LoaaServer {
     pub async fn new(db_config: &DatabaseConfig, owner_id: String) -> Result<Self> {
@@ -121,6 +592,15 @@ impl LoaaServer {
 
     pub async fn with_event_sender(db_config: &DatabaseConfig, event_sender: Option<EventSender>, owner_id: String) -> Result<Self> {
         let database = init_database_with_config(db_config).await?;
+
+        // Re-key data from before households existed
+        let migrated = HouseholdWorkflow::new(HouseholdRepository::new(database.client.clone()))
+            .migrate_owner_ids()
+            .await?;
+        if migrated > 0 {
+            eprintln!("Migrated data for {} owner(s) into households", migrated);
+        }
+
         Self::with_shared_database(&database, event_sender, owner_id)
     }
 
@@ -136,10 +616,82 @@ impl LoaaServer {
         let kid_repo = KidRepository::new(database.client.clone());
         let ledger_repo = LedgerRepository::new(database.client.clone());
 
+        let budgets = || BudgetWorkflow::new(
+            HouseholdRepository::new(database.client.clone()),
+            KidRepository::new(database.client.clone()),
+            LedgerRepository::new(database.client.clone()),
+        );
         let workflow = TaskCompletionWorkflow::new(
             TaskRepository::new(database.client.clone()),
             KidRepository::new(database.client.clone()),
             LedgerRepository::new(database.client.clone()),
+        )
+        .with_budgets(budgets());
+        let pack_import = PackImportWorkflow::new(TaskRepository::new(database.client.clone()));
+        let csv = CsvWorkflow::new(
+            KidRepository::new(database.client.clone()),
+            TaskRepository::new(database.client.clone()),
+            LedgerRepository::new(database.client.clone()),
+        );
+        let reports = ReportWorkflow::new(
+            KidRepository::new(database.client.clone()),
+            TaskRepository::new(database.client.clone()),
+            LedgerRepository::new(database.client.clone()),
+        );
+        let forecasts = ForecastWorkflow::new(
+            HouseholdRepository::new(database.client.clone()),
+            KidRepository::new(database.client.clone()),
+            TaskRepository::new(database.client.clone()),
+            LedgerRepository::new(database.client.clone()),
+        );
+        let households = HouseholdWorkflow::new(HouseholdRepository::new(database.client.clone()));
+        let claims = ClaimWorkflow::new(
+            ClaimRepository::new(database.client.clone()),
+            KidRepository::new(database.client.clone()),
+            TaskRepository::new(database.client.clone()),
+            AttachmentRepository::new(database.client.clone()),
+            TaskCompletionWorkflow::new(
+                TaskRepository::new(database.client.clone()),
+                KidRepository::new(database.client.clone()),
+                LedgerRepository::new(database.client.clone()),
+            )
+            .with_budgets(budgets()),
+        );
+        let withdrawals = WithdrawalWorkflow::new(
+            WithdrawalRepository::new(database.client.clone()),
+            KidRepository::new(database.client.clone()),
+            LedgerRepository::new(database.client.clone()),
+        );
+        let transfers = TransferWorkflow::new(
+            TransferRepository::new(database.client.clone()),
+            KidRepository::new(database.client.clone()),
+            LedgerRepository::new(database.client.clone()),
+        );
+        // Purging a kid also removes their photos, which live in the web server's attachment dir
+        let archive = ArchiveWorkflow::new(
+            KidRepository::new(database.client.clone()),
+            TaskRepository::new(database.client.clone()),
+            AttachmentRepository::new(database.client.clone()),
+            Arc::new(LocalFsStore::new(&AttachmentConfig::from_env().dir)?),
+            ArchiveConfig::from_env().restore_window(),
+        );
+        let policy = Policy::new(
+            KidRepository::new(database.client.clone()),
+            TaskRepository::new(database.client.clone()),
+            LedgerRepository::new(database.client.clone()),
+            HouseholdWorkflow::new(HouseholdRepository::new(database.client.clone())),
+        );
+        // Invite codes are signed with the same secret as the web server so
+        // links created here can be used to register there
+        let invites = InviteWorkflow::new(
+            InviteRepository::new(database.client.clone()),
+            UserRepository::new(database.client.clone()),
+            HouseholdWorkflow::new(HouseholdRepository::new(database.client.clone())),
+            std::env::var("LOAA_JWT_SECRET").unwrap_or_else(|_| "insecure-default-change-me".to_string()),
+        );
+        let idempotency = IdempotencyWorkflow::new(
+            IdempotencyRepository::new(database.client.clone()),
+            IdempotencyConfig::from_env().retention(),
         );
 
         Ok(Self {
@@ -147,6 +699,20 @@ impl LoaaServer {
             kid_repo: Arc::new(RwLock::new(kid_repo)),
             ledger_repo: Arc::new(RwLock::new(ledger_repo)),
             workflow: Arc::new(RwLock::new(workflow)),
+            pack_import: Arc::new(RwLock::new(pack_import)),
+            csv: Arc::new(RwLock::new(csv)),
+            reports: Arc::new(RwLock::new(reports)),
+            forecasts: Arc::new(RwLock::new(forecasts)),
+            households: Arc::new(RwLock::new(households)),
+            budgets: Arc::new(RwLock::new(budgets())),
+            claims: Arc::new(RwLock::new(claims)),
+            withdrawals: Arc::new(RwLock::new(withdrawals)),
+            transfers: Arc::new(RwLock::new(transfers)),
+            archive: Arc::new(RwLock::new(archive)),
+            invites: Arc::new(RwLock::new(invites)),
+            policy: Arc::new(RwLock::new(policy)),
+            idempotency: Arc::new(RwLock::new(idempotency)),
+            templates: Arc::new(TemplateLibrary::from_env()),
             event_sender,
             owner_id,
             tool_router: Self::tool_router(),
@@ -160,10 +726,49 @@ impl LoaaServer {
         }
     }
 
-    /// Get the owner_id from HTTP request context (JWT) or fall back to the server default.
+    fn withdrawal_updated(&self, withdrawal: &Withdrawal) {
+        self.emit_event(DataEvent::WithdrawalUpdated {
+            id: withdrawal.id.to_string(),
+            kid_id: withdrawal.kid_id.to_string(),
+            status: withdrawal.status.as_str().to_string(),
+            amount: withdrawal.amount.to_string(),
+        });
+    }
+
+    fn transfer_updated(&self, transfer: &Transfer) {
+        self.emit_event(DataEvent::TransferUpdated {
+            id: transfer.id.to_string(),
+            from_kid_id: transfer.from_kid_id.to_string(),
+            to_kid_id: transfer.to_kid_id.to_string(),
+            status: transfer.status.as_str().to_string(),
+            amount: transfer.amount.to_string(),
+        });
+    }
+
+    /// The budgets a payout of `paid` to a kid counted toward, emitting an
+    /// event for each threshold it took the spending past
+    async fn budgets_after_payout(&self, kid_id: Uuid, paid: Decimal) -> Result<Vec<serde_json::Value>, McpError> {
+        let statuses = self.workflow.read().await
+            .budget_statuses(kid_id, chrono::Utc::now().date_naive())
+            .await
+            .map_err(core_error)?;
+        for status in &statuses {
+            if let Some(threshold) = status.crossed(paid) {
+                self.emit_event(DataEvent::BudgetThresholdCrossed {
+                    kid_id: status.kid_id.map(|id| id.to_string()),
+                    name: status.name.clone(),
+                    threshold: threshold.as_str().to_string(),
+                    remaining: status.remaining.to_string(),
+                });
+            }
+        }
+        Ok(statuses.iter().map(budget_json).collect())
+    }
+
+    /// Get the user_id from HTTP request context (JWT) or fall back to the server default.
     /// In HTTP mode with JWT auth, the AuthenticatedUser is extracted from request extensions.
     /// In stdio mode (local CLI), we use the server's owner_id.
-    fn get_owner_id(&self, extensions: &Extensions) -> String {
+    fn get_user_id(&self, extensions: &Extensions) -> String {
         // Try to get HTTP Parts from MCP extensions (only present in HTTP mode)
         if let Some(parts) = extensions.get::<http::request::Parts>() {
             // Try to get AuthenticatedUser from HTTP request extensions
@@ -175,13 +780,49 @@ impl LoaaServer {
         self.owner_id.clone()
     }
 
+    /// Resolve the calling user to their household; its ID is what `owner_id` fields hold
+    async fn get_owner_id(&self, extensions: &Extensions) -> Result<String, McpError> {
+        self.get_household_owner_id(extensions, false).await
+    }
+
+    /// Like `get_owner_id`, but rejects household viewers, who can't make changes
+    async fn get_editor_owner_id(&self, extensions: &Extensions) -> Result<String, McpError> {
+        self.get_household_owner_id(extensions, true).await
+    }
+
+    async fn get_household_owner_id(&self, extensions: &Extensions, edit: bool) -> Result<String, McpError> {
+        let actor = self.get_actor(extensions).await?;
+        if edit && !actor.can_edit() {
+            return Err(McpError::invalid_request("Household viewers can't make changes", None));
+        }
+        Ok(actor.household_id().unwrap_or_default().to_string())
+    }
+
+    /// One of the household's kids, by ID or by name (ignoring case)
+    async fn find_kid(&self, owner_id: &str, kid: &str) -> Result<Kid, McpError> {
+        let kids = self.kid_repo.read().await.list_by_owner(owner_id).await.map_err(core_error)?;
+        let kid = kid.trim();
+        let id = Uuid::parse_str(kid).ok();
+        kids.into_iter()
+            .find(|k| Some(k.id) == id || k.name.eq_ignore_ascii_case(kid))
+            .ok_or_else(|| McpError::resource_not_found(format!("No kid is named {}", kid), None))
+    }
+
+    /// The calling user as a policy actor, for tools that work on records by ID
+    async fn get_actor(&self, extensions: &Extensions) -> Result<Actor, McpError> {
+        let user_id = self.get_user_id(extensions);
+        self.policy.read().await.actor_for_user(&user_id).await.map_err(|e| {
+            McpError::internal_error("database_error", Some(json!({"error": e.to_string()})))
+        })
+    }
+
     #[tool(description = "Create a new kid in the system. Returns the created kid with their ID.")]
     async fn create_kid(
         &self,
         extensions: Extensions,
         Parameters(params): Parameters<CreateKidParams>,
     ) -> Result<CallToolResult, McpError> {
-        let owner_id = self.get_owner_id(&extensions);
+        let owner_id = self.get_editor_owner_id(&extensions).await?;
         let kid = Kid::new(params.name, owner_id).map_err(|e| {
             McpError::invalid_request(e.to_string(), None)
         })?;
@@ -210,7 +851,7 @@ impl LoaaServer {
 
     #[tool(description = "List all kids owned by the current user.")]
     async fn list_kids(&self, extensions: Extensions) -> Result<CallToolResult, McpError> {
-        let owner_id = self.get_owner_id(&extensions);
+        let owner_id = self.get_owner_id(&extensions).await?;
         let kid_repo = self.kid_repo.read().await;
         let kids = kid_repo.list_by_owner(&owner_id).await.map_err(|e| {
             McpError::internal_error("database_error", Some(json!({"error": e.to_string()})))
@@ -229,19 +870,21 @@ impl LoaaServer {
         )]))
     }
 
-    #[tool(description = "Delete a kid by ID. This will also delete all associated ledger entries.")]
+    #[tool(description = "Delete a kid by ID. The kid is archived: hidden from lists but their ledger is kept, and they can be restored with restore_archived until the restore window (30 days by default) passes. Use purge_archived to remove them and all their records right away.")]
     async fn delete_kid(
         &self,
+        extensions: Extensions,
         Parameters(params): Parameters<DeleteKidParams>,
     ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
         let kid_id = Uuid::parse_str(&params.id).map_err(|e| {
             McpError::invalid_request(format!("Invalid kid ID: {}", e), None)
         })?;
 
-        let kid_repo = self.kid_repo.read().await;
-        kid_repo.delete(kid_id).await.map_err(|e| {
-            McpError::internal_error("database_error", Some(json!({"error": e.to_string()})))
-        })?;
+        self.archive.read().await
+            .archive_kid(&actor, kid_id, chrono::Utc::now())
+            .await
+            .map_err(core_error)?;
 
         // Emit event for SSE clients
         self.emit_event(DataEvent::KidDeleted {
@@ -250,7 +893,7 @@ impl LoaaServer {
 
         let response = json!({
             "success": true,
-            "message": format!("Kid {} deleted successfully", kid_id)
+            "message": format!("Kid {} archived; it can be restored with restore_archived", kid_id)
         });
 
         Ok(CallToolResult::success(vec![Content::text(
@@ -258,13 +901,13 @@ impl LoaaServer {
         )]))
     }
 
-    #[tool(description = "Create a new task. Value should be a decimal string (e.g., '1.50'). Cadence must be one of: 'daily', 'weekly', 'onetime'.")]
+    #[tool(description = "Create a new task. Value should be a decimal string (e.g., '1.50'). Cadence must be one of: 'daily', 'weekly', 'onetime'. Optionally set a category ('room', 'kitchen', 'school', 'pets', 'yard', 'other') and free-form tags.")]
     async fn create_task(
         &self,
         extensions: Extensions,
         Parameters(params): Parameters<CreateTaskParams>,
     ) -> Result<CallToolResult, McpError> {
-        let owner_id = self.get_owner_id(&extensions);
+        let owner_id = self.get_editor_owner_id(&extensions).await?;
         let value_dec =
             Decimal::from_str(&params.value).map_err(|e| {
                 McpError::invalid_request(format!("Invalid value format: {}", e), None)
@@ -282,10 +925,14 @@ impl LoaaServer {
             }
         };
 
+        let category = params.category.as_deref().map(parse_category).transpose()?;
+
         let task = Task::new(params.name, params.description, value_dec, cadence_enum, owner_id)
             .map_err(|e| {
                 McpError::invalid_request(e.to_string(), None)
-            })?;
+            })?
+            .with_category(category)
+            .with_tags(params.tags.unwrap_or_default());
 
         let task_repo = self.task_repo.read().await;
         let created = task_repo.create(task).await.map_err(|e| {
@@ -298,48 +945,28 @@ impl LoaaServer {
             name: created.name.clone(),
         });
 
-        let response = json!({
-            "id": created.id.to_string(),
-            "name": created.name,
-            "description": created.description,
-            "value": created.value.to_string(),
-            "cadence": match created.cadence {
-                Cadence::Daily => "daily",
-                Cadence::Weekly => "weekly",
-                Cadence::OneTime => "onetime"
-            },
-            "created_at": created.created_at.to_rfc3339(),
-            "needs_reset": created.needs_reset()
-        });
+        let response = task_json(&created);
 
         Ok(CallToolResult::success(vec![Content::text(
             serde_json::to_string_pretty(&response).unwrap(),
         )]))
     }
 
-    #[tool(description = "List all tasks owned by the current user.")]
-    async fn list_tasks(&self, extensions: Extensions) -> Result<CallToolResult, McpError> {
-        let owner_id = self.get_owner_id(&extensions);
+    #[tool(description = "List all tasks owned by the current user. Optionally filter by category or tag.")]
+    async fn list_tasks(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<ListTasksParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner_id = self.get_owner_id(&extensions).await?;
+        let category = params.category.as_deref().map(parse_category).transpose()?;
         let task_repo = self.task_repo.read().await;
-        let tasks = task_repo.list_by_owner(&owner_id).await.map_err(|e| {
+        let tasks = task_repo.list_filtered(&owner_id, category, params.tag.as_deref()).await.map_err(|e| {
             McpError::internal_error("database_error", Some(json!({"error": e.to_string()})))
         })?;
 
         let response = json!({
-            "tasks": tasks.iter().map(|t| json!({
-                "id": t.id.to_string(),
-                "name": t.name,
-                "description": t.description,
-                "value": t.value.to_string(),
-                "cadence": match t.cadence {
-                    Cadence::Daily => "daily",
-                    Cadence::Weekly => "weekly",
-                    Cadence::OneTime => "onetime"
-                },
-                "created_at": t.created_at.to_rfc3339(),
-                "last_reset": t.last_reset.to_rfc3339(),
-                "needs_reset": t.needs_reset()
-            })).collect::<Vec<_>>()
+            "tasks": tasks.iter().map(task_json).collect::<Vec<_>>()
         });
 
         Ok(CallToolResult::success(vec![Content::text(
@@ -347,33 +974,30 @@ impl LoaaServer {
         )]))
     }
 
-    #[tool(description = "Update an existing task. All fields except id are optional. Value should be a decimal string (e.g., '1.50'). Cadence must be one of: 'daily', 'weekly', 'onetime'.")]
+    #[tool(description = "Update an existing task. All fields except id are optional; fields left out keep their current values. Pass expected_version to make sure nobody else changed the task since you last read it. Value should be a decimal string (e.g., '1.50'). Cadence must be one of: 'daily', 'weekly', 'onetime'.")]
     async fn update_task(
         &self,
+        extensions: Extensions,
         Parameters(params): Parameters<UpdateTaskParams>,
     ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
         let task_id = Uuid::parse_str(&params.id).map_err(|e| {
             McpError::invalid_request(format!("Invalid task ID: {}", e), None)
         })?;
 
-        let task_repo = self.task_repo.read().await;
-        let mut task = task_repo.get(task_id).await.map_err(|e| {
-            McpError::resource_not_found(format!("Task not found: {}", e), None)
-        })?;
-
-        if let Some(n) = params.name {
-            task.name = n;
-        }
-        if let Some(d) = params.description {
-            task.description = d;
-        }
+        let mut patch = TaskPatch {
+            name: params.name,
+            description: params.description,
+            tags: params.tags,
+            ..Default::default()
+        };
         if let Some(v) = params.value {
-            task.value = Decimal::from_str(&v).map_err(|e| {
+            patch.value = Some(Decimal::from_str(&v).map_err(|e| {
                 McpError::invalid_request(format!("Invalid value format: {}", e), None)
-            })?;
+            })?);
         }
         if let Some(c) = params.cadence {
-            task.cadence = match c.to_lowercase().as_str() {
+            patch.cadence = Some(match c.to_lowercase().as_str() {
                 "daily" => Cadence::Daily,
                 "weekly" => Cadence::Weekly,
                 "onetime" | "one-time" | "one_time" => Cadence::OneTime,
@@ -383,12 +1007,38 @@ impl LoaaServer {
                         None,
                     ))
                 }
-            };
+            });
+        }
+        if let Some(c) = params.category {
+            patch.category = Some(if c.eq_ignore_ascii_case("none") {
+                None
+            } else {
+                Some(parse_category(&c)?)
+            });
         }
 
-        let updated = task_repo.update(task).await.map_err(|e| {
-            McpError::internal_error("database_error", Some(json!({"error": e.to_string()})))
-        })?;
+        let policy = self.policy.read().await;
+        let updated = match policy.patch_task(&actor, task_id, &patch, params.expected_version).await {
+            Ok(updated) => updated,
+            Err(loaa_core::Error::NotFound(e)) => {
+                return Err(McpError::resource_not_found(format!("Task not found: {}", e), None))
+            }
+            // Show the caller what they'd be overwriting so they can decide
+            Err(loaa_core::Error::Conflict(msg)) => {
+                let current = policy.task(&actor, task_id).await.map_err(core_error)?;
+                return Err(McpError::invalid_request(
+                    format!(
+                        "Conflict: {}. It is now at version {}: {}. Review the current task and retry with expected_version {}.",
+                        msg,
+                        current.version,
+                        task_json(&current),
+                        current.version
+                    ),
+                    Some(json!({"current": task_json(&current)})),
+                ));
+            }
+            Err(e) => return Err(core_error(e)),
+        };
 
         // Emit event for SSE clients
         self.emit_event(DataEvent::TaskUpdated {
@@ -396,37 +1046,28 @@ impl LoaaServer {
             name: updated.name.clone(),
         });
 
-        let response = json!({
-            "id": updated.id.to_string(),
-            "name": updated.name,
-            "description": updated.description,
-            "value": updated.value.to_string(),
-            "cadence": match updated.cadence {
-                Cadence::Daily => "daily",
-                Cadence::Weekly => "weekly",
-                Cadence::OneTime => "onetime"
-            },
-            "needs_reset": updated.needs_reset()
-        });
+        let response = task_json(&updated);
 
         Ok(CallToolResult::success(vec![Content::text(
             serde_json::to_string_pretty(&response).unwrap(),
         )]))
     }
 
-    #[tool(description = "Delete a task by ID.")]
+    #[tool(description = "Delete a task by ID. The task is archived: hidden from lists while past completions keep referring to it, and it can be restored with restore_archived until the restore window passes.")]
     async fn delete_task(
         &self,
+        extensions: Extensions,
         Parameters(params): Parameters<DeleteTaskParams>,
     ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
         let task_id = Uuid::parse_str(&params.id).map_err(|e| {
             McpError::invalid_request(format!("Invalid task ID: {}", e), None)
         })?;
 
-        let task_repo = self.task_repo.read().await;
-        task_repo.delete(task_id).await.map_err(|e| {
-            McpError::internal_error("database_error", Some(json!({"error": e.to_string()})))
-        })?;
+        self.archive.read().await
+            .archive_task(&actor, task_id, chrono::Utc::now())
+            .await
+            .map_err(core_error)?;
 
         // Emit event for SSE clients
         self.emit_event(DataEvent::TaskDeleted {
@@ -435,7 +1076,7 @@ impl LoaaServer {
 
         let response = json!({
             "success": true,
-            "message": format!("Task {} deleted successfully", task_id)
+            "message": format!("Task {} archived; it can be restored with restore_archived", task_id)
         });
 
         Ok(CallToolResult::success(vec![Content::text(
@@ -443,11 +1084,13 @@ impl LoaaServer {
         )]))
     }
 
-    #[tool(description = "Mark a task as complete for a specific kid. This creates a ledger entry and resets the task if it's a recurring task (daily/weekly).")]
+    #[tool(description = "Mark a task as complete for a specific kid. This creates a ledger entry and resets the task if it's a recurring task (daily/weekly). Optionally rate the quality (1-5), pay only a percentage of the value (0-100) and leave a comment.")]
     async fn complete_task(
         &self,
+        extensions: Extensions,
         Parameters(params): Parameters<CompleteTaskParams>,
     ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
         let task_uuid = Uuid::parse_str(&params.task_id).map_err(|e| {
             McpError::invalid_request(format!("Invalid task ID: {}", e), None)
         })?;
@@ -455,13 +1098,24 @@ impl LoaaServer {
             McpError::invalid_request(format!("Invalid kid ID: {}", e), None)
         })?;
 
+        let review = if params.quality.is_some() || params.percent.is_some() || params.comment.is_some() {
+            Some(
+                CompletionReview::new(params.quality, params.percent.unwrap_or(100), params.comment)
+                    .map_err(|e| McpError::invalid_request(e.to_string(), None))?,
+            )
+        } else {
+            None
+        };
+
         let workflow = self.workflow.read().await;
         let entry = workflow
-            .complete_task(task_uuid, kid_uuid)
+            .complete_task_as(&actor, task_uuid, kid_uuid, review)
             .await
-            .map_err(|e| {
-                McpError::internal_error("workflow_error", Some(json!({"error": e.to_string()})))
+            .map_err(|e| match e {
+                loaa_core::Error::Forbidden(_) | loaa_core::Error::Validation(_) => core_error(e),
+                e => McpError::internal_error("workflow_error", Some(json!({"error": e.to_string()}))),
             })?;
+        drop(workflow);
 
         // Emit event for SSE clients
         self.emit_event(DataEvent::TaskCompleted {
@@ -469,6 +1123,7 @@ impl LoaaServer {
             task_id: task_uuid.to_string(),
             amount: entry.amount.to_string(),
         });
+        let budgets = self.budgets_after_payout(kid_uuid, entry.amount).await?;
 
         let response = json!({
             "success": true,
@@ -478,11 +1133,15 @@ impl LoaaServer {
                 "amount": entry.amount.to_string(),
                 "entry_type": match entry.entry_type {
                     EntryType::Earned => "earned",
-                    EntryType::Adjusted => "adjusted"
+                    EntryType::Adjusted => "adjusted",
+                    EntryType::Payout => "payout",
+                    EntryType::Transfer => "transfer"
                 },
                 "description": entry.description,
+                "review": review_json(&entry),
                 "created_at": entry.created_at.to_rfc3339()
-            }
+            },
+            "budgets": budgets
         });
 
         Ok(CallToolResult::success(vec![Content::text(
@@ -493,28 +1152,31 @@ impl LoaaServer {
     #[tool(description = "Get the ledger (transaction history and balance) for a specific kid.")]
     async fn get_ledger(
         &self,
+        extensions: Extensions,
         Parameters(params): Parameters<GetLedgerParams>,
     ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
         let kid_uuid = Uuid::parse_str(&params.kid_id).map_err(|e| {
             McpError::invalid_request(format!("Invalid kid ID: {}", e), None)
         })?;
 
-        let ledger_repo = self.ledger_repo.read().await;
-        let ledger = ledger_repo.get_ledger(kid_uuid).await.map_err(|e| {
-            McpError::internal_error("database_error", Some(json!({"error": e.to_string()})))
-        })?;
+        let ledger = self.policy.read().await.ledger(&actor, kid_uuid).await.map_err(core_error)?;
 
         let response = json!({
             "kid_id": ledger.kid_id.to_string(),
             "balance": ledger.balance.to_string(),
+            "average_quality": quality_json(&ledger.average_quality()),
             "entries": ledger.entries.iter().map(|e| json!({
                 "id": e.id.to_string(),
                 "amount": e.amount.to_string(),
                 "entry_type": match e.entry_type {
                     EntryType::Earned => "earned",
-                    EntryType::Adjusted => "adjusted"
+                    EntryType::Adjusted => "adjusted",
+                    EntryType::Payout => "payout",
+                    EntryType::Transfer => "transfer"
                 },
                 "description": e.description,
+                "review": review_json(e),
                 "created_at": e.created_at.to_rfc3339()
             })).collect::<Vec<_>>()
         });
@@ -524,11 +1186,892 @@ impl LoaaServer {
         )]))
     }
 
+    #[tool(description = "Report average quality ratings per kid and per task, based on reviewed task completions.")]
+    async fn get_quality_report(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<GetQualityReportParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner_id = self.get_owner_id(&extensions).await?;
+
+        let kid_repo = self.kid_repo.read().await;
+        let mut kids = kid_repo.list_by_owner(&owner_id).await.map_err(|e| {
+            McpError::internal_error("database_error", Some(json!({"error": e.to_string()})))
+        })?;
+        if let Some(kid_id) = params.kid_id {
+            let kid_uuid = Uuid::parse_str(&kid_id).map_err(|e| {
+                McpError::invalid_request(format!("Invalid kid ID: {}", e), None)
+            })?;
+            kids.retain(|k| k.id == kid_uuid);
+        }
+
+        let task_repo = self.task_repo.read().await;
+        let tasks = task_repo.list_by_owner(&owner_id).await.map_err(|e| {
+            McpError::internal_error("database_error", Some(json!({"error": e.to_string()})))
+        })?;
+
+        let ledger_repo = self.ledger_repo.read().await;
+        let mut report = Vec::new();
+        for kid in kids {
+            let ledger = ledger_repo.get_ledger(kid.id).await.map_err(|e| {
+                McpError::internal_error("database_error", Some(json!({"error": e.to_string()})))
+            })?;
+
+            let by_task = ledger.average_quality_by_task();
+            report.push(json!({
+                "kid_id": kid.id.to_string(),
+                "name": kid.name,
+                "average_quality": quality_json(&ledger.average_quality()),
+                "tasks": by_task.iter().map(|(task_id, stat)| json!({
+                    "task_id": task_id.to_string(),
+                    "name": tasks.iter().find(|t| t.id == *task_id).map(|t| t.name.clone()),
+                    "average": stat.average.to_string(),
+                    "rated_count": stat.rated_count
+                })).collect::<Vec<_>>()
+            }));
+        }
+
+        let response = json!({ "kids": report });
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "List task claims kids have submitted from their PIN login that are waiting for a parent to approve or reject.")]
+    async fn list_pending_claims(&self, extensions: Extensions) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let claims = self.claims.read().await.list_pending(&actor).await.map_err(core_error)?;
+
+        let response = json!({
+            "claims": claims.iter().map(|c| json!({
+                "id": c.id.to_string(),
+                "kid_id": c.kid_id.to_string(),
+                "task_id": c.task_id.to_string(),
+                "note": c.note,
+                "attachment_id": c.attachment_id.map(|id| id.to_string()),
+                "created_at": c.created_at.to_rfc3339()
+            })).collect::<Vec<_>>()
+        });
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Approve a kid's task claim. This completes the task for the kid and pays them. Optionally rate the quality (1-5), pay only a percentage of the value (0-100) and leave a comment.")]
+    async fn approve_claim(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<ApproveClaimParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let claim_uuid = Uuid::parse_str(&params.claim_id).map_err(|e| {
+            McpError::invalid_request(format!("Invalid claim ID: {}", e), None)
+        })?;
+
+        let review = if params.quality.is_some() || params.percent.is_some() || params.comment.is_some() {
+            Some(
+                CompletionReview::new(params.quality, params.percent.unwrap_or(100), params.comment)
+                    .map_err(|e| McpError::invalid_request(e.to_string(), None))?,
+            )
+        } else {
+            None
+        };
+
+        let (claim, entry) = self.claims.read().await
+            .approve(&actor, claim_uuid, review)
+            .await
+            .map_err(core_error)?;
+
+        self.emit_event(DataEvent::TaskCompleted {
+            kid_id: claim.kid_id.to_string(),
+            task_id: claim.task_id.to_string(),
+            amount: entry.amount.to_string(),
+        });
+        let budgets = self.budgets_after_payout(claim.kid_id, entry.amount).await?;
+
+        let response = json!({
+            "success": true,
+            "claim_id": claim.id.to_string(),
+            "ledger_entry": {
+                "id": entry.id.to_string(),
+                "kid_id": entry.kid_id.to_string(),
+                "amount": entry.amount.to_string(),
+                "description": entry.description,
+                "review": review_json(&entry),
+                "created_at": entry.created_at.to_rfc3339()
+            },
+            "budgets": budgets
+        });
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Reject a kid's task claim without paying anything.")]
+    async fn reject_claim(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<RejectClaimParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let claim_uuid = Uuid::parse_str(&params.claim_id).map_err(|e| {
+            McpError::invalid_request(format!("Invalid claim ID: {}", e), None)
+        })?;
+
+        let claim = self.claims.read().await
+            .reject(&actor, claim_uuid)
+            .await
+            .map_err(core_error)?;
+
+        let response = json!({
+            "success": true,
+            "claim_id": claim.id.to_string()
+        });
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Request a withdrawal of part of a kid's balance. The amount comes off the balance straight away as a payout entry and waits for a parent to approve it; it must be covered by the balance.")]
+    async fn request_withdrawal(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<RequestWithdrawalParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let kid = self.find_kid(actor.household_id().unwrap_or_default(), &params.kid).await?;
+        let amount = Decimal::from_str(params.amount.trim()).map_err(|e| {
+            McpError::invalid_request(format!("Invalid amount: {}", e), None)
+        })?;
+
+        let withdrawal = self.withdrawals.read().await
+            .request(&actor, kid.id, amount, params.note)
+            .await
+            .map_err(core_error)?;
+        self.withdrawal_updated(&withdrawal);
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&json!({
+                "success": true,
+                "kid": kid.name,
+                "withdrawal": withdrawal_json(&withdrawal)
+            })).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "List withdrawals waiting for a parent: requested ones to approve or decline, and approved ones to pay.")]
+    async fn list_pending_payouts(&self, extensions: Extensions) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let withdrawals = self.withdrawals.read().await.list_open(&actor).await.map_err(core_error)?;
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&json!({
+                "withdrawals": withdrawals.iter().map(withdrawal_json).collect::<Vec<_>>()
+            })).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Approve a requested withdrawal. The kid is then owed the money until it's marked paid.")]
+    async fn approve_withdrawal(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<WithdrawalParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let withdrawal_id = parse_withdrawal_id(&params.withdrawal_id)?;
+
+        let withdrawal = self.withdrawals.read().await
+            .approve(&actor, withdrawal_id)
+            .await
+            .map_err(core_error)?;
+        self.withdrawal_updated(&withdrawal);
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&json!({
+                "success": true,
+                "withdrawal": withdrawal_json(&withdrawal)
+            })).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Decline a requested or approved withdrawal that hasn't been paid. The amount goes back on the kid's balance.")]
+    async fn decline_withdrawal(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<WithdrawalParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let withdrawal_id = parse_withdrawal_id(&params.withdrawal_id)?;
+
+        let withdrawal = self.withdrawals.read().await
+            .decline(&actor, withdrawal_id)
+            .await
+            .map_err(core_error)?;
+        self.withdrawal_updated(&withdrawal);
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&json!({
+                "success": true,
+                "withdrawal": withdrawal_json(&withdrawal)
+            })).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Mark an approved withdrawal as paid, recording how: 'cash', 'bank_transfer' or 'gift_card'.")]
+    async fn mark_withdrawal_paid(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<MarkWithdrawalPaidParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let withdrawal_id = parse_withdrawal_id(&params.withdrawal_id)?;
+        let method = PaymentMethod::parse(&params.method).ok_or_else(|| {
+            McpError::invalid_request("Invalid method. Must be 'cash', 'bank_transfer' or 'gift_card'", None)
+        })?;
+
+        let withdrawal = self.withdrawals.read().await
+            .mark_paid(&actor, withdrawal_id, method)
+            .await
+            .map_err(core_error)?;
+        self.withdrawal_updated(&withdrawal);
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&json!({
+                "success": true,
+                "withdrawal": withdrawal_json(&withdrawal)
+            })).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Show, for each kid, how much of their withdrawals the parents still owe (requested and approved) and how much has been paid.")]
+    async fn get_payout_summary(&self, extensions: Extensions) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let summary = self.withdrawals.read().await.summary(&actor).await.map_err(core_error)?;
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&json!({
+                "kids": summary.iter().map(|s| json!({
+                    "kid_id": s.kid_id.to_string(),
+                    "name": s.name,
+                    "balance": s.balance.to_string(),
+                    "requested": s.requested.to_string(),
+                    "approved": s.approved.to_string(),
+                    "owed": s.owed.to_string(),
+                    "paid": s.paid.to_string()
+                })).collect::<Vec<_>>()
+            })).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Move money from one kid's balance to another's. Writes a debit on the sender's ledger and a credit on the receiver's together, linked by a transfer ID, and only if the sender's balance covers it. With require_approval, the transfer waits for a parent to approve it instead.")]
+    async fn transfer_between_kids(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<TransferBetweenKidsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let from = self.find_kid(actor.household_id().unwrap_or_default(), &params.from_kid).await?;
+        let to = self.find_kid(actor.household_id().unwrap_or_default(), &params.to_kid).await?;
+        let amount = Decimal::from_str(params.amount.trim()).map_err(|e| {
+            McpError::invalid_request(format!("Invalid amount: {}", e), None)
+        })?;
+
+        let transfer = self.transfers.read().await
+            .transfer(&actor, from.id, to.id, amount, params.note, params.require_approval.unwrap_or(false))
+            .await
+            .map_err(core_error)?;
+        self.transfer_updated(&transfer);
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&json!({
+                "success": true,
+                "from": from.name,
+                "to": to.name,
+                "transfer": transfer_json(&transfer)
+            })).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "List transfers between kids that are waiting for a parent to approve or decline.")]
+    async fn list_pending_transfers(&self, extensions: Extensions) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let transfers = self.transfers.read().await.list_pending(&actor).await.map_err(core_error)?;
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&json!({
+                "transfers": transfers.iter().map(transfer_json).collect::<Vec<_>>()
+            })).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Approve a pending transfer, moving the money if the sender's balance still covers it.")]
+    async fn approve_transfer(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<TransferParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let transfer_id = parse_transfer_id(&params.transfer_id)?;
+
+        let transfer = self.transfers.read().await
+            .approve(&actor, transfer_id)
+            .await
+            .map_err(core_error)?;
+        self.transfer_updated(&transfer);
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&json!({
+                "success": true,
+                "transfer": transfer_json(&transfer)
+            })).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Decline a pending transfer. Nothing is moved.")]
+    async fn decline_transfer(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<TransferParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let transfer_id = parse_transfer_id(&params.transfer_id)?;
+
+        let transfer = self.transfers.read().await
+            .decline(&actor, transfer_id)
+            .await
+            .map_err(core_error)?;
+        self.transfer_updated(&transfer);
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&json!({
+                "success": true,
+                "transfer": transfer_json(&transfer)
+            })).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Break down each kid's task earnings by task category (room, kitchen, school, pets, yard, other).")]
+    async fn get_earnings_by_category(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<GetEarningsByCategoryParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let owner_id = self.get_owner_id(&extensions).await?;
+
+        let kid_repo = self.kid_repo.read().await;
+        let mut kids = kid_repo.list_by_owner(&owner_id).await.map_err(|e| {
+            McpError::internal_error("database_error", Some(json!({"error": e.to_string()})))
+        })?;
+        if let Some(kid_id) = params.kid_id {
+            let kid_uuid = Uuid::parse_str(&kid_id).map_err(|e| {
+                McpError::invalid_request(format!("Invalid kid ID: {}", e), None)
+            })?;
+            kids.retain(|k| k.id == kid_uuid);
+        }
+
+        let task_repo = self.task_repo.read().await;
+        let tasks = task_repo.list_by_owner(&owner_id).await.map_err(|e| {
+            McpError::internal_error("database_error", Some(json!({"error": e.to_string()})))
+        })?;
+
+        let ledger_repo = self.ledger_repo.read().await;
+        let mut report = Vec::new();
+        for kid in kids {
+            let ledger = ledger_repo.get_ledger(kid.id).await.map_err(|e| {
+                McpError::internal_error("database_error", Some(json!({"error": e.to_string()})))
+            })?;
+
+            report.push(json!({
+                "kid_id": kid.id.to_string(),
+                "name": kid.name,
+                "categories": ledger.earnings_by_category(&tasks).iter().map(|(category, total)| json!({
+                    "category": category.map(|c| c.as_str()).unwrap_or("uncategorized"),
+                    "earned": total.to_string()
+                })).collect::<Vec<_>>()
+            }));
+        }
+
+        let response = json!({ "kids": report });
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Report what kids earned per day, week or month, broken down by task, category and entry type, with each period compared to the one before and the whole span to the span before it. 'earned' counts task earnings; 'net' includes adjustments. Use it to answer questions like how much a kid earned last month.")]
+    async fn get_earnings_report(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<GetEarningsReportParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+
+        let period = match params.period {
+            Some(period) => ReportPeriod::parse(&period).ok_or_else(|| {
+                McpError::invalid_request("Invalid period. Must be 'day', 'week' or 'month'", None)
+            })?,
+            None => ReportPeriod::Month,
+        };
+        let kid_id = match params.kid {
+            Some(kid) => Some(self.find_kid(actor.household_id().unwrap_or_default(), &kid).await?.id),
+            None => None,
+        };
+        let to = parse_day("to", params.to)?.unwrap_or_else(|| chrono::Utc::now().date_naive());
+        let query = match parse_day("from", params.from)? {
+            Some(from) => ReportQuery::new(kid_id, period, from, to),
+            None => ReportQuery::recent(kid_id, period, params.periods.unwrap_or(period.default_count()), to),
+        }
+        .map_err(core_error)?;
+
+        let report = self.reports.read().await.earnings(&actor, &query).await.map_err(core_error)?;
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&report).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Project what each kid is expected to be paid over the next week and month, from task values and cadences and how often each kid did each task over the last four weeks. One-time tasks nobody has done yet count toward the household total. Includes a warning when the projection goes over the household's payout budget.")]
+    async fn get_payout_forecast(
+        &self,
+        extensions: Extensions,
+        Parameters(_params): Parameters<GetPayoutForecastParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let forecast = self.forecasts.read().await
+            .forecast(&actor, chrono::Utc::now().date_naive())
+            .await
+            .map_err(core_error)?;
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&forecast).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Show each payout budget (the household's and any kid's) with what's been paid and what's left this week or month.")]
+    async fn get_payout_budgets(
+        &self,
+        extensions: Extensions,
+        Parameters(_params): Parameters<GetPayoutBudgetsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let statuses = self.budgets.read().await
+            .statuses(&actor, chrono::Utc::now().date_naive())
+            .await
+            .map_err(core_error)?;
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&json!({
+                "budgets": statuses.iter().map(budget_json).collect::<Vec<_>>()
+            })).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Set a weekly or monthly payout budget for the household, or for one kid on top of it. Task completions and claim approvals that go over a budget are paid with a warning, or refused if its enforcement is 'block'. Forecasts are checked against the household's budget. Call without an amount to remove a budget.")]
+    async fn set_payout_budget(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<SetPayoutBudgetParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let user_id = self.get_user_id(&extensions);
+        let budget = match params.amount {
+            Some(amount) => {
+                let amount = Decimal::from_str(amount.trim()).map_err(|e| {
+                    McpError::invalid_request(format!("Invalid amount: {}", e), None)
+                })?;
+                let period = match params.period {
+                    Some(period) => BudgetPeriod::parse(&period).ok_or_else(|| {
+                        McpError::invalid_request("Invalid period. Must be 'week' or 'month'", None)
+                    })?,
+                    None => BudgetPeriod::Week,
+                };
+                let enforcement = match params.enforcement {
+                    Some(enforcement) => BudgetEnforcement::parse(&enforcement).ok_or_else(|| {
+                        McpError::invalid_request("Invalid enforcement. Must be 'warn' or 'block'", None)
+                    })?,
+                    None => BudgetEnforcement::Warn,
+                };
+                Some(PayoutBudget::new(amount, period).map_err(core_error)?.with_enforcement(enforcement))
+            }
+            None => None,
+        };
+
+        let actor = self.get_actor(&extensions).await?;
+        let households = self.households.read().await;
+        let household = match params.kid {
+            Some(kid) => {
+                let kid = self.find_kid(actor.household_id().unwrap_or_default(), &kid).await?;
+                households.set_kid_budget(&user_id, &kid, budget).await
+            }
+            None => households.set_budget(&user_id, budget).await,
+        }
+        .map_err(core_error)?;
+
+        let statuses = self.budgets.read().await
+            .statuses(&actor, chrono::Utc::now().date_naive())
+            .await
+            .map_err(core_error)?;
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&json!({
+                "household": household.name,
+                "budgets": statuses.iter().map(budget_json).collect::<Vec<_>>()
+            })).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "List the built-in library of chore templates, grouped into packs by age band and room, with suggested values and cadences.")]
+    async fn list_task_templates(
+        &self,
+        Parameters(params): Parameters<ListTaskTemplatesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let age_band = match params.age_band {
+            Some(band) => Some(AgeBand::parse(&band).ok_or_else(|| {
+                McpError::invalid_request(
+                    "Invalid age band. Must be 'preschool', 'elementary', 'tween', or 'teen'",
+                    None,
+                )
+            })?),
+            None => None,
+        };
+
+        let packs = self.templates.filter(age_band, params.room.as_deref());
+
+        let response = json!({
+            "packs": packs.iter().map(|p| json!({
+                "id": p.id,
+                "name": p.name,
+                "description": p.description,
+                "age_band": p.age_band,
+                "ages": p.age_band.label(),
+                "room": p.room,
+                "templates": p.templates.iter().map(|t| json!({
+                    "name": t.name,
+                    "description": t.description,
+                    "suggested_value": t.suggested_value.to_string(),
+                    "cadence": match t.cadence {
+                        Cadence::Daily => "daily",
+                        Cadence::Weekly => "weekly",
+                        Cadence::OneTime => "onetime"
+                    }
+                })).collect::<Vec<_>>()
+            })).collect::<Vec<_>>()
+        });
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Import a chore pack, creating a task for each template. Values can be scaled with value_scale (e.g. '0.5' for half the suggested values). Tasks that already exist by name are skipped.")]
+    async fn import_task_pack(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<ImportTaskPackParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let pack = self.templates.get(&params.pack_id).ok_or_else(|| {
+            McpError::resource_not_found(format!("Task pack not found: {}", params.pack_id), None)
+        })?;
+
+        let value_scale = match params.value_scale {
+            Some(scale) => Decimal::from_str(&scale).map_err(|e| {
+                McpError::invalid_request(format!("Invalid value scale: {}", e), None)
+            })?,
+            None => Decimal::ONE,
+        };
+
+        let pack_import = self.pack_import.read().await;
+        let result = pack_import
+            .import_pack(pack, &actor, value_scale)
+            .await
+            .map_err(core_error)?;
+
+        // Emit events for SSE clients
+        for task in &result.created {
+            self.emit_event(DataEvent::TaskCreated {
+                id: task.id.to_string(),
+                name: task.name.clone(),
+            });
+        }
+
+        let response = json!({
+            "pack_id": pack.id,
+            "created": result.created.iter().map(|t| json!({
+                "id": t.id.to_string(),
+                "name": t.name,
+                "value": t.value.to_string(),
+                "cadence": match t.cadence {
+                    Cadence::Daily => "daily",
+                    Cadence::Weekly => "weekly",
+                    Cadence::OneTime => "onetime"
+                }
+            })).collect::<Vec<_>>(),
+            "skipped": result.skipped
+        });
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Export all tasks as CSV text (columns: name, description, value, cadence, category, tags), for opening in a spreadsheet.")]
+    async fn export_tasks_csv(
+        &self,
+        extensions: Extensions,
+        Parameters(_params): Parameters<ExportTasksCsvParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let csv = self.csv.read().await.export_tasks(&actor).await.map_err(core_error)?;
+        Ok(CallToolResult::success(vec![Content::text(csv)]))
+    }
+
+    #[tool(description = "Export ledger entries as CSV text (columns: date, kid, kid_id, type, amount, description, category), oldest first. Optionally limit to one kid and to a date range (YYYY-MM-DD, inclusive).")]
+    async fn export_ledger_csv(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<ExportLedgerCsvParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let kid_id = params.kid_id
+            .map(|id| Uuid::parse_str(&id).map_err(|e| {
+                McpError::invalid_request(format!("Invalid kid ID: {}", e), None)
+            }))
+            .transpose()?;
+        let filter = LedgerFilter {
+            kid_id,
+            from: parse_day("from", params.from)?,
+            to: parse_day("to", params.to)?,
+        };
+
+        let csv = self.csv.read().await.export_ledger(&actor, &filter).await.map_err(core_error)?;
+        Ok(CallToolResult::success(vec![Content::text(csv)]))
+    }
+
+    #[tool(description = "Import tasks from CSV text with a header row. Fields: name, value and cadence (required), description, category, tags (separated by ';'). Map fields to differently named columns with columns. Rows named like an existing task are skipped. If any row has an error nothing is imported; use dry_run to check a file first.")]
+    async fn import_tasks_csv(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<ImportCsvParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let mapping = column_mapping(params.columns);
+        let report = self.csv.read().await
+            .import_tasks(&actor, &params.csv, &mapping, params.dry_run.unwrap_or(false))
+            .await
+            .map_err(core_error)?;
+
+        if report.written {
+            self.emit_event(DataEvent::TasksImported { count: report.imported });
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&import_report_json(&report)).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Import past ledger entries from CSV text with a header row. Fields: date (YYYY-MM-DD or RFC 3339), amount and kid (name or ID) or kid_id (required), type ('earned' or 'adjusted'; defaults by the amount's sign), description, category. Map fields to differently named columns with columns. Rows matching an existing entry are skipped. If any row has an error nothing is imported; use dry_run to check a file first.")]
+    async fn import_ledger_csv(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<ImportCsvParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let mapping = column_mapping(params.columns);
+        let report = self.csv.read().await
+            .import_ledger(&actor, &params.csv, &mapping, params.dry_run.unwrap_or(false), chrono::Utc::now())
+            .await
+            .map_err(core_error)?;
+
+        if report.written {
+            self.emit_event(DataEvent::LedgerImported { count: report.imported });
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&import_report_json(&report)).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "List archived (deleted) kids and tasks that can still be restored.")]
+    async fn list_archived(
+        &self,
+        extensions: Extensions,
+        Parameters(_params): Parameters<ListArchivedParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let (kids, tasks) = self.archive.read().await.list_archived(&actor).await.map_err(core_error)?;
+
+        let response = json!({
+            "kids": kids.iter().map(|k| json!({
+                "id": k.id.to_string(),
+                "name": k.name,
+                "archived_at": k.archived_at.map(|t| t.to_rfc3339())
+            })).collect::<Vec<_>>(),
+            "tasks": tasks.iter().map(|t| json!({
+                "id": t.id.to_string(),
+                "name": t.name,
+                "archived_at": t.archived_at.map(|t| t.to_rfc3339())
+            })).collect::<Vec<_>>()
+        });
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Restore an archived kid or task. Kind must be 'kid' or 'task'. Only possible within the restore window.")]
+    async fn restore_archived(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<ArchivedRecordParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let is_kid = parse_archived_kind(&params.kind)?;
+        let id = Uuid::parse_str(&params.id).map_err(|e| {
+            McpError::invalid_request(format!("Invalid ID: {}", e), None)
+        })?;
+
+        let archive = self.archive.read().await;
+        let name = if is_kid {
+            let kid = archive.restore_kid(&actor, id, chrono::Utc::now()).await.map_err(core_error)?;
+            self.emit_event(DataEvent::KidCreated {
+                id: kid.id.to_string(),
+                name: kid.name.clone(),
+            });
+            kid.name
+        } else {
+            let task = archive.restore_task(&actor, id, chrono::Utc::now()).await.map_err(core_error)?;
+            self.emit_event(DataEvent::TaskCreated {
+                id: task.id.to_string(),
+                name: task.name.clone(),
+            });
+            task.name
+        };
+
+        let response = json!({
+            "success": true,
+            "message": format!("Restored {} '{}'", params.kind.to_lowercase(), name)
+        });
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Permanently remove an archived kid or task. Kind must be 'kid' or 'task'. Purging a kid also removes their ledger, claims, photos and kid login. This cannot be undone.")]
+    async fn purge_archived(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<ArchivedRecordParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
+        let is_kid = parse_archived_kind(&params.kind)?;
+        let id = Uuid::parse_str(&params.id).map_err(|e| {
+            McpError::invalid_request(format!("Invalid ID: {}", e), None)
+        })?;
+
+        let archive = self.archive.read().await;
+        if is_kid {
+            archive.purge_kid(&actor, id).await.map_err(core_error)?;
+        } else {
+            archive.purge_task(&actor, id).await.map_err(core_error)?;
+        }
+
+        let response = json!({
+            "success": true,
+            "message": format!("Permanently removed {} {}", params.kind.to_lowercase(), id)
+        });
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Create a link that lets a co-parent or grandparent register and join your household. Role must be 'owner', 'parent' or 'viewer'. The link works once and expires after ttl_days (default 7).")]
+    async fn create_invite(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<CreateInviteParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let user_id = self.get_user_id(&extensions);
+        let role = HouseholdRole::parse(&params.role).ok_or_else(|| {
+            McpError::invalid_request("Invalid role. Must be 'owner', 'parent', or 'viewer'", None)
+        })?;
+        let ttl = match params.ttl_days {
+            Some(days) => chrono::Duration::days(days.into()),
+            None => default_invite_ttl(),
+        };
+
+        let (invite, code) = self.invites.read().await
+            .create(&user_id, role, ttl)
+            .await
+            .map_err(core_error)?;
+
+        let base_url = std::env::var("LOAA_BASE_URL")
+            .unwrap_or_else(|_| "http://127.0.0.1:3000".to_string());
+        let mut response = invite_json(&invite);
+        response["link"] = json!(format!("{}/?invite={}", base_url.trim_end_matches('/'), code));
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "List household invites that haven't been used, revoked or expired yet.")]
+    async fn list_invites(
+        &self,
+        extensions: Extensions,
+        Parameters(_params): Parameters<ListInvitesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let user_id = self.get_user_id(&extensions);
+        let invites = self.invites.read().await
+            .list_pending(&user_id, chrono::Utc::now())
+            .await
+            .map_err(core_error)?;
+
+        let response = json!({
+            "invites": invites.iter().map(invite_json).collect::<Vec<_>>()
+        });
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Revoke a household invite so its link can no longer be used.")]
+    async fn revoke_invite(
+        &self,
+        extensions: Extensions,
+        Parameters(params): Parameters<RevokeInviteParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let user_id = self.get_user_id(&extensions);
+        let invite_id = Uuid::from_str(&params.invite_id).map_err(|e| {
+            McpError::invalid_request(format!("Invalid invite ID: {}", e), None)
+        })?;
+
+        let invite = self.invites.read().await
+            .revoke(&user_id, invite_id)
+            .await
+            .map_err(core_error)?;
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&json!({
+                "revoked": true,
+                "invite": invite_json(&invite)
+            })).unwrap(),
+        )]))
+    }
+
     #[tool(description = "Manually adjust a kid's balance. Use positive amounts to add money, negative to deduct. Amount should be a decimal string (e.g., '5.00' or '-2.50').")]
     async fn adjust_balance(
         &self,
+        extensions: Extensions,
         Parameters(params): Parameters<AdjustBalanceParams>,
     ) -> Result<CallToolResult, McpError> {
+        let actor = self.get_actor(&extensions).await?;
         let kid_uuid = Uuid::parse_str(&params.kid_id).map_err(|e| {
             McpError::invalid_request(format!("Invalid kid ID: {}", e), None)
         })?;
@@ -537,11 +2080,10 @@ impl LoaaServer {
         })?;
 
         let description = params.description.clone();
-        let entry = LedgerEntry::adjusted(kid_uuid, amount_dec, params.description);
-        let ledger_repo = self.ledger_repo.read().await;
-        let created = ledger_repo.create_entry(entry).await.map_err(|e| {
-            McpError::internal_error("database_error", Some(json!({"error": e.to_string()})))
-        })?;
+        let created = self.policy.read().await
+            .adjust_balance(&actor, kid_uuid, amount_dec, params.description)
+            .await
+            .map_err(core_error)?;
 
         // Emit event for SSE clients
         self.emit_event(DataEvent::BalanceAdjusted {
@@ -591,32 +2133,45 @@ impl rmcp::handler::server::ServerHandler for LoaaServer {
         }
     }
 
-    fn list_tools(
+    async fn list_tools(
         &self,
         _request: Option<PaginatedRequestParam>,
         _context: RequestContext<RoleServer>,
-    ) -> impl std::future::Future<Output = Result<ListToolsResult, McpError>> + Send + '_ {
-        async move {
-            let tools = self.tool_router.list_all();
-            Ok(ListToolsResult {
-                tools,
-                next_cursor: None,
-            })
-        }
+    ) -> Result<ListToolsResult, McpError> {
+        let tools = self.tool_router.list_all().into_iter().map(with_idempotency_key).collect();
+        Ok(ListToolsResult {
+            tools,
+            next_cursor: None,
+        })
     }
 
-    fn call_tool(
+    async fn call_tool(
         &self,
-        request: CallToolRequestParam,
+        mut request: CallToolRequestParam,
         context: RequestContext<RoleServer>,
-    ) -> impl std::future::Future<Output = Result<CallToolResult, McpError>> + Send + '_ {
-        async move {
-            use rmcp::handler::server::tool::ToolCallContext;
-
-            let tool_context = ToolCallContext::new(self, request, context);
+    ) -> Result<CallToolResult, McpError> {
+        use rmcp::handler::server::tool::ToolCallContext;
 
-            self.tool_router.call(tool_context).await
+        let key = match request.arguments.as_mut().and_then(|args| args.remove("idempotency_key")) {
+            None | Some(serde_json::Value::Null) => None,
+            Some(serde_json::Value::String(key)) => Some(key),
+            Some(_) => return Err(McpError::invalid_params("idempotency_key must be a string", None)),
+        };
+        let operation = request.name.to_string();
+        if key.is_none() || !MUTATING_TOOLS.contains(&operation.as_str()) {
+            return self.tool_router.call(ToolCallContext::new(self, request, context)).await;
         }
+
+        // A replayed call returns the stored result without running the tool
+        let scope = self.get_user_id(&context.extensions);
+        let arguments = request.arguments.clone();
+        let idempotency = self.idempotency.read().await;
+        idempotency
+            .run(&scope, key.as_deref(), &operation, &arguments, || {
+                self.tool_router.call(ToolCallContext::new(self, request, context))
+            })
+            .await
+            .map_err(core_error)?
     }
 }
 
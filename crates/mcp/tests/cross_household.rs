@@ -0,0 +1,165 @@
+use loaa_core::config::{DatabaseConfig, DatabaseMode};
+use loaa_core::{ClaimRepository, Database, HouseholdRepository, HouseholdWorkflow, TaskClaim, Uuid};
+use loaa_mcp::LoaaServer;
+use rmcp::model::{CallToolRequestParam, CallToolResult, ErrorCode};
+use rmcp::service::{RoleClient, RunningService, ServiceError};
+use rmcp::ServiceExt;
+use serde_json::{json, Value};
+
+// These tests use a fresh in-memory database, so they don't need a running
+// SurrealDB server. Each server acts for the user it was started with, like
+// the stdio server does.
+
+type Client = RunningService<RoleClient, ()>;
+
+async fn fresh_database() -> Database {
+    Database::init_with_config(&DatabaseConfig {
+        mode: DatabaseMode::Memory,
+        url: None,
+        path: None,
+        namespace: None,
+        database: None,
+        username: None,
+        password: None,
+        token: None,
+    })
+    .await
+    .expect("Failed to initialize database")
+}
+
+/// Connect a client to a server acting for `user_id`
+async fn connect(database: &Database, user_id: &str) -> Client {
+    let server = LoaaServer::with_shared_database(database, None, user_id.to_string()).unwrap();
+    let (server_io, client_io) = tokio::io::duplex(64 * 1024);
+    tokio::spawn(async move {
+        if let Ok(service) = server.serve(server_io).await {
+            let _ = service.waiting().await;
+        }
+    });
+    ().serve(client_io).await.unwrap()
+}
+
+async fn call(client: &Client, tool: &'static str, arguments: Value) -> Result<CallToolResult, ServiceError> {
+    client
+        .call_tool(CallToolRequestParam {
+            name: tool.into(),
+            arguments: arguments.as_object().cloned(),
+        })
+        .await
+}
+
+/// The JSON a successful call returned
+async fn ok(client: &Client, tool: &'static str, arguments: Value) -> Value {
+    let result = call(client, tool, arguments).await.unwrap();
+    assert_ne!(result.is_error, Some(true), "{} failed", tool);
+    let text = &result.content[0].as_text().unwrap().text;
+    serde_json::from_str(text).unwrap_or(Value::String(text.clone()))
+}
+
+/// Refused as another household's record: forbidden, or not found at all
+async fn assert_refused(client: &Client, tool: &'static str, arguments: Value) {
+    match call(client, tool, arguments).await {
+        Err(ServiceError::McpError(e)) => assert!(
+            e.code == ErrorCode::RESOURCE_NOT_FOUND || e.message.starts_with("Forbidden"),
+            "{} failed for another reason: {}",
+            tool,
+            e.message
+        ),
+        other => panic!("{} wasn't refused: {:?}", tool, other),
+    }
+}
+
+fn id(value: &Value) -> String {
+    value["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_tools_refuse_ids_from_another_household() {
+    std::env::set_var("LOAA_ATTACHMENT_DIR", std::env::temp_dir().join("loaa-mcp-tests"));
+    let database = fresh_database().await;
+    let home = connect(&database, "parent-a").await;
+    let stranger = connect(&database, "parent-b").await;
+
+    // The household's records, created through its own server
+    let kai = id(&ok(&home, "create_kid", json!({ "name": "Kai" })).await);
+    let mia = id(&ok(&home, "create_kid", json!({ "name": "Mia" })).await);
+    let gone = id(&ok(&home, "create_kid", json!({ "name": "Gone" })).await);
+    ok(&home, "delete_kid", json!({ "id": gone })).await;
+    let task = id(&ok(
+        &home,
+        "create_task",
+        json!({ "name": "Dishes", "description": "", "value": "2.00", "cadence": "daily" }),
+    )
+    .await);
+    ok(&home, "adjust_balance", json!({ "kid_id": kai, "amount": "10.00", "description": "Start" })).await;
+    let withdrawal = id(&ok(&home, "request_withdrawal", json!({ "kid": kai, "amount": "2.00" })).await["withdrawal"]);
+    let transfer = id(&ok(
+        &home,
+        "transfer_between_kids",
+        json!({ "from_kid": kai, "to_kid": mia, "amount": "1.00", "require_approval": true }),
+    )
+    .await["transfer"]);
+    let invite = id(&ok(&home, "create_invite", json!({ "role": "viewer" })).await);
+    let owner_id = HouseholdWorkflow::new(HouseholdRepository::new(database.client.clone()))
+        .household_for_user("parent-a")
+        .await
+        .unwrap()
+        .owner_id();
+    let claim = ClaimRepository::new(database.client.clone())
+        .create(TaskClaim::new(owner_id, Uuid::parse_str(&kai).unwrap(), Uuid::parse_str(&task).unwrap(), None, None))
+        .await
+        .unwrap()
+        .id
+        .to_string();
+
+    let ledger = ok(&home, "get_ledger", json!({ "kid_id": kai })).await;
+
+    // Every tool that takes an ID refuses the other household's
+    let attempts = [
+        ("delete_kid", json!({ "id": kai })),
+        ("update_task", json!({ "id": task, "name": "Mine now" })),
+        ("delete_task", json!({ "id": task })),
+        ("complete_task", json!({ "task_id": task, "kid_id": kai })),
+        ("get_ledger", json!({ "kid_id": kai })),
+        ("adjust_balance", json!({ "kid_id": kai, "amount": "-10.00", "description": "Gotcha" })),
+        ("approve_claim", json!({ "claim_id": claim })),
+        ("reject_claim", json!({ "claim_id": claim })),
+        ("request_withdrawal", json!({ "kid": kai, "amount": "1.00" })),
+        ("approve_withdrawal", json!({ "withdrawal_id": withdrawal })),
+        ("decline_withdrawal", json!({ "withdrawal_id": withdrawal })),
+        ("mark_withdrawal_paid", json!({ "withdrawal_id": withdrawal, "method": "cash" })),
+        ("transfer_between_kids", json!({ "from_kid": kai, "to_kid": mia, "amount": "1.00" })),
+        ("approve_transfer", json!({ "transfer_id": transfer })),
+        ("decline_transfer", json!({ "transfer_id": transfer })),
+        ("get_earnings_report", json!({ "kid": kai })),
+        ("set_payout_budget", json!({ "kid": kai, "amount": "1.00" })),
+        ("export_ledger_csv", json!({ "kid_id": kai })),
+        ("restore_archived", json!({ "kind": "kid", "id": gone })),
+        ("purge_archived", json!({ "kind": "kid", "id": gone })),
+        ("revoke_invite", json!({ "invite_id": invite })),
+    ];
+    for (tool, arguments) in attempts {
+        assert_refused(&stranger, tool, arguments).await;
+    }
+
+    // Filters by kid only ever narrow down the caller's own kids
+    for tool in ["get_quality_report", "get_earnings_by_category"] {
+        let report = ok(&stranger, tool, json!({ "kid_id": kai })).await;
+        assert_eq!(report["kids"], json!([]), "{} showed another household's kid", tool);
+    }
+
+    // And nothing of the household changed
+    assert_eq!(ok(&home, "get_ledger", json!({ "kid_id": kai })).await, ledger);
+    let tasks = ok(&home, "list_tasks", json!({})).await;
+    assert!(tasks.to_string().contains("Dishes"));
+    let payouts = ok(&home, "list_pending_payouts", json!({})).await;
+    assert_eq!(payouts["withdrawals"][0]["status"], "requested");
+    let transfers = ok(&home, "list_pending_transfers", json!({})).await;
+    assert_eq!(transfers["transfers"][0]["id"], transfer.as_str());
+    let claims = ok(&home, "list_pending_claims", json!({})).await;
+    assert_eq!(claims["claims"][0]["id"], claim.as_str());
+    let archived = ok(&home, "list_archived", json!({})).await;
+    assert_eq!(archived["kids"][0]["id"], gone.as_str());
+    let invites = ok(&home, "list_invites", json!({})).await;
+    assert!(invites.to_string().contains(&invite));
+}
@@ -0,0 +1,223 @@
+//! Photo attachment endpoints
+//! Uploads arrive as multipart forms; images are only served to the owning account
+//! (kid logins only see their own photos)
+
+use axum::{
+    extract::{Multipart, Path},
+    http::{header, StatusCode},
+    response::{IntoResponse, Redirect, Response},
+    Json,
+};
+use loaa_core::{Actor, Error, Uuid};
+use std::str::FromStr;
+use tower_sessions::Session;
+
+use crate::dto::AttachmentDto;
+use crate::server_functions::{get_attachment_workflow, get_claim_workflow, get_membership};
+
+type HandlerError = (StatusCode, String);
+
+fn error_response(e: Error) -> HandlerError {
+    match e {
+        Error::NotFound(_) => (StatusCode::NOT_FOUND, "Attachment not found".to_string()),
+        Error::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
+        Error::Validation(msg) => (StatusCode::BAD_REQUEST, msg),
+        other => {
+            eprintln!("Attachment error: {}", other);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Attachment error".to_string())
+        }
+    }
+}
+
+/// Who is making the request: a parent account, or a kid logged in with a PIN
+async fn session_actor(session: &Session) -> Result<Actor, HandlerError> {
+    let get = |key: &'static str| async move {
+        session.get::<String>(key).await.ok().flatten()
+    };
+    let unauthenticated = || (StatusCode::UNAUTHORIZED, "Not authenticated".to_string());
+
+    if get("account_type").await.as_deref() == Some("kid") {
+        let owner_id = get("owner_id").await.ok_or_else(unauthenticated)?;
+        let kid_id = get("kid_id").await.ok_or_else(unauthenticated)?;
+        return Ok(Actor::kid(owner_id, parse_uuid("kid ID", &kid_id)?));
+    }
+
+    let user_id = get("user_id").await.ok_or_else(unauthenticated)?;
+    let (household, role) = get_membership(&user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Actor::member(user_id, household.owner_id(), role))
+}
+
+fn parse_uuid(field: &str, value: &str) -> Result<Uuid, HandlerError> {
+    Uuid::from_str(value.trim())
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid {}: {}", field, e)))
+}
+
+async fn workflow() -> Result<loaa_core::AttachmentWorkflow, HandlerError> {
+    get_attachment_workflow()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// Upload a photo
+///
+/// Multipart fields: `file` (required), `kid_id` (required), and optionally
+/// `task_id`, `ledger_entry_id` to link the photo to a completion, and
+/// `return_to` to redirect there instead of returning JSON (for plain HTML
+/// forms).
+///
+/// Kid logins always upload for themselves; setting `claim` together with
+/// `task_id` also claims the task with the photo as proof.
+pub async fn upload_attachment(
+    session: Session,
+    mut multipart: Multipart,
+) -> Result<Response, HandlerError> {
+    let actor = session_actor(&session).await?;
+    // Household viewers can look at photos but not upload them
+    if matches!(actor, Actor::Member { .. }) && !actor.can_edit() {
+        return Err((StatusCode::FORBIDDEN, "Viewers can't upload photos".to_string()));
+    }
+
+    let mut file = None;
+    let mut kid_id = None;
+    let mut task_id = None;
+    let mut ledger_entry_id = None;
+    let mut return_to = None;
+    let mut claim = false;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+        if name == "file" {
+            let bytes = field
+                .bytes()
+                .await
+                .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+            file = Some(bytes.to_vec());
+            continue;
+        }
+
+        let value = field
+            .text()
+            .await
+            .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+        if value.trim().is_empty() {
+            continue;
+        }
+        match name.as_str() {
+            "kid_id" => kid_id = Some(parse_uuid("kid ID", &value)?),
+            "task_id" => task_id = Some(parse_uuid("task ID", &value)?),
+            "ledger_entry_id" => ledger_entry_id = Some(parse_uuid("ledger entry ID", &value)?),
+            "claim" => claim = true,
+            // Only allow redirects within this site
+            "return_to" if value.starts_with('/') && !value.starts_with("//") => return_to = Some(value),
+            _ => {}
+        }
+    }
+
+    let file = file.ok_or((StatusCode::BAD_REQUEST, "Missing file".to_string()))?;
+    let own_kid_id = match &actor {
+        Actor::Kid { kid_id, .. } => Some(*kid_id),
+        _ => None,
+    };
+    let kid_id = match own_kid_id {
+        Some(own_kid_id) => own_kid_id,
+        None => kid_id.ok_or((StatusCode::BAD_REQUEST, "Missing kid_id".to_string()))?,
+    };
+    if own_kid_id.is_some() && ledger_entry_id.is_some() {
+        return Err((StatusCode::FORBIDDEN, "Parent account required".to_string()));
+    }
+
+    let attachment = workflow()
+        .await?
+        .upload(&actor, kid_id, task_id, ledger_entry_id, file)
+        .await
+        .map_err(error_response)?;
+
+    eprintln!("📷 Stored attachment {} for kid {}", attachment.id, kid_id);
+
+    if claim {
+        let task_id = task_id.ok_or((StatusCode::BAD_REQUEST, "Missing task_id".to_string()))?;
+        if own_kid_id.is_none() {
+            return Err((StatusCode::FORBIDDEN, "Kid login required".to_string()));
+        }
+        get_claim_workflow()
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .claim_task(&actor, kid_id, task_id, None, Some(attachment.id))
+            .await
+            .map_err(error_response)?;
+    }
+
+    match return_to {
+        Some(path) => Ok(Redirect::to(&path).into_response()),
+        None => Ok((StatusCode::CREATED, Json(AttachmentDto::from(attachment))).into_response()),
+    }
+}
+
+/// Serve the original photo
+pub async fn get_attachment(
+    session: Session,
+    Path(id): Path<String>,
+) -> Result<Response, HandlerError> {
+    let actor = session_actor(&session).await?;
+    let id = parse_uuid("attachment ID", &id)?;
+
+    let (attachment, bytes) = workflow()
+        .await?
+        .read(&actor, id)
+        .await
+        .map_err(error_response)?;
+
+    Ok(image_response(attachment.content_type, bytes))
+}
+
+/// Serve the photo's thumbnail
+pub async fn get_attachment_thumbnail(
+    session: Session,
+    Path(id): Path<String>,
+) -> Result<Response, HandlerError> {
+    let actor = session_actor(&session).await?;
+    let id = parse_uuid("attachment ID", &id)?;
+
+    let bytes = workflow()
+        .await?
+        .read_thumbnail(&actor, id)
+        .await
+        .map_err(error_response)?;
+
+    Ok(image_response(loaa_core::attachments::THUMBNAIL_CONTENT_TYPE.to_string(), bytes))
+}
+
+fn image_response(content_type: String, bytes: Vec<u8>) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, content_type),
+            // Photos are private to the account, never cache them in shared caches
+            (header::CACHE_CONTROL, "private, max-age=3600".to_string()),
+        ],
+        bytes,
+    )
+        .into_response()
+}
+
+/// Periodically delete photos whose retention period has passed
+pub async fn run_retention_purge(interval: std::time::Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        let retention = crate::server_functions::get_attachment_config().retention();
+        match workflow().await {
+            Ok(workflow) => match workflow.purge_expired(retention, chrono::Utc::now()).await {
+                Ok(0) => {}
+                Ok(count) => eprintln!("🧹 Purged {} expired attachment(s)", count),
+                Err(e) => eprintln!("❌ Attachment purge failed: {}", e),
+            },
+            Err((_, e)) => eprintln!("❌ Attachment purge failed: {}", e),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Backup download for the admin panel
+//! The archive holds every household's data, so only the admin account can fetch it
+
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use tower_sessions::Session;
+
+use crate::server_functions::get_backup_workflow;
+
+/// Download a backup of the whole database as a JSON-lines file
+pub async fn download_backup(session: Session) -> Result<Response, (StatusCode, String)> {
+    let account_type: Option<String> = session.get("account_type").await.ok().flatten();
+    if account_type.as_deref() != Some("admin") {
+        return Err((StatusCode::FORBIDDEN, "Admin access required".to_string()));
+    }
+
+    let now = Utc::now();
+    let archive = get_backup_workflow()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .export(now)
+        .await
+        .map_err(|e| {
+            eprintln!("Backup error: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Backup failed".to_string())
+        })?;
+
+    eprintln!("💾 Admin downloaded a backup");
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/x-ndjson".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"loaa-backup-{}.jsonl\"", now.format("%Y-%m-%d")),
+            ),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+        archive,
+    )
+        .into_response())
+}
@@ -0,0 +1,66 @@
+use loaa_core::workflows::{BackupWorkflow, RestoreMode};
+use loaa_core::{Config, Database};
+
+const USAGE: &str = "Usage:
+  backup export <file>              write every table to a backup file
+  backup verify <file>              check a backup without restoring it
+  backup restore <file> [--replace] restore a backup, merging it into the
+                                    existing data unless --replace is given";
+
+/// Back up the database to a JSON-lines file, or restore one
+///
+/// Uses the same `LOAA_DB_*` settings as the server, so exporting from one
+/// mode and restoring into another moves a family's data between them.
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (command, path) = match args.as_slice() {
+        [command, path, ..] => (command.as_str(), path.as_str()),
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let config = Config::from_env();
+    config.validate()?;
+
+    match command {
+        "export" => {
+            let db = Database::init_with_config(&config.database).await?;
+            let archive = BackupWorkflow::new(db.client.clone()).export(chrono::Utc::now()).await?;
+            db.flush().await?;
+            std::fs::write(path, &archive)?;
+            println!("💾 Backed up {} records to {}", archive.lines().count() - 1, path);
+        }
+        "verify" => {
+            let db = Database::connect(&config.database).await?;
+            let manifest = BackupWorkflow::new(db.client.clone()).verify(&std::fs::read_to_string(path)?)?;
+            println!("✓ {} is a valid backup from {}", path, manifest.created_at.to_rfc3339());
+            for table in manifest.tables {
+                println!("  {:<14} {} records", table.name, table.records);
+            }
+        }
+        "restore" => {
+            let mode = if args.iter().any(|arg| arg == "--replace") {
+                RestoreMode::Replace
+            } else {
+                RestoreMode::Merge
+            };
+            let db = Database::init_with_config(&config.database).await?;
+            let summary = BackupWorkflow::new(db.client.clone())
+                .restore(&std::fs::read_to_string(path)?, mode)
+                .await?;
+            db.flush().await?;
+            println!(
+                "✅ Restored {} records ({} already there, {} under new IDs)",
+                summary.restored, summary.skipped, summary.remapped
+            );
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use loaa_core::{Config, Database, MigrationRunner};
+
+/// Apply pending database migrations, or just list them with `--dry-run`
+///
+/// The servers also migrate on startup; this is for checking what an upgrade
+/// will do before deploying it.
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let dry_run = std::env::args().any(|arg| arg == "--dry-run");
+
+    let config = Config::from_env();
+    config.validate()?;
+    let db = Database::connect(&config.database).await?;
+    let runner = MigrationRunner::new(db.client.clone());
+
+    println!("🗄️  Applied migrations:");
+    for applied in runner.applied().await? {
+        println!("  ✓ {:04} {} ({})", applied.version, applied.name, applied.applied_at.to_rfc3339());
+    }
+
+    let pending = runner.pending().await?;
+    if pending.is_empty() {
+        println!("\nDatabase is up to date.");
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("\n📋 Pending migrations (dry run, nothing applied):");
+        for migration in pending {
+            println!("  • {:04} {} (sha256 {})", migration.version, migration.name, migration.checksum());
+        }
+        return Ok(());
+    }
+
+    println!("\n🚀 Applying migrations...");
+    for migration in runner.run().await? {
+        println!("  ✓ {:04} {}", migration.version, migration.name);
+    }
+    db.flush().await?;
+    Ok(())
+}
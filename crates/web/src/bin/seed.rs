@@ -1,6 +1,6 @@
 use loaa_core::{
     init_database_with_config, Config, Kid, KidRepository, Task, TaskRepository,
-    Cadence, LedgerRepository, LedgerEntry, User, UserRepository, hash_password,
+    Cadence, LedgerRepository, LedgerEntry, Repository, User, UserRepository, hash_password,
     models::AccountType
 };
 use rust_decimal_macros::dec;
@@ -197,6 +197,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("\n✅ Transactions created successfully!");
     }
 
+    // SQLite mode would otherwise still be saving as the process exits
+    db.flush().await?;
+
     println!("🎯 Ready to track chores!\n");
     println!("Visit http://127.0.0.1:3000 to see the data");
 
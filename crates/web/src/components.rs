@@ -1,6 +1,7 @@
 use leptos::*;
 use crate::server_functions::*;
 use crate::dto::*;
+use crate::idempotency::Submission;
 
 #[cfg(feature = "hydrate")]
 use wasm_bindgen::prelude::*;
@@ -13,6 +14,8 @@ pub enum View {
     Admin,
     Dashboard,
     Ledger(UuidDto),
+    /// Home screen for a kid logged in with a PIN
+    Kid,
 }
 
 #[component]
@@ -22,6 +25,20 @@ pub fn Login(set_view: WriteSignal<View>) -> impl IntoView {
     let (error, set_error) = create_signal(Option::<String>::None);
     let (logging_in, set_logging_in) = create_signal(false);
     let (oauth_completing, set_oauth_completing) = create_signal(false);
+    let (kid_mode, set_kid_mode) = create_signal(false);
+    let (invite_code, set_invite_code) = create_signal(Option::<String>::None);
+
+    // Invite links look like /?invite=<code>; effects only run in the browser
+    create_effect(move |_| {
+        let search = leptos::window().location().search().unwrap_or_default();
+        let code = search
+            .trim_start_matches('?')
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("invite="))
+            .filter(|code| !code.is_empty())
+            .map(str::to_string);
+        set_invite_code.set(code);
+    });
 
     let on_submit = move |ev: leptos::ev::SubmitEvent| {
         ev.prevent_default();
@@ -31,6 +48,24 @@ pub fn Login(set_view: WriteSignal<View>) -> impl IntoView {
         let username_val = username.get();
         let password_val = password.get();
 
+        if kid_mode.get() {
+            spawn_local(async move {
+                match kid_login(username_val, password_val).await {
+                    Ok(KidLoginResultDto::Success) => set_view.set(View::Kid),
+                    Ok(KidLoginResultDto::InvalidPin) => {
+                        set_error.set(Some("Invalid username or PIN".to_string()));
+                    }
+                    Ok(KidLoginResultDto::Locked(until)) => {
+                        let minutes = (until - chrono::Utc::now()).num_minutes().max(1);
+                        set_error.set(Some(format!("Too many tries. Try again in {} minutes.", minutes)));
+                    }
+                    Err(e) => set_error.set(Some(format!("Login error: {}", e))),
+                }
+                set_logging_in.set(false);
+            });
+            return;
+        }
+
         spawn_local(async move {
             match login(username_val, password_val).await {
                 Ok(true) => {
@@ -78,7 +113,9 @@ pub fn Login(set_view: WriteSignal<View>) -> impl IntoView {
                 <h1>"Loa'a"</h1>
                 <p class="subtitle">"Chore and rewards tracking system"</p>
 
-                {move || if oauth_completing.get() {
+                {move || if let Some(code) = invite_code.get() {
+                    view! { <InviteRegistration code=code /> }.into_view()
+                } else if oauth_completing.get() {
                     view! {
                         <div class="oauth-completing">
                             <p class="oauth-message">"Completing authorization..."</p>
@@ -102,11 +139,12 @@ pub fn Login(set_view: WriteSignal<View>) -> impl IntoView {
                             </div>
 
                             <div class="form-group">
-                                <label for="password">"Password"</label>
+                                <label for="password">{move || if kid_mode.get() { "PIN" } else { "Password" }}</label>
                                 <input
                                     type="password"
                                     id="password"
                                     name="password"
+                                    inputmode=move || if kid_mode.get() { "numeric" } else { "text" }
                                     required
                                     disabled=move || logging_in.get()
                                     on:input=move |ev| set_password.set(event_target_value(&ev))
@@ -125,6 +163,18 @@ pub fn Login(set_view: WriteSignal<View>) -> impl IntoView {
                             >
                                 {move || if logging_in.get() { "Logging in..." } else { "Log In" }}
                             </button>
+
+                            <button
+                                type="button"
+                                class="login-mode-btn"
+                                on:click=move |_| {
+                                    set_error.set(None);
+                                    set_password.set(String::new());
+                                    set_kid_mode.update(|kid| *kid = !*kid);
+                                }
+                            >
+                                {move || if kid_mode.get() { "Parent login" } else { "Kid login with PIN" }}
+                            </button>
                         </form>
                     }.into_view()
                 }}
@@ -133,6 +183,97 @@ pub fn Login(set_view: WriteSignal<View>) -> impl IntoView {
     }
 }
 
+#[component]
+fn InviteRegistration(code: String) -> impl IntoView {
+    let (info, set_info) = create_signal(Option::<InviteInfoDto>::None);
+    let (username, set_username) = create_signal(String::new());
+    let (password, set_password) = create_signal(String::new());
+    let (error, set_error) = create_signal(Option::<String>::None);
+    let (registering, set_registering) = create_signal(false);
+
+    let check_code = code.clone();
+    spawn_local(async move {
+        match check_invite(check_code).await {
+            Ok(data) => set_info.set(Some(data)),
+            Err(e) => set_error.set(Some(e.to_string())),
+        }
+    });
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error.set(None);
+        set_registering.set(true);
+
+        let code = code.clone();
+        let username_val = username.get().trim().to_string();
+        let password_val = password.get();
+        spawn_local(async move {
+            match register_with_invite(code, username_val, password_val).await {
+                Ok(()) => {
+                    // Reload without the used code in the address bar
+                    let _ = leptos::window().location().set_href("/");
+                }
+                Err(e) => {
+                    set_error.set(Some(e.to_string()));
+                    set_registering.set(false);
+                }
+            }
+        });
+    };
+
+    view! {
+        <form on:submit=on_submit>
+            {move || info.get().map(|data| {
+                let role = match data.role {
+                    HouseholdRoleDto::Owner => "an owner",
+                    HouseholdRoleDto::Parent => "a parent",
+                    HouseholdRoleDto::Viewer => "a viewer",
+                };
+                view! {
+                    <p class="invite-info">
+                        "You're invited to join " <strong>{data.household_name}</strong> " as " {role} "."
+                    </p>
+                }
+            })}
+
+            <div class="form-group">
+                <label for="invite-username">"Choose a username"</label>
+                <input
+                    type="text"
+                    id="invite-username"
+                    required
+                    disabled=move || registering.get()
+                    on:input=move |ev| set_username.set(event_target_value(&ev))
+                    prop:value=move || username.get()
+                />
+            </div>
+
+            <div class="form-group">
+                <label for="invite-password">"Choose a password"</label>
+                <input
+                    type="password"
+                    id="invite-password"
+                    minlength="8"
+                    required
+                    disabled=move || registering.get()
+                    on:input=move |ev| set_password.set(event_target_value(&ev))
+                    prop:value=move || password.get()
+                />
+            </div>
+
+            {move || error.get().map(|err| view! { <p class="error">{err}</p> })}
+
+            <button
+                type="submit"
+                class="login-btn"
+                disabled=move || registering.get() || info.get().is_none()
+            >
+                {move || if registering.get() { "Creating account..." } else { "Create account" }}
+            </button>
+        </form>
+    }
+}
+
 #[component]
 pub fn Dashboard() -> impl IntoView {
     let (current_view, set_current_view) = create_signal(View::Login);
@@ -147,6 +288,9 @@ pub fn Dashboard() -> impl IntoView {
                         Ok(AccountTypeDto::Admin) => {
                             set_current_view.set(View::Admin);
                         }
+                        Ok(AccountTypeDto::Kid) => {
+                            set_current_view.set(View::Kid);
+                        }
                         _ => {
                             set_current_view.set(View::Dashboard);
                         }
@@ -222,6 +366,21 @@ pub fn Dashboard() -> impl IntoView {
                         </div>
                     </div>
                 }.into_view(),
+                View::Kid => view! {
+                    <div>
+                        <nav class="navbar">
+                            <div class="navbar-brand">"Loa'a"</div>
+                            <button class="logout-btn" on:click=handle_logout>
+                                "Log Out"
+                            </button>
+                        </nav>
+                        <div class="container">
+                            <main>
+                                <KidHome />
+                            </main>
+                        </div>
+                    </div>
+                }.into_view(),
             }}
         </div>
     }
@@ -237,11 +396,28 @@ fn DashboardView(set_view: WriteSignal<View>) -> impl IntoView {
     let (tasks, set_tasks) = create_signal(Vec::<TaskDto>::new());
     let (is_loaded, set_is_loaded) = create_signal(false);
     let (recent_activity, set_recent_activity) = create_signal(Vec::<LedgerEntryDto>::new());
+    let (budgets, set_budgets) = create_signal(Vec::<BudgetStatusDto>::new());
+    let (category_filter, set_category_filter) = create_signal(String::new());
+    let (tag_filter, set_tag_filter) = create_signal(String::new());
+    // Bumped whenever a kid or task is archived, restored or purged
+    let (archive_version, set_archive_version) = create_signal(0u32);
+
+    // Tasks matching the category and tag filters
+    let filtered_tasks = move || {
+        let category = category_filter.get();
+        let tag = tag_filter.get().trim().to_lowercase();
+        tasks.get()
+            .into_iter()
+            .filter(|task| category.is_empty() || task.category.as_deref() == Some(category.as_str()))
+            .filter(|task| tag.is_empty() || task.tags.contains(&tag))
+            .collect::<Vec<_>>()
+    };
 
     // Update signals when resource loads
     create_effect(move |_| {
         if let Some(Ok(data)) = dashboard_data.get() {
             set_kid_summaries.set(data.kid_summaries);
+            set_budgets.set(data.budgets);
             set_is_loaded.set(true);
             // Also fetch tasks and recent activity
             spawn_local(async move {
@@ -255,6 +431,23 @@ fn DashboardView(set_view: WriteSignal<View>) -> impl IntoView {
         }
     });
 
+    // Reload kids and tasks after archiving or restoring (not on first run)
+    create_effect(move |previous: Option<u32>| {
+        let version = archive_version.get();
+        if previous.is_some() {
+            spawn_local(async move {
+                if let Ok(data) = get_dashboard_data().await {
+                    set_kid_summaries.set(data.kid_summaries);
+                    set_budgets.set(data.budgets);
+                }
+                if let Ok(task_list) = get_tasks().await {
+                    set_tasks.set(task_list);
+                }
+            });
+        }
+        version
+    });
+
     // Set up SSE connection for real-time updates (client-side only)
     #[cfg(feature = "hydrate")]
     {
@@ -277,6 +470,7 @@ fn DashboardView(set_view: WriteSignal<View>) -> impl IntoView {
                     spawn_local(async move {
                         if let Ok(data) = get_dashboard_data().await {
                             set_kid_summaries.set(data.kid_summaries);
+                            set_budgets.set(data.budgets);
                         }
                         if let Ok(task_list) = get_tasks().await {
                             set_tasks.set(task_list);
@@ -306,21 +500,55 @@ fn DashboardView(set_view: WriteSignal<View>) -> impl IntoView {
             fallback=|| view! { <p>"Loading dashboard..."</p> }
         >
             <div>
+                <PendingClaims />
+
+                <PendingPayouts />
+
                 <section class="kids-section">
                     <h2>"Kids"</h2>
                     <div class="kids-grid">
                         {move || kid_summaries.get().into_iter().map(|summary| {
-                            view! { <KidSummaryCard summary=summary set_view=set_view /> }
+                            view! { <KidSummaryCard summary=summary set_view=set_view set_archive_version=set_archive_version /> }
                         }).collect::<Vec<_>>()}
                     </div>
                 </section>
 
+                <EarningsPanel recent_activity=recent_activity />
+
+                <ForecastPanel recent_activity=recent_activity />
+
+                <BudgetPanel kid_summaries=kid_summaries budgets=budgets set_budgets=set_budgets />
+
+                <TransferPanel kid_summaries=kid_summaries />
+
                 <section class="tasks-section">
                     <h2>"Tasks"</h2>
+                    <div class="task-filters">
+                        <select
+                            prop:value=move || category_filter.get()
+                            on:change=move |ev| set_category_filter.set(event_target_value(&ev))
+                        >
+                            <option value="">"All categories"</option>
+                            <option value="room">"Room"</option>
+                            <option value="kitchen">"Kitchen"</option>
+                            <option value="school">"School"</option>
+                            <option value="pets">"Pets"</option>
+                            <option value="yard">"Yard"</option>
+                            <option value="other">"Other"</option>
+                        </select>
+                        <input
+                            type="text"
+                            placeholder="Filter by tag"
+                            prop:value=move || tag_filter.get()
+                            on:input=move |ev| set_tag_filter.set(event_target_value(&ev))
+                        />
+                    </div>
                     {move || {
-                        let task_list = tasks.get();
-                        if task_list.is_empty() {
+                        let task_list = filtered_tasks();
+                        if tasks.get().is_empty() {
                             view! { <p class="empty-state">"No tasks yet. Create tasks via Claude."</p> }.into_view()
+                        } else if task_list.is_empty() {
+                            view! { <p class="empty-state">"No tasks match these filters."</p> }.into_view()
                         } else {
                             view! {
                                 <div class="tasks-grid">
@@ -330,6 +558,17 @@ fn DashboardView(set_view: WriteSignal<View>) -> impl IntoView {
                                             CadenceDto::Weekly => "Weekly",
                                             CadenceDto::OneTime => "One-time",
                                         };
+                                        let task_id = task.id.clone();
+                                        let submission = Submission::new();
+                                        let handle_archive = move |_| {
+                                            let task_id = task_id.clone();
+                                            spawn_local(async move {
+                                                match submission.send(archive_task(task_id)).await {
+                                                    Ok(()) => set_archive_version.update(|v| *v += 1),
+                                                    Err(e) => leptos::logging::log!("Failed to delete task: {}", e),
+                                                }
+                                            });
+                                        };
                                         view! {
                                             <div class="task-card">
                                                 <div class="task-header">
@@ -338,6 +577,13 @@ fn DashboardView(set_view: WriteSignal<View>) -> impl IntoView {
                                                 </div>
                                                 <p class="task-description">{task.description}</p>
                                                 <span class="task-cadence">{cadence_label}</span>
+                                                {task.category.map(|category| view! {
+                                                    <span class="task-category">{category}</span>
+                                                })}
+                                                {task.tags.into_iter().map(|tag| view! {
+                                                    <span class="task-tag">"#"{tag}</span>
+                                                }).collect::<Vec<_>>()}
+                                                <button class="archive-btn" on:click=handle_archive>"Delete"</button>
                                             </div>
                                         }
                                     }).collect::<Vec<_>>()}
@@ -347,6 +593,14 @@ fn DashboardView(set_view: WriteSignal<View>) -> impl IntoView {
                     }}
                 </section>
 
+                <ChorePacks set_tasks=set_tasks />
+
+                <SpreadsheetPanel set_tasks=set_tasks />
+
+                <HouseholdPanel />
+
+                <ArchivedPanel archive_version=archive_version set_archive_version=set_archive_version />
+
                 <section class="recent-activity">
                     <h2>"Recent Activity"</h2>
                     {move || {
@@ -363,6 +617,8 @@ fn DashboardView(set_view: WriteSignal<View>) -> impl IntoView {
                                             let entry_type = match entry.entry_type {
                                                 EntryTypeDto::Earned => "Earned",
                                                 EntryTypeDto::Adjusted => "Adjusted",
+                                                EntryTypeDto::Payout => "Payout",
+                                                EntryTypeDto::Transfer => "Transfer",
                                             };
                                             let sign = if entry.amount >= rust_decimal::Decimal::ZERO { "+" } else { "" };
                                             let time_ago = format_time_ago(entry.created_at);
@@ -387,18 +643,49 @@ fn DashboardView(set_view: WriteSignal<View>) -> impl IntoView {
 }
 
 #[component]
-fn KidSummaryCard(summary: KidSummaryDto, set_view: WriteSignal<View>) -> impl IntoView {
+fn KidSummaryCard(
+    summary: KidSummaryDto,
+    set_view: WriteSignal<View>,
+    set_archive_version: WriteSignal<u32>,
+) -> impl IntoView {
     let kid_id = summary.kid.id.clone();
+    let archive_id = summary.kid.id.clone();
+    let submission = Submission::new();
+
+    let handle_archive = move |_| {
+        let kid_id = archive_id.clone();
+        spawn_local(async move {
+            match submission.send(archive_kid(kid_id)).await {
+                Ok(()) => set_archive_version.update(|v| *v += 1),
+                Err(e) => leptos::logging::log!("Failed to delete kid: {}", e),
+            }
+        });
+    };
     view! {
         <div class="kid-card">
             <div class="kid-header">
                 <h3>{summary.kid.name.clone()}</h3>
                 <span class="balance">"Balance: $"{summary.balance.to_string()}</span>
             </div>
+            {summary.average_quality.map(|avg| view! {
+                <div class="average-quality">"Avg quality: "{avg.to_string()}" / 5"</div>
+            })}
+            {(!summary.earnings_by_category.is_empty()).then(|| view! {
+                <ul class="category-earnings">
+                    {summary.earnings_by_category.iter().map(|earning| view! {
+                        <li>
+                            <span class="category-name">{earning.category.clone()}</span>
+                            <span class="category-amount">"$"{earning.amount.to_string()}</span>
+                        </li>
+                    }).collect::<Vec<_>>()}
+                </ul>
+            })}
             {summary.recent_entry.map(|entry| {
                 let entry_type = match entry.entry_type {
                     EntryTypeDto::Earned => "Earned",
                     EntryTypeDto::Adjusted => "Adjusted",
+                    EntryTypeDto::Payout => "Payout",
+                    EntryTypeDto::Transfer => "Transfer",
                 };
                 let sign = if entry.amount >= rust_decimal::Decimal::ZERO { "+" } else { "" };
                 view! {
@@ -415,10 +702,1345 @@ fn KidSummaryCard(summary: KidSummaryDto, set_view: WriteSignal<View>) -> impl I
             >
                 "View Ledger"
             </button>
+            <KidLoginSetup kid_id=summary.kid.id.clone() />
+            <button class="archive-btn" on:click=handle_archive>"Delete"</button>
+        </div>
+    }
+}
+
+/// Deleted kids and tasks, which can be restored until their window passes
+#[component]
+fn ArchivedPanel(archive_version: ReadSignal<u32>, set_archive_version: WriteSignal<u32>) -> impl IntoView {
+    let archived = create_resource(move || archive_version.get(), |_| get_archived());
+    let (message, set_message) = create_signal(Option::<String>::None);
+
+    let handle_restore = move |submission: Submission, kind: ArchivedKindDto, id: String| {
+        set_message.set(None);
+        spawn_local(async move {
+            match submission.send(restore_archived(kind, id)).await {
+                Ok(()) => set_archive_version.update(|v| *v += 1),
+                Err(e) => set_message.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    let handle_purge = move |submission: Submission, kind: ArchivedKindDto, id: String| {
+        set_message.set(None);
+        spawn_local(async move {
+            match submission.send(purge_archived(kind, id)).await {
+                Ok(()) => set_archive_version.update(|v| *v += 1),
+                Err(e) => set_message.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    view! {
+        <Suspense fallback=|| ()>
+            {move || archived.get().and_then(|result| result.ok()).filter(|items| !items.is_empty()).map(|items| view! {
+                <section class="archived-section">
+                    <h2>"Recently Deleted"</h2>
+                    {move || message.get().map(|msg| view! { <p class="pack-message">{msg}</p> })}
+                    <ul class="archived-list">
+                        {items.into_iter().map(|item| {
+                            let kind_label = match item.kind {
+                                ArchivedKindDto::Kid => "Kid",
+                                ArchivedKindDto::Task => "Task",
+                            };
+                            let (kind, restore_id, purge_id) = (item.kind, item.id.clone(), item.id.clone());
+                            let submission = Submission::new();
+                            view! {
+                                <li class="archived-item">
+                                    <span class="archived-kind">{kind_label}</span>
+                                    <span class="archived-name">{item.name}</span>
+                                    <span class="archived-until">
+                                        "Restorable until "{item.restorable_until.format("%Y-%m-%d").to_string()}
+                                    </span>
+                                    <button class="approve-btn" on:click=move |_| handle_restore(submission, kind, restore_id.clone())>
+                                        "Restore"
+                                    </button>
+                                    <button class="reject-btn" on:click=move |_| handle_purge(submission, kind, purge_id.clone())>
+                                        "Delete forever"
+                                    </button>
+                                </li>
+                            }
+                        }).collect::<Vec<_>>()}
+                    </ul>
+                </section>
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn HouseholdPanel() -> impl IntoView {
+    let (household, set_household) = create_signal(Option::<HouseholdDto>::None);
+    let (message, set_message) = create_signal(Option::<String>::None);
+
+    let refresh = move || {
+        spawn_local(async move {
+            match get_household().await {
+                Ok(data) => set_household.set(Some(data)),
+                Err(e) => set_message.set(Some(format!("Error loading household: {}", e))),
+            }
+        });
+    };
+    refresh();
+
+    let handle_role = move |submission: Submission, user_id: String, role: String| {
+        let role = match role.as_str() {
+            "owner" => HouseholdRoleDto::Owner,
+            "parent" => HouseholdRoleDto::Parent,
+            _ => HouseholdRoleDto::Viewer,
+        };
+        set_message.set(None);
+        spawn_local(async move {
+            match submission.send(set_household_role(user_id, role)).await {
+                Ok(data) => set_household.set(Some(data)),
+                Err(e) => {
+                    set_message.set(Some(e.to_string()));
+                    refresh();
+                }
+            }
+        });
+    };
+
+    let handle_remove = move |submission: Submission, user_id: String| {
+        set_message.set(None);
+        spawn_local(async move {
+            if let Err(e) = submission.send(remove_household_member(user_id)).await {
+                set_message.set(Some(e.to_string()));
+            }
+            refresh();
+        });
+    };
+
+    view! {
+        <section class="household-section">
+            <h2>"Household"</h2>
+            {move || message.get().map(|msg| view! { <p class="pack-message">{msg}</p> })}
+            {move || household.get().map(|data| {
+                let is_owner = data.your_role == HouseholdRoleDto::Owner;
+                view! {
+                    <div>
+                        <p class="household-name">{data.name}</p>
+                        <ul class="household-members">
+                            {data.members.into_iter().map(|member| {
+                                let role = match member.role {
+                                    HouseholdRoleDto::Owner => "owner",
+                                    HouseholdRoleDto::Parent => "parent",
+                                    HouseholdRoleDto::Viewer => "viewer",
+                                };
+                                let role_user_id = member.user_id.clone();
+                                let remove_user_id = member.user_id.clone();
+                                let submission = Submission::new();
+                                view! {
+                                    <li class="household-member">
+                                        <span class="member-name">
+                                            {member.username}
+                                            {member.is_you.then_some(" (you)")}
+                                        </span>
+                                        {if is_owner && !member.is_you {
+                                            view! {
+                                                <select
+                                                    prop:value=role
+                                                    on:change=move |ev| handle_role(submission, role_user_id.clone(), event_target_value(&ev))
+                                                >
+                                                    <option value="owner">"Owner"</option>
+                                                    <option value="parent">"Parent"</option>
+                                                    <option value="viewer">"Viewer"</option>
+                                                </select>
+                                                <button class="reject-btn" on:click=move |_| handle_remove(submission, remove_user_id.clone())>
+                                                    "Remove"
+                                                </button>
+                                            }.into_view()
+                                        } else {
+                                            view! { <span class="member-role">{role}</span> }.into_view()
+                                        }}
+                                    </li>
+                                }
+                            }).collect::<Vec<_>>()}
+                        </ul>
+                        {(data.your_role != HouseholdRoleDto::Viewer).then(|| view! {
+                            <InvitePanel can_invite_owner=is_owner />
+                        })}
+                    </div>
+                }
+            })}
+        </section>
+    }
+}
+
+#[component]
+fn InvitePanel(can_invite_owner: bool) -> impl IntoView {
+    let (invites, set_invites) = create_signal(Vec::<InviteDto>::new());
+    let (role, set_role) = create_signal("parent".to_string());
+    let (new_link, set_new_link) = create_signal(Option::<String>::None);
+    let (message, set_message) = create_signal(Option::<String>::None);
+
+    let refresh = move || {
+        spawn_local(async move {
+            match list_invites().await {
+                Ok(data) => set_invites.set(data),
+                Err(e) => set_message.set(Some(format!("Error loading invites: {}", e))),
+            }
+        });
+    };
+    refresh();
+
+    let submission = Submission::new();
+    let handle_create = move |_| {
+        let role = match role.get().as_str() {
+            "owner" => HouseholdRoleDto::Owner,
+            "parent" => HouseholdRoleDto::Parent,
+            _ => HouseholdRoleDto::Viewer,
+        };
+        set_message.set(None);
+        spawn_local(async move {
+            match submission.send(create_invite(role, None)).await {
+                Ok(invite) => {
+                    set_new_link.set(invite.link);
+                    refresh();
+                }
+                Err(e) => set_message.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    let handle_revoke = move |submission: Submission, invite_id: UuidDto| {
+        set_message.set(None);
+        spawn_local(async move {
+            if let Err(e) = submission.send(revoke_invite(invite_id)).await {
+                set_message.set(Some(e.to_string()));
+            }
+            refresh();
+        });
+    };
+
+    view! {
+        <div class="invite-panel">
+            <h3>"Invite someone"</h3>
+            <div class="invite-create">
+                <select on:change=move |ev| set_role.set(event_target_value(&ev)) prop:value=move || role.get()>
+                    {can_invite_owner.then(|| view! { <option value="owner">"Owner"</option> })}
+                    <option value="parent">"Parent"</option>
+                    <option value="viewer">"Viewer"</option>
+                </select>
+                <button on:click=handle_create>"Create invite link"</button>
+            </div>
+            {move || new_link.get().map(|link| view! {
+                <p class="invite-link">
+                    "Share this link (valid for 7 days, works once): "
+                    <input type="text" readonly prop:value=link />
+                </p>
+            })}
+            {move || message.get().map(|msg| view! { <p class="pack-message">{msg}</p> })}
+            <ul class="invite-list">
+                {move || invites.get().into_iter().map(|invite| {
+                    let role = match invite.role {
+                        HouseholdRoleDto::Owner => "owner",
+                        HouseholdRoleDto::Parent => "parent",
+                        HouseholdRoleDto::Viewer => "viewer",
+                    };
+                    let invite_id = invite.id.clone();
+                    let submission = Submission::new();
+                    view! {
+                        <li class="invite-item">
+                            <span>{role} " invite, expires " {invite.expires_at.format("%Y-%m-%d").to_string()}</span>
+                            <button class="reject-btn" on:click=move |_| handle_revoke(submission, invite_id.clone())>
+                                "Revoke"
+                            </button>
+                        </li>
+                    }
+                }).collect::<Vec<_>>()}
+            </ul>
+        </div>
+    }
+}
+
+#[component]
+fn KidLoginSetup(kid_id: UuidDto) -> impl IntoView {
+    let (username, set_username) = create_signal(String::new());
+    let (pin, set_pin) = create_signal(String::new());
+    let (message, set_message) = create_signal(Option::<String>::None);
+    let submission = Submission::new();
+
+    // With a username this creates the kid's login; without one it resets the PIN
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let kid_id = kid_id.clone();
+        let username_val = username.get().trim().to_string();
+        let pin_val = pin.get();
+
+        spawn_local(async move {
+            let result = if username_val.is_empty() {
+                submission.send(reset_kid_pin(kid_id, pin_val)).await.map(|_| "PIN updated".to_string())
+            } else {
+                submission.send(create_kid_login(kid_id, username_val, pin_val)).await
+                    .map(|account| format!("Login '{}' created", account.username))
+            };
+            match result {
+                Ok(msg) => {
+                    set_username.set(String::new());
+                    set_pin.set(String::new());
+                    set_message.set(Some(msg));
+                }
+                Err(e) => set_message.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    view! {
+        <details class="kid-login-setup">
+            <summary>"Kid login"</summary>
+            <form on:submit=on_submit>
+                <input
+                    type="text"
+                    placeholder="Username (leave empty to reset PIN)"
+                    on:input=move |ev| set_username.set(event_target_value(&ev))
+                    prop:value=move || username.get()
+                />
+                <input
+                    type="password"
+                    inputmode="numeric"
+                    placeholder="PIN (4-8 digits)"
+                    required
+                    on:input=move |ev| {
+                        // The PIN isn't part of the key's arguments, so a new one is a new submission
+                        submission.reset();
+                        set_pin.set(event_target_value(&ev));
+                    }
+                    prop:value=move || pin.get()
+                />
+                <button type="submit">"Save"</button>
+            </form>
+            {move || message.get().map(|msg| view! { <p class="kid-login-message">{msg}</p> })}
+        </details>
+    }
+}
+
+#[component]
+fn PendingClaims() -> impl IntoView {
+    let (claims, set_claims) = create_signal(Vec::<TaskClaimDto>::new());
+    let (error, set_error) = create_signal(Option::<String>::None);
+
+    let refresh = move || {
+        spawn_local(async move {
+            match get_pending_claims().await {
+                Ok(list) => set_claims.set(list),
+                Err(e) => set_error.set(Some(format!("Error loading claims: {}", e))),
+            }
+        });
+    };
+    refresh();
+
+    let handle_review = move |submission: Submission, claim_id: UuidDto, approve: bool| {
+        set_error.set(None);
+        spawn_local(async move {
+            let result = if approve {
+                submission.send(approve_claim(claim_id, None, None, None)).await
+            } else {
+                submission.send(reject_claim(claim_id)).await
+            };
+            if let Err(e) = result {
+                set_error.set(Some(e.to_string()));
+            }
+            refresh();
+        });
+    };
+
+    view! {
+        <Show when=move || !claims.get().is_empty() || error.get().is_some()>
+            <section class="claims-section">
+                <h2>"Waiting for Approval"</h2>
+                {move || error.get().map(|err| view! { <p class="error">{err}</p> })}
+                <ul class="claims-list">
+                    {move || claims.get().into_iter().map(|claim| {
+                        let approve_id = claim.id.clone();
+                        let reject_id = claim.id.clone();
+                        let submission = Submission::new();
+                        view! {
+                            <li class="claim-item">
+                                <span class="claim-kid">{claim.kid_name}</span>
+                                <span class="claim-task">{claim.task_name}" — $"{claim.task_value.to_string()}</span>
+                                <span class="claim-time">{format_time_ago(claim.created_at)}</span>
+                                {claim.note.map(|note| view! { <span class="claim-note">{note}</span> })}
+                                {claim.attachment_id.map(|id| view! {
+                                    <a href=format!("/api/attachments/{}", id) target="_blank">
+                                        <img class="claim-photo" src=format!("/api/attachments/{}/thumbnail", id) alt="Photo proof" />
+                                    </a>
+                                })}
+                                <button class="approve-btn" on:click=move |_| handle_review(submission, approve_id.clone(), true)>"Approve"</button>
+                                <button class="reject-btn" on:click=move |_| handle_review(submission, reject_id.clone(), false)>"Reject"</button>
+                            </li>
+                        }
+                    }).collect::<Vec<_>>()}
+                </ul>
+            </section>
+        </Show>
+    }
+}
+
+#[component]
+fn PendingPayouts() -> impl IntoView {
+    let (withdrawals, set_withdrawals) = create_signal(Vec::<WithdrawalDto>::new());
+    let (summary, set_summary) = create_signal(Vec::<PayoutSummaryDto>::new());
+    let (error, set_error) = create_signal(Option::<String>::None);
+
+    let refresh = move || {
+        spawn_local(async move {
+            match get_pending_payouts().await {
+                Ok(list) => set_withdrawals.set(list),
+                Err(e) => set_error.set(Some(format!("Error loading withdrawals: {}", e))),
+            }
+            if let Ok(list) = get_payout_summary().await {
+                set_summary.set(list);
+            }
+        });
+    };
+    refresh();
+
+    // "approve", "decline", or a payment method to mark it paid with
+    let handle_step = move |submission: Submission, withdrawal_id: UuidDto, step: String| {
+        set_error.set(None);
+        spawn_local(async move {
+            let result = match step.as_str() {
+                "approve" => submission.send(approve_withdrawal(withdrawal_id)).await,
+                "decline" => submission.send(decline_withdrawal(withdrawal_id)).await,
+                method => submission.send(mark_withdrawal_paid(withdrawal_id, method.to_string())).await,
+            };
+            if let Err(e) = result {
+                set_error.set(Some(e.to_string()));
+            }
+            refresh();
+        });
+    };
+
+    let has_payouts = move || {
+        summary.with(|list| list.iter().any(|s| !s.owed.is_zero() || !s.paid.is_zero()))
+    };
+
+    view! {
+        <Show when=move || !withdrawals.get().is_empty() || has_payouts() || error.get().is_some()>
+            <section class="claims-section payouts-section">
+                <h2>"Payouts"</h2>
+                {move || error.get().map(|err| view! { <p class="error">{err}</p> })}
+                <ul class="claims-list">
+                    {move || withdrawals.get().into_iter().map(|withdrawal| {
+                        let id = withdrawal.id.clone();
+                        let decline_id = withdrawal.id.clone();
+                        let (method, set_method) = create_signal("cash".to_string());
+                        let submission = Submission::new();
+                        let actions = if withdrawal.status == WithdrawalStatusDto::Requested {
+                            view! {
+                                <button class="approve-btn" on:click=move |_| handle_step(submission, id.clone(), "approve".to_string())>"Approve"</button>
+                            }.into_view()
+                        } else {
+                            view! {
+                                <select on:change=move |ev| set_method.set(event_target_value(&ev))>
+                                    <option value="cash">"Cash"</option>
+                                    <option value="bank_transfer">"Bank transfer"</option>
+                                    <option value="gift_card">"Gift card"</option>
+                                </select>
+                                <button class="approve-btn" on:click=move |_| handle_step(submission, id.clone(), method.get_untracked())>"Mark paid"</button>
+                            }.into_view()
+                        };
+                        view! {
+                            <li class="claim-item">
+                                <span class="claim-kid">{withdrawal.kid_name}</span>
+                                <span class="claim-task">"$"{withdrawal.amount.to_string()}</span>
+                                <span class="claim-time">{format_time_ago(withdrawal.requested_at)}</span>
+                                {withdrawal.note.map(|note| view! { <span class="claim-note">{note}</span> })}
+                                {actions}
+                                <button class="reject-btn" on:click=move |_| handle_step(submission, decline_id.clone(), "decline".to_string())>"Decline"</button>
+                            </li>
+                        }
+                    }).collect::<Vec<_>>()}
+                </ul>
+                <Show when=has_payouts>
+                    <table class="payout-summary">
+                        <thead>
+                            <tr>
+                                <th>"Kid"</th>
+                                <th>"Balance"</th>
+                                <th>"Owed"</th>
+                                <th>"Paid"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {move || summary.get().into_iter().map(|row| view! {
+                                <tr>
+                                    <td>{row.name}</td>
+                                    <td>"$"{row.balance.to_string()}</td>
+                                    <td>"$"{row.owed.to_string()}</td>
+                                    <td>"$"{row.paid.to_string()}</td>
+                                </tr>
+                            }).collect::<Vec<_>>()}
+                        </tbody>
+                    </table>
+                </Show>
+            </section>
+        </Show>
+    }
+}
+
+#[component]
+fn KidHome() -> impl IntoView {
+    let home = create_resource(|| (), |_| get_kid_home());
+    let (message, set_message) = create_signal(Option::<String>::None);
+    let (withdraw_amount, set_withdraw_amount) = create_signal(String::new());
+    let (withdraw_note, set_withdraw_note) = create_signal(String::new());
+
+    let withdrawal = Submission::new();
+    let handle_withdraw = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_message.set(None);
+        let note = Some(withdraw_note.get_untracked()).filter(|n| !n.trim().is_empty());
+        spawn_local(async move {
+            match withdrawal.send(request_withdrawal(withdraw_amount.get_untracked(), note)).await {
+                Ok(()) => {
+                    set_withdraw_amount.set(String::new());
+                    set_withdraw_note.set(String::new());
+                    set_message.set(Some("Asked! A parent will check it soon.".to_string()));
+                }
+                Err(e) => set_message.set(Some(e.to_string())),
+            }
+            home.refetch();
+        });
+    };
+
+    let (send_to, set_send_to) = create_signal(String::new());
+    let (send_amount, set_send_amount) = create_signal(String::new());
+    let (send_note, set_send_note) = create_signal(String::new());
+
+    let transfer = Submission::new();
+    let handle_send = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_message.set(None);
+        let note = Some(send_note.get_untracked()).filter(|n| !n.trim().is_empty());
+        spawn_local(async move {
+            match transfer.send(request_transfer(send_to.get_untracked(), send_amount.get_untracked(), note)).await {
+                Ok(()) => {
+                    set_send_amount.set(String::new());
+                    set_send_note.set(String::new());
+                    set_message.set(Some("Asked! A parent will check it soon.".to_string()));
+                }
+                Err(e) => set_message.set(Some(e.to_string())),
+            }
+            home.refetch();
+        });
+    };
+
+    let handle_claim = move |submission: Submission, task_id: UuidDto| {
+        set_message.set(None);
+        spawn_local(async move {
+            match submission.send(claim_task(task_id, None)).await {
+                Ok(()) => set_message.set(Some("Sent! A parent will check it soon.".to_string())),
+                Err(e) => set_message.set(Some(e.to_string())),
+            }
+            home.refetch();
+        });
+    };
+
+    view! {
+        <div class="kid-home">
+            <Suspense fallback=move || view! { <p>"Loading..."</p> }>
+                {move || home.get().map(|result| match result {
+                    Ok(data) => {
+                        let pending: Vec<UuidDto> = data.claims.iter()
+                            .filter(|c| matches!(c.status, ClaimStatusDto::Pending))
+                            .map(|c| c.task_id.clone())
+                            .collect();
+                        view! {
+                            <div>
+                                <div class="kid-home-header">
+                                    <h2>"Hi, "{data.kid.name}"!"</h2>
+                                    <span class="balance-value">"$"{data.balance.to_string()}</span>
+                                </div>
+                                {move || message.get().map(|msg| view! { <p class="pack-message">{msg}</p> })}
+
+                                <section class="tasks-section">
+                                    <h2>"Chores"</h2>
+                                    <div class="tasks-grid">
+                                        {data.tasks.into_iter().map(|task| {
+                                            let waiting = pending.contains(&task.id);
+                                            let task_id = task.id.clone();
+                                            let submission = Submission::new();
+                                            view! {
+                                                <div class="task-card">
+                                                    <div class="task-header">
+                                                        <h3>{task.name}</h3>
+                                                        <span class="task-value">"$"{task.value.to_string()}</span>
+                                                    </div>
+                                                    <p class="task-description">{task.description}</p>
+                                                    {if waiting {
+                                                        view! { <span class="claim-waiting">"Waiting for approval"</span> }.into_view()
+                                                    } else {
+                                                        view! {
+                                                            <div class="claim-actions">
+                                                                <button class="claim-btn" on:click=move |_| handle_claim(submission, task_id.clone())>"Done!"</button>
+                                                                <form class="attachment-upload" method="post" action="/api/attachments" enctype="multipart/form-data">
+                                                                    <input type="hidden" name="task_id" value=task.id.clone() />
+                                                                    <input type="hidden" name="claim" value="1" />
+                                                                    <input type="hidden" name="return_to" value="/" />
+                                                                    <input type="file" name="file" accept="image/jpeg,image/png" required />
+                                                                    <button type="submit">"Done with photo"</button>
+                                                                </form>
+                                                            </div>
+                                                        }.into_view()
+                                                    }}
+                                                </div>
+                                            }
+                                        }).collect::<Vec<_>>()}
+                                    </div>
+                                </section>
+
+                                {(!data.claims.is_empty()).then(|| view! {
+                                    <section class="claims-section">
+                                        <h2>"My Claims"</h2>
+                                        <ul class="claims-list">
+                                            {data.claims.iter().take(10).map(|claim| {
+                                                let status = match claim.status {
+                                                    ClaimStatusDto::Pending => "Waiting",
+                                                    ClaimStatusDto::Approved => "Approved",
+                                                    ClaimStatusDto::Rejected => "Not approved",
+                                                };
+                                                view! {
+                                                    <li class="claim-item">
+                                                        <span class="claim-task">{claim.task_name.clone()}</span>
+                                                        <span class={format!("badge badge-{}", status.to_lowercase().replace(' ', "-"))}>{status}</span>
+                                                    </li>
+                                                }
+                                            }).collect::<Vec<_>>()}
+                                        </ul>
+                                    </section>
+                                })}
+
+                                <section class="claims-section withdraw-section">
+                                    <h2>"Take Out Money"</h2>
+                                    <form class="form-row" on:submit=handle_withdraw>
+                                        <label for="withdraw-amount">"$"</label>
+                                        <input
+                                            type="number"
+                                            id="withdraw-amount"
+                                            min="0.01"
+                                            step="0.01"
+                                            required
+                                            prop:value=move || withdraw_amount.get()
+                                            on:input=move |ev| set_withdraw_amount.set(event_target_value(&ev))
+                                        />
+                                        <input
+                                            type="text"
+                                            placeholder="What for? (optional)"
+                                            prop:value=move || withdraw_note.get()
+                                            on:input=move |ev| set_withdraw_note.set(event_target_value(&ev))
+                                        />
+                                        <button type="submit" class="claim-btn">"Ask"</button>
+                                    </form>
+                                    <ul class="claims-list">
+                                        {data.withdrawals.iter().take(10).map(|withdrawal| {
+                                            let status = match withdrawal.status {
+                                                WithdrawalStatusDto::Requested => "Waiting",
+                                                WithdrawalStatusDto::Approved => "Approved",
+                                                WithdrawalStatusDto::Paid => "Paid",
+                                                WithdrawalStatusDto::Declined => "Not approved",
+                                            };
+                                            view! {
+                                                <li class="claim-item">
+                                                    <span class="claim-task">"$"{withdrawal.amount.to_string()}</span>
+                                                    {withdrawal.note.clone().map(|note| view! { <span class="claim-note">{note}</span> })}
+                                                    <span class={format!("badge badge-{}", status.to_lowercase().replace(' ', "-"))}>{status}</span>
+                                                </li>
+                                            }
+                                        }).collect::<Vec<_>>()}
+                                    </ul>
+                                </section>
+
+                                {(!data.siblings.is_empty()).then(|| {
+                                    let kid_id = data.kid.id.clone();
+                                    if send_to.get_untracked().is_empty() {
+                                        set_send_to.set(data.siblings[0].id.clone());
+                                    }
+                                    view! {
+                                        <section class="claims-section transfer-section">
+                                            <h2>"Send Money"</h2>
+                                            <form class="form-row" on:submit=handle_send>
+                                                <select prop:value=move || send_to.get() on:change=move |ev| set_send_to.set(event_target_value(&ev))>
+                                                    {data.siblings.iter().map(|sibling| view! {
+                                                        <option value=sibling.id.clone()>{sibling.name.clone()}</option>
+                                                    }).collect::<Vec<_>>()}
+                                                </select>
+                                                <label for="send-amount">"$"</label>
+                                                <input
+                                                    type="number"
+                                                    id="send-amount"
+                                                    min="0.01"
+                                                    step="0.01"
+                                                    required
+                                                    prop:value=move || send_amount.get()
+                                                    on:input=move |ev| set_send_amount.set(event_target_value(&ev))
+                                                />
+                                                <input
+                                                    type="text"
+                                                    placeholder="What for? (optional)"
+                                                    prop:value=move || send_note.get()
+                                                    on:input=move |ev| set_send_note.set(event_target_value(&ev))
+                                                />
+                                                <button type="submit" class="claim-btn">"Ask"</button>
+                                            </form>
+                                            <ul class="claims-list">
+                                                {data.transfers.iter().take(10).map(|transfer| {
+                                                    let status = match transfer.status {
+                                                        TransferStatusDto::Pending => "Waiting",
+                                                        TransferStatusDto::Completed => "Sent",
+                                                        TransferStatusDto::Declined => "Not approved",
+                                                    };
+                                                    let who = if transfer.from_kid_id == kid_id {
+                                                        format!("To {}", transfer.to_name)
+                                                    } else {
+                                                        format!("From {}", transfer.from_name)
+                                                    };
+                                                    view! {
+                                                        <li class="claim-item">
+                                                            <span class="claim-task">{who}" $"{transfer.amount.to_string()}</span>
+                                                            {transfer.note.clone().map(|note| view! { <span class="claim-note">{note}</span> })}
+                                                            <span class={format!("badge badge-{}", status.to_lowercase().replace(' ', "-"))}>{status}</span>
+                                                        </li>
+                                                    }
+                                                }).collect::<Vec<_>>()}
+                                            </ul>
+                                        </section>
+                                    }
+                                })}
+
+                                <section class="recent-activity">
+                                    <h2>"Recent Activity"</h2>
+                                    <ul class="activity-list">
+                                        {data.entries.into_iter().map(|entry| {
+                                            let sign = if entry.amount >= rust_decimal::Decimal::ZERO { "+" } else { "" };
+                                            view! {
+                                                <li class="activity-item">
+                                                    <span class="activity-time">{format_time_ago(entry.created_at)}</span>
+                                                    <span class="activity-description">{entry.description}</span>
+                                                    <span class="activity-amount">{sign}{"$"}{entry.amount.to_string()}</span>
+                                                </li>
+                                            }
+                                        }).collect::<Vec<_>>()}
+                                    </ul>
+                                </section>
+                            </div>
+                        }.into_view()
+                    }
+                    Err(e) => view! {
+                        <p class="error">"Error loading: " {e.to_string()}</p>
+                    }.into_view(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn ChorePacks(set_tasks: WriteSignal<Vec<TaskDto>>) -> impl IntoView {
+    let packs = create_resource(|| (), |_| get_task_packs());
+    let (value_scale, set_value_scale) = create_signal("1".to_string());
+    let (importing, set_importing) = create_signal(false);
+    let (message, set_message) = create_signal(Option::<String>::None);
+
+    let handle_import = move |submission: Submission, pack_id: String| {
+        let scale = match value_scale.get().trim().parse::<rust_decimal::Decimal>() {
+            Ok(scale) => scale,
+            Err(_) => {
+                set_message.set(Some("Value scale must be a number, e.g. 1 or 0.5".to_string()));
+                return;
+            }
+        };
+        set_importing.set(true);
+        set_message.set(None);
+
+        spawn_local(async move {
+            match submission.send(import_task_pack(pack_id, scale)).await {
+                Ok(result) => {
+                    let mut msg = format!("Added {} tasks", result.created.len());
+                    if !result.skipped.is_empty() {
+                        msg.push_str(&format!(" (skipped existing: {})", result.skipped.join(", ")));
+                    }
+                    set_message.set(Some(msg));
+                    if let Ok(task_list) = get_tasks().await {
+                        set_tasks.set(task_list);
+                    }
+                }
+                Err(e) => {
+                    set_message.set(Some(format!("Failed to import pack: {}", e)));
+                }
+            }
+            set_importing.set(false);
+        });
+    };
+
+    view! {
+        <section class="packs-section">
+            <h2>"Chore Packs"</h2>
+            <div class="form-row">
+                <label for="value-scale">"Value scale"</label>
+                <input
+                    type="text"
+                    id="value-scale"
+                    class="scale-input"
+                    disabled=move || importing.get()
+                    on:input=move |ev| set_value_scale.set(event_target_value(&ev))
+                    prop:value=move || value_scale.get()
+                />
+            </div>
+            {move || message.get().map(|msg| view! { <p class="pack-message">{msg}</p> })}
+            <Suspense fallback=move || view! { <p>"Loading packs..."</p> }>
+                {move || packs.get().map(|result| match result {
+                    Ok(pack_list) => view! {
+                        <div class="packs-grid">
+                            {pack_list.into_iter().map(|pack| {
+                                let pack_id = pack.id.clone();
+                                let submission = Submission::new();
+                                view! {
+                                    <div class="pack-card">
+                                        <div class="pack-header">
+                                            <h3>{pack.name}</h3>
+                                            <span class="pack-meta">{pack.age_band}" · "{pack.room}</span>
+                                        </div>
+                                        <p class="pack-description">{pack.description}</p>
+                                        <ul class="pack-templates">
+                                            {pack.templates.into_iter().map(|t| view! {
+                                                <li>{t.name}" — $"{t.suggested_value.to_string()}</li>
+                                            }).collect::<Vec<_>>()}
+                                        </ul>
+                                        <button
+                                            class="import-btn"
+                                            disabled=move || importing.get()
+                                            on:click=move |_| handle_import(submission, pack_id.clone())
+                                        >
+                                            "Import"
+                                        </button>
+                                    </div>
+                                }
+                            }).collect::<Vec<_>>()}
+                        </div>
+                    }.into_view(),
+                    Err(e) => view! {
+                        <p class="error">"Error loading packs: " {e.to_string()}</p>
+                    }.into_view(),
+                })}
+            </Suspense>
+        </section>
+    }
+}
+
+#[component]
+fn SpreadsheetPanel(set_tasks: WriteSignal<Vec<TaskDto>>) -> impl IntoView {
+    let (from, set_from) = create_signal(String::new());
+    let (to, set_to) = create_signal(String::new());
+    let (ledger_import, set_ledger_import) = create_signal(false);
+    let (csv, set_csv) = create_signal(String::new());
+    let (columns, set_columns) = create_signal(String::new());
+    let (importing, set_importing) = create_signal(false);
+    let (report, set_report) = create_signal(Option::<CsvImportReportDto>::None);
+    let (error, set_error) = create_signal(Option::<String>::None);
+
+    let ledger_href = move || format!("/api/export/ledger.csv?from={}&to={}", from.get(), to.get());
+
+    // A dry run checks the file and reports what would happen
+    let submission = Submission::new();
+    let handle_import = move |dry_run: bool| {
+        set_importing.set(true);
+        set_error.set(None);
+        spawn_local(async move {
+            let result = if ledger_import.get_untracked() {
+                submission.send(import_ledger_csv(csv.get_untracked(), columns.get_untracked(), dry_run)).await
+            } else {
+                submission.send(import_tasks_csv(csv.get_untracked(), columns.get_untracked(), dry_run)).await
+            };
+            match result {
+                Ok(result) => {
+                    if result.written && !ledger_import.get_untracked() {
+                        if let Ok(task_list) = get_tasks().await {
+                            set_tasks.set(task_list);
+                        }
+                    }
+                    set_report.set(Some(result));
+                }
+                Err(e) => {
+                    set_report.set(None);
+                    set_error.set(Some(format!("Failed to import: {}", e)));
+                }
+            }
+            set_importing.set(false);
+        });
+    };
+
+    view! {
+        <section class="spreadsheet-section">
+            <h2>"Spreadsheets"</h2>
+            <div class="form-row">
+                <a class="create-btn" href="/api/export/tasks.csv" download>"Download Tasks (CSV)"</a>
+            </div>
+            <div class="form-row">
+                <label for="ledger-from">"From"</label>
+                <input type="date" id="ledger-from" on:input=move |ev| set_from.set(event_target_value(&ev)) prop:value=move || from.get() />
+                <label for="ledger-to">"To"</label>
+                <input type="date" id="ledger-to" on:input=move |ev| set_to.set(event_target_value(&ev)) prop:value=move || to.get() />
+                <a class="create-btn" href=ledger_href download>"Download Ledgers (CSV)"</a>
+            </div>
+
+            <h3>"Import from CSV"</h3>
+            <div class="form-row">
+                <select on:change=move |ev| set_ledger_import.set(event_target_value(&ev) == "ledger")>
+                    <option value="tasks">"Tasks (name, value, cadence, description, category, tags)"</option>
+                    <option value="ledger">"Ledger history (date, kid, amount, type, description, category)"</option>
+                </select>
+            </div>
+            <textarea
+                class="csv-input"
+                rows="8"
+                placeholder="Paste CSV with a header row"
+                on:input=move |ev| set_csv.set(event_target_value(&ev))
+                prop:value=move || csv.get()
+            ></textarea>
+            <div class="form-row">
+                <input
+                    type="text"
+                    class="columns-input"
+                    placeholder="Column names, if different: name=Chore, value=Pay"
+                    on:input=move |ev| set_columns.set(event_target_value(&ev))
+                    prop:value=move || columns.get()
+                />
+                <button class="import-btn" disabled=move || importing.get() on:click=move |_| handle_import(true)>
+                    "Check"
+                </button>
+                <button class="import-btn" disabled=move || importing.get() on:click=move |_| handle_import(false)>
+                    "Import"
+                </button>
+            </div>
+            {move || error.get().map(|err| view! { <p class="error">{err}</p> })}
+            {move || report.get().map(|report| {
+                let summary = if report.written {
+                    format!("Imported {} of {} rows", report.imported, report.rows)
+                } else if report.errors.is_empty() {
+                    format!("{} of {} rows can be imported", report.imported, report.rows)
+                } else {
+                    format!("Nothing was imported: {} of {} rows have errors", report.errors.len(), report.rows)
+                };
+                view! {
+                    <div class="csv-report">
+                        <p class="pack-message">{summary}</p>
+                        {(!report.skipped.is_empty()).then(|| view! {
+                            <p>"Already there: " {report.skipped.join(", ")}</p>
+                        })}
+                        <ul class="csv-errors">
+                            {report.errors.into_iter().map(|e| view! {
+                                <li>"Line "{e.line}": "{e.message}</li>
+                            }).collect::<Vec<_>>()}
+                        </ul>
+                    </div>
+                }
+            })}
+        </section>
+    }
+}
+
+/// Earnings per day, week or month as bar charts, with breakdowns
+#[component]
+fn EarningsPanel(recent_activity: ReadSignal<Vec<LedgerEntryDto>>) -> impl IntoView {
+    let (period, set_period) = create_signal("week".to_string());
+    // Reload when the period changes or a new ledger entry arrives
+    let report = create_resource(
+        move || (period.get(), recent_activity.with(|entries| entries.first().map(|e| e.id.clone()))),
+        |(period, _)| get_report(period, None, None, None),
+    );
+
+    view! {
+        <section class="earnings-section">
+            <div class="earnings-header">
+                <h2>"Earnings"</h2>
+                <select
+                    prop:value=move || period.get()
+                    on:change=move |ev| set_period.set(event_target_value(&ev))
+                >
+                    <option value="day">"Daily"</option>
+                    <option value="week">"Weekly"</option>
+                    <option value="month">"Monthly"</option>
+                </select>
+            </div>
+            <Suspense fallback=move || view! { <p>"Loading earnings..."</p> }>
+                {move || report.get().map(|result| match result {
+                    Ok(report) if report.kids.is_empty() => view! { <p>"No kids yet."</p> }.into_view(),
+                    Ok(report) => report.kids.into_iter().map(|kid| view! { <KidEarningsChart kid=kid /> }).collect_view(),
+                    Err(e) => view! { <p class="error">"Failed to load earnings: "{e.to_string()}</p> }.into_view(),
+                })}
+            </Suspense>
+        </section>
+    }
+}
+
+#[component]
+fn KidEarningsChart(kid: KidEarningsDto) -> impl IntoView {
+    let zero = rust_decimal::Decimal::ZERO;
+    let highest = kid.periods.iter().map(|p| p.earned).max().unwrap_or(zero).max(rust_decimal::Decimal::ONE);
+    let height = move |amount: rust_decimal::Decimal| {
+        let percent = (amount.max(zero) * rust_decimal::Decimal::ONE_HUNDRED / highest).round_dp(1);
+        format!("height: {}%", percent)
+    };
+    let comparison = match kid.change_percent {
+        Some(percent) if percent >= zero => format!("▲ {}% on the period before", percent),
+        Some(percent) => format!("▼ {}% on the period before", percent.abs()),
+        None => "Nothing earned in the period before".to_string(),
+    };
+    let breakdown = |title: &'static str, items: Vec<EarningsBreakdownDto>| {
+        let largest = items.iter().map(|b| b.total.abs()).max().unwrap_or(zero).max(rust_decimal::Decimal::ONE);
+        (!items.is_empty()).then(|| view! {
+            <div class="earnings-breakdown">
+                <h4>{title}</h4>
+                <ul>
+                    {items.into_iter().map(|item| {
+                        let width = format!("width: {}%", (item.total.abs() * rust_decimal::Decimal::ONE_HUNDRED / largest).round_dp(1));
+                        view! {
+                            <li>
+                                <span class="breakdown-label">{item.label}</span>
+                                <span class="breakdown-bar"><span style=width></span></span>
+                                <span class="breakdown-amount">"$"{item.total.to_string()}</span>
+                            </li>
+                        }
+                    }).collect::<Vec<_>>()}
+                </ul>
+            </div>
+        })
+    };
+
+    view! {
+        <div class="kid-earnings">
+            <div class="kid-earnings-header">
+                <h3>{kid.name}</h3>
+                <span class="earned-total">"Earned $"{kid.earned.to_string()}</span>
+                <span class="earned-change">{comparison}</span>
+            </div>
+            <div class="earnings-chart">
+                {kid.periods.into_iter().map(|p| {
+                    let title = format!("{}: earned ${}, net ${}", p.label, p.earned, p.net);
+                    view! {
+                        <div class="chart-column" title=title>
+                            <span class="chart-value">{(!p.earned.is_zero()).then(|| format!("${}", p.earned))}</span>
+                            <div class="chart-bar-area"><div class="chart-bar" style=height(p.earned)></div></div>
+                            <span class="chart-label">{p.label}</span>
+                        </div>
+                    }
+                }).collect::<Vec<_>>()}
+            </div>
+            <div class="earnings-breakdowns">
+                {breakdown("By task", kid.by_task)}
+                {breakdown("By category", kid.by_category)}
+                {breakdown("By type", kid.by_entry_type)}
+            </div>
         </div>
     }
 }
 
+/// Expected payouts for the next week and month, with a warning when they
+/// go over the household's budget
+#[component]
+fn ForecastPanel(recent_activity: ReadSignal<Vec<LedgerEntryDto>>) -> impl IntoView {
+    let forecast = create_resource(
+        move || recent_activity.with(|entries| entries.first().map(|e| e.id.clone())),
+        |_| get_forecast(),
+    );
+
+    view! {
+        <section class="forecast-section">
+            <h2>"Payout Forecast"</h2>
+            <Suspense fallback=move || view! { <p>"Loading forecast..."</p> }>
+                {move || forecast.get().map(|result| match result {
+                    Ok(forecast) => {
+                        let budget = forecast.budget.map(|b| format!("Budget: ${} per {}", b.amount, b.period));
+                        view! {
+                            {forecast.warning.map(|warning| view! { <p class="forecast-warning">"⚠ "{warning}</p> })}
+                            <div class="forecast-totals">
+                                <span>"Next week: $"{forecast.next_week.to_string()}</span>
+                                <span>"Next month: $"{forecast.next_month.to_string()}</span>
+                                {budget.map(|budget| view! { <span class="forecast-budget">{budget}</span> })}
+                            </div>
+                            {(!forecast.open_one_time.is_zero()).then(|| view! {
+                                <p class="forecast-note">
+                                    "Includes $"{forecast.open_one_time.to_string()}" of one-time tasks nobody has done yet"
+                                </p>
+                            })}
+                            <table class="forecast-table">
+                                <thead>
+                                    <tr>
+                                        <th>"Kid"</th>
+                                        <th>"Completion rate"</th>
+                                        <th>"Next week"</th>
+                                        <th>"Next month"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {forecast.kids.into_iter().map(|kid| {
+                                        let rate = format!("{}%", (kid.completion_rate * rust_decimal::Decimal::ONE_HUNDRED).round());
+                                        let tasks = kid.tasks.iter()
+                                            .map(|t| format!("{}: ${} a week", t.name, t.next_week))
+                                            .collect::<Vec<_>>()
+                                            .join("\n");
+                                        view! {
+                                            <tr title=tasks>
+                                                <td>{kid.name}</td>
+                                                <td>{rate}</td>
+                                                <td>"$"{kid.next_week.to_string()}</td>
+                                                <td>"$"{kid.next_month.to_string()}</td>
+                                            </tr>
+                                        }
+                                    }).collect::<Vec<_>>()}
+                                </tbody>
+                            </table>
+                        }.into_view()
+                    }
+                    Err(e) => view! { <p class="error">"Failed to load forecast: "{e.to_string()}</p> }.into_view(),
+                })}
+            </Suspense>
+        </section>
+    }
+}
+
+/// What's left of each payout budget, and a form to set them
+#[component]
+fn BudgetPanel(
+    kid_summaries: ReadSignal<Vec<KidSummaryDto>>,
+    budgets: ReadSignal<Vec<BudgetStatusDto>>,
+    set_budgets: WriteSignal<Vec<BudgetStatusDto>>,
+) -> impl IntoView {
+    // An empty kid ID stands for the whole household
+    let (kid_id, set_kid_id) = create_signal(String::new());
+    let (amount, set_amount) = create_signal(String::new());
+    let (period, set_period) = create_signal("week".to_string());
+    let (enforcement, set_enforcement) = create_signal("warn".to_string());
+    let (message, set_message) = create_signal(Option::<String>::None);
+
+    // Show the chosen budget's current settings
+    create_effect(move |_| {
+        let kid = kid_id.get();
+        let current = budgets.with(|list| {
+            list.iter()
+                .find(|status| status.kid_id.clone().unwrap_or_default() == kid)
+                .map(|status| status.budget.clone())
+        });
+        match current {
+            Some(budget) => {
+                set_amount.set(budget.amount.to_string());
+                set_period.set(budget.period);
+                set_enforcement.set(budget.enforcement);
+            }
+            None => set_amount.set(String::new()),
+        }
+    });
+
+    let submission = Submission::new();
+    let save = move |amount: Option<String>| {
+        let kid = kid_id.get_untracked();
+        set_message.set(None);
+        spawn_local(async move {
+            let budget = set_payout_budget((!kid.is_empty()).then_some(kid), amount, period.get_untracked(), enforcement.get_untracked());
+            match submission.send(budget).await {
+                Ok(statuses) => {
+                    set_budgets.set(statuses);
+                    set_message.set(Some("Budget saved".to_string()));
+                }
+                Err(e) => set_message.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    view! {
+        <section class="budget-section">
+            <h2>"Payout Budgets"</h2>
+            {move || {
+                let list = budgets.get();
+                if list.is_empty() {
+                    view! { <p class="empty-state">"No budgets set."</p> }.into_view()
+                } else {
+                    view! {
+                        <ul class="budget-list">
+                            {list.into_iter().map(|status| {
+                                let class = if status.remaining <= rust_decimal::Decimal::ZERO { "budget-status over" } else { "budget-status" };
+                                let left = status.remaining.max(rust_decimal::Decimal::ZERO);
+                                let mode = if status.budget.enforcement == "block" { "blocks payouts over it" } else { "warns only" };
+                                view! {
+                                    <li class=class>
+                                        <strong>{status.name}</strong>
+                                        ": $"{left.to_string()}" of $"{status.budget.amount.to_string()}" left this "{status.budget.period.clone()}
+                                        <span class="budget-mode">" ("{mode}")"</span>
+                                        {status.warning.map(|warning| view! { <p class="forecast-warning">"⚠ "{warning}</p> })}
+                                    </li>
+                                }
+                            }).collect::<Vec<_>>()}
+                        </ul>
+                    }.into_view()
+                }
+            }}
+            <div class="form-row budget-form">
+                <select prop:value=move || kid_id.get() on:change=move |ev| set_kid_id.set(event_target_value(&ev))>
+                    <option value="">"Whole household"</option>
+                    {move || kid_summaries.get().into_iter().map(|summary| view! {
+                        <option value=summary.kid.id.clone()>{summary.kid.name}</option>
+                    }).collect::<Vec<_>>()}
+                </select>
+                <label for="budget-amount">"$"</label>
+                <input
+                    type="number"
+                    id="budget-amount"
+                    min="0"
+                    step="0.01"
+                    placeholder="None"
+                    prop:value=move || amount.get()
+                    on:input=move |ev| set_amount.set(event_target_value(&ev))
+                />
+                <select prop:value=move || period.get() on:change=move |ev| set_period.set(event_target_value(&ev))>
+                    <option value="week">"per week"</option>
+                    <option value="month">"per month"</option>
+                </select>
+                <select prop:value=move || enforcement.get() on:change=move |ev| set_enforcement.set(event_target_value(&ev))>
+                    <option value="warn">"Warn when over"</option>
+                    <option value="block">"Block payouts over it"</option>
+                </select>
+                <button class="create-btn" on:click=move |_| save(Some(amount.get_untracked()))>"Save"</button>
+                <button class="reject-btn" on:click=move |_| save(None)>"Remove"</button>
+            </div>
+            {move || message.get().map(|msg| view! { <p class="pack-message">{msg}</p> })}
+        </section>
+    }
+}
+
+/// Moves money between two kids, and lists transfers waiting for approval
+#[component]
+fn TransferPanel(kid_summaries: ReadSignal<Vec<KidSummaryDto>>) -> impl IntoView {
+    let (pending, set_pending) = create_signal(Vec::<TransferDto>::new());
+    let (from_kid, set_from_kid) = create_signal(String::new());
+    let (to_kid, set_to_kid) = create_signal(String::new());
+    let (amount, set_amount) = create_signal(String::new());
+    let (note, set_note) = create_signal(String::new());
+    let (require_approval, set_require_approval) = create_signal(false);
+    let (message, set_message) = create_signal(Option::<String>::None);
+
+    let refresh = move || {
+        spawn_local(async move {
+            match get_pending_transfers().await {
+                Ok(list) => set_pending.set(list),
+                Err(e) => set_message.set(Some(format!("Error loading transfers: {}", e))),
+            }
+        });
+    };
+    refresh();
+
+    let submission = Submission::new();
+    let handle_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_message.set(None);
+        let note = Some(note.get_untracked()).filter(|n| !n.trim().is_empty());
+        spawn_local(async move {
+            let transfer = transfer_between_kids(
+                from_kid.get_untracked(),
+                to_kid.get_untracked(),
+                amount.get_untracked(),
+                note,
+                require_approval.get_untracked(),
+            );
+            match submission.send(transfer).await {
+                Ok(transfer) => {
+                    set_amount.set(String::new());
+                    set_note.set(String::new());
+                    let done = if transfer.status == TransferStatusDto::Pending { "waiting for approval" } else { "done" };
+                    set_message.set(Some(format!(
+                        "Transfer of ${} from {} to {} {}", transfer.amount, transfer.from_name, transfer.to_name, done
+                    )));
+                }
+                Err(e) => set_message.set(Some(e.to_string())),
+            }
+            refresh();
+        });
+    };
+
+    let handle_review = move |submission: Submission, transfer_id: UuidDto, approve: bool| {
+        set_message.set(None);
+        spawn_local(async move {
+            let result = if approve {
+                submission.send(approve_transfer(transfer_id)).await
+            } else {
+                submission.send(decline_transfer(transfer_id)).await
+            };
+            if let Err(e) = result {
+                set_message.set(Some(e.to_string()));
+            }
+            refresh();
+        });
+    };
+
+    let kid_options = move || kid_summaries.get().into_iter().map(|summary| view! {
+        <option value=summary.kid.id.clone()>{summary.kid.name}</option>
+    }).collect::<Vec<_>>();
+
+    view! {
+        <Show when=move || kid_summaries.with(|list| list.len() >= 2)>
+            <section class="budget-section transfer-section">
+                <h2>"Transfers"</h2>
+                <ul class="claims-list">
+                    {move || pending.get().into_iter().map(|transfer| {
+                        let id = transfer.id.clone();
+                        let decline_id = transfer.id.clone();
+                        let submission = Submission::new();
+                        view! {
+                            <li class="claim-item">
+                                <span class="claim-kid">{transfer.from_name}" → "{transfer.to_name}</span>
+                                <span class="claim-task">"$"{transfer.amount.to_string()}</span>
+                                <span class="claim-time">{format_time_ago(transfer.created_at)}</span>
+                                {transfer.note.map(|note| view! { <span class="claim-note">{note}</span> })}
+                                <button class="approve-btn" on:click=move |_| handle_review(submission, id.clone(), true)>"Approve"</button>
+                                <button class="reject-btn" on:click=move |_| handle_review(submission, decline_id.clone(), false)>"Decline"</button>
+                            </li>
+                        }
+                    }).collect::<Vec<_>>()}
+                </ul>
+                <form class="form-row budget-form" on:submit=handle_submit>
+                    <select required prop:value=move || from_kid.get() on:change=move |ev| set_from_kid.set(event_target_value(&ev))>
+                        <option value="">"From..."</option>
+                        {kid_options}
+                    </select>
+                    <select required prop:value=move || to_kid.get() on:change=move |ev| set_to_kid.set(event_target_value(&ev))>
+                        <option value="">"To..."</option>
+                        {kid_options}
+                    </select>
+                    <label for="transfer-amount">"$"</label>
+                    <input
+                        type="number"
+                        id="transfer-amount"
+                        min="0.01"
+                        step="0.01"
+                        required
+                        prop:value=move || amount.get()
+                        on:input=move |ev| set_amount.set(event_target_value(&ev))
+                    />
+                    <input
+                        type="text"
+                        placeholder="Note (optional)"
+                        prop:value=move || note.get()
+                        on:input=move |ev| set_note.set(event_target_value(&ev))
+                    />
+                    <label>
+                        <input
+                            type="checkbox"
+                            prop:checked=move || require_approval.get()
+                            on:change=move |ev| set_require_approval.set(event_target_checked(&ev))
+                        />
+                        " Needs approval"
+                    </label>
+                    <button type="submit" class="create-btn">"Transfer"</button>
+                </form>
+                {move || message.get().map(|msg| view! { <p class="pack-message">{msg}</p> })}
+            </section>
+        </Show>
+    }
+}
 
 fn format_time_ago(dt: chrono::DateTime<chrono::Utc>) -> String {
     let now = chrono::Utc::now();
@@ -435,6 +2057,39 @@ fn format_time_ago(dt: chrono::DateTime<chrono::Utc>) -> String {
     }
 }
 
+/// Month picker with PDF and HTML downloads of that month's statement
+#[component]
+fn StatementLinks(kid_id: UuidDto) -> impl IntoView {
+    use chrono::Datelike;
+
+    // Last month, the latest one that's complete
+    let today = chrono::Utc::now().date_naive();
+    let last_month = today.with_day(1).unwrap_or(today) - chrono::Months::new(1);
+    let (month, set_month) = create_signal(last_month.format("%Y-%m").to_string());
+    let href = move |format: &str| {
+        format!("/api/statements/{}?month={}&format={}", kid_id, month.get(), format)
+    };
+    let pdf_href = {
+        let href = href.clone();
+        move || href("pdf")
+    };
+
+    view! {
+        <div class="statement-links">
+            <label>
+                "Monthly statement "
+                <input
+                    type="month"
+                    prop:value=move || month.get()
+                    on:input=move |ev| set_month.set(event_target_value(&ev))
+                />
+            </label>
+            <a class="create-btn" href=pdf_href download>"PDF"</a>
+            <a class="create-btn" href=move || href("html") download>"HTML"</a>
+        </div>
+    }
+}
+
 #[component]
 pub fn LedgerView(kid_id: UuidDto, set_view: WriteSignal<View>) -> impl IntoView {
     let ledger = create_resource(move || kid_id.clone(), get_ledger);
@@ -459,7 +2114,11 @@ pub fn LedgerView(kid_id: UuidDto, set_view: WriteSignal<View>) -> impl IntoView
                                             <span class="balance-label">"Current Balance:"</span>
                                             <span class="balance-value">"$"{ledger_data.balance.to_string()}</span>
                                         </div>
+                                        <a class="create-btn" href=format!("/api/export/ledger.csv?kid_id={}", ledger_data.kid_id) download>
+                                            "Download CSV"
+                                        </a>
                                     </div>
+                                    <StatementLinks kid_id=ledger_data.kid_id.clone()/>
 
                                     <section class="transactions">
                                         <h3>"All Transactions"</h3>
@@ -467,6 +2126,7 @@ pub fn LedgerView(kid_id: UuidDto, set_view: WriteSignal<View>) -> impl IntoView
                                             view! { <p>"No transactions yet."</p> }.into_view()
                                         } else {
                                             let mut running_balance = rust_decimal::Decimal::ZERO;
+                                            let ledger_kid_id = ledger_data.kid_id.clone();
                                             view! {
                                                 <table class="ledger-table">
                                                     <thead>
@@ -484,6 +2144,8 @@ pub fn LedgerView(kid_id: UuidDto, set_view: WriteSignal<View>) -> impl IntoView
                                                             let entry_type = match entry.entry_type {
                                                                 EntryTypeDto::Earned => "Earned",
                                                                 EntryTypeDto::Adjusted => "Adjusted",
+                                                                EntryTypeDto::Payout => "Payout",
+                                                                EntryTypeDto::Transfer => "Transfer",
                                                             };
                                                             let sign = if entry.amount >= rust_decimal::Decimal::ZERO { "+" } else { "" };
                                                             let date_str = entry.created_at.format("%Y-%m-%d").to_string();
@@ -501,7 +2163,34 @@ pub fn LedgerView(kid_id: UuidDto, set_view: WriteSignal<View>) -> impl IntoView
                                                                             {entry_type}
                                                                         </span>
                                                                     </td>
-                                                                    <td class="description-cell">{entry.description}</td>
+                                                                    <td class="description-cell">
+                                                                        {entry.description}
+                                                                        {entry.review.map(|review| view! {
+                                                                            <div class="review-details">
+                                                                                {review.quality.map(|q| format!("Quality {}/5", q))}
+                                                                                {review.original_value.filter(|_| review.percent_paid < 100).map(|v| format!(" · {}% of ${}", review.percent_paid, v))}
+                                                                                {review.comment.map(|c| view! { <div class="review-comment">{c}</div> })}
+                                                                            </div>
+                                                                        })}
+                                                                        {(!entry.attachment_ids.is_empty()).then(|| view! {
+                                                                            <div class="attachment-thumbnails">
+                                                                                {entry.attachment_ids.iter().map(|id| view! {
+                                                                                    <a href=format!("/api/attachments/{}", id) target="_blank">
+                                                                                        <img src=format!("/api/attachments/{}/thumbnail", id) alt="Photo proof" />
+                                                                                    </a>
+                                                                                }).collect::<Vec<_>>()}
+                                                                            </div>
+                                                                        })}
+                                                                        {matches!(entry.entry_type, EntryTypeDto::Earned).then(|| view! {
+                                                                            <form class="attachment-upload" method="post" action="/api/attachments" enctype="multipart/form-data">
+                                                                                <input type="hidden" name="kid_id" value=ledger_kid_id.clone() />
+                                                                                <input type="hidden" name="ledger_entry_id" value=entry.id.clone() />
+                                                                                <input type="hidden" name="return_to" value="/" />
+                                                                                <input type="file" name="file" accept="image/jpeg,image/png" required />
+                                                                                <button type="submit">"Attach photo"</button>
+                                                                            </form>
+                                                                        })}
+                                                                    </td>
                                                                     <td class={format!("amount-cell {}", if entry.amount >= rust_decimal::Decimal::ZERO { "positive" } else { "negative" })}>
                                                                         {sign}{"$"}{entry.amount.abs().to_string()}
                                                                     </td>
@@ -562,6 +2251,18 @@ fn AdminPanel() -> impl IntoView {
         });
     });
 
+    // Load the outcome of the last scheduled backup
+    let (backup_status, set_backup_status) = create_signal(Option::<BackupStatusDto>::None);
+    create_effect(move |_| {
+        spawn_local(async move {
+            match get_backup_status().await {
+                Ok(status) => set_backup_status.set(Some(status)),
+                Err(e) => set_error.set(Some(format!("Failed to load backup status: {}", e))),
+            }
+        });
+    });
+
+    let submission = Submission::new();
     let on_create = move |ev: leptos::ev::SubmitEvent| {
         ev.prevent_default();
         set_error.set(None);
@@ -571,7 +2272,7 @@ fn AdminPanel() -> impl IntoView {
         let password = new_password.get();
 
         spawn_local(async move {
-            match create_account(username, password).await {
+            match submission.send(create_account(username, password)).await {
                 Ok(new_account) => {
                     // Add to list
                     set_accounts.update(|accts| accts.push(new_account));
@@ -587,9 +2288,9 @@ fn AdminPanel() -> impl IntoView {
         });
     };
 
-    let handle_delete = move |account_id: String, username: String| {
+    let handle_delete = move |submission: Submission, account_id: String, username: String| {
         spawn_local(async move {
-            match delete_account(account_id.clone()).await {
+            match submission.send(delete_account(account_id.clone())).await {
                 Ok(()) => {
                     set_accounts.update(|accts| {
                         accts.retain(|a| a.id != account_id);
@@ -631,7 +2332,11 @@ fn AdminPanel() -> impl IntoView {
                                 placeholder="Password"
                                 required
                                 disabled=move || creating.get()
-                                on:input=move |ev| set_new_password.set(event_target_value(&ev))
+                                on:input=move |ev| {
+                                    // Like PINs, passwords aren't part of the key's arguments
+                                    submission.reset();
+                                    set_new_password.set(event_target_value(&ev));
+                                }
                                 prop:value=move || new_password.get()
                             />
                             <button type="submit" class="create-btn" disabled=move || creating.get()>
@@ -652,6 +2357,7 @@ fn AdminPanel() -> impl IntoView {
                                 <div class="accounts-list">
                                     {account_list.into_iter().map(|account| {
                                         let account_id = account.id.clone();
+                                        let submission = Submission::new();
                                         let username = account.username.clone();
                                         let username_for_delete = account.username.clone();
                                         let created = account.created_at.format("%Y-%m-%d").to_string();
@@ -663,7 +2369,7 @@ fn AdminPanel() -> impl IntoView {
                                                 </div>
                                                 <button
                                                     class="delete-btn"
-                                                    on:click=move |_| handle_delete(account_id.clone(), username_for_delete.clone())
+                                                    on:click=move |_| handle_delete(submission, account_id.clone(), username_for_delete.clone())
                                                 >
                                                     "Delete"
                                                 </button>
@@ -675,6 +2381,34 @@ fn AdminPanel() -> impl IntoView {
                         }
                     }}
                 </section>
+                <section class="backup-section">
+                    <h2>"Backup"</h2>
+                    <p>"Download every household's data as a backup file. Restore it with "<code>"just backup restore <file>"</code>"."</p>
+                    <a class="create-btn" href="/api/admin/backup" download>"Download Backup"</a>
+                    {move || backup_status.get().map(|status| {
+                        let summary = match (status.enabled, status.last) {
+                            (false, _) => "Scheduled backups are off. Set LOAA_BACKUP_DIR to turn them on.".to_string(),
+                            (true, None) => "Scheduled backups are on, but none has run yet.".to_string(),
+                            (true, Some(last)) => match last.error {
+                                None => format!(
+                                    "Last backup: {} ({} records, {})",
+                                    last.attempted_at.format("%Y-%m-%d %H:%M UTC"),
+                                    last.records,
+                                    last.file.unwrap_or_default(),
+                                ),
+                                Some(error) => format!(
+                                    "Last backup failed at {}: {}. Last success: {}",
+                                    last.attempted_at.format("%Y-%m-%d %H:%M UTC"),
+                                    error,
+                                    last.last_success_at
+                                        .map(|at| at.format("%Y-%m-%d %H:%M UTC").to_string())
+                                        .unwrap_or_else(|| "never".to_string()),
+                                ),
+                            },
+                        };
+                        view! { <p class="backup-status">{summary}</p> }
+                    })}
+                </section>
             </Show>
         </div>
     }
@@ -0,0 +1,115 @@
+//! CSV downloads of ledgers and tasks, for opening in a spreadsheet
+//! Any member of a household can download its data; kid logins can't
+
+use axum::{
+    extract::Query,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{NaiveDate, Utc};
+use loaa_core::workflows::LedgerFilter;
+use loaa_core::{Actor, Error, Uuid};
+use serde::Deserialize;
+use tower_sessions::Session;
+
+use crate::server_functions::{get_csv_workflow, get_membership};
+
+type HandlerError = (StatusCode, String);
+
+fn error_response(e: Error) -> HandlerError {
+    match e {
+        Error::NotFound(_) => (StatusCode::NOT_FOUND, "Kid not found".to_string()),
+        Error::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
+        Error::Validation(msg) => (StatusCode::BAD_REQUEST, msg),
+        other => {
+            eprintln!("CSV export error: {}", other);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Export failed".to_string())
+        }
+    }
+}
+
+/// The logged-in parent, who can read their household's data
+async fn session_actor(session: &Session) -> Result<Actor, HandlerError> {
+    let user_id: Option<String> = session.get("user_id").await.ok().flatten();
+    let account_type: Option<String> = session.get("account_type").await.ok().flatten();
+    let user_id = match (user_id, account_type.as_deref()) {
+        (_, Some("kid")) => return Err((StatusCode::FORBIDDEN, "Parent account required".to_string())),
+        (Some(user_id), _) => user_id,
+        (None, _) => return Err((StatusCode::UNAUTHORIZED, "Not authenticated".to_string())),
+    };
+    let (household, role) = get_membership(&user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Actor::member(user_id, household.owner_id(), role))
+}
+
+fn csv_response(name: &str, csv: String) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}-{}.csv\"", name, Utc::now().format("%Y-%m-%d")),
+            ),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+        csv,
+    )
+        .into_response()
+}
+
+/// Download the household's tasks
+pub async fn export_tasks(session: Session) -> Result<Response, HandlerError> {
+    let actor = session_actor(&session).await?;
+    let csv = get_csv_workflow()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .export_tasks(&actor)
+        .await
+        .map_err(error_response)?;
+    Ok(csv_response("loaa-tasks", csv))
+}
+
+/// Query parameters for a ledger download; all are optional
+#[derive(Debug, Deserialize)]
+pub struct LedgerExportQuery {
+    kid_id: Option<String>,
+    /// First day to include, as YYYY-MM-DD
+    from: Option<String>,
+    /// Last day to include, as YYYY-MM-DD
+    to: Option<String>,
+}
+
+fn parse_day(field: &str, day: Option<String>) -> Result<Option<NaiveDate>, HandlerError> {
+    day.filter(|day| !day.trim().is_empty())
+        .map(|day| {
+            NaiveDate::parse_from_str(day.trim(), "%Y-%m-%d")
+                .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid {} date: {}", field, e)))
+        })
+        .transpose()
+}
+
+/// Download ledger entries for one kid or all of them
+pub async fn export_ledger(
+    session: Session,
+    Query(query): Query<LedgerExportQuery>,
+) -> Result<Response, HandlerError> {
+    let actor = session_actor(&session).await?;
+    let kid_id = query.kid_id
+        .filter(|id| !id.trim().is_empty())
+        .map(|id| Uuid::parse_str(id.trim()).map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid kid ID: {}", e))))
+        .transpose()?;
+    let filter = LedgerFilter {
+        kid_id,
+        from: parse_day("from", query.from)?,
+        to: parse_day("to", query.to)?,
+    };
+
+    let csv = get_csv_workflow()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .export_ledger(&actor, &filter)
+        .await
+        .map_err(error_response)?;
+    Ok(csv_response("loaa-ledger", csv))
+}
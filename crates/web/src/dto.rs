@@ -25,6 +25,8 @@ pub struct TaskDto {
     pub description: String,
     pub value: Decimal,
     pub cadence: CadenceDto,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -46,6 +48,30 @@ pub struct LedgerEntryDto {
     pub amount: Decimal,
     pub description: String,
     pub entry_type: EntryTypeDto,
+    pub review: Option<CompletionReviewDto>,
+    /// Photo proof attached to this completion
+    pub attachment_ids: Vec<UuidDto>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Review recorded when a completion was approved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionReviewDto {
+    pub quality: Option<u8>,
+    pub percent_paid: u8,
+    pub comment: Option<String>,
+    pub original_value: Option<Decimal>,
+}
+
+// Photo attachment DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentDto {
+    pub id: UuidDto,
+    pub kid_id: UuidDto,
+    pub task_id: Option<UuidDto>,
+    pub ledger_entry_id: Option<UuidDto>,
+    pub content_type: String,
+    pub size_bytes: u64,
     pub created_at: DateTime<Utc>,
 }
 
@@ -54,6 +80,8 @@ pub struct LedgerEntryDto {
 pub enum EntryTypeDto {
     Earned,
     Adjusted,
+    Payout,
+    Transfer,
 }
 
 // Ledger DTO
@@ -70,6 +98,15 @@ pub struct KidSummaryDto {
     pub kid: KidDto,
     pub balance: Decimal,
     pub recent_entry: Option<LedgerEntryDto>,
+    pub average_quality: Option<Decimal>,
+    pub earnings_by_category: Vec<CategoryEarningsDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryEarningsDto {
+    /// Category name, or "uncategorized"
+    pub category: String,
+    pub amount: Decimal,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,6 +114,118 @@ pub struct DashboardDataDto {
     pub kid_summaries: Vec<KidSummaryDto>,
     pub total_kids: usize,
     pub active_tasks: usize,
+    /// Payout budgets with what's left of them, household's first
+    pub budgets: Vec<BudgetStatusDto>,
+}
+
+// Chore pack DTOs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskTemplateDto {
+    pub name: String,
+    pub description: String,
+    pub suggested_value: Decimal,
+    pub cadence: CadenceDto,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskPackDto {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub age_band: String,
+    pub room: String,
+    pub templates: Vec<TaskTemplateDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackImportResultDto {
+    pub created: Vec<TaskDto>,
+    pub skipped: Vec<String>,
+}
+
+// Outcome of a CSV import, or what it would do on a dry run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvImportReportDto {
+    pub rows: usize,
+    pub imported: usize,
+    pub skipped: Vec<String>,
+    pub errors: Vec<CsvRowErrorDto>,
+    pub written: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvRowErrorDto {
+    pub line: u64,
+    pub message: String,
+}
+
+// Earnings report DTOs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EarningsReportDto {
+    /// "day", "week" or "month"
+    pub period: String,
+    pub from: chrono::NaiveDate,
+    pub to: chrono::NaiveDate,
+    pub kids: Vec<KidEarningsDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KidEarningsDto {
+    pub kid_id: UuidDto,
+    pub name: String,
+    pub earned: Decimal,
+    pub net: Decimal,
+    pub previous_earned: Decimal,
+    pub change_percent: Option<Decimal>,
+    pub periods: Vec<PeriodEarningsDto>,
+    pub by_task: Vec<EarningsBreakdownDto>,
+    pub by_category: Vec<EarningsBreakdownDto>,
+    pub by_entry_type: Vec<EarningsBreakdownDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodEarningsDto {
+    pub label: String,
+    pub earned: Decimal,
+    pub net: Decimal,
+    pub change: Decimal,
+    pub change_percent: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EarningsBreakdownDto {
+    pub label: String,
+    pub total: Decimal,
+    pub entries: u64,
+}
+
+// Payout forecast DTOs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastDto {
+    pub kids: Vec<KidForecastDto>,
+    pub open_one_time: Decimal,
+    pub next_week: Decimal,
+    pub next_month: Decimal,
+    pub budget: Option<PayoutBudgetDto>,
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KidForecastDto {
+    pub kid_id: UuidDto,
+    pub name: String,
+    pub completion_rate: Decimal,
+    pub next_week: Decimal,
+    pub next_month: Decimal,
+    pub tasks: Vec<TaskForecastDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskForecastDto {
+    pub name: String,
+    pub rate: Decimal,
+    pub next_week: Decimal,
+    pub next_month: Decimal,
 }
 
 // Account DTOs
@@ -84,6 +233,7 @@ pub struct DashboardDataDto {
 pub enum AccountTypeDto {
     Admin,
     User,
+    Kid,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -94,6 +244,199 @@ pub struct AccountDto {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+// Kid self-service DTOs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum KidLoginResultDto {
+    Success,
+    InvalidPin,
+    /// Too many wrong PINs; locked until this time
+    Locked(DateTime<Utc>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClaimStatusDto {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskClaimDto {
+    pub id: UuidDto,
+    pub kid_id: UuidDto,
+    pub kid_name: String,
+    pub task_id: UuidDto,
+    pub task_name: String,
+    pub task_value: Decimal,
+    pub status: ClaimStatusDto,
+    pub note: Option<String>,
+    pub attachment_id: Option<UuidDto>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Everything a kid sees after logging in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KidHomeDto {
+    pub kid: KidDto,
+    pub balance: Decimal,
+    pub tasks: Vec<TaskDto>,
+    pub entries: Vec<LedgerEntryDto>,
+    pub claims: Vec<TaskClaimDto>,
+    pub withdrawals: Vec<WithdrawalDto>,
+    /// The kid's brothers and sisters, to send money to
+    pub siblings: Vec<KidDto>,
+    pub transfers: Vec<TransferDto>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WithdrawalStatusDto {
+    Requested,
+    Approved,
+    Paid,
+    Declined,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithdrawalDto {
+    pub id: UuidDto,
+    pub kid_id: UuidDto,
+    pub kid_name: String,
+    pub amount: Decimal,
+    pub note: Option<String>,
+    pub status: WithdrawalStatusDto,
+    /// How it was paid ("cash", "bank transfer", "gift card")
+    pub method: Option<String>,
+    pub requested_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransferStatusDto {
+    Pending,
+    Completed,
+    Declined,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferDto {
+    pub id: UuidDto,
+    pub from_kid_id: UuidDto,
+    pub from_name: String,
+    pub to_kid_id: UuidDto,
+    pub to_name: String,
+    pub amount: Decimal,
+    pub note: Option<String>,
+    pub status: TransferStatusDto,
+    pub created_at: DateTime<Utc>,
+}
+
+// What each kid has withdrawn: still owed, and already paid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutSummaryDto {
+    pub kid_id: UuidDto,
+    pub name: String,
+    pub balance: Decimal,
+    pub requested: Decimal,
+    pub approved: Decimal,
+    pub owed: Decimal,
+    pub paid: Decimal,
+}
+
+// Household DTOs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HouseholdRoleDto {
+    Owner,
+    Parent,
+    Viewer,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HouseholdMemberDto {
+    pub user_id: String,
+    pub username: String,
+    pub role: HouseholdRoleDto,
+    /// Whether this member is the logged-in user
+    pub is_you: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HouseholdDto {
+    pub id: UuidDto,
+    pub name: String,
+    pub your_role: HouseholdRoleDto,
+    pub members: Vec<HouseholdMemberDto>,
+    pub budget: Option<PayoutBudgetDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutBudgetDto {
+    pub amount: Decimal,
+    /// "week" or "month"
+    pub period: String,
+    /// "warn" or "block"
+    pub enforcement: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetStatusDto {
+    /// None for the household's budget
+    pub kid_id: Option<UuidDto>,
+    pub name: String,
+    pub budget: PayoutBudgetDto,
+    pub spent: Decimal,
+    pub remaining: Decimal,
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteDto {
+    pub id: UuidDto,
+    pub role: HouseholdRoleDto,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Shareable registration link; only returned when the invite is created
+    pub link: Option<String>,
+}
+
+// What someone opening an invite link is being invited to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteInfoDto {
+    pub household_name: String,
+    pub role: HouseholdRoleDto,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ArchivedKindDto {
+    Kid,
+    Task,
+}
+
+// A deleted kid or task that can still be restored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedItemDto {
+    pub kind: ArchivedKindDto,
+    pub id: UuidDto,
+    pub name: String,
+    pub archived_at: DateTime<Utc>,
+    pub restorable_until: DateTime<Utc>,
+}
+
+// Scheduled backups, for the admin panel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupStatusDto {
+    pub enabled: bool,
+    pub last: Option<LastBackupDto>,
+}
+
+// The outcome of the last scheduled backup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LastBackupDto {
+    pub attempted_at: DateTime<Utc>,
+    pub file: Option<String>,
+    pub records: usize,
+    pub error: Option<String>,
+    pub last_success_at: Option<DateTime<Utc>>,
+}
+
 // Conversion functions (only available on server side)
 #[cfg(feature = "ssr")]
 pub mod convert {
@@ -119,6 +462,8 @@ pub mod convert {
                 description: task.description,
                 value: task.value,
                 cadence: task.cadence.into(),
+                category: task.category.map(|c| c.as_str().to_string()),
+                tags: task.tags,
                 created_at: task.created_at,
                 updated_at: task.updated_at,
             }
@@ -147,22 +492,46 @@ pub mod convert {
 
     impl From<LedgerEntry> for LedgerEntryDto {
         fn from(entry: LedgerEntry) -> Self {
+            let original_value = entry.original_value;
             LedgerEntryDto {
                 id: entry.id.to_string(),
                 kid_id: entry.kid_id.to_string(),
                 amount: entry.amount,
                 description: entry.description,
                 entry_type: entry.entry_type.into(),
+                review: entry.review.map(|review| CompletionReviewDto {
+                    quality: review.quality,
+                    percent_paid: review.percent_paid,
+                    comment: review.comment,
+                    original_value,
+                }),
+                attachment_ids: Vec::new(),
                 created_at: entry.created_at,
             }
         }
     }
 
+    impl From<Attachment> for AttachmentDto {
+        fn from(attachment: Attachment) -> Self {
+            AttachmentDto {
+                id: attachment.id.to_string(),
+                kid_id: attachment.kid_id.to_string(),
+                task_id: attachment.task_id.map(|id| id.to_string()),
+                ledger_entry_id: attachment.ledger_entry_id.map(|id| id.to_string()),
+                content_type: attachment.content_type,
+                size_bytes: attachment.size_bytes,
+                created_at: attachment.created_at,
+            }
+        }
+    }
+
     impl From<EntryType> for EntryTypeDto {
         fn from(et: EntryType) -> Self {
             match et {
                 EntryType::Earned => EntryTypeDto::Earned,
                 EntryType::Adjusted => EntryTypeDto::Adjusted,
+                EntryType::Payout => EntryTypeDto::Payout,
+                EntryType::Transfer => EntryTypeDto::Transfer,
             }
         }
     }
@@ -177,11 +546,116 @@ pub mod convert {
         }
     }
 
+    impl From<loaa_core::TaskPack> for TaskPackDto {
+        fn from(pack: loaa_core::TaskPack) -> Self {
+            TaskPackDto {
+                id: pack.id,
+                name: pack.name,
+                description: pack.description,
+                age_band: pack.age_band.label().to_string(),
+                room: pack.room,
+                templates: pack.templates.into_iter().map(|t| TaskTemplateDto {
+                    name: t.name,
+                    description: t.description,
+                    suggested_value: t.suggested_value,
+                    cadence: t.cadence.into(),
+                }).collect(),
+            }
+        }
+    }
+
+    impl From<loaa_core::PackImportResult> for PackImportResultDto {
+        fn from(result: loaa_core::PackImportResult) -> Self {
+            PackImportResultDto {
+                created: result.created.into_iter().map(Into::into).collect(),
+                skipped: result.skipped,
+            }
+        }
+    }
+
     impl From<AccountType> for AccountTypeDto {
         fn from(at: AccountType) -> Self {
             match at {
                 AccountType::Admin => AccountTypeDto::Admin,
                 AccountType::User => AccountTypeDto::User,
+                AccountType::Kid => AccountTypeDto::Kid,
+            }
+        }
+    }
+
+    impl From<ClaimStatus> for ClaimStatusDto {
+        fn from(status: ClaimStatus) -> Self {
+            match status {
+                ClaimStatus::Pending => ClaimStatusDto::Pending,
+                ClaimStatus::Approved => ClaimStatusDto::Approved,
+                ClaimStatus::Rejected => ClaimStatusDto::Rejected,
+            }
+        }
+    }
+
+    impl From<WithdrawalStatus> for WithdrawalStatusDto {
+        fn from(status: WithdrawalStatus) -> Self {
+            match status {
+                WithdrawalStatus::Requested => WithdrawalStatusDto::Requested,
+                WithdrawalStatus::Approved => WithdrawalStatusDto::Approved,
+                WithdrawalStatus::Paid => WithdrawalStatusDto::Paid,
+                WithdrawalStatus::Declined => WithdrawalStatusDto::Declined,
+            }
+        }
+    }
+
+    impl From<TransferStatus> for TransferStatusDto {
+        fn from(status: TransferStatus) -> Self {
+            match status {
+                TransferStatus::Pending => TransferStatusDto::Pending,
+                TransferStatus::Completed => TransferStatusDto::Completed,
+                TransferStatus::Declined => TransferStatusDto::Declined,
+            }
+        }
+    }
+
+    impl From<loaa_core::workflows::PayoutSummary> for PayoutSummaryDto {
+        fn from(summary: loaa_core::workflows::PayoutSummary) -> Self {
+            PayoutSummaryDto {
+                kid_id: summary.kid_id.to_string(),
+                name: summary.name,
+                balance: summary.balance,
+                requested: summary.requested,
+                approved: summary.approved,
+                owed: summary.owed,
+                paid: summary.paid,
+            }
+        }
+    }
+
+    impl From<HouseholdRole> for HouseholdRoleDto {
+        fn from(role: HouseholdRole) -> Self {
+            match role {
+                HouseholdRole::Owner => HouseholdRoleDto::Owner,
+                HouseholdRole::Parent => HouseholdRoleDto::Parent,
+                HouseholdRole::Viewer => HouseholdRoleDto::Viewer,
+            }
+        }
+    }
+
+    impl From<HouseholdRoleDto> for HouseholdRole {
+        fn from(dto: HouseholdRoleDto) -> Self {
+            match dto {
+                HouseholdRoleDto::Owner => HouseholdRole::Owner,
+                HouseholdRoleDto::Parent => HouseholdRole::Parent,
+                HouseholdRoleDto::Viewer => HouseholdRole::Viewer,
+            }
+        }
+    }
+
+    impl From<Invite> for InviteDto {
+        fn from(invite: Invite) -> Self {
+            InviteDto {
+                id: invite.id.to_string(),
+                role: invite.role.into(),
+                created_at: invite.created_at,
+                expires_at: invite.expires_at,
+                link: None,
             }
         }
     }
@@ -196,4 +670,109 @@ pub mod convert {
             }
         }
     }
+
+    impl From<loaa_core::workflows::BackupStatus> for LastBackupDto {
+        fn from(status: loaa_core::workflows::BackupStatus) -> Self {
+            LastBackupDto {
+                attempted_at: status.attempted_at,
+                file: status.file,
+                records: status.records,
+                error: status.error,
+                last_success_at: status.last_success_at,
+            }
+        }
+    }
+
+    impl From<loaa_core::workflows::CsvImportReport> for CsvImportReportDto {
+        fn from(report: loaa_core::workflows::CsvImportReport) -> Self {
+            CsvImportReportDto {
+                rows: report.rows,
+                imported: report.imported,
+                skipped: report.skipped,
+                errors: report.errors.into_iter().map(|e| CsvRowErrorDto { line: e.line, message: e.message }).collect(),
+                written: report.written,
+            }
+        }
+    }
+
+    impl From<loaa_core::reports::EarningsReport> for EarningsReportDto {
+        fn from(report: loaa_core::reports::EarningsReport) -> Self {
+            let breakdown = |items: Vec<loaa_core::reports::EarningsBreakdown>| {
+                items.into_iter()
+                    .map(|b| EarningsBreakdownDto { label: b.label, total: b.total, entries: b.entries })
+                    .collect()
+            };
+            EarningsReportDto {
+                period: report.period.as_str().to_string(),
+                from: report.from,
+                to: report.to,
+                kids: report.kids.into_iter().map(|kid| KidEarningsDto {
+                    kid_id: kid.kid_id.to_string(),
+                    name: kid.name,
+                    earned: kid.earned,
+                    net: kid.net,
+                    previous_earned: kid.previous_earned,
+                    change_percent: kid.change_percent,
+                    periods: kid.periods.into_iter().map(|p| PeriodEarningsDto {
+                        label: p.label,
+                        earned: p.earned,
+                        net: p.net,
+                        change: p.change,
+                        change_percent: p.change_percent,
+                    }).collect(),
+                    by_task: breakdown(kid.by_task),
+                    by_category: breakdown(kid.by_category),
+                    by_entry_type: breakdown(kid.by_entry_type),
+                }).collect(),
+            }
+        }
+    }
+
+    impl From<PayoutBudget> for PayoutBudgetDto {
+        fn from(budget: PayoutBudget) -> Self {
+            PayoutBudgetDto {
+                amount: budget.amount,
+                period: budget.period.as_str().to_string(),
+                enforcement: budget.enforcement.as_str().to_string(),
+            }
+        }
+    }
+
+    impl From<loaa_core::BudgetStatus> for BudgetStatusDto {
+        fn from(status: loaa_core::BudgetStatus) -> Self {
+            BudgetStatusDto {
+                kid_id: status.kid_id.map(|id| id.to_string()),
+                warning: status.warning(),
+                name: status.name,
+                budget: status.budget.into(),
+                spent: status.spent,
+                remaining: status.remaining,
+            }
+        }
+    }
+
+    impl From<loaa_core::forecast::Forecast> for ForecastDto {
+        fn from(forecast: loaa_core::forecast::Forecast) -> Self {
+            ForecastDto {
+                kids: forecast.kids.into_iter().map(|kid| KidForecastDto {
+                    kid_id: kid.kid_id.to_string(),
+                    name: kid.name,
+                    completion_rate: kid.completion_rate,
+                    next_week: kid.next_week,
+                    next_month: kid.next_month,
+                    tasks: kid.tasks.into_iter().map(|t| TaskForecastDto {
+                        name: t.name,
+                        rate: t.rate,
+                        next_week: t.next_week,
+                        next_month: t.next_month,
+                    }).collect(),
+                }).collect(),
+                open_one_time: forecast.open_one_time,
+                next_week: forecast.next_week,
+                next_month: forecast.next_month,
+                budget: forecast.budget.map(Into::into),
+                warning: forecast.warning,
+            }
+        }
+    }
 }
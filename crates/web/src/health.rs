@@ -0,0 +1,68 @@
+//! Health check for uptime monitors and container orchestrators
+//! Reports whether the database answers and how the last scheduled backup went
+
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use loaa_core::workflows::BackupWorkflow;
+use serde::Serialize;
+
+use crate::server_functions::{check_database, get_backup_config};
+
+#[derive(Serialize)]
+pub struct Health {
+    /// "ok", "degraded" when the last backup failed, or "unavailable"
+    pub status: &'static str,
+    pub database: String,
+    pub backup: BackupHealth,
+}
+
+#[derive(Serialize)]
+pub struct BackupHealth {
+    pub enabled: bool,
+    pub ok: Option<bool>,
+    pub attempted_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+/// Answer 200 while the database is up, even if backups are failing, and 503
+/// when it isn't
+pub async fn health() -> impl IntoResponse {
+    let database = check_database().await;
+
+    let config = get_backup_config();
+    let last = config.dir.as_deref().map(BackupWorkflow::last_status);
+    let backup = match last {
+        None => BackupHealth { enabled: false, ok: None, attempted_at: None, last_success_at: None, error: None },
+        Some(Ok(None)) => BackupHealth { enabled: true, ok: None, attempted_at: None, last_success_at: None, error: None },
+        Some(Ok(Some(status))) => BackupHealth {
+            enabled: true,
+            ok: Some(status.is_ok()),
+            attempted_at: Some(status.attempted_at),
+            last_success_at: status.last_success_at,
+            error: status.error,
+        },
+        Some(Err(e)) => BackupHealth {
+            enabled: true,
+            ok: Some(false),
+            attempted_at: None,
+            last_success_at: None,
+            error: Some(e.to_string()),
+        },
+    };
+
+    let (code, status) = match (&database, backup.ok) {
+        (Err(_), _) => (StatusCode::SERVICE_UNAVAILABLE, "unavailable"),
+        (Ok(()), Some(false)) => (StatusCode::OK, "degraded"),
+        (Ok(()), _) => (StatusCode::OK, "ok"),
+    };
+    let database = match database {
+        Ok(()) => "ok".to_string(),
+        Err(e) => {
+            eprintln!("❌ Health check failed: {}", e);
+            "unavailable".to_string()
+        }
+    };
+
+    (code, Json(Health { status, database, backup }))
+}
@@ -0,0 +1,125 @@
+//! Idempotency keys for the dashboard's server function calls
+//!
+//! Each form submission gets a key (see `Submission`) that `KeyedClient` sends
+//! in the `Idempotency-Key` header. Sending the same submission again, after a
+//! double click or a dropped connection, reuses its key, so the server makes
+//! the change only once (see `once` in `server_functions`).
+
+use leptos::server_fn::client::browser::BrowserClient;
+use leptos::server_fn::client::Client;
+use leptos::server_fn::error::ServerFnError;
+use leptos::server_fn::request::browser::BrowserRequest;
+use leptos::server_fn::response::browser::BrowserResponse;
+use leptos::{store_value, StoredValue};
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+thread_local! {
+    // The key of the submission whose call is being polled
+    static CURRENT_KEY: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+fn current_key() -> Option<String> {
+    CURRENT_KEY.with(|current| current.borrow().clone())
+}
+
+/// Sends server function calls with `fetch`, like the default client, adding
+/// the key of the submission they were made for
+pub struct KeyedClient;
+
+impl<CustErr> Client<CustErr> for KeyedClient {
+    type Request = BrowserRequest;
+    type Response = BrowserResponse;
+
+    fn send(req: Self::Request) -> impl Future<Output = Result<Self::Response, ServerFnError<CustErr>>> + Send {
+        if let Some(key) = current_key() {
+            req.headers().set("Idempotency-Key", &key);
+        }
+        <BrowserClient as Client<CustErr>>::send(req)
+    }
+}
+
+/// A form's submission, which keeps its idempotency key until the server has
+/// accepted it
+#[derive(Clone, Copy)]
+pub struct Submission(StoredValue<Option<String>>);
+
+impl Default for Submission {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Submission {
+    pub fn new() -> Self {
+        Self(store_value(None))
+    }
+
+    /// Start a new submission, e.g. when a field left out of the key's
+    /// arguments changes
+    pub fn reset(self) {
+        self.0.try_set_value(None);
+    }
+
+    /// Make a server function call with this submission's key
+    ///
+    /// A failed call keeps the key for sending the form again; once a call
+    /// succeeds the next submission gets a new one.
+    pub async fn send<T, E>(self, call: impl Future<Output = Result<T, E>>) -> Result<T, E> {
+        let key = self.0.try_get_value().flatten().unwrap_or_else(new_key);
+        self.0.try_set_value(Some(key.clone()));
+        let result = WithKey { key, call: Box::pin(call) }.await;
+        if result.is_ok() {
+            self.0.try_set_value(None);
+        }
+        result
+    }
+}
+
+fn new_key() -> String {
+    leptos::window()
+        .crypto()
+        .expect("Browser without crypto support")
+        .random_uuid()
+}
+
+/// A call that sees `key` as the current key while it's polled; the request
+/// is sent on the first poll
+struct WithKey<F> {
+    key: String,
+    call: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for WithKey<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let previous = CURRENT_KEY.with(|current| current.replace(Some(this.key.clone())));
+        let poll = this.call.as_mut().poll(cx);
+        CURRENT_KEY.with(|current| *current.borrow_mut() = previous);
+        poll
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_key_is_current_only_while_its_call_runs() {
+        let seen = WithKey {
+            key: "k".to_string(),
+            call: Box::pin(async {
+                let before = current_key();
+                tokio::task::yield_now().await;
+                (before, current_key())
+            }),
+        }
+        .await;
+        assert_eq!(seen, (Some("k".to_string()), Some("k".to_string())));
+        assert_eq!(current_key(), None);
+    }
+}
@@ -2,6 +2,7 @@ pub mod app;
 pub mod dto;
 pub mod server_functions;
 pub mod components;
+pub mod idempotency;
 
 #[cfg(feature = "ssr")]
 pub mod oauth;
@@ -12,6 +13,21 @@ pub mod mcp;
 #[cfg(feature = "ssr")]
 pub mod sse;
 
+#[cfg(feature = "ssr")]
+pub mod attachments;
+
+#[cfg(feature = "ssr")]
+pub mod backup;
+
+#[cfg(feature = "ssr")]
+pub mod health;
+
+#[cfg(feature = "ssr")]
+pub mod csv_export;
+
+#[cfg(feature = "ssr")]
+pub mod statements;
+
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
 pub fn hydrate() {
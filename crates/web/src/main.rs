@@ -16,6 +16,11 @@ async fn main() {
         AppState,
     };
     use loaa_web::sse::sse_handler;
+    use loaa_web::attachments::{upload_attachment, get_attachment, get_attachment_thumbnail, run_retention_purge};
+    use loaa_web::backup::download_backup;
+    use loaa_web::health::health;
+    use loaa_web::csv_export::{export_ledger, export_tasks};
+    use loaa_web::statements::download_statement;
     use loaa_core::config::Config;
     use loaa_core::create_event_channel;
     use tower_http::services::ServeDir;
@@ -110,15 +115,73 @@ async fn main() {
         ])
         .allow_credentials(true);
 
+    // Purge photo attachments past their retention period
+    println!(
+        "📷 Attachments stored in {} (kept {} days after completion)",
+        config.attachments.dir.display(),
+        config.attachments.retention_days,
+    );
+    tokio::spawn(run_retention_purge(std::time::Duration::from_secs(60 * 60)));
+
+    // Purge deleted kids and tasks once they can no longer be restored
+    println!(
+        "🗃️  Deleted kids and tasks can be restored for {} days",
+        loaa_web::server_functions::get_archive_config().restore_days,
+    );
+    tokio::spawn(loaa_web::server_functions::run_archive_purge(std::time::Duration::from_secs(60 * 60)));
+    tokio::spawn(loaa_web::server_functions::run_idempotency_purge(std::time::Duration::from_secs(60 * 60)));
+
+    // Write scheduled backups when a backup directory is configured
+    match &config.backup.dir {
+        Some(dir) => {
+            println!(
+                "💾 Backing up to {} every {} hours (keeping {} daily, {} weekly)",
+                dir.display(),
+                config.backup.interval().as_secs() / 3600,
+                config.backup.keep_daily,
+                config.backup.keep_weekly,
+            );
+            tokio::spawn(loaa_web::server_functions::run_scheduled_backups());
+        }
+        None => println!("💾 Scheduled backups are off (set LOAA_BACKUP_DIR to turn them on)"),
+    }
+
+    // Archive each month's statements when a statement directory is configured
+    match &config.statements.dir {
+        Some(dir) => {
+            println!("🧾 Archiving monthly statements to {}", dir.display());
+            tokio::spawn(loaa_web::server_functions::run_statement_archive(std::time::Duration::from_secs(24 * 60 * 60)));
+        }
+        None => println!("🧾 Statement archiving is off (set LOAA_STATEMENT_DIR to turn it on)"),
+    }
+
+    // Photo attachment routes (uploads may be larger than axum's default body limit)
+    let attachment_router = Router::new()
+        .route("/attachments", post(upload_attachment))
+        .route("/attachments/:id", get(get_attachment))
+        .route("/attachments/:id/thumbnail", get(get_attachment_thumbnail))
+        .layer(axum::extract::DefaultBodyLimit::max(config.attachments.max_bytes as usize + 64 * 1024));
+
     // Create SSE router with its own state
     let sse_router = Router::new()
         .route("/events", get(sse_handler))
         .with_state(event_sender);
 
+    // Admin-only backup download, and the health check for monitors
+    let admin_router = Router::new()
+        .route("/admin/backup", get(download_backup))
+        .route("/health", get(health));
+
+    // CSV downloads for spreadsheets, and monthly statements
+    let export_router = Router::new()
+        .route("/export/tasks.csv", get(export_tasks))
+        .route("/export/ledger.csv", get(export_ledger))
+        .route("/statements/:kid_id", get(download_statement));
+
     // Serve static files BEFORE leptos routes so they take precedence
     let app = Router::new()
-        // SSE endpoint for real-time updates (nested under /api)
-        .nest("/api", sse_router)
+        // SSE endpoint for real-time updates, photo attachments, downloads and health (nested under /api)
+        .nest("/api", sse_router.merge(attachment_router).merge(admin_router).merge(export_router))
         // OAuth discovery endpoints (with CORS)
         .route(
             "/.well-known/oauth-authorization-server",
@@ -156,8 +219,28 @@ async fn main() {
     println!("🚀 Listening on http://{}", &addr);
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
     axum::serve(listener, app.into_make_service())
+        .with_graceful_shutdown(shutdown_signal())
         .await
         .unwrap();
+    loaa_web::server_functions::flush_database().await;
+}
+
+/// Ctrl-C, or the SIGTERM `docker stop` sends
+#[cfg(feature = "ssr")]
+async fn shutdown_signal() {
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = terminate => {}
+    }
+    println!("👋 Shutting down");
 }
 
 #[cfg(not(feature = "ssr"))]
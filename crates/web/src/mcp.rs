@@ -48,8 +48,35 @@ pub async fn start_mcp_server(
     eprintln!("  - list_tasks: List all tasks");
     eprintln!("  - update_task: Update an existing task");
     eprintln!("  - delete_task: Delete a task");
+    eprintln!("  - list_archived: List deleted kids and tasks that can be restored");
+    eprintln!("  - restore_archived: Restore a deleted kid or task");
+    eprintln!("  - purge_archived: Permanently remove a deleted kid or task");
+    eprintln!("  - list_task_templates: Browse the chore template library");
+    eprintln!("  - import_task_pack: Create tasks from a chore pack");
     eprintln!("  - complete_task: Mark a task as complete");
     eprintln!("  - get_ledger: Get ledger for a kid");
+    eprintln!("  - get_quality_report: Average quality ratings per kid and task");
+    eprintln!("  - get_earnings_by_category: Earnings per kid broken down by task category");
+    eprintln!("  - get_earnings_report: Earnings per kid by day, week or month");
+    eprintln!("  - get_payout_forecast: Expected payouts for the next week and month");
+    eprintln!("  - get_payout_budgets: What's left of each payout budget");
+    eprintln!("  - set_payout_budget: Set a weekly or monthly payout budget for the household or a kid");
+    eprintln!("  - list_pending_claims: Task claims kids submitted that await approval");
+    eprintln!("  - approve_claim: Approve a claim and pay the kid");
+    eprintln!("  - reject_claim: Reject a claim without paying");
+    eprintln!("  - request_withdrawal: Ask to take money out of a kid's balance");
+    eprintln!("  - list_pending_payouts: Withdrawals waiting to be approved or paid");
+    eprintln!("  - approve_withdrawal: Approve a withdrawal");
+    eprintln!("  - decline_withdrawal: Decline a withdrawal and return the money to the balance");
+    eprintln!("  - mark_withdrawal_paid: Record that a withdrawal was paid, and how");
+    eprintln!("  - get_payout_summary: What's owed and paid per kid");
+    eprintln!("  - transfer_between_kids: Move money from one kid's balance to another's");
+    eprintln!("  - list_pending_transfers: Transfers waiting for approval");
+    eprintln!("  - approve_transfer: Approve a transfer and move the money");
+    eprintln!("  - decline_transfer: Decline a transfer without moving anything");
+    eprintln!("  - create_invite: Create a link for a co-parent to join the household");
+    eprintln!("  - list_invites: List unused household invites");
+    eprintln!("  - revoke_invite: Revoke a household invite");
     eprintln!("  - adjust_balance: Manually adjust a kid's balance");
 
     // Run the HTTP server from the loaa-mcp library
@@ -402,6 +402,20 @@ pub async fn authorize_get(
         return Ok(Redirect::to("/").into_response());
     }
 
+    // Kid logins can't grant MCP access to their parent's data
+    let account_type: Option<String> = session.get("account_type")
+        .await
+        .map_err(|e| (
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Session error: {}", e)
+        ))?;
+    if account_type.as_deref() == Some("kid") {
+        return Err((
+            axum::http::StatusCode::FORBIDDEN,
+            "Parent account required".to_string()
+        ).into());
+    }
+
     // User is authenticated - show consent page
     let user_id = user_id.unwrap();
 
@@ -1,15 +1,26 @@
 use leptos::*;
 use crate::dto::*;
+use crate::idempotency::KeyedClient;
 
 #[cfg(feature = "ssr")]
 use loaa_core::{
-    Database, KidRepository, TaskRepository, LedgerRepository, UserRepository,
-    init_database_with_config, Config, Uuid, verify_password, hash_password
+    Database, KidRepository, TaskRepository, LedgerRepository, Repository, UserRepository, AttachmentRepository,
+    ClaimRepository, HouseholdRepository, InviteRepository, IdempotencyRepository, WithdrawalRepository, TransferRepository, init_database_with_config, Config, AttachmentConfig, ArchiveConfig, BackupConfig, IdempotencyConfig, StatementConfig, AttachmentStore, LocalFsStore, Uuid,
+    verify_password, hash_password
 };
 #[cfg(feature = "ssr")]
 use loaa_core::models::*;
 #[cfg(feature = "ssr")]
-use loaa_core::workflows::TaskCompletionWorkflow;
+use loaa_core::workflows::{
+    ArchiveWorkflow, AttachmentWorkflow, BackupWorkflow, BudgetWorkflow, ClaimWorkflow, ColumnMapping, CsvWorkflow, ForecastWorkflow, HouseholdWorkflow, IdempotencyWorkflow, InviteWorkflow, KidAccountWorkflow,
+    PackImportWorkflow, ReportWorkflow, StatementWorkflow, TaskCompletionWorkflow, TransferWorkflow, WithdrawalWorkflow,
+};
+#[cfg(feature = "ssr")]
+use loaa_core::reports::{ReportPeriod, ReportQuery};
+#[cfg(feature = "ssr")]
+use loaa_core::policy::{Actor, Policy};
+#[cfg(feature = "ssr")]
+use loaa_core::templates::TemplateLibrary;
 #[cfg(feature = "ssr")]
 use std::sync::Arc;
 #[cfg(feature = "ssr")]
@@ -20,25 +31,406 @@ use std::str::FromStr;
 use tower_sessions::Session;
 #[cfg(feature = "ssr")]
 use leptos_axum::extract;
+#[cfg(feature = "ssr")]
+use serde_json::json;
+
+#[cfg(feature = "ssr")]
+static DB: OnceCell<Arc<Database>> = OnceCell::const_new();
 
 // Helper to get database connection
 #[cfg(feature = "ssr")]
 async fn get_db() -> Result<Arc<Database>, ServerFnError> {
-    static DB: OnceCell<Arc<Database>> = OnceCell::const_new();
     DB.get_or_try_init(|| async {
         // Load configuration from environment
         let config = Config::from_env();
         config.validate()
             .map_err(|e| ServerFnError::new(format!("Config validation error: {}", e)))?;
 
-        init_database_with_config(&config.database).await
-            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))
-            .map(Arc::new)
+        let database = init_database_with_config(&config.database).await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+        // Re-key data from before households existed
+        let households = HouseholdWorkflow::new(HouseholdRepository::new(database.client.clone()));
+        match households.migrate_owner_ids().await {
+            Ok(0) => {}
+            Ok(count) => eprintln!("🏠 Migrated data for {} owner(s) into households", count),
+            Err(e) => return Err(ServerFnError::new(format!("Household migration failed: {}", e))),
+        }
+
+        Ok(Arc::new(database))
     })
     .await
     .cloned()
 }
 
+// Helper to get attachment settings (LOAA_ATTACHMENT_*)
+#[cfg(feature = "ssr")]
+pub(crate) fn get_attachment_config() -> &'static AttachmentConfig {
+    static CONFIG: std::sync::OnceLock<AttachmentConfig> = std::sync::OnceLock::new();
+    CONFIG.get_or_init(AttachmentConfig::from_env)
+}
+
+// Helper to get the photo store in LOAA_ATTACHMENT_DIR
+#[cfg(feature = "ssr")]
+fn get_attachment_store() -> Result<Arc<dyn AttachmentStore>, ServerFnError> {
+    static STORE: std::sync::OnceLock<Arc<dyn AttachmentStore>> = std::sync::OnceLock::new();
+    match STORE.get() {
+        Some(store) => Ok(store.clone()),
+        None => {
+            let store: Arc<dyn AttachmentStore> = Arc::new(LocalFsStore::new(&get_attachment_config().dir)
+                .map_err(|e| ServerFnError::new(format!("Attachment store error: {}", e)))?);
+            Ok(STORE.get_or_init(|| store).clone())
+        }
+    }
+}
+
+// Helper to build the attachment workflow on the shared database and store
+#[cfg(feature = "ssr")]
+pub(crate) async fn get_attachment_workflow() -> Result<AttachmentWorkflow, ServerFnError> {
+    let db = get_db().await?;
+    Ok(AttachmentWorkflow::new(
+        AttachmentRepository::new(db.client.clone()),
+        KidRepository::new(db.client.clone()),
+        LedgerRepository::new(db.client.clone()),
+        get_attachment_store()?,
+        get_attachment_config().max_bytes,
+    ))
+}
+
+// Helper to build the backup workflow on the shared database
+#[cfg(feature = "ssr")]
+pub(crate) async fn get_backup_workflow() -> Result<BackupWorkflow, ServerFnError> {
+    let db = get_db().await?;
+    Ok(BackupWorkflow::new(db.client.clone()))
+}
+
+// Helper to build the CSV import/export workflow on the shared database
+#[cfg(feature = "ssr")]
+pub(crate) async fn get_csv_workflow() -> Result<CsvWorkflow, ServerFnError> {
+    let db = get_db().await?;
+    Ok(CsvWorkflow::new(
+        KidRepository::new(db.client.clone()),
+        TaskRepository::new(db.client.clone()),
+        LedgerRepository::new(db.client.clone()),
+    ))
+}
+
+// Helper to build the statement workflow
+#[cfg(feature = "ssr")]
+pub(crate) async fn get_statement_workflow() -> Result<StatementWorkflow, ServerFnError> {
+    let db = get_db().await?;
+    Ok(StatementWorkflow::new(
+        KidRepository::new(db.client.clone()),
+        LedgerRepository::new(db.client.clone()),
+    ))
+}
+
+// Helper to get statement archive settings (LOAA_STATEMENT_DIR)
+#[cfg(feature = "ssr")]
+pub fn get_statement_config() -> &'static StatementConfig {
+    static CONFIG: std::sync::OnceLock<StatementConfig> = std::sync::OnceLock::new();
+    CONFIG.get_or_init(StatementConfig::from_env)
+}
+
+/// Once a day, archive last month's statements to LOAA_STATEMENT_DIR; kids'
+/// statements already there are left alone
+#[cfg(feature = "ssr")]
+pub async fn run_statement_archive(period: std::time::Duration) {
+    let Some(dir) = get_statement_config().dir.as_deref() else { return };
+    let mut ticker = tokio::time::interval(period);
+    loop {
+        ticker.tick().await;
+        let now = chrono::Utc::now();
+        let month = loaa_core::statements::previous_month(now);
+        match get_statement_workflow().await {
+            Ok(workflow) => match workflow.archive_month(dir, month, now).await {
+                Ok(0) => {}
+                Ok(count) => eprintln!("🧾 Archived {} statement(s) for {}", count, month.format("%B %Y")),
+                Err(e) => eprintln!("❌ Statement archive failed: {}", e),
+            },
+            Err(e) => eprintln!("❌ Statement archive failed: {}", e),
+        }
+    }
+}
+
+// Helper to get scheduled backup settings (LOAA_BACKUP_*)
+#[cfg(feature = "ssr")]
+pub fn get_backup_config() -> &'static BackupConfig {
+    static CONFIG: std::sync::OnceLock<BackupConfig> = std::sync::OnceLock::new();
+    CONFIG.get_or_init(BackupConfig::from_env)
+}
+
+/// Periodically write a backup to LOAA_BACKUP_DIR and rotate old ones out
+#[cfg(feature = "ssr")]
+pub async fn run_scheduled_backups() {
+    let config = get_backup_config();
+    let mut ticker = tokio::time::interval(config.interval());
+    loop {
+        ticker.tick().await;
+        match get_backup_workflow().await {
+            Ok(workflow) => match workflow.run_scheduled(config, chrono::Utc::now()).await {
+                Ok(status) => match (&status.file, &status.error) {
+                    (Some(file), None) => eprintln!("💾 Wrote backup {} ({} records)", file, status.records),
+                    (_, error) => eprintln!("❌ Scheduled backup failed: {}", error.as_deref().unwrap_or("unknown error")),
+                },
+                Err(e) => eprintln!("❌ Scheduled backup failed: {}", e),
+            },
+            Err(e) => eprintln!("❌ Scheduled backup failed: {}", e),
+        }
+    }
+}
+
+// Helper to check that the shared database answers
+#[cfg(feature = "ssr")]
+pub(crate) async fn check_database() -> Result<(), ServerFnError> {
+    let db = get_db().await?;
+    db.client.health().await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))
+}
+
+// Helper to get archive settings (LOAA_RESTORE_WINDOW_DAYS)
+#[cfg(feature = "ssr")]
+pub fn get_archive_config() -> &'static ArchiveConfig {
+    static CONFIG: std::sync::OnceLock<ArchiveConfig> = std::sync::OnceLock::new();
+    CONFIG.get_or_init(ArchiveConfig::from_env)
+}
+
+// Helper to build the archive workflow on the shared database and store
+#[cfg(feature = "ssr")]
+async fn get_archive_workflow() -> Result<ArchiveWorkflow, ServerFnError> {
+    let db = get_db().await?;
+    Ok(ArchiveWorkflow::new(
+        KidRepository::new(db.client.clone()),
+        TaskRepository::new(db.client.clone()),
+        AttachmentRepository::new(db.client.clone()),
+        get_attachment_store()?,
+        get_archive_config().restore_window(),
+    ))
+}
+
+/// Periodically purge archived kids and tasks whose restore window has passed
+#[cfg(feature = "ssr")]
+pub async fn run_archive_purge(interval: std::time::Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        match get_archive_workflow().await {
+            Ok(workflow) => match workflow.purge_expired(chrono::Utc::now()).await {
+                Ok(0) => {}
+                Ok(count) => eprintln!("🧹 Purged {} archived kid(s)/task(s)", count),
+                Err(e) => eprintln!("❌ Archive purge failed: {}", e),
+            },
+            Err(e) => eprintln!("❌ Archive purge failed: {}", e),
+        }
+    }
+}
+
+// Helper to build the budget workflow on the shared database
+#[cfg(feature = "ssr")]
+async fn get_budget_workflow() -> Result<BudgetWorkflow, ServerFnError> {
+    let db = get_db().await?;
+    Ok(BudgetWorkflow::new(
+        HouseholdRepository::new(db.client.clone()),
+        KidRepository::new(db.client.clone()),
+        LedgerRepository::new(db.client.clone()),
+    ))
+}
+
+// Helper to build the task completion workflow, which checks payout budgets
+#[cfg(feature = "ssr")]
+async fn get_completion_workflow() -> Result<TaskCompletionWorkflow, ServerFnError> {
+    let db = get_db().await?;
+    Ok(TaskCompletionWorkflow::new(
+        TaskRepository::new(db.client.clone()),
+        KidRepository::new(db.client.clone()),
+        LedgerRepository::new(db.client.clone()),
+    )
+    .with_budgets(get_budget_workflow().await?))
+}
+
+// Helper to build the claim workflow on the shared database
+#[cfg(feature = "ssr")]
+pub(crate) async fn get_claim_workflow() -> Result<ClaimWorkflow, ServerFnError> {
+    let db = get_db().await?;
+    let completion = get_completion_workflow().await?;
+    Ok(ClaimWorkflow::new(
+        ClaimRepository::new(db.client.clone()),
+        KidRepository::new(db.client.clone()),
+        TaskRepository::new(db.client.clone()),
+        AttachmentRepository::new(db.client.clone()),
+        completion,
+    ))
+}
+
+// Helper to build the withdrawal workflow on the shared database
+#[cfg(feature = "ssr")]
+async fn get_withdrawal_workflow() -> Result<WithdrawalWorkflow, ServerFnError> {
+    let db = get_db().await?;
+    Ok(WithdrawalWorkflow::new(
+        WithdrawalRepository::new(db.client.clone()),
+        KidRepository::new(db.client.clone()),
+        LedgerRepository::new(db.client.clone()),
+    ))
+}
+
+// Helper to build the transfer workflow on the shared database
+#[cfg(feature = "ssr")]
+async fn get_transfer_workflow() -> Result<TransferWorkflow, ServerFnError> {
+    let db = get_db().await?;
+    Ok(TransferWorkflow::new(
+        TransferRepository::new(db.client.clone()),
+        KidRepository::new(db.client.clone()),
+        LedgerRepository::new(db.client.clone()),
+    ))
+}
+
+// Helper to build a completion review from optional form fields
+#[cfg(feature = "ssr")]
+fn review_from_params(
+    quality: Option<u8>,
+    percent: Option<u8>,
+    comment: Option<String>,
+) -> Result<Option<CompletionReview>, ServerFnError> {
+    if quality.is_none() && percent.is_none() && comment.is_none() {
+        return Ok(None);
+    }
+    CompletionReview::new(quality, percent.unwrap_or(100), comment)
+        .map(Some)
+        .map_err(|e| ServerFnError::new(format!("Validation error: {}", e)))
+}
+
+// Helper to build the idempotency workflow on the shared database
+#[cfg(feature = "ssr")]
+async fn get_idempotency_workflow() -> Result<IdempotencyWorkflow, ServerFnError> {
+    let db = get_db().await?;
+    Ok(IdempotencyWorkflow::new(
+        IdempotencyRepository::new(db.client.clone()),
+        IdempotencyConfig::from_env().retention(),
+    ))
+}
+
+// Helper to run a server function that changes data at most once per
+// `Idempotency-Key` request header; a retry with the same key and arguments
+// gets the first response back. The arguments are stored as a plain SHA-256
+// hash, so PINs and passwords must be left out of them.
+#[cfg(feature = "ssr")]
+async fn once<T, F, Fut>(operation: &str, arguments: serde_json::Value, f: F) -> Result<T, ServerFnError>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Result<T, ServerFnError>>,
+{
+    let headers = extract::<axum::http::HeaderMap>().await
+        .map_err(|e| ServerFnError::new(format!("Failed to read headers: {}", e)))?;
+    let Some(key) = headers.get("idempotency-key").and_then(|v| v.to_str().ok()).map(str::to_string) else {
+        return f().await;
+    };
+
+    let session = extract::<Session>().await
+        .map_err(|e| ServerFnError::new(format!("Failed to extract session: {}", e)))?;
+    let user_id: Option<String> = session.get("user_id")
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get session: {}", e)))?;
+    let user_id = user_id.ok_or_else(|| ServerFnError::new("Not authenticated".to_string()))?;
+
+    // Scoped apart from MCP calls by the same user, whose results differ
+    get_idempotency_workflow().await?
+        .run(&format!("web:{}", user_id), Some(&key), operation, &arguments, f).await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+}
+
+/// Save outstanding changes before the server exits (see `Database::flush`)
+#[cfg(feature = "ssr")]
+pub async fn flush_database() {
+    if let Some(database) = DB.get() {
+        if let Err(e) = database.flush().await {
+            eprintln!("❌ Failed to save database changes: {}", e);
+        }
+    }
+}
+
+/// Periodically delete idempotency keys past their retention window
+#[cfg(feature = "ssr")]
+pub async fn run_idempotency_purge(interval: std::time::Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        match get_idempotency_workflow().await {
+            Ok(workflow) => {
+                if let Err(e) = workflow.purge_expired(chrono::Utc::now()).await {
+                    eprintln!("❌ Idempotency key purge failed: {}", e);
+                }
+            }
+            Err(e) => eprintln!("❌ Idempotency key purge failed: {}", e),
+        }
+    }
+}
+
+// Helper to describe a claim with its kid and task names
+#[cfg(feature = "ssr")]
+fn claim_dto(claim: TaskClaim, kids: &[Kid], tasks: &[Task]) -> TaskClaimDto {
+    let kid_name = kids.iter().find(|k| k.id == claim.kid_id)
+        .map(|k| k.name.clone())
+        .unwrap_or_default();
+    let task = tasks.iter().find(|t| t.id == claim.task_id);
+    TaskClaimDto {
+        id: claim.id.to_string(),
+        kid_id: claim.kid_id.to_string(),
+        kid_name,
+        task_id: claim.task_id.to_string(),
+        task_name: task.map(|t| t.name.clone()).unwrap_or_else(|| "(deleted task)".to_string()),
+        task_value: task.map(|t| t.value).unwrap_or_default(),
+        status: claim.status.into(),
+        note: claim.note,
+        attachment_id: claim.attachment_id.map(|id| id.to_string()),
+        created_at: claim.created_at,
+    }
+}
+
+// Helper to describe a withdrawal with its kid's name
+#[cfg(feature = "ssr")]
+fn withdrawal_dto(withdrawal: Withdrawal, kids: &[Kid]) -> WithdrawalDto {
+    let kid_name = kids.iter().find(|k| k.id == withdrawal.kid_id)
+        .map(|k| k.name.clone())
+        .unwrap_or_default();
+    WithdrawalDto {
+        id: withdrawal.id.to_string(),
+        kid_id: withdrawal.kid_id.to_string(),
+        kid_name,
+        amount: withdrawal.amount,
+        note: withdrawal.note,
+        status: withdrawal.status.into(),
+        method: withdrawal.method.map(|m| m.label().to_string()),
+        requested_at: withdrawal.requested_at,
+    }
+}
+
+// Helper to describe a transfer with both kids' names
+#[cfg(feature = "ssr")]
+fn transfer_dto(transfer: Transfer, kids: &[Kid]) -> TransferDto {
+    let name = |id: Uuid| kids.iter().find(|k| k.id == id)
+        .map(|k| k.name.clone())
+        .unwrap_or_default();
+    TransferDto {
+        id: transfer.id.to_string(),
+        from_kid_id: transfer.from_kid_id.to_string(),
+        from_name: name(transfer.from_kid_id),
+        to_kid_id: transfer.to_kid_id.to_string(),
+        to_name: name(transfer.to_kid_id),
+        amount: transfer.amount,
+        note: transfer.note,
+        status: transfer.status.into(),
+        created_at: transfer.created_at,
+    }
+}
+
+// Helper to get the task template library (built-in packs plus LOAA_TEMPLATE_DIR)
+#[cfg(feature = "ssr")]
+fn get_template_library() -> &'static TemplateLibrary {
+    static LIBRARY: std::sync::OnceLock<TemplateLibrary> = std::sync::OnceLock::new();
+    LIBRARY.get_or_init(TemplateLibrary::from_env)
+}
+
 #[server]
 pub async fn get_kids() -> Result<Vec<KidDto>, ServerFnError> {
     let owner_id = get_owner_id().await?;
@@ -49,16 +441,18 @@ pub async fn get_kids() -> Result<Vec<KidDto>, ServerFnError> {
     Ok(kids.into_iter().map(Into::into).collect())
 }
 
-#[server]
+#[server(client = KeyedClient)]
 pub async fn create_kid(name: String) -> Result<KidDto, ServerFnError> {
-    let owner_id = get_owner_id().await?;
-    let kid = Kid::new(name, owner_id)
-        .map_err(|e| ServerFnError::new(format!("Validation error: {}", e)))?;
-    let db = get_db().await?;
-    let kid_repo = KidRepository::new(db.client.clone());
-    let created = kid_repo.create(kid).await
-        .map_err(|e| ServerFnError::new(format!("Failed to create kid: {}", e)))?;
-    Ok(created.into())
+    once("create_kid", json!([name]), || async move {
+        let owner_id = get_editor_owner_id().await?;
+        let kid = Kid::new(name, owner_id)
+            .map_err(|e| ServerFnError::new(format!("Validation error: {}", e)))?;
+        let db = get_db().await?;
+        let kid_repo = KidRepository::new(db.client.clone());
+        let created = kid_repo.create(kid).await
+            .map_err(|e| ServerFnError::new(format!("Failed to create kid: {}", e)))?;
+        Ok(created.into())
+    }).await
 }
 
 #[server]
@@ -71,59 +465,229 @@ pub async fn get_tasks() -> Result<Vec<TaskDto>, ServerFnError> {
     Ok(tasks.into_iter().map(Into::into).collect())
 }
 
-#[server]
+#[server(client = KeyedClient)]
 pub async fn create_task(
     name: String,
     description: String,
     value: rust_decimal::Decimal,
     cadence: CadenceDto,
 ) -> Result<TaskDto, ServerFnError> {
-    let owner_id = get_owner_id().await?;
-    let task = Task::new(name, description, value, cadence.into(), owner_id)
-        .map_err(|e| ServerFnError::new(format!("Validation error: {}", e)))?;
-    let db = get_db().await?;
-    let task_repo = TaskRepository::new(db.client.clone());
-    let created = task_repo.create(task).await
-        .map_err(|e| ServerFnError::new(format!("Failed to create task: {}", e)))?;
-    Ok(created.into())
+    once("create_task", json!([name, description, value, cadence]), || async move {
+        let owner_id = get_editor_owner_id().await?;
+        let task = Task::new(name, description, value, cadence.into(), owner_id)
+            .map_err(|e| ServerFnError::new(format!("Validation error: {}", e)))?;
+        let db = get_db().await?;
+        let task_repo = TaskRepository::new(db.client.clone());
+        let created = task_repo.create(task).await
+            .map_err(|e| ServerFnError::new(format!("Failed to create task: {}", e)))?;
+        Ok(created.into())
+    }).await
 }
 
 #[server]
-pub async fn complete_task(kid_id: UuidDto, task_id: UuidDto) -> Result<(), ServerFnError> {
-    let db = get_db().await?;
+pub async fn get_task_packs() -> Result<Vec<TaskPackDto>, ServerFnError> {
+    get_owner_id().await?;
+    Ok(get_template_library().packs().iter().cloned().map(Into::into).collect())
+}
 
-    let kid_uuid = Uuid::from_str(&kid_id)
-        .map_err(|e| ServerFnError::new(format!("Invalid kid ID: {}", e)))?;
-    let task_uuid = Uuid::from_str(&task_id)
-        .map_err(|e| ServerFnError::new(format!("Invalid task ID: {}", e)))?;
+#[server(client = KeyedClient)]
+pub async fn import_task_pack(
+    pack_id: String,
+    value_scale: rust_decimal::Decimal,
+) -> Result<PackImportResultDto, ServerFnError> {
+    once("import_task_pack", json!([pack_id, value_scale]), || async move {
+        let actor = get_actor().await?;
+        let pack = get_template_library().get(&pack_id)
+            .ok_or_else(|| ServerFnError::new(format!("Task pack not found: {}", pack_id)))?;
+        let db = get_db().await?;
+        let workflow = PackImportWorkflow::new(TaskRepository::new(db.client.clone()));
+        let result = workflow.import_pack(pack, &actor, value_scale).await
+            .map_err(|e| ServerFnError::new(format!("Failed to import task pack: {}", e)))?;
+        Ok(result.into())
+    }).await
+}
 
-    // Use the TaskCompletionWorkflow to handle task completion
-    // This ensures recurring tasks are properly reset
-    let task_repo = TaskRepository::new(db.client.clone());
-    let kid_repo = KidRepository::new(db.client.clone());
-    let ledger_repo = LedgerRepository::new(db.client.clone());
+/// Import tasks from CSV text; `columns` maps fields to the file's column
+/// names as `field=Column` pairs
+#[server(client = KeyedClient)]
+pub async fn import_tasks_csv(
+    csv: String,
+    columns: String,
+    dry_run: bool,
+) -> Result<CsvImportReportDto, ServerFnError> {
+    once("import_tasks_csv", json!([csv, columns, dry_run]), || async move {
+        let actor = get_actor().await?;
+        let mapping = ColumnMapping::parse(&columns)
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        let report = get_csv_workflow().await?
+            .import_tasks(&actor, &csv, &mapping, dry_run).await
+            .map_err(|e| ServerFnError::new(format!("Failed to import tasks: {}", e)))?;
+        Ok(report.into())
+    }).await
+}
 
-    let workflow = TaskCompletionWorkflow::new(task_repo, kid_repo, ledger_repo);
-    workflow.complete_task(task_uuid, kid_uuid).await
-        .map_err(|e| ServerFnError::new(format!("Failed to complete task: {}", e)))?;
+/// Import past ledger entries from CSV text; `columns` maps fields to the
+/// file's column names as `field=Column` pairs
+#[server(client = KeyedClient)]
+pub async fn import_ledger_csv(
+    csv: String,
+    columns: String,
+    dry_run: bool,
+) -> Result<CsvImportReportDto, ServerFnError> {
+    once("import_ledger_csv", json!([csv, columns, dry_run]), || async move {
+        let actor = get_actor().await?;
+        let mapping = ColumnMapping::parse(&columns)
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        let report = get_csv_workflow().await?
+            .import_ledger(&actor, &csv, &mapping, dry_run, chrono::Utc::now()).await
+            .map_err(|e| ServerFnError::new(format!("Failed to import ledger: {}", e)))?;
+        Ok(report.into())
+    }).await
+}
 
-    Ok(())
+#[server(client = KeyedClient)]
+pub async fn complete_task(
+    kid_id: UuidDto,
+    task_id: UuidDto,
+    quality: Option<u8>,
+    percent: Option<u8>,
+    comment: Option<String>,
+    attachment_id: Option<UuidDto>,
+) -> Result<(), ServerFnError> {
+    once("complete_task", json!([kid_id, task_id, quality, percent, comment, attachment_id]), || async move {
+        let actor = get_actor().await?;
+
+        let kid_uuid = Uuid::from_str(&kid_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid kid ID: {}", e)))?;
+        let task_uuid = Uuid::from_str(&task_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid task ID: {}", e)))?;
+
+        let review = review_from_params(quality, percent, comment)?;
+
+        // The workflow resets recurring tasks and checks payout budgets
+        let workflow = get_completion_workflow().await?;
+        let entry = workflow.complete_task_as(&actor, task_uuid, kid_uuid, review).await
+            .map_err(|e| ServerFnError::new(format!("Failed to complete task: {}", e)))?;
+
+        if let Some(attachment_id) = attachment_id {
+            let attachment_uuid = Uuid::from_str(&attachment_id)
+                .map_err(|e| ServerFnError::new(format!("Invalid attachment ID: {}", e)))?;
+            get_attachment_workflow().await?
+                .link_to_completion(&actor, attachment_uuid, entry.id).await
+                .map_err(|e| ServerFnError::new(format!("Failed to attach photo: {}", e)))?;
+        }
+
+        Ok(())
+    }).await
 }
 
 #[server]
 pub async fn get_ledger(kid_id: UuidDto) -> Result<LedgerDto, ServerFnError> {
+    let actor = get_actor().await?;
     let db = get_db().await?;
     let kid_uuid = Uuid::from_str(&kid_id)
         .map_err(|e| ServerFnError::new(format!("Invalid kid ID: {}", e)))?;
-    let ledger_repo = LedgerRepository::new(db.client.clone());
-    let ledger = ledger_repo.get_ledger(kid_uuid).await
+    let ledger = get_policy().await?
+        .ledger(&actor, kid_uuid).await
         .map_err(|e| ServerFnError::new(format!("Failed to get ledger: {}", e)))?;
-    Ok(ledger.into())
+
+    let attachment_repo = AttachmentRepository::new(db.client.clone());
+    let attachments = attachment_repo.list_by_kid(kid_uuid).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list attachments: {}", e)))?;
+
+    let mut ledger: LedgerDto = ledger.into();
+    for entry in ledger.entries.iter_mut() {
+        entry.attachment_ids = attachments.iter()
+            .filter(|a| a.ledger_entry_id.map(|id| id.to_string()).as_deref() == Some(entry.id.as_str()))
+            .map(|a| a.id.to_string())
+            .collect();
+    }
+    Ok(ledger)
+}
+
+#[server(client = KeyedClient)]
+pub async fn archive_kid(kid_id: UuidDto) -> Result<(), ServerFnError> {
+    once("archive_kid", json!([kid_id]), || async move {
+        let actor = get_actor().await?;
+        let kid_uuid = Uuid::from_str(&kid_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid kid ID: {}", e)))?;
+        get_archive_workflow().await?
+            .archive_kid(&actor, kid_uuid, chrono::Utc::now()).await
+            .map_err(|e| ServerFnError::new(format!("Failed to archive kid: {}", e)))?;
+        Ok(())
+    }).await
+}
+
+#[server(client = KeyedClient)]
+pub async fn archive_task(task_id: UuidDto) -> Result<(), ServerFnError> {
+    once("archive_task", json!([task_id]), || async move {
+        let actor = get_actor().await?;
+        let task_uuid = Uuid::from_str(&task_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid task ID: {}", e)))?;
+        get_archive_workflow().await?
+            .archive_task(&actor, task_uuid, chrono::Utc::now()).await
+            .map_err(|e| ServerFnError::new(format!("Failed to archive task: {}", e)))?;
+        Ok(())
+    }).await
+}
+
+#[server]
+pub async fn get_archived() -> Result<Vec<ArchivedItemDto>, ServerFnError> {
+    let actor = get_actor().await?;
+    let (kids, tasks) = get_archive_workflow().await?
+        .list_archived(&actor).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list archived items: {}", e)))?;
+
+    let window = get_archive_config().restore_window();
+    let item = |kind, id: Uuid, name: String, archived_at: chrono::DateTime<chrono::Utc>| ArchivedItemDto {
+        kind,
+        id: id.to_string(),
+        name,
+        archived_at,
+        restorable_until: archived_at + window,
+    };
+    Ok(kids.into_iter()
+        .filter_map(|k| k.archived_at.map(|at| item(ArchivedKindDto::Kid, k.id, k.name, at)))
+        .chain(tasks.into_iter()
+            .filter_map(|t| t.archived_at.map(|at| item(ArchivedKindDto::Task, t.id, t.name, at))))
+        .collect())
+}
+
+#[server(client = KeyedClient)]
+pub async fn restore_archived(kind: ArchivedKindDto, id: UuidDto) -> Result<(), ServerFnError> {
+    once("restore_archived", json!([kind, id]), || async move {
+        let actor = get_actor().await?;
+        let uuid = Uuid::from_str(&id)
+            .map_err(|e| ServerFnError::new(format!("Invalid ID: {}", e)))?;
+        let workflow = get_archive_workflow().await?;
+        let now = chrono::Utc::now();
+        let result = match kind {
+            ArchivedKindDto::Kid => workflow.restore_kid(&actor, uuid, now).await.map(|_| ()),
+            ArchivedKindDto::Task => workflow.restore_task(&actor, uuid, now).await.map(|_| ()),
+        };
+        result.map_err(|e| ServerFnError::new(format!("Failed to restore: {}", e)))
+    }).await
+}
+
+#[server(client = KeyedClient)]
+pub async fn purge_archived(kind: ArchivedKindDto, id: UuidDto) -> Result<(), ServerFnError> {
+    once("purge_archived", json!([kind, id]), || async move {
+        let actor = get_actor().await?;
+        let uuid = Uuid::from_str(&id)
+            .map_err(|e| ServerFnError::new(format!("Invalid ID: {}", e)))?;
+        let workflow = get_archive_workflow().await?;
+        let result = match kind {
+            ArchivedKindDto::Kid => workflow.purge_kid(&actor, uuid).await,
+            ArchivedKindDto::Task => workflow.purge_task(&actor, uuid).await,
+        };
+        result.map_err(|e| ServerFnError::new(format!("Failed to delete: {}", e)))
+    }).await
 }
 
 #[server]
 pub async fn get_dashboard_data() -> Result<DashboardDataDto, ServerFnError> {
-    let owner_id = get_owner_id().await?;
+    let actor = get_actor().await?;
+    let owner_id = actor.household_id().unwrap_or_default().to_string();
     let db = get_db().await?;
     let kid_repo = KidRepository::new(db.client.clone());
     let task_repo = TaskRepository::new(db.client.clone());
@@ -141,21 +705,92 @@ pub async fn get_dashboard_data() -> Result<DashboardDataDto, ServerFnError> {
             .map_err(|e| ServerFnError::new(format!("Failed to get ledger: {}", e)))?;
 
         let recent_entry = ledger.entries.last().cloned().map(Into::into);
+        let average_quality = ledger.average_quality().map(|stat| stat.average);
+        let earnings_by_category = ledger.earnings_by_category(&tasks)
+            .into_iter()
+            .map(|(category, amount)| CategoryEarningsDto {
+                category: category.map(|c| c.as_str()).unwrap_or("uncategorized").to_string(),
+                amount,
+            })
+            .collect();
 
         kid_summaries.push(KidSummaryDto {
             kid: kid.clone().into(),
             balance: ledger.balance,
             recent_entry,
+            average_quality,
+            earnings_by_category,
         });
     }
 
+    let budgets = get_budget_workflow().await?
+        .statuses(&actor, chrono::Utc::now().date_naive()).await
+        .map_err(|e| ServerFnError::new(format!("Failed to check budgets: {}", e)))?;
+
     Ok(DashboardDataDto {
         kid_summaries,
         total_kids: kids.len(),
         active_tasks: tasks.len(),
+        budgets: budgets.into_iter().map(Into::into).collect(),
     })
 }
 
+/// Earnings per day, week or month for one kid or all of them; without
+/// dates, the most recent periods up to today
+#[server]
+pub async fn get_report(
+    period: String,
+    kid_id: Option<UuidDto>,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<EarningsReportDto, ServerFnError> {
+    let actor = get_actor().await?;
+    let period = ReportPeriod::parse(&period)
+        .ok_or_else(|| ServerFnError::new("Period should be day, week or month".to_string()))?;
+    let kid_id = kid_id
+        .filter(|id| !id.is_empty())
+        .map(|id| Uuid::from_str(&id).map_err(|e| ServerFnError::new(format!("Invalid kid ID: {}", e))))
+        .transpose()?;
+    let day = |field: &str, day: Option<String>| {
+        day.filter(|day| !day.trim().is_empty())
+            .map(|day| chrono::NaiveDate::parse_from_str(day.trim(), "%Y-%m-%d")
+                .map_err(|e| ServerFnError::new(format!("Invalid {} date: {}", field, e))))
+            .transpose()
+    };
+    let to = day("to", to)?.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let query = match day("from", from)? {
+        Some(from) => ReportQuery::new(kid_id, period, from, to),
+        None => ReportQuery::recent(kid_id, period, period.default_count(), to),
+    }
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let db = get_db().await?;
+    let report = ReportWorkflow::new(
+        KidRepository::new(db.client.clone()),
+        TaskRepository::new(db.client.clone()),
+        LedgerRepository::new(db.client.clone()),
+    )
+    .earnings(&actor, &query).await
+    .map_err(|e| ServerFnError::new(format!("Failed to build report: {}", e)))?;
+    Ok(report.into())
+}
+
+/// Projected payouts per kid for the next week and month
+#[server]
+pub async fn get_forecast() -> Result<ForecastDto, ServerFnError> {
+    let actor = get_actor().await?;
+    let db = get_db().await?;
+    let forecast = ForecastWorkflow::new(
+        HouseholdRepository::new(db.client.clone()),
+        KidRepository::new(db.client.clone()),
+        TaskRepository::new(db.client.clone()),
+        LedgerRepository::new(db.client.clone()),
+    )
+    .forecast(&actor, chrono::Utc::now().date_naive()).await
+    .map_err(|e| ServerFnError::new(format!("Failed to build forecast: {}", e)))?;
+    Ok(forecast.into())
+}
+
 #[server]
 pub async fn get_recent_activity(limit: usize) -> Result<Vec<LedgerEntryDto>, ServerFnError> {
     let owner_id = get_owner_id().await?;
@@ -219,6 +854,11 @@ pub async fn login(username: String, password: String) -> Result<bool, ServerFnE
         Err(_) => return Ok(false), // User not found
     };
 
+    // Kid accounts log in with a PIN through kid_login, which enforces the lockout
+    if user.is_kid() {
+        return Ok(false);
+    }
+
     // Verify password
     let is_valid = verify_password(&password, &user.password_hash)
         .map_err(|e| ServerFnError::new(format!("Password verification error: {}", e)))?;
@@ -237,6 +877,7 @@ pub async fn login(username: String, password: String) -> Result<bool, ServerFnE
         let account_type_str = match user.account_type {
             loaa_core::models::AccountType::Admin => "admin",
             loaa_core::models::AccountType::User => "user",
+            loaa_core::models::AccountType::Kid => "kid",
         };
         session.insert("account_type", account_type_str.to_string())
             .await
@@ -333,6 +974,7 @@ pub async fn get_account_type() -> Result<AccountTypeDto, ServerFnError> {
 
     match account_type.as_deref() {
         Some("admin") => Ok(AccountTypeDto::Admin),
+        Some("kid") => Ok(AccountTypeDto::Kid),
         _ => Ok(AccountTypeDto::User),
     }
 }
@@ -353,12 +995,20 @@ async fn require_admin() -> Result<(), ServerFnError> {
     Ok(())
 }
 
-// Helper to get the current owner_id from session
+// Helper to get the logged-in parent's user_id from session
+// Kid sessions are rejected, so every parent-only server function goes through here
 #[cfg(feature = "ssr")]
-async fn get_owner_id() -> Result<String, ServerFnError> {
+async fn get_user_id() -> Result<String, ServerFnError> {
     let session = extract::<Session>().await
         .map_err(|e| ServerFnError::new(format!("Failed to extract session: {}", e)))?;
 
+    let account_type: Option<String> = session.get("account_type")
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get session: {}", e)))?;
+    if account_type.as_deref() == Some("kid") {
+        return Err(ServerFnError::new("Parent access required".to_string()));
+    }
+
     let user_id: Option<String> = session.get("user_id")
         .await
         .map_err(|e| ServerFnError::new(format!("Failed to get session: {}", e)))?;
@@ -366,6 +1016,641 @@ async fn get_owner_id() -> Result<String, ServerFnError> {
     user_id.ok_or_else(|| ServerFnError::new("Not authenticated".to_string()))
 }
 
+// Helper to get the household workflow on the shared database
+#[cfg(feature = "ssr")]
+async fn get_household_workflow() -> Result<HouseholdWorkflow, ServerFnError> {
+    let db = get_db().await?;
+    Ok(HouseholdWorkflow::new(HouseholdRepository::new(db.client.clone())))
+}
+
+// Helper to resolve a user to their household and role
+#[cfg(feature = "ssr")]
+pub(crate) async fn get_membership(user_id: &str) -> Result<(Household, HouseholdRole), ServerFnError> {
+    get_household_workflow().await?
+        .membership(user_id).await
+        .map_err(|e| ServerFnError::new(format!("Failed to resolve household: {}", e)))
+}
+
+// Helper to get the current household's ID, which is what owner_id fields hold
+#[cfg(feature = "ssr")]
+async fn get_owner_id() -> Result<String, ServerFnError> {
+    let user_id = get_user_id().await?;
+    let (household, _) = get_membership(&user_id).await?;
+    Ok(household.owner_id())
+}
+
+// Like get_owner_id, but for changes, which viewers can't make
+#[cfg(feature = "ssr")]
+async fn get_editor_owner_id() -> Result<String, ServerFnError> {
+    let user_id = get_user_id().await?;
+    let (household, role) = get_membership(&user_id).await?;
+    if !role.can_edit() {
+        return Err(ServerFnError::new("Viewers can't make changes".to_string()));
+    }
+    Ok(household.owner_id())
+}
+
+// Helper to build the access policy on the shared database
+#[cfg(feature = "ssr")]
+async fn get_policy() -> Result<Policy, ServerFnError> {
+    let db = get_db().await?;
+    Ok(Policy::new(
+        KidRepository::new(db.client.clone()),
+        TaskRepository::new(db.client.clone()),
+        LedgerRepository::new(db.client.clone()),
+        HouseholdWorkflow::new(HouseholdRepository::new(db.client.clone())),
+    ))
+}
+
+// Helper to get the logged-in parent as a policy actor, for server functions
+// that take record IDs from the client
+#[cfg(feature = "ssr")]
+async fn get_actor() -> Result<Actor, ServerFnError> {
+    let user_id = get_user_id().await?;
+    get_policy().await?
+        .actor_for_user(&user_id).await
+        .map_err(|e| ServerFnError::new(format!("Failed to resolve household: {}", e)))
+}
+
+// Helper to describe a household for the current user
+#[cfg(feature = "ssr")]
+async fn household_dto(household: Household, user_id: &str) -> Result<HouseholdDto, ServerFnError> {
+    let db = get_db().await?;
+    let users = UserRepository::new(db.client.clone()).list().await
+        .map_err(|e| ServerFnError::new(format!("Failed to list users: {}", e)))?;
+
+    let members = household.members.iter().map(|member| {
+        let username = users.iter()
+            .find(|u| u.id.to_string() == member.user_id)
+            .map(|u| u.username.clone())
+            .unwrap_or_else(|| member.user_id.clone());
+        HouseholdMemberDto {
+            user_id: member.user_id.clone(),
+            username,
+            role: member.role.into(),
+            is_you: member.user_id == user_id,
+        }
+    }).collect();
+
+    Ok(HouseholdDto {
+        id: household.owner_id(),
+        name: household.name.clone(),
+        your_role: household.role_of(user_id).map(Into::into).unwrap_or(HouseholdRoleDto::Viewer),
+        members,
+        budget: household.budget.map(Into::into),
+    })
+}
+
+#[server]
+pub async fn get_household() -> Result<HouseholdDto, ServerFnError> {
+    let user_id = get_user_id().await?;
+    let (household, _) = get_membership(&user_id).await?;
+    household_dto(household, &user_id).await
+}
+
+#[server(client = KeyedClient)]
+pub async fn rename_household(name: String) -> Result<HouseholdDto, ServerFnError> {
+    once("rename_household", json!([name]), || async move {
+        let user_id = get_user_id().await?;
+        let household = get_household_workflow().await?
+            .rename(&user_id, name).await
+            .map_err(|e| ServerFnError::new(format!("Failed to rename household: {}", e)))?;
+        household_dto(household, &user_id).await
+    }).await
+}
+
+/// Set the weekly or monthly payout budget of the household, or of one kid
+/// when a kid ID is given; no amount clears it
+#[server(client = KeyedClient)]
+pub async fn set_payout_budget(
+    kid_id: Option<UuidDto>,
+    amount: Option<String>,
+    period: String,
+    enforcement: String,
+) -> Result<Vec<BudgetStatusDto>, ServerFnError> {
+    once("set_payout_budget", json!([kid_id, amount, period, enforcement]), || async move {
+        let user_id = get_user_id().await?;
+        let budget = match amount.as_deref().map(str::trim).filter(|a| !a.is_empty()) {
+            Some(amount) => {
+                let amount = rust_decimal::Decimal::from_str(amount)
+                    .map_err(|e| ServerFnError::new(format!("Invalid amount: {}", e)))?;
+                let period = BudgetPeriod::parse(&period)
+                    .ok_or_else(|| ServerFnError::new("Period should be week or month".to_string()))?;
+                let enforcement = BudgetEnforcement::parse(&enforcement)
+                    .ok_or_else(|| ServerFnError::new("Enforcement should be warn or block".to_string()))?;
+                Some(PayoutBudget::new(amount, period).map_err(|e| ServerFnError::new(e.to_string()))?
+                    .with_enforcement(enforcement))
+            }
+            None => None,
+        };
+
+        let actor = get_actor().await?;
+        let households = get_household_workflow().await?;
+        match kid_id.filter(|id| !id.is_empty()) {
+            Some(kid_id) => {
+                let kid_uuid = Uuid::from_str(&kid_id)
+                    .map_err(|e| ServerFnError::new(format!("Invalid kid ID: {}", e)))?;
+                let kid = get_policy().await?.kid(&actor, kid_uuid).await
+                    .map_err(|e| ServerFnError::new(format!("Failed to find kid: {}", e)))?;
+                households.set_kid_budget(&user_id, &kid, budget).await
+            }
+            None => households.set_budget(&user_id, budget).await,
+        }
+        .map_err(|e| ServerFnError::new(format!("Failed to set budget: {}", e)))?;
+
+        let statuses = get_budget_workflow().await?
+            .statuses(&actor, chrono::Utc::now().date_naive()).await
+            .map_err(|e| ServerFnError::new(format!("Failed to check budgets: {}", e)))?;
+        Ok(statuses.into_iter().map(Into::into).collect())
+    }).await
+}
+
+#[server(client = KeyedClient)]
+pub async fn set_household_role(member_user_id: String, role: HouseholdRoleDto) -> Result<HouseholdDto, ServerFnError> {
+    once("set_household_role", json!([member_user_id, role]), || async move {
+        let user_id = get_user_id().await?;
+        let household = get_household_workflow().await?
+            .set_role(&user_id, &member_user_id, role.into()).await
+            .map_err(|e| ServerFnError::new(format!("Failed to change role: {}", e)))?;
+        household_dto(household, &user_id).await
+    }).await
+}
+
+// Helper to build the invite workflow; codes are signed with the JWT secret
+// so invites created through MCP work on the web too
+#[cfg(feature = "ssr")]
+async fn get_invite_workflow() -> Result<InviteWorkflow, ServerFnError> {
+    let db = get_db().await?;
+    Ok(InviteWorkflow::new(
+        InviteRepository::new(db.client.clone()),
+        UserRepository::new(db.client.clone()),
+        HouseholdWorkflow::new(HouseholdRepository::new(db.client.clone())),
+        Config::from_env().server.jwt_secret,
+    ))
+}
+
+#[server(client = KeyedClient)]
+pub async fn create_invite(role: HouseholdRoleDto, ttl_days: Option<u32>) -> Result<InviteDto, ServerFnError> {
+    once("create_invite", json!([role, ttl_days]), || async move {
+        let user_id = get_user_id().await?;
+        let ttl = match ttl_days {
+            Some(days) => chrono::Duration::days(days.into()),
+            None => default_invite_ttl(),
+        };
+
+        let (invite, code) = get_invite_workflow().await?
+            .create(&user_id, role.into(), ttl).await
+            .map_err(|e| ServerFnError::new(format!("Failed to create invite: {}", e)))?;
+
+        let base_url = std::env::var("LOAA_BASE_URL")
+            .unwrap_or_else(|_| "http://127.0.0.1:3000".to_string());
+        let mut dto = InviteDto::from(invite);
+        dto.link = Some(format!("{}/?invite={}", base_url.trim_end_matches('/'), code));
+        Ok(dto)
+    }).await
+}
+
+#[server]
+pub async fn list_invites() -> Result<Vec<InviteDto>, ServerFnError> {
+    let user_id = get_user_id().await?;
+    let invites = get_invite_workflow().await?
+        .list_pending(&user_id, chrono::Utc::now()).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list invites: {}", e)))?;
+    Ok(invites.into_iter().map(Into::into).collect())
+}
+
+#[server(client = KeyedClient)]
+pub async fn revoke_invite(invite_id: UuidDto) -> Result<(), ServerFnError> {
+    once("revoke_invite", json!([invite_id]), || async move {
+        let user_id = get_user_id().await?;
+        let invite_uuid = Uuid::from_str(&invite_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid invite ID: {}", e)))?;
+        get_invite_workflow().await?
+            .revoke(&user_id, invite_uuid).await
+            .map_err(|e| ServerFnError::new(format!("Failed to revoke invite: {}", e)))?;
+        Ok(())
+    }).await
+}
+
+// Public: shown on the registration form before the invite is used
+#[server]
+pub async fn check_invite(code: String) -> Result<InviteInfoDto, ServerFnError> {
+    let invite = get_invite_workflow().await?
+        .check(&code, chrono::Utc::now()).await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let db = get_db().await?;
+    let household = HouseholdRepository::new(db.client.clone()).get(invite.household_id).await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(InviteInfoDto {
+        household_name: household.name,
+        role: invite.role.into(),
+    })
+}
+
+// Public: creates the account, joins the household and logs in
+#[server]
+pub async fn register_with_invite(code: String, username: String, password: String) -> Result<(), ServerFnError> {
+    let (user, household) = get_invite_workflow().await?
+        .register(&code, username, &password, chrono::Utc::now()).await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let session = extract::<Session>().await
+        .map_err(|e| ServerFnError::new(format!("Failed to extract session: {}", e)))?;
+    session.insert("user_id", user.id.to_string())
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to set session: {}", e)))?;
+    session.insert("account_type", "user".to_string())
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to set session: {}", e)))?;
+
+    eprintln!("✅ {} joined household {} with an invite", user.username, household.id);
+    Ok(())
+}
+
+#[server(client = KeyedClient)]
+pub async fn remove_household_member(member_user_id: String) -> Result<(), ServerFnError> {
+    once("remove_household_member", json!([member_user_id]), || async move {
+        let user_id = get_user_id().await?;
+        get_household_workflow().await?
+            .remove_member(&user_id, &member_user_id).await
+            .map_err(|e| ServerFnError::new(format!("Failed to remove member: {}", e)))?;
+        Ok(())
+    }).await
+}
+
+// Helper to get the kid and owner for a kid session
+#[cfg(feature = "ssr")]
+async fn get_kid_session() -> Result<(Uuid, String), ServerFnError> {
+    let session = extract::<Session>().await
+        .map_err(|e| ServerFnError::new(format!("Failed to extract session: {}", e)))?;
+
+    let account_type: Option<String> = session.get("account_type")
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get session: {}", e)))?;
+    if account_type.as_deref() != Some("kid") {
+        return Err(ServerFnError::new("Kid login required".to_string()));
+    }
+
+    let kid_id: Option<String> = session.get("kid_id")
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get session: {}", e)))?;
+    let owner_id: Option<String> = session.get("owner_id")
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get session: {}", e)))?;
+
+    match (kid_id.and_then(|id| Uuid::from_str(&id).ok()), owner_id) {
+        (Some(kid_id), Some(owner_id)) => Ok((kid_id, owner_id)),
+        _ => Err(ServerFnError::new("Not authenticated".to_string())),
+    }
+}
+
+#[server]
+pub async fn kid_login(username: String, pin: String) -> Result<KidLoginResultDto, ServerFnError> {
+    let db = get_db().await?;
+    let workflow = KidAccountWorkflow::new(
+        UserRepository::new(db.client.clone()),
+        KidRepository::new(db.client.clone()),
+    );
+
+    let (user, kid) = match workflow.login(&username, &pin, chrono::Utc::now()).await
+        .map_err(|e| ServerFnError::new(format!("Login error: {}", e)))?
+    {
+        loaa_core::workflows::KidLogin::Success { user, kid } => (user, kid),
+        loaa_core::workflows::KidLogin::InvalidCredentials => return Ok(KidLoginResultDto::InvalidPin),
+        loaa_core::workflows::KidLogin::Locked { until } => {
+            eprintln!("🔒 Kid login locked for '{}' until {}", username, until);
+            return Ok(KidLoginResultDto::Locked(until));
+        }
+    };
+
+    let session = extract::<Session>().await
+        .map_err(|e| ServerFnError::new(format!("Failed to extract session: {}", e)))?;
+    for (key, value) in [
+        ("user_id", user.id.to_string()),
+        ("account_type", "kid".to_string()),
+        ("kid_id", kid.id.to_string()),
+        ("owner_id", kid.owner_id.clone()),
+    ] {
+        session.insert(key, value)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Failed to set session: {}", e)))?;
+    }
+
+    Ok(KidLoginResultDto::Success)
+}
+
+#[server]
+pub async fn get_kid_home() -> Result<KidHomeDto, ServerFnError> {
+    let (kid_id, owner_id) = get_kid_session().await?;
+    let actor = Actor::kid(owner_id.clone(), kid_id);
+    let db = get_db().await?;
+
+    let kid = KidRepository::new(db.client.clone()).get(kid_id).await
+        .map_err(|e| ServerFnError::new(format!("Failed to get kid: {}", e)))?;
+    let tasks = TaskRepository::new(db.client.clone()).list_by_owner(&owner_id).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list tasks: {}", e)))?;
+    let ledger = LedgerRepository::new(db.client.clone()).get_ledger(kid_id).await
+        .map_err(|e| ServerFnError::new(format!("Failed to get ledger: {}", e)))?;
+    let claims = get_claim_workflow().await?.list_for_kid(&actor, kid_id).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list claims: {}", e)))?;
+    let withdrawals = get_withdrawal_workflow().await?.list_for_kid(&actor, kid_id).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list withdrawals: {}", e)))?;
+    let transfers = get_transfer_workflow().await?.list_for_kid(&actor, kid_id).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list transfers: {}", e)))?;
+    let household = KidRepository::new(db.client.clone()).list_by_owner(&owner_id).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list kids: {}", e)))?;
+
+    let kids = [kid.clone()];
+    let balance = ledger.balance;
+    let mut entries: Vec<LedgerEntryDto> = ledger.entries.into_iter().rev().map(Into::into).collect();
+    entries.truncate(20);
+
+    Ok(KidHomeDto {
+        kid: kid.into(),
+        balance,
+        claims: claims.into_iter().map(|c| claim_dto(c, &kids, &tasks)).collect(),
+        withdrawals: withdrawals.into_iter().map(|w| withdrawal_dto(w, &kids)).collect(),
+        transfers: transfers.into_iter().map(|t| transfer_dto(t, &household)).collect(),
+        siblings: household.into_iter().filter(|k| k.id != kid_id).map(Into::into).collect(),
+        tasks: tasks.into_iter().map(Into::into).collect(),
+        entries,
+    })
+}
+
+#[server(client = KeyedClient)]
+pub async fn claim_task(task_id: UuidDto, note: Option<String>) -> Result<(), ServerFnError> {
+    once("claim_task", json!([task_id, note]), || async move {
+        let (kid_id, owner_id) = get_kid_session().await?;
+        let task_uuid = Uuid::from_str(&task_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid task ID: {}", e)))?;
+
+        get_claim_workflow().await?
+            .claim_task(&Actor::kid(owner_id, kid_id), kid_id, task_uuid, note, None).await
+            .map_err(|e| ServerFnError::new(format!("Failed to claim task: {}", e)))?;
+        Ok(())
+    }).await
+}
+
+#[server]
+pub async fn get_pending_claims() -> Result<Vec<TaskClaimDto>, ServerFnError> {
+    let actor = get_actor().await?;
+    let owner_id = actor.household_id().unwrap_or_default().to_string();
+    let db = get_db().await?;
+
+    let claims = get_claim_workflow().await?.list_pending(&actor).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list claims: {}", e)))?;
+    let kids = KidRepository::new(db.client.clone()).list_by_owner(&owner_id).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list kids: {}", e)))?;
+    let tasks = TaskRepository::new(db.client.clone()).list_by_owner(&owner_id).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list tasks: {}", e)))?;
+
+    Ok(claims.into_iter().map(|c| claim_dto(c, &kids, &tasks)).collect())
+}
+
+#[server(client = KeyedClient)]
+pub async fn approve_claim(
+    claim_id: UuidDto,
+    quality: Option<u8>,
+    percent: Option<u8>,
+    comment: Option<String>,
+) -> Result<(), ServerFnError> {
+    once("approve_claim", json!([claim_id, quality, percent, comment]), || async move {
+        let actor = get_actor().await?;
+        let claim_uuid = Uuid::from_str(&claim_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid claim ID: {}", e)))?;
+        let review = review_from_params(quality, percent, comment)?;
+
+        get_claim_workflow().await?
+            .approve(&actor, claim_uuid, review).await
+            .map_err(|e| ServerFnError::new(format!("Failed to approve claim: {}", e)))?;
+        Ok(())
+    }).await
+}
+
+#[server(client = KeyedClient)]
+pub async fn reject_claim(claim_id: UuidDto) -> Result<(), ServerFnError> {
+    once("reject_claim", json!([claim_id]), || async move {
+        let actor = get_actor().await?;
+        let claim_uuid = Uuid::from_str(&claim_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid claim ID: {}", e)))?;
+
+        get_claim_workflow().await?
+            .reject(&actor, claim_uuid).await
+            .map_err(|e| ServerFnError::new(format!("Failed to reject claim: {}", e)))?;
+        Ok(())
+    }).await
+}
+
+#[server(client = KeyedClient)]
+pub async fn request_withdrawal(amount: String, note: Option<String>) -> Result<(), ServerFnError> {
+    once("request_withdrawal", json!([amount, note]), || async move {
+        let (kid_id, owner_id) = get_kid_session().await?;
+        let amount = rust_decimal::Decimal::from_str(amount.trim())
+            .map_err(|e| ServerFnError::new(format!("Invalid amount: {}", e)))?;
+
+        get_withdrawal_workflow().await?
+            .request(&Actor::kid(owner_id, kid_id), kid_id, amount, note).await
+            .map_err(|e| ServerFnError::new(format!("Failed to request withdrawal: {}", e)))?;
+        Ok(())
+    }).await
+}
+
+#[server]
+pub async fn get_pending_payouts() -> Result<Vec<WithdrawalDto>, ServerFnError> {
+    let actor = get_actor().await?;
+    let owner_id = actor.household_id().unwrap_or_default().to_string();
+    let db = get_db().await?;
+
+    let withdrawals = get_withdrawal_workflow().await?.list_open(&actor).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list withdrawals: {}", e)))?;
+    let kids = KidRepository::new(db.client.clone()).list_by_owner(&owner_id).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list kids: {}", e)))?;
+
+    Ok(withdrawals.into_iter().map(|w| withdrawal_dto(w, &kids)).collect())
+}
+
+#[server]
+pub async fn get_payout_summary() -> Result<Vec<PayoutSummaryDto>, ServerFnError> {
+    let actor = get_actor().await?;
+    let summary = get_withdrawal_workflow().await?.summary(&actor).await
+        .map_err(|e| ServerFnError::new(format!("Failed to summarize payouts: {}", e)))?;
+    Ok(summary.into_iter().map(Into::into).collect())
+}
+
+#[server(client = KeyedClient)]
+pub async fn approve_withdrawal(withdrawal_id: UuidDto) -> Result<(), ServerFnError> {
+    once("approve_withdrawal", json!([withdrawal_id]), || async move {
+        let actor = get_actor().await?;
+        let withdrawal_uuid = Uuid::from_str(&withdrawal_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid withdrawal ID: {}", e)))?;
+
+        get_withdrawal_workflow().await?
+            .approve(&actor, withdrawal_uuid).await
+            .map_err(|e| ServerFnError::new(format!("Failed to approve withdrawal: {}", e)))?;
+        Ok(())
+    }).await
+}
+
+#[server(client = KeyedClient)]
+pub async fn decline_withdrawal(withdrawal_id: UuidDto) -> Result<(), ServerFnError> {
+    once("decline_withdrawal", json!([withdrawal_id]), || async move {
+        let actor = get_actor().await?;
+        let withdrawal_uuid = Uuid::from_str(&withdrawal_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid withdrawal ID: {}", e)))?;
+
+        get_withdrawal_workflow().await?
+            .decline(&actor, withdrawal_uuid).await
+            .map_err(|e| ServerFnError::new(format!("Failed to decline withdrawal: {}", e)))?;
+        Ok(())
+    }).await
+}
+
+#[server(client = KeyedClient)]
+pub async fn mark_withdrawal_paid(withdrawal_id: UuidDto, method: String) -> Result<(), ServerFnError> {
+    once("mark_withdrawal_paid", json!([withdrawal_id, method]), || async move {
+        let actor = get_actor().await?;
+        let withdrawal_uuid = Uuid::from_str(&withdrawal_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid withdrawal ID: {}", e)))?;
+        let method = PaymentMethod::parse(&method)
+            .ok_or_else(|| ServerFnError::new("Method should be cash, bank_transfer or gift_card".to_string()))?;
+
+        get_withdrawal_workflow().await?
+            .mark_paid(&actor, withdrawal_uuid, method).await
+            .map_err(|e| ServerFnError::new(format!("Failed to mark withdrawal paid: {}", e)))?;
+        Ok(())
+    }).await
+}
+
+#[server(client = KeyedClient)]
+pub async fn transfer_between_kids(
+    from_kid_id: UuidDto,
+    to_kid_id: UuidDto,
+    amount: String,
+    note: Option<String>,
+    require_approval: bool,
+) -> Result<TransferDto, ServerFnError> {
+    once("transfer_between_kids", json!([from_kid_id, to_kid_id, amount, note, require_approval]), || async move {
+        let actor = get_actor().await?;
+        let owner_id = actor.household_id().unwrap_or_default().to_string();
+        let from_uuid = Uuid::from_str(&from_kid_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid kid ID: {}", e)))?;
+        let to_uuid = Uuid::from_str(&to_kid_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid kid ID: {}", e)))?;
+        let amount = rust_decimal::Decimal::from_str(amount.trim())
+            .map_err(|e| ServerFnError::new(format!("Invalid amount: {}", e)))?;
+
+        let transfer = get_transfer_workflow().await?
+            .transfer(&actor, from_uuid, to_uuid, amount, note, require_approval).await
+            .map_err(|e| ServerFnError::new(format!("Failed to transfer: {}", e)))?;
+        let db = get_db().await?;
+        let kids = KidRepository::new(db.client.clone()).list_by_owner(&owner_id).await
+            .map_err(|e| ServerFnError::new(format!("Failed to list kids: {}", e)))?;
+        Ok(transfer_dto(transfer, &kids))
+    }).await
+}
+
+#[server(client = KeyedClient)]
+pub async fn request_transfer(to_kid_id: UuidDto, amount: String, note: Option<String>) -> Result<(), ServerFnError> {
+    once("request_transfer", json!([to_kid_id, amount, note]), || async move {
+        let (kid_id, owner_id) = get_kid_session().await?;
+        let to_uuid = Uuid::from_str(&to_kid_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid kid ID: {}", e)))?;
+        let amount = rust_decimal::Decimal::from_str(amount.trim())
+            .map_err(|e| ServerFnError::new(format!("Invalid amount: {}", e)))?;
+
+        // Kids' transfers always wait for a parent
+        get_transfer_workflow().await?
+            .transfer(&Actor::kid(owner_id, kid_id), kid_id, to_uuid, amount, note, true).await
+            .map_err(|e| ServerFnError::new(format!("Failed to request transfer: {}", e)))?;
+        Ok(())
+    }).await
+}
+
+#[server]
+pub async fn get_pending_transfers() -> Result<Vec<TransferDto>, ServerFnError> {
+    let actor = get_actor().await?;
+    let owner_id = actor.household_id().unwrap_or_default().to_string();
+    let db = get_db().await?;
+
+    let transfers = get_transfer_workflow().await?.list_pending(&actor).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list transfers: {}", e)))?;
+    let kids = KidRepository::new(db.client.clone()).list_by_owner(&owner_id).await
+        .map_err(|e| ServerFnError::new(format!("Failed to list kids: {}", e)))?;
+
+    Ok(transfers.into_iter().map(|t| transfer_dto(t, &kids)).collect())
+}
+
+#[server(client = KeyedClient)]
+pub async fn approve_transfer(transfer_id: UuidDto) -> Result<(), ServerFnError> {
+    once("approve_transfer", json!([transfer_id]), || async move {
+        let actor = get_actor().await?;
+        let transfer_uuid = Uuid::from_str(&transfer_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid transfer ID: {}", e)))?;
+
+        get_transfer_workflow().await?
+            .approve(&actor, transfer_uuid).await
+            .map_err(|e| ServerFnError::new(format!("Failed to approve transfer: {}", e)))?;
+        Ok(())
+    }).await
+}
+
+#[server(client = KeyedClient)]
+pub async fn decline_transfer(transfer_id: UuidDto) -> Result<(), ServerFnError> {
+    once("decline_transfer", json!([transfer_id]), || async move {
+        let actor = get_actor().await?;
+        let transfer_uuid = Uuid::from_str(&transfer_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid transfer ID: {}", e)))?;
+
+        get_transfer_workflow().await?
+            .decline(&actor, transfer_uuid).await
+            .map_err(|e| ServerFnError::new(format!("Failed to decline transfer: {}", e)))?;
+        Ok(())
+    }).await
+}
+
+#[server(client = KeyedClient)]
+pub async fn create_kid_login(
+    kid_id: UuidDto,
+    username: String,
+    pin: String,
+) -> Result<AccountDto, ServerFnError> {
+    once("create_kid_login", json!([kid_id, username]), || async move {
+        let actor = get_actor().await?;
+        let kid_uuid = Uuid::from_str(&kid_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid kid ID: {}", e)))?;
+        let db = get_db().await?;
+
+        let workflow = KidAccountWorkflow::new(
+            UserRepository::new(db.client.clone()),
+            KidRepository::new(db.client.clone()),
+        );
+        let user = workflow.create_account(&actor, kid_uuid, username, &pin).await
+            .map_err(|e| ServerFnError::new(format!("Failed to create kid login: {}", e)))?;
+
+        eprintln!("✅ Created kid login: {}", user.username);
+        Ok(user.into())
+    }).await
+}
+
+#[server(client = KeyedClient)]
+pub async fn reset_kid_pin(kid_id: UuidDto, pin: String) -> Result<(), ServerFnError> {
+    once("reset_kid_pin", json!([kid_id]), || async move {
+        let actor = get_actor().await?;
+        let kid_uuid = Uuid::from_str(&kid_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid kid ID: {}", e)))?;
+        let db = get_db().await?;
+
+        let workflow = KidAccountWorkflow::new(
+            UserRepository::new(db.client.clone()),
+            KidRepository::new(db.client.clone()),
+        );
+        workflow.reset_pin(&actor, kid_uuid, &pin).await
+            .map_err(|e| ServerFnError::new(format!("Failed to reset PIN: {}", e)))?;
+        Ok(())
+    }).await
+}
+
 #[server]
 pub async fn list_accounts() -> Result<Vec<AccountDto>, ServerFnError> {
     require_admin().await?;
@@ -380,50 +1665,145 @@ pub async fn list_accounts() -> Result<Vec<AccountDto>, ServerFnError> {
 }
 
 #[server]
-pub async fn create_account(username: String, password: String) -> Result<AccountDto, ServerFnError> {
+pub async fn get_backup_status() -> Result<BackupStatusDto, ServerFnError> {
     require_admin().await?;
 
-    let db = get_db().await?;
-    let user_repo = UserRepository::new(db.client.clone());
+    let config = get_backup_config();
+    let last = match &config.dir {
+        Some(dir) => BackupWorkflow::last_status(dir)
+            .map_err(|e| ServerFnError::new(format!("Failed to read backup status: {}", e)))?,
+        None => None,
+    };
+    Ok(BackupStatusDto {
+        enabled: config.dir.is_some(),
+        last: last.map(Into::into),
+    })
+}
 
-    // Check if username already exists
-    if user_repo.get_by_username(&username).await.is_ok() {
-        return Err(ServerFnError::new(format!("Username '{}' already exists", username)));
-    }
+#[server(client = KeyedClient)]
+pub async fn create_account(username: String, password: String) -> Result<AccountDto, ServerFnError> {
+    once("create_account", json!([username]), || async move {
+        require_admin().await?;
 
-    // Create new user
-    let mut user = loaa_core::models::User::new(username)
-        .map_err(|e| ServerFnError::new(format!("Invalid user data: {}", e)))?;
+        let db = get_db().await?;
+        let user_repo = UserRepository::new(db.client.clone());
 
-    // Hash password
-    user.password_hash = hash_password(&password)
-        .map_err(|e| ServerFnError::new(format!("Failed to hash password: {}", e)))?;
+        // Check if username already exists
+        if user_repo.get_by_username(&username).await.is_ok() {
+            return Err(ServerFnError::new(format!("Username '{}' already exists", username)));
+        }
+
+        // Create new user
+        let mut user = loaa_core::models::User::new(username)
+            .map_err(|e| ServerFnError::new(format!("Invalid user data: {}", e)))?;
+
+        // Hash password
+        user.password_hash = hash_password(&password)
+            .map_err(|e| ServerFnError::new(format!("Failed to hash password: {}", e)))?;
 
-    // Save to database
-    let created = user_repo.create(user).await
-        .map_err(|e| ServerFnError::new(format!("Failed to create user: {}", e)))?;
+        // Save to database
+        let created = user_repo.create(user).await
+            .map_err(|e| ServerFnError::new(format!("Failed to create user: {}", e)))?;
 
-    eprintln!("✅ Created account: {}", created.username);
-    Ok(created.into())
+        eprintln!("✅ Created account: {}", created.username);
+        Ok(created.into())
+    }).await
 }
 
-#[server]
+#[server(client = KeyedClient)]
 pub async fn delete_account(user_id: String) -> Result<(), ServerFnError> {
-    require_admin().await?;
+    once("delete_account", json!([user_id]), || async move {
+        require_admin().await?;
 
-    let uuid = uuid::Uuid::parse_str(&user_id)
-        .map_err(|e| ServerFnError::new(format!("Invalid user ID: {}", e)))?;
+        let uuid = uuid::Uuid::parse_str(&user_id)
+            .map_err(|e| ServerFnError::new(format!("Invalid user ID: {}", e)))?;
 
-    let db = get_db().await?;
-    let user_repo = UserRepository::new(db.client.clone());
+        let db = get_db().await?;
+        let user_repo = UserRepository::new(db.client.clone());
 
-    // Get the user first to log who we're deleting
-    let user = user_repo.get(uuid).await
-        .map_err(|e| ServerFnError::new(format!("User not found: {}", e)))?;
+        // Get the user first to log who we're deleting
+        let user = user_repo.get(uuid).await
+            .map_err(|e| ServerFnError::new(format!("User not found: {}", e)))?;
 
-    user_repo.delete(uuid).await
-        .map_err(|e| ServerFnError::new(format!("Failed to delete user: {}", e)))?;
+        user_repo.delete(uuid).await
+            .map_err(|e| ServerFnError::new(format!("Failed to delete user: {}", e)))?;
 
-    eprintln!("🗑️ Deleted account: {}", user.username);
-    Ok(())
+        eprintln!("🗑️ Deleted account: {}", user.username);
+        Ok(())
+    }).await
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use tower_sessions::MemoryStore;
+
+    /// Make the following calls as `user_id`, sending `key` as the
+    /// `Idempotency-Key` header
+    async fn act_as(user_id: &str, account_type: Option<&str>, key: &str) {
+        let session = Session::new(None, Arc::new(MemoryStore::default()), None);
+        session.insert("user_id", user_id).await.unwrap();
+        if let Some(account_type) = account_type {
+            session.insert("account_type", account_type).await.unwrap();
+        }
+        let mut request = Request::new(());
+        request.headers_mut().insert("idempotency-key", key.parse().unwrap());
+        let (mut parts, _) = request.into_parts();
+        parts.extensions.insert(session);
+        provide_context(parts);
+    }
+
+    #[tokio::test]
+    async fn test_stored_keys_reveal_no_pins_or_passwords() {
+        std::env::set_var("LOAA_ATTACHMENT_DIR", std::env::temp_dir().join("loaa-web-tests"));
+        let runtime = leptos::create_runtime();
+        let (pin, new_pin, password) = ("4821", "7315", "correct horse battery");
+
+        act_as("parent-pins", None, "kid").await;
+        let kid = create_kid("Kai".to_string()).await.unwrap();
+        let username = format!("kai-{}", uuid::Uuid::new_v4().simple());
+        act_as("parent-pins", None, "login").await;
+        create_kid_login(kid.id.clone(), username.clone(), pin.to_string()).await.unwrap();
+        act_as("parent-pins", None, "reset").await;
+        reset_kid_pin(kid.id.clone(), new_pin.to_string()).await.unwrap();
+        let account = format!("grandma-{}", uuid::Uuid::new_v4().simple());
+        act_as("admin", Some("admin"), "account").await;
+        create_account(account.clone(), password.to_string()).await.unwrap();
+
+        let db = get_db().await.unwrap();
+        let operations = ["create_kid_login", "reset_kid_pin", "create_account"];
+        let rows: Vec<serde_json::Value> = db.client
+            .query("SELECT * OMIT id FROM idempotency_key WHERE operation IN $operations")
+            .bind(("operations", operations))
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+        assert_eq!(rows.len(), 3);
+        let rows = serde_json::to_string(&rows).unwrap();
+        for secret in [pin, new_pin, password] {
+            assert!(!rows.contains(secret), "{} stored in {}", secret, rows);
+        }
+
+        // The hashes are of the other arguments alone, so trying every PIN
+        // or password against them finds nothing
+        let mut hashes: Vec<String> = db.client
+            .query("SELECT VALUE request_hash FROM idempotency_key WHERE operation IN $operations")
+            .bind(("operations", operations))
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+        let mut expected = vec![
+            IdempotencyKey::hash_arguments(&json!([kid.id, username])).unwrap(),
+            IdempotencyKey::hash_arguments(&json!([kid.id])).unwrap(),
+            IdempotencyKey::hash_arguments(&json!([account])).unwrap(),
+        ];
+        hashes.sort();
+        expected.sort();
+        assert_eq!(hashes, expected);
+
+        runtime.dispose();
+    }
 }
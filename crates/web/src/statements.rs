@@ -0,0 +1,96 @@
+//! Monthly statement downloads, as PDF or a standalone HTML page
+//! Parents can download any of their household's kids; kid logins only their own
+
+use axum::{
+    extract::{Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use loaa_core::statements::{parse_month, previous_month};
+use loaa_core::{Actor, Error, Uuid};
+use serde::Deserialize;
+use tower_sessions::Session;
+
+use crate::server_functions::{get_membership, get_statement_workflow};
+
+type HandlerError = (StatusCode, String);
+
+fn error_response(e: Error) -> HandlerError {
+    match e {
+        Error::NotFound(_) => (StatusCode::NOT_FOUND, "Kid not found".to_string()),
+        Error::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
+        Error::Validation(msg) => (StatusCode::BAD_REQUEST, msg),
+        other => {
+            eprintln!("Statement error: {}", other);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Statement failed".to_string())
+        }
+    }
+}
+
+/// Who is asking: a parent account, or a kid logged in with a PIN
+async fn session_actor(session: &Session) -> Result<Actor, HandlerError> {
+    let get = |key: &'static str| async move { session.get::<String>(key).await.ok().flatten() };
+    if get("account_type").await.as_deref() == Some("kid") {
+        let kid_id = get("kid_id").await.and_then(|id| Uuid::parse_str(&id).ok());
+        return match (get("owner_id").await, kid_id) {
+            (Some(owner_id), Some(kid_id)) => Ok(Actor::kid(owner_id, kid_id)),
+            _ => Err((StatusCode::UNAUTHORIZED, "Not authenticated".to_string())),
+        };
+    }
+    let user_id = get("user_id").await
+        .ok_or_else(|| (StatusCode::UNAUTHORIZED, "Not authenticated".to_string()))?;
+    let (household, role) = get_membership(&user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Actor::member(user_id, household.owner_id(), role))
+}
+
+/// Query parameters for a statement; both are optional
+#[derive(Debug, Deserialize)]
+pub struct StatementQuery {
+    /// The month as YYYY-MM; last month when left out
+    month: Option<String>,
+    /// "pdf" (the default) or "html"
+    format: Option<String>,
+}
+
+/// Download a kid's statement for one month
+pub async fn download_statement(
+    session: Session,
+    Path(kid_id): Path<String>,
+    Query(query): Query<StatementQuery>,
+) -> Result<Response, HandlerError> {
+    let actor = session_actor(&session).await?;
+    let kid_id = Uuid::parse_str(kid_id.trim())
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid kid ID: {}", e)))?;
+    let month = match query.month.filter(|month| !month.trim().is_empty()) {
+        Some(month) => parse_month(&month).map_err(error_response)?,
+        None => previous_month(Utc::now()),
+    };
+
+    let statement = get_statement_workflow()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .statement(&actor, kid_id, month)
+        .await
+        .map_err(error_response)?;
+
+    let (content_type, extension, body) = match query.format.as_deref().unwrap_or("pdf") {
+        "pdf" => ("application/pdf", "pdf", statement.to_pdf()),
+        "html" => ("text/html; charset=utf-8", "html", statement.to_html().into_bytes()),
+        other => return Err((StatusCode::BAD_REQUEST, format!("Unknown format '{}', use pdf or html", other))),
+    };
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}.{}\"", statement.file_stem(), extension),
+            ),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+        body,
+    )
+        .into_response())
+}
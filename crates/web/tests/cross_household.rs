@@ -0,0 +1,46 @@
+use axum::http::Request;
+use leptos::provide_context;
+use loaa_web::dto::CadenceDto;
+use loaa_web::server_functions::{complete_task, create_kid, create_task, get_ledger};
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+use tower_sessions::{MemoryStore, Session};
+
+// The server functions use their own database from LOAA_DB_MODE, which
+// defaults to an in-memory one, so these tests don't need a running
+// SurrealDB server.
+
+/// Make the following server function calls as the parent `user_id`, the way
+/// the session layer does for a request
+async fn log_in(user_id: &str) {
+    let session = Session::new(None, Arc::new(MemoryStore::default()), None);
+    session.insert("user_id", user_id).await.unwrap();
+    let (mut parts, _) = Request::new(()).into_parts();
+    parts.extensions.insert(session);
+    provide_context(parts);
+}
+
+#[tokio::test]
+async fn test_server_functions_refuse_ids_from_another_household() {
+    std::env::set_var("LOAA_ATTACHMENT_DIR", std::env::temp_dir().join("loaa-web-tests"));
+    let runtime = leptos::create_runtime();
+
+    log_in("parent-a").await;
+    let kid = create_kid("Kai".to_string()).await.unwrap();
+    let task = create_task("Dishes".to_string(), String::new(), dec!(2.00), CadenceDto::Daily)
+        .await
+        .unwrap();
+
+    log_in("parent-b").await;
+    let ledger = get_ledger(kid.id.clone()).await;
+    assert!(ledger.unwrap_err().to_string().contains("belongs to another household"));
+    let completed = complete_task(kid.id.clone(), task.id.clone(), None, None, None, None).await;
+    assert!(completed.unwrap_err().to_string().contains("belongs to another household"));
+
+    // The kid earned nothing from the attempt
+    log_in("parent-a").await;
+    let ledger = get_ledger(kid.id.clone()).await.unwrap();
+    assert!(ledger.entries.is_empty());
+
+    runtime.dispose();
+}